@@ -0,0 +1,270 @@
+// Badges unlocked by playing well, and the stats they are judged on
+
+use std::collections::BTreeSet;
+use std::io;
+use std::time::Duration;
+
+use crate::migration::{self, Schema};
+use crate::storage;
+
+// File the stats and unlocked achievements are stored in, inside the data directory
+const FILE_NAME: &str = "achievements.txt";
+
+// How the file has changed between versions
+const SCHEMA: Schema = Schema { file: FILE_NAME, name: "achievements", legacy_key: None, steps: &[] };
+
+// Totals kept across games
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub wins: u32,   // Games won, ever
+    pub streak: u32, // Games won in a row, reset by abandoning a game part-way
+}
+
+// How a single won game went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameResult {
+    pub attempts: u32,     // Guesses the win took
+    pub optimal: u32,      // Guesses binary search needs in the worst case for the range
+    pub elapsed: Duration, // Time on the game clock
+}
+
+// A badge, unlocked the first time its predicate holds after a win
+#[derive(Debug, Clone, Copy)]
+pub struct Achievement {
+    pub key: &'static str,         // The value written to the achievements file
+    pub name: &'static str,        // Shown on the badge and in the unlock toast
+    pub description: &'static str, // How to unlock it
+    unlocked_by: fn(&Stats, &GameResult) -> bool, // Judged with the stats already updated for this win
+}
+
+impl Achievement {
+    // Whether this win, on top of the stats so far, earns the achievement
+    pub fn is_earned(&self, stats: &Stats, result: &GameResult) -> bool {
+        (self.unlocked_by)(stats, result)
+    }
+}
+
+// Every achievement, in the order shown on the badges screen
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        key: "first_win",
+        name: "First Win",
+        description: "Win a game",
+        unlocked_by: |stats, _| stats.wins >= 1,
+    },
+    Achievement {
+        key: "first_try",
+        name: "Mind Reader",
+        description: "Win with your very first guess",
+        unlocked_by: |_, result| result.attempts == 1,
+    },
+    Achievement {
+        key: "five_or_fewer",
+        name: "Sharpshooter",
+        description: "Win in 5 guesses or fewer",
+        unlocked_by: |_, result| result.attempts <= 5,
+    },
+    Achievement {
+        key: "optimal",
+        name: "By the Book",
+        description: "Win within the optimal number of guesses",
+        unlocked_by: |_, result| result.attempts <= result.optimal,
+    },
+    Achievement {
+        key: "quick",
+        name: "Quick Draw",
+        description: "Win in under 30 seconds",
+        unlocked_by: |_, result| result.elapsed < Duration::from_secs(30),
+    },
+    Achievement {
+        key: "streak_10",
+        name: "On a Roll",
+        description: "Win 10 games in a row without abandoning one",
+        unlocked_by: |stats, _| stats.streak >= 10,
+    },
+    Achievement {
+        key: "wins_50",
+        name: "Regular",
+        description: "Win 50 games",
+        unlocked_by: |stats, _| stats.wins >= 50,
+    },
+];
+
+// The player's stats and unlocked achievements, saved after every change
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Progress {
+    pub stats: Stats,
+    pub unlocked: BTreeSet<String>, // Keys of the achievements unlocked so far
+}
+
+impl Progress {
+    // Load the saved progress, starting afresh if there is none or it can't be read
+    pub fn load() -> Progress {
+        let values = match migration::load(&SCHEMA) {
+            Ok(Some(values)) => values,
+            Ok(None) => return Progress::default(), // No games won yet
+            Err(error) => {
+                tracing::warn!(%error, "Could not read achievements");
+                return Progress::default();
+            }
+        };
+
+        let count = |key: &str| values.get(key).and_then(|value| value.parse().ok()).unwrap_or(0);
+
+        Progress {
+            stats: Stats { wins: count("wins"), streak: count("streak") },
+            unlocked: values
+                .get("unlocked")
+                .map(|list| list.split(',').filter(|key| !key.is_empty()).map(str::to_string).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    // Save the progress, logging rather than failing if the file can't be written
+    fn save(&self) {
+        let unlocked: Vec<&str> = self.unlocked.iter().map(String::as_str).collect();
+        let contents = storage::write_key_values([
+            ("wins", self.stats.wins.to_string()),
+            ("streak", self.stats.streak.to_string()),
+            ("unlocked", unlocked.join(",")),
+        ]);
+
+        if let Err(error) = migration::save(&SCHEMA, &contents) {
+            tracing::warn!(%error, "Could not save achievements");
+        }
+    }
+
+    // Whether the achievement with this key has been unlocked
+    pub fn is_unlocked(&self, achievement: &Achievement) -> bool {
+        self.unlocked.contains(achievement.key)
+    }
+
+    // Count a win, returning the achievements it unlocked for the first time
+    pub fn record_win(&mut self, result: &GameResult) -> Vec<&'static Achievement> {
+        self.stats.wins += 1;
+        self.stats.streak += 1;
+
+        let newly_unlocked: Vec<&'static Achievement> = ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| !self.is_unlocked(achievement) && achievement.is_earned(&self.stats, result))
+            .collect();
+        self.unlocked.extend(newly_unlocked.iter().map(|achievement| achievement.key.to_string()));
+
+        self.save();
+        newly_unlocked
+    }
+
+    // Note a game left unfinished, which breaks the winning streak
+    pub fn record_abandoned(&mut self) {
+        if self.stats.streak > 0 {
+            self.stats.streak = 0;
+            self.save();
+        }
+    }
+
+    // Forget every stat and achievement and delete the saved file; they're forgotten even if that fails
+    pub fn reset(&mut self) -> io::Result<()> {
+        *self = Progress::default();
+        migration::remove(&SCHEMA)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn win(attempts: u32, seconds: u64) -> GameResult {
+        GameResult { attempts, optimal: 7, elapsed: Duration::from_secs(seconds) }
+    }
+
+    fn keys(achievements: &[&Achievement]) -> Vec<&'static str> {
+        achievements.iter().map(|achievement| achievement.key).collect()
+    }
+
+    fn achievement(key: &str) -> &'static Achievement {
+        ACHIEVEMENTS.iter().find(|achievement| achievement.key == key).unwrap()
+    }
+
+    #[test]
+    fn every_predicate_holds_exactly_at_its_threshold() {
+        let stats = |wins, streak| Stats { wins, streak };
+        let cases: [(&str, Stats, GameResult, bool); 14] = [
+            ("first_win", stats(1, 1), win(20, 100), true),
+            ("first_win", stats(0, 0), win(20, 100), false),
+            ("first_try", stats(1, 1), win(1, 100), true),
+            ("first_try", stats(1, 1), win(2, 100), false),
+            ("five_or_fewer", stats(1, 1), win(5, 100), true),
+            ("five_or_fewer", stats(1, 1), win(6, 100), false),
+            ("optimal", stats(1, 1), win(7, 100), true),
+            ("optimal", stats(1, 1), win(8, 100), false),
+            ("quick", stats(1, 1), GameResult { elapsed: Duration::from_millis(29_999), ..win(20, 0) }, true),
+            ("quick", stats(1, 1), win(20, 30), false),
+            ("streak_10", stats(10, 10), win(20, 100), true),
+            ("streak_10", stats(30, 9), win(20, 100), false),
+            ("wins_50", stats(50, 0), win(20, 100), true),
+            ("wins_50", stats(49, 49), win(20, 100), false),
+        ];
+        for (key, stats, result, earned) in cases {
+            assert_eq!(achievement(key).is_earned(&stats, &result), earned, "{} with {:?} {:?}", key, stats, result);
+        }
+    }
+
+    #[test]
+    fn keys_are_unique() {
+        let keys: BTreeSet<&str> = ACHIEVEMENTS.iter().map(|achievement| achievement.key).collect();
+        assert_eq!(keys.len(), ACHIEVEMENTS.len());
+        assert!(keys.iter().all(|key| !key.contains(',')), "keys are stored comma-separated");
+    }
+
+    #[test]
+    fn each_achievement_unlocks_only_once() {
+        let mut progress = Progress::default();
+        assert_eq!(keys(&progress.record_win(&win(1, 10))), ["first_win", "first_try", "five_or_fewer", "optimal", "quick"]);
+        assert_eq!(keys(&progress.record_win(&win(1, 10))), Vec::<&str>::new());
+        assert_eq!(progress.stats, Stats { wins: 2, streak: 2 });
+        assert!(progress.is_unlocked(achievement("first_try")));
+        assert!(!progress.is_unlocked(achievement("streak_10")));
+    }
+
+    #[test]
+    fn a_streak_of_ten_unlocks_on_the_tenth_win() {
+        let mut progress = Progress::default();
+        for _ in 0..9 {
+            progress.record_win(&win(20, 100));
+        }
+        progress.record_abandoned();
+        assert_eq!(progress.stats, Stats { wins: 9, streak: 0 });
+
+        for _ in 0..9 {
+            assert!(!keys(&progress.record_win(&win(20, 100))).contains(&"streak_10"));
+        }
+        assert_eq!(keys(&progress.record_win(&win(20, 100))), ["streak_10"]);
+    }
+
+    #[test]
+    fn progress_is_saved_and_loaded_back() {
+        Progress::default().reset().unwrap();
+        assert_eq!(Progress::load(), Progress::default());
+
+        let mut progress = Progress::default();
+        progress.record_win(&win(3, 100));
+        progress.record_win(&win(20, 100));
+        assert_eq!(Progress::load(), progress);
+
+        progress.record_abandoned();
+        assert_eq!(Progress::load().stats, Stats { wins: 2, streak: 0 });
+
+        progress.reset().unwrap();
+        assert_eq!(progress, Progress::default());
+        assert_eq!(Progress::load(), Progress::default());
+    }
+
+    #[test]
+    fn damaged_values_count_as_nothing() {
+        migration::save(&SCHEMA, "wins = lots\nstreak = -3\nunlocked = first_win,,quick\n").unwrap();
+        let progress = Progress::load();
+        assert_eq!(progress.stats, Stats::default());
+        assert_eq!(progress.unlocked, BTreeSet::from(["first_win".to_string(), "quick".to_string()]));
+        migration::remove(&SCHEMA).unwrap();
+    }
+}
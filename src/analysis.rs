@@ -0,0 +1,437 @@
+// Post-game analysis of how efficiently the player found the secret
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::gamelog::Win;
+
+// Letter grade for how close the player came to optimal play
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Grade {
+    A,
+    B,
+    C,
+    D,
+    F,
+}
+
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letter = match self {
+            Grade::A => "A",
+            Grade::B => "B",
+            Grade::C => "C",
+            Grade::D => "D",
+            Grade::F => "F",
+        };
+        write!(f, "{}", letter)
+    }
+}
+
+impl Grade {
+    // The next grade up, or `None` for the best
+    pub fn better(self) -> Option<Grade> {
+        match self {
+            Grade::A => None,
+            Grade::B => Some(Grade::A),
+            Grade::C => Some(Grade::B),
+            Grade::D => Some(Grade::C),
+            Grade::F => Some(Grade::D),
+        }
+    }
+
+    // The most guesses that still earn this grade against `optimal`, or `None` for a fail,
+    // which has no limit; the inverse of the thresholds in `grade`
+    pub fn most_attempts(self, optimal: u32) -> Option<u32> {
+        let optimal = optimal.max(1);
+        match self {
+            Grade::A => Some(optimal),
+            Grade::B => Some(optimal * 5 / 4),
+            Grade::C => Some(optimal * 6 / 4),
+            Grade::D => Some(optimal * 2),
+            Grade::F => None,
+        }
+    }
+}
+
+// The number of guesses binary search needs in the worst case to find any value in `min..=max`
+//
+// This is ceil(log2(n + 1)) for a range of n values: 7 for 1–100, 10 for 1–1000.
+pub fn optimal_guesses(min: u32, max: u32) -> u32 {
+    let size = u64::from(max - min) + 1; // Widen so the full u32 range doesn't overflow
+    u64::BITS - size.leading_zeros()
+}
+
+// Grade a game from the guesses it took against the optimal count for its range
+//
+// The grade depends on the ratio of actual to optimal guesses:
+//   A  at most the optimal count (lucky guesses can beat it)
+//   B  up to 25% over
+//   C  up to 50% over
+//   D  up to twice the optimal count
+//   F  anything worse
+pub fn grade(attempts: u32, optimal: u32) -> Grade {
+    // Compare in quarters of the optimal count to keep the thresholds exact
+    let (attempts, optimal) = (u64::from(attempts) * 4, u64::from(optimal.max(1)));
+
+    if attempts <= optimal * 4 {
+        Grade::A
+    } else if attempts <= optimal * 5 {
+        Grade::B
+    } else if attempts <= optimal * 6 {
+        Grade::C
+    } else if attempts <= optimal * 8 {
+        Grade::D
+    } else {
+        Grade::F
+    }
+}
+
+// How hard a range is, going by how many values it holds:
+//   Easy     up to 10
+//   Medium   up to 100
+//   Hard     up to 1,000
+//   Expert   up to 100,000
+//   Extreme  anything bigger
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+    Extreme,
+}
+
+impl Difficulty {
+    // Every difficulty, easiest first
+    pub const ALL: [Difficulty; 5] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard, Difficulty::Expert, Difficulty::Extreme];
+
+    // The difficulty of the range `min..=max`
+    pub fn of(min: u32, max: u32) -> Difficulty {
+        let size = u64::from(max.saturating_sub(min)) + 1; // Widen so the full u32 range doesn't overflow
+        match size {
+            0..=10 => Difficulty::Easy,
+            11..=100 => Difficulty::Medium,
+            101..=1_000 => Difficulty::Hard,
+            1_001..=100_000 => Difficulty::Expert,
+            _ => Difficulty::Extreme,
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+            Difficulty::Expert => "Expert",
+            Difficulty::Extreme => "Extreme",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// A readable difficulty for the range `min..=max`, such as "Medium (1–100)"
+pub fn difficulty_label(min: u32, max: u32) -> String {
+    format!("{} ({}–{})", Difficulty::of(min, max), min, max)
+}
+
+// How the wins at one difficulty went on average
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyStats {
+    pub difficulty: Difficulty,
+    pub wins: u32,                      // Wins over ranges of this difficulty
+    pub average_attempts: Option<f64>,  // Guesses per win, or `None` with no wins to average
+    pub average_time: Option<Duration>, // Time per win, or `None` likewise
+}
+
+// The wins broken down by difficulty, one row for every difficulty, easiest first
+//
+// A difficulty nobody has won at still gets its row, with nothing to average, so a table
+// built from it keeps its shape and never divides by zero.
+pub fn by_difficulty(wins: &[Win]) -> Vec<DifficultyStats> {
+    Difficulty::ALL
+        .into_iter()
+        .map(|difficulty| {
+            let (count, attempts, seconds) = wins
+                .iter()
+                .filter(|win| Difficulty::of(win.min, win.max) == difficulty)
+                .fold((0u32, 0u64, 0u64), |(count, attempts, seconds), win| {
+                    (count + 1, attempts + u64::from(win.attempts), seconds + win.took)
+                });
+            let average = |total: u64| (count > 0).then(|| total as f64 / f64::from(count));
+            DifficultyStats {
+                difficulty,
+                wins: count,
+                average_attempts: average(attempts),
+                average_time: average(seconds).map(Duration::from_secs_f64),
+            }
+        })
+        .collect()
+}
+
+// How one guess compared with the best guess available at that point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepReview {
+    pub guess: u32,      // What the player guessed
+    pub low: u32,        // Smallest value still possible before this guess
+    pub high: u32,       // Largest value still possible before this guess
+    pub optimal: u32,    // The midpoint of `low..=high`, which halves the range
+    pub suboptimal: bool, // Whether the guess strayed far from the midpoint
+}
+
+// Why a game got its grade: the counts it was judged on, and the guesses that strayed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GradeBreakdown {
+    pub grade: Grade,
+    pub attempts: u32,                        // Guesses the win took
+    pub optimal: u32,                         // Guesses binary search needs in the worst case
+    pub deviations: Vec<(usize, StepReview)>, // The guesses that strayed far from the midpoint, by index from 0
+    pub next: Option<(Grade, u32)>,           // The grade up from this one and the most guesses it allows
+}
+
+// Explain the grade for a game won in `attempts` guesses, from the step-by-step review of them
+pub fn explain(attempts: u32, optimal: u32, reviews: &[StepReview]) -> GradeBreakdown {
+    let grade = grade(attempts, optimal);
+    GradeBreakdown {
+        grade,
+        attempts,
+        optimal,
+        deviations: reviews.iter().copied().enumerate().filter(|(_, review)| review.suboptimal).collect(),
+        next: grade.better().and_then(|better| Some((better, better.most_attempts(optimal)?))),
+    }
+}
+
+// The guess that splits the feasible range `low..=high` most evenly
+pub fn optimal_guess(low: u32, high: u32) -> u32 {
+    low + (high - low) / 2
+}
+
+// Whether a guess wasted information given the feasible range at the time
+//
+// Guesses outside the feasible range could never have been right. Inside it, a guess
+// counts as sub-optimal when it falls outside the middle half of the range, i.e. more
+// than a quarter of the range's width away from the midpoint. Being one off is always
+// allowed, since tiny ranges have no single best guess.
+pub fn is_suboptimal(guess: u32, low: u32, high: u32) -> bool {
+    if guess < low || guess > high {
+        return true;
+    }
+
+    let distance = u64::from(guess.abs_diff(optimal_guess(low, high)));
+    let width = u64::from(high - low) + 1;
+    distance > (width / 4).max(1)
+}
+
+// Replay a game's guesses, narrowing the feasible range step by step
+//
+// The winning guess is never flagged: however it was chosen, nothing was wasted.
+pub fn review_guesses(min: u32, max: u32, secret: u32, guesses: &[u32]) -> Vec<StepReview> {
+    let (mut low, mut high) = (min, max);
+
+    guesses
+        .iter()
+        .map(|&guess| {
+            let review = StepReview {
+                guess,
+                low,
+                high,
+                optimal: optimal_guess(low, high),
+                suboptimal: guess != secret && is_suboptimal(guess, low, high),
+            };
+
+            // Apply the feedback this guess produced before reviewing the next one
+            if guess < secret {
+                low = low.max(guess + 1);
+            } else if guess > secret {
+                high = high.min(guess - 1);
+            }
+
+            review
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_search_needs_the_bits_of_the_range() {
+        let cases = [(1, 1, 1), (1, 2, 2), (1, 3, 2), (1, 100, 7), (1, 127, 7), (1, 128, 8), (1, 1_000, 10), (0, u32::MAX, 33)];
+        for (min, max, optimal) in cases {
+            assert_eq!(optimal_guesses(min, max), optimal, "{}-{}", min, max);
+        }
+    }
+
+    #[test]
+    fn grades_follow_the_ratio_to_optimal() {
+        // Against 8, the thresholds land on whole guesses: 8, 10, 12 and 16
+        let cases = [
+            (1, Grade::A),
+            (8, Grade::A),
+            (9, Grade::B),
+            (10, Grade::B),
+            (11, Grade::C),
+            (12, Grade::C),
+            (13, Grade::D),
+            (16, Grade::D),
+            (17, Grade::F),
+        ];
+        for (attempts, expected) in cases {
+            assert_eq!(grade(attempts, 8), expected, "{} guesses", attempts);
+        }
+    }
+
+    #[test]
+    fn grades_against_an_awkward_optimal_stay_exact() {
+        // Against 7, B allows 8.75 guesses and C 10.5
+        assert_eq!(grade(7, 7), Grade::A);
+        assert_eq!(grade(8, 7), Grade::B);
+        assert_eq!(grade(9, 7), Grade::C);
+        assert_eq!(grade(10, 7), Grade::C);
+        assert_eq!(grade(11, 7), Grade::D);
+        assert_eq!(grade(14, 7), Grade::D);
+        assert_eq!(grade(15, 7), Grade::F);
+    }
+
+    #[test]
+    fn an_optimal_of_zero_grades_like_one_and_huge_counts_dont_overflow() {
+        assert_eq!(grade(1, 0), Grade::A);
+        assert_eq!(grade(2, 0), Grade::D);
+        assert_eq!(grade(u32::MAX, u32::MAX), Grade::A);
+        assert_eq!(grade(u32::MAX, 1), Grade::F);
+    }
+
+    #[test]
+    fn the_best_guess_halves_the_range() {
+        assert_eq!(optimal_guess(1, 100), 50);
+        assert_eq!(optimal_guess(51, 100), 75);
+        assert_eq!(optimal_guess(7, 7), 7);
+        assert_eq!(optimal_guess(0, u32::MAX), u32::MAX / 2, "no overflow at the top of the range");
+    }
+
+    #[test]
+    fn guesses_outside_the_middle_half_are_suboptimal() {
+        // 1-100 is 100 wide, so anything more than 25 from 50 strays
+        assert!(!is_suboptimal(50, 1, 100));
+        assert!(!is_suboptimal(25, 1, 100));
+        assert!(!is_suboptimal(75, 1, 100));
+        assert!(is_suboptimal(24, 1, 100));
+        assert!(is_suboptimal(76, 1, 100));
+        assert!(is_suboptimal(1, 1, 100));
+    }
+
+    #[test]
+    fn guesses_that_were_already_ruled_out_are_suboptimal() {
+        assert!(is_suboptimal(50, 51, 100));
+        assert!(is_suboptimal(101, 51, 100));
+    }
+
+    #[test]
+    fn one_off_is_always_fine_in_a_tiny_range() {
+        assert!(!is_suboptimal(1, 1, 3));
+        assert!(!is_suboptimal(3, 1, 3));
+        assert!(!is_suboptimal(4, 4, 5));
+        assert!(!is_suboptimal(9, 9, 9));
+    }
+
+    #[test]
+    fn the_review_narrows_the_range_as_the_game_did() {
+        let reviews = review_guesses(1, 100, 37, &[50, 25, 90, 37]);
+        let ranges: Vec<(u32, u32, u32)> = reviews.iter().map(|review| (review.low, review.high, review.optimal)).collect();
+        assert_eq!(ranges, [(1, 100, 50), (1, 49, 25), (26, 49, 37), (26, 49, 37)]);
+        let flagged: Vec<bool> = reviews.iter().map(|review| review.suboptimal).collect();
+        assert_eq!(flagged, [false, false, true, false], "90 was already ruled out");
+    }
+
+    #[test]
+    fn the_winning_guess_is_never_flagged() {
+        let reviews = review_guesses(1, 100, 1, &[1]);
+        assert_eq!(reviews, [StepReview { guess: 1, low: 1, high: 100, optimal: 50, suboptimal: false }]);
+    }
+
+    #[test]
+    fn a_review_of_no_guesses_is_empty() {
+        assert!(review_guesses(1, 100, 37, &[]).is_empty());
+    }
+
+    #[test]
+    fn difficulty_goes_by_how_many_values_the_range_holds() {
+        let cases = [
+            (1, 1, Difficulty::Easy),
+            (1, 10, Difficulty::Easy),
+            (1, 11, Difficulty::Medium),
+            (0, 99, Difficulty::Medium),
+            (1, 100, Difficulty::Medium),
+            (1, 101, Difficulty::Hard),
+            (1, 1_000, Difficulty::Hard),
+            (1, 1_001, Difficulty::Expert),
+            (1, 100_000, Difficulty::Expert),
+            (1, 100_001, Difficulty::Extreme),
+            (0, u32::MAX, Difficulty::Extreme),
+        ];
+        for (min, max, expected) in cases {
+            assert_eq!(Difficulty::of(min, max), expected, "{}-{}", min, max);
+        }
+    }
+
+    #[test]
+    fn a_backwards_range_counts_as_a_single_value() {
+        assert_eq!(Difficulty::of(100, 1), Difficulty::Easy);
+    }
+
+    #[test]
+    fn the_difficulty_label_names_the_range() {
+        assert_eq!(difficulty_label(1, 100), "Medium (1–100)");
+        assert_eq!(difficulty_label(1, 1_000_000), "Extreme (1–1000000)");
+    }
+
+    // A win over `min..=max` in `attempts` guesses that took `took` seconds
+    fn win(min: u32, max: u32, attempts: u32, took: u64) -> Win {
+        Win { time: 1_700_000_000, min, max, attempts, took }
+    }
+
+    #[test]
+    fn every_difficulty_gets_a_row_even_without_wins() {
+        let stats = by_difficulty(&[]);
+        assert_eq!(stats.iter().map(|row| row.difficulty).collect::<Vec<_>>(), Difficulty::ALL);
+        for row in stats {
+            assert_eq!(row.wins, 0);
+            assert_eq!(row.average_attempts, None, "nothing to average for {}", row.difficulty);
+            assert_eq!(row.average_time, None);
+        }
+    }
+
+    #[test]
+    fn wins_are_averaged_within_their_difficulty() {
+        let wins = [win(1, 100, 6, 20), win(1, 100, 9, 41), win(1, 1_000, 10, 30), win(1, 100, 7, 30)];
+        let stats = by_difficulty(&wins);
+        let medium = stats[1];
+        assert_eq!(medium.difficulty, Difficulty::Medium);
+        assert_eq!(medium.wins, 3);
+        assert_eq!(medium.average_attempts, Some(22.0 / 3.0));
+        assert_eq!(medium.average_time, Some(Duration::from_secs_f64(91.0 / 3.0)));
+
+        let hard = stats[2];
+        assert_eq!((hard.wins, hard.average_attempts, hard.average_time), (1, Some(10.0), Some(Duration::from_secs(30))));
+        for empty in [stats[0], stats[3], stats[4]] {
+            assert_eq!((empty.wins, empty.average_attempts, empty.average_time), (0, None, None), "{}", empty.difficulty);
+        }
+    }
+
+    #[test]
+    fn wins_read_from_the_log_carry_how_long_they_took() {
+        let log = "100|start|classic|1|1000\n130|guess|500|too_big\n175|end|won|250|8\n";
+        let stats = by_difficulty(&crate::gamelog::parse_wins(log));
+        assert_eq!(stats[2].wins, 1);
+        assert_eq!(stats[2].average_time, Some(Duration::from_secs(75)));
+    }
+
+    #[test]
+    fn grades_are_shown_as_letters() {
+        let letters: Vec<String> = [Grade::A, Grade::B, Grade::C, Grade::D, Grade::F].iter().map(Grade::to_string).collect();
+        assert_eq!(letters, ["A", "B", "C", "D", "F"]);
+    }
+}
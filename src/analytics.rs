@@ -0,0 +1,310 @@
+// Opt-in play statistics, kept only on this computer
+//
+// Events are appended as JSON lines to a file in the data directory, so the user can
+// read exactly what was recorded. Nothing is ever sent anywhere.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::storage;
+
+// File the events are appended to, inside the data directory
+pub const FILE_NAME: &str = "analytics.jsonl";
+
+// Something worth counting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    SessionStarted,
+    SessionEnded,
+    GameStarted(&'static str), // The mode's key, such as "classic"
+    GameWon(u32),              // Guesses the win took
+    FeatureUsed(&'static str), // The feature's key, such as "review"
+}
+
+impl Event {
+    // The event's name and detail as written to the file
+    fn parts(self) -> (&'static str, String) {
+        match self {
+            Event::SessionStarted => ("session_started", String::new()),
+            Event::SessionEnded => ("session_ended", String::new()),
+            Event::GameStarted(mode) => ("game_started", mode.to_string()),
+            Event::GameWon(attempts) => ("game_won", attempts.to_string()),
+            Event::FeatureUsed(feature) => ("feature_used", feature.to_string()),
+        }
+    }
+}
+
+// One event read back from the file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub time: u64,      // Seconds since the Unix epoch
+    pub event: String,  // The event's name
+    pub detail: String, // The mode, feature or guess count, if the event has one
+}
+
+// Append an event, logging rather than failing if the file can't be written
+pub fn record(event: Event) {
+    let time = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+    let (name, detail) = event.parts();
+
+    // Names and details are plain identifiers and numbers, so nothing needs escaping
+    let line = format!("{{\"time\":{},\"event\":\"{}\",\"detail\":\"{}\"}}\n", time, name, detail);
+    if let Err(error) = storage::append(FILE_NAME, &line) {
+        tracing::warn!(%error, "Could not record play statistics");
+    }
+}
+
+// Read every recorded event, skipping lines that weren't written by `record`
+pub fn load() -> Vec<Record> {
+    match storage::load(FILE_NAME) {
+        Ok(contents) => contents.unwrap_or_default().lines().filter_map(parse_record).collect(),
+        Err(error) => {
+            tracing::warn!(%error, "Could not read play statistics");
+            Vec::new()
+        }
+    }
+}
+
+// Delete everything recorded so far
+pub fn delete() {
+    if let Err(error) = storage::remove(FILE_NAME) {
+        tracing::warn!(%error, "Could not delete play statistics");
+    }
+}
+
+// Read one line in the flat format `record` writes
+fn parse_record(line: &str) -> Option<Record> {
+    let body = line.trim().strip_prefix('{')?.strip_suffix('}')?;
+    let mut fields = BTreeMap::new();
+    for field in body.split(',') {
+        let (key, value) = field.split_once(':')?;
+        let key = key.trim().strip_prefix('"')?.strip_suffix('"')?;
+        let value = value.trim();
+        fields.insert(key, value.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(value));
+    }
+
+    Some(Record {
+        time: fields.get("time")?.parse().ok()?,
+        event: fields.get("event")?.to_string(),
+        detail: fields.get("detail")?.to_string(),
+    })
+}
+
+// Trends worked out from the recorded events
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Insights {
+    pub sessions: u32,                       // Sessions started
+    pub average_session: Option<Duration>,   // Mean length of sessions that ended cleanly
+    pub games_per_mode: BTreeMap<String, u32>, // Games started in each mode
+    pub wins: u32,                           // Games won
+    pub average_attempts: Option<f64>,       // Mean guesses per win
+    pub feature_use: BTreeMap<String, u32>,  // Times each feature was used
+}
+
+impl Insights {
+    // Summarize the events, which are expected oldest first as they appear in the file
+    pub fn from_records(records: &[Record]) -> Insights {
+        let mut insights = Insights::default();
+        let mut session_start = None;
+        let (mut session_total, mut ended_sessions) = (0, 0);
+        let mut total_attempts = 0u64;
+
+        for record in records {
+            match record.event.as_str() {
+                "session_started" => {
+                    insights.sessions += 1;
+                    session_start = Some(record.time);
+                }
+                // Only pair an end with the session it closes; crashed sessions have no end
+                "session_ended" => {
+                    if let Some(start) = session_start.take() {
+                        session_total += record.time.saturating_sub(start);
+                        ended_sessions += 1;
+                    }
+                }
+                "game_started" => *insights.games_per_mode.entry(record.detail.clone()).or_default() += 1,
+                "game_won" => {
+                    if let Ok(attempts) = record.detail.parse::<u64>() {
+                        insights.wins += 1;
+                        total_attempts += attempts;
+                    }
+                }
+                "feature_used" => *insights.feature_use.entry(record.detail.clone()).or_default() += 1,
+                _ => {} // Events from newer versions are ignored
+            }
+        }
+
+        insights.average_session = (ended_sessions > 0).then(|| Duration::from_secs(session_total / ended_sessions));
+        insights.average_attempts = (insights.wins > 0).then(|| total_attempts as f64 / f64::from(insights.wins));
+        insights
+    }
+
+    // The mode with the most games, and how many games it has
+    pub fn favourite_mode(&self) -> Option<(&str, u32)> {
+        most_common(&self.games_per_mode)
+    }
+
+    // The feature used most often, and how many times
+    pub fn favourite_feature(&self) -> Option<(&str, u32)> {
+        most_common(&self.feature_use)
+    }
+
+    // Games started across every mode
+    pub fn games(&self) -> u32 {
+        self.games_per_mode.values().sum()
+    }
+}
+
+// The key with the highest count, preferring the first alphabetically on a tie
+fn most_common(counts: &BTreeMap<String, u32>) -> Option<(&str, u32)> {
+    counts
+        .iter()
+        .fold(None, |best: Option<(&str, u32)>, (key, &count)| match best {
+            Some((_, best_count)) if best_count >= count => best,
+            _ => Some((key.as_str(), count)),
+        })
+}
+
+// A readable name for a mode or feature key
+pub fn label(key: &str) -> &str {
+    match key {
+        "classic" => "Classic",
+        "mystery" => "Mystery operation",
+        "computer_guesses" => "Computer guesses",
+        "retry" => "Retries",
+        "practice" => "Practice drills",
+        "tournament" => "Tournaments",
+        "endless" => "Endless",
+        "golf" => "Golf courses",
+        "pass_and_play" => "pass and play",
+        "review" => "the guess review",
+        "grade_breakdown" => "the grade breakdown",
+        "deduce" => "deducing from constraints",
+        "gallery" => "the demo gallery",
+        "candidates" => "the candidates count",
+        "hints" => "hints",
+        "peek" => "the peek",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: u64, event: &str, detail: &str) -> Record {
+        Record { time, event: event.to_string(), detail: detail.to_string() }
+    }
+
+    #[test]
+    fn recorded_events_read_back_in_order() {
+        delete();
+        for event in [
+            Event::SessionStarted,
+            Event::GameStarted("classic"),
+            Event::FeatureUsed("hints"),
+            Event::GameWon(6),
+            Event::SessionEnded,
+        ] {
+            record(event);
+        }
+
+        let records = load();
+        let read: Vec<(&str, &str)> = records.iter().map(|r| (r.event.as_str(), r.detail.as_str())).collect();
+        assert_eq!(
+            read,
+            [
+                ("session_started", ""),
+                ("game_started", "classic"),
+                ("feature_used", "hints"),
+                ("game_won", "6"),
+                ("session_ended", ""),
+            ]
+        );
+        assert!(records.windows(2).all(|pair| pair[0].time <= pair[1].time));
+
+        delete();
+        assert!(load().is_empty());
+    }
+
+    #[test]
+    fn lines_not_written_by_record_are_skipped() {
+        assert_eq!(
+            parse_record("{\"time\":12,\"event\":\"game_won\",\"detail\":\"4\"}"),
+            Some(at(12, "game_won", "4"))
+        );
+        assert_eq!(parse_record(" { \"time\": 3 , \"event\": \"x\", \"detail\": \"\" } "), Some(at(3, "x", "")));
+        for line in [
+            "",
+            "not json",
+            "{\"time\":\"soon\",\"event\":\"game_won\",\"detail\":\"4\"}",
+            "{\"event\":\"game_won\",\"detail\":\"4\"}",
+            "{\"time\":1,\"event\":\"game_won\"}",
+            "{\"time\":1,\"event\":\"game_won\",\"detail\":\"4\"",
+            "{time:1,\"event\":\"game_won\",\"detail\":\"4\"}",
+        ] {
+            assert_eq!(parse_record(line), None, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn a_damaged_file_keeps_its_good_lines() {
+        storage::save(FILE_NAME, "{\"time\":1,\"event\":\"session_started\",\"detail\":\"\"}\ngarbage\n{\"time\":2,\"ev").unwrap();
+        assert_eq!(load(), [at(1, "session_started", "")]);
+        delete();
+    }
+
+    #[test]
+    fn insights_sum_up_the_events() {
+        let insights = Insights::from_records(&[
+            at(100, "session_started", ""),
+            at(110, "game_started", "classic"),
+            at(120, "game_won", "5"),
+            at(130, "game_started", "classic"),
+            at(140, "game_won", "8"),
+            at(150, "feature_used", "review"),
+            at(160, "session_ended", ""),
+            at(200, "session_started", ""), // Crashed, so it has no end
+            at(300, "session_started", ""),
+            at(310, "game_started", "golf"),
+            at(320, "game_won", "lots"), // Not a count, so not a win
+            at(330, "feature_used", "hints"),
+            at(340, "feature_used", "review"),
+            at(350, "newer_event", "whatever"),
+            at(400, "session_ended", ""),
+            at(500, "session_ended", ""), // Closes nothing
+        ]);
+
+        assert_eq!(insights.sessions, 3);
+        assert_eq!(insights.average_session, Some(Duration::from_secs((60 + 100) / 2)));
+        assert_eq!(insights.games(), 3);
+        assert_eq!(insights.favourite_mode(), Some(("classic", 2)));
+        assert_eq!(insights.wins, 2);
+        assert_eq!(insights.average_attempts, Some(6.5));
+        assert_eq!(insights.favourite_feature(), Some(("review", 2)));
+    }
+
+    #[test]
+    fn no_events_make_empty_insights() {
+        let insights = Insights::from_records(&[]);
+        assert_eq!(insights, Insights::default());
+        assert_eq!(insights.average_session, None);
+        assert_eq!(insights.average_attempts, None);
+        assert_eq!(insights.favourite_mode(), None);
+        assert_eq!(insights.favourite_feature(), None);
+        assert_eq!(insights.games(), 0);
+    }
+
+    #[test]
+    fn ties_go_to_the_first_key_alphabetically() {
+        let counts = BTreeMap::from([("golf".to_string(), 2), ("classic".to_string(), 2), ("endless".to_string(), 1)]);
+        assert_eq!(most_common(&counts), Some(("classic", 2)));
+    }
+
+    #[test]
+    fn unknown_keys_are_shown_as_they_are() {
+        assert_eq!(label("classic"), "Classic");
+        assert_eq!(label("hints"), "hints");
+        assert_eq!(label("warmup"), "warmup");
+    }
+}
@@ -0,0 +1,168 @@
+// Numeric animations driven by the clock tick
+
+use std::time::{Duration, Instant};
+
+// How long a reveal takes from first frame to the final value
+const REVEAL_DURATION: Duration = Duration::from_millis(900);
+
+// How long the thermometer takes to settle on a new reading, a few clock ticks
+const GAUGE_DURATION: Duration = Duration::from_millis(750);
+
+// How long a highlight takes to fade away, long enough to find it on screen first
+const FADE_DURATION: Duration = Duration::from_secs(4);
+
+// Ease out along a cubic, so motion starts fast and settles gently; `progress` runs from 0 to 1
+pub fn ease_out(progress: f64) -> f64 {
+    1.0 - (1.0 - progress.clamp(0.0, 1.0)).powi(3)
+}
+
+// A counter running from one number to another, slowing as it nears the end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CountTo {
+    from: u32,        // The value shown on the first frame
+    to: u32,          // The value shown once finished, exactly
+    started: Instant, // When the first frame was shown
+}
+
+impl CountTo {
+    pub fn new(from: u32, to: u32, started: Instant) -> CountTo {
+        CountTo { from, to, started }
+    }
+
+    // How far through the animation `now` is, from 0 to 1
+    fn progress(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.started);
+        (elapsed.as_secs_f64() / REVEAL_DURATION.as_secs_f64()).min(1.0)
+    }
+
+    // Whether the final value has been reached
+    pub fn is_finished(&self, now: Instant) -> bool {
+        self.progress(now) >= 1.0
+    }
+
+    // The value to show at `now`, which is always exactly `to` once finished
+    pub fn value(&self, now: Instant) -> u32 {
+        if self.is_finished(now) {
+            return self.to; // Never trust floating point for the number that matters
+        }
+
+        // Ease out, so the count races at first and settles onto the secret
+        let eased = ease_out(self.progress(now));
+        let distance = f64::from(self.to) - f64::from(self.from);
+        (f64::from(self.from) + distance * eased).round() as u32
+    }
+
+    // A flourish growing around the value, for a counter with no distance to cover
+    pub fn flourish(&self, now: Instant) -> usize {
+        (self.progress(now) * 3.0).ceil() as usize
+    }
+}
+
+// A level gliding from one reading to the next, such as the proximity thermometer's
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Gauge {
+    from: f32,                // The level when the latest move began
+    to: f32,                  // The level the gauge is moving to
+    started: Option<Instant>, // When the latest move began, or `None` if it never moved
+}
+
+impl Gauge {
+    // Move to `level` from wherever the gauge is at `now`, so a move never jumps
+    pub fn set(&mut self, level: f32, now: Instant) {
+        self.from = self.level(now);
+        self.to = level;
+        self.started = Some(now);
+    }
+
+    // How far through the latest move `now` is, from 0 to 1
+    fn progress(&self, now: Instant) -> f64 {
+        let Some(started) = self.started else {
+            return 1.0;
+        };
+        let elapsed = now.saturating_duration_since(started);
+        (elapsed.as_secs_f64() / GAUGE_DURATION.as_secs_f64()).min(1.0)
+    }
+
+    // Whether the gauge is still on its way to the latest reading
+    pub fn is_moving(&self, now: Instant) -> bool {
+        self.progress(now) < 1.0
+    }
+
+    // The level to draw at `now`, exactly the latest reading once the move is over
+    pub fn level(&self, now: Instant) -> f32 {
+        if !self.is_moving(now) {
+            return self.to;
+        }
+        self.from + (self.to - self.from) * ease_out(self.progress(now)) as f32
+    }
+}
+
+// A highlight fading from full strength to nothing, such as a new leaderboard entry's
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fade {
+    started: Instant, // When it was first shown at full strength
+}
+
+impl Fade {
+    pub fn new(started: Instant) -> Fade {
+        Fade { started }
+    }
+
+    // How far through the fade `now` is, from 0 to 1
+    fn progress(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.started);
+        (elapsed.as_secs_f64() / FADE_DURATION.as_secs_f64()).min(1.0)
+    }
+
+    // Whether there's still some highlight left to draw
+    pub fn is_fading(&self, now: Instant) -> bool {
+        self.progress(now) < 1.0
+    }
+
+    // How strongly to draw the highlight at `now`, from 1 when it appears to 0 once faded
+    pub fn strength(&self, now: Instant) -> f32 {
+        1.0 - ease_out(self.progress(now)) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn easing_starts_and_ends_on_the_endpoints() {
+        assert_eq!(ease_out(0.0), 0.0);
+        assert_eq!(ease_out(0.5), 0.875, "past halfway by the middle");
+        assert_eq!(ease_out(1.0), 1.0);
+        assert_eq!(ease_out(-0.5), 0.0, "progress before the start holds at the start");
+        assert_eq!(ease_out(1.5), 1.0, "and after the end at the end");
+    }
+
+    #[test]
+    fn a_gauge_glides_to_its_reading_and_stays_there() {
+        let start = Instant::now();
+        let mut gauge = Gauge::default();
+        assert_eq!(gauge.level(start), 0.0);
+        assert!(!gauge.is_moving(start), "a gauge that never moved is at rest");
+
+        gauge.set(1.0, start);
+        assert_eq!(gauge.level(start), 0.0);
+        assert_eq!(gauge.level(start + GAUGE_DURATION / 2), 0.875);
+        assert!(!gauge.is_moving(start + GAUGE_DURATION));
+        assert_eq!(gauge.level(start + GAUGE_DURATION * 2), 1.0);
+    }
+
+    #[test]
+    fn a_new_reading_mid_move_carries_on_from_where_the_gauge_is() {
+        let start = Instant::now();
+        let mut gauge = Gauge::default();
+        gauge.set(1.0, start);
+
+        let midway = start + GAUGE_DURATION / 2;
+        let shown = gauge.level(midway);
+        gauge.set(0.0, midway);
+        assert_eq!(gauge.level(midway), shown, "no jump when the reading changes");
+        assert!(gauge.level(midway + GAUGE_DURATION / 4) < shown);
+        assert_eq!(gauge.level(midway + GAUGE_DURATION), 0.0);
+    }
+}
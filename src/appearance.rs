@@ -0,0 +1,162 @@
+// Working out which theme to display from the user's choice and the operating system
+
+use std::process::Command;
+
+use crate::color::Rgb;
+use crate::preferences::ThemeChoice;
+
+// The accent colors macOS offers, by the number it stores, from -1 for graphite
+const MACOS_ACCENTS: [Rgb; 8] = [
+    Rgb::new(0x8E, 0x8E, 0x93), // Graphite
+    Rgb::new(0xFF, 0x3B, 0x30), // Red
+    Rgb::new(0xFF, 0x95, 0x00), // Orange
+    Rgb::new(0xFF, 0xCC, 0x00), // Yellow
+    Rgb::new(0x28, 0xCD, 0x41), // Green
+    Rgb::new(0x00, 0x7A, 0xFF), // Blue, also used when none was picked
+    Rgb::new(0xAF, 0x52, 0xDE), // Purple
+    Rgb::new(0xFF, 0x2D, 0x55), // Pink
+];
+
+// The accent colors GNOME offers, by the name it stores
+const GNOME_ACCENTS: [(&str, Rgb); 9] = [
+    ("blue", Rgb::new(0x35, 0x84, 0xE4)),
+    ("teal", Rgb::new(0x21, 0x90, 0xA4)),
+    ("green", Rgb::new(0x3A, 0x94, 0x4A)),
+    ("yellow", Rgb::new(0xC8, 0x88, 0x00)),
+    ("orange", Rgb::new(0xED, 0x5B, 0x00)),
+    ("red", Rgb::new(0xE6, 0x2D, 0x42)),
+    ("pink", Rgb::new(0xD5, 0x61, 0x99)),
+    ("purple", Rgb::new(0x91, 0x41, 0xAC)),
+    ("slate", Rgb::new(0x6F, 0x83, 0x96)),
+];
+
+// The light/dark preference reported by the operating system
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemTheme {
+    Light,
+    Dark,
+}
+
+// Ask the operating system for its light/dark preference, or `None` if it can't be determined
+pub fn detect_system_theme() -> Option<SystemTheme> {
+    match dark_light::detect() {
+        dark_light::Mode::Dark => Some(SystemTheme::Dark),
+        dark_light::Mode::Light => Some(SystemTheme::Light),
+        dark_light::Mode::Default => None, // The platform gave no answer
+    }
+}
+
+// Ask the operating system whether the user wants reduced motion, or `None` if it can't be told
+//
+// macOS exposes this as an accessibility default and GNOME as its animations switch.
+// Elsewhere, including Windows, there is no setting we can read without a platform API.
+pub fn detect_reduced_motion() -> Option<bool> {
+    let (program, args, reduced) = if cfg!(target_os = "macos") {
+        ("defaults", &["read", "com.apple.universalaccess", "reduceMotion"][..], "1")
+    } else if cfg!(all(unix, not(target_os = "macos"))) {
+        ("gsettings", &["get", "org.gnome.desktop.interface", "enable-animations"][..], "false")
+    } else {
+        return None;
+    };
+
+    Some(read_setting(program, args)? == reduced)
+}
+
+// Ask the operating system for its accent color, or `None` where there isn't one to read
+//
+// Windows keeps it in the registry, macOS and GNOME (from version 47) as one of a fixed
+// set of choices, and KDE as red, green and blue; KDE leaves it empty when the color
+// scheme decides it instead.
+pub fn detect_accent_color() -> Option<Rgb> {
+    if cfg!(target_os = "windows") {
+        // One line of the output is the value, as in "AccentColor    REG_DWORD    0xffd77800"
+        let output = read_setting("reg", &["query", r"HKCU\Software\Microsoft\Windows\DWM", "/v", "AccentColor"])?;
+        let value = output.split_whitespace().find_map(|word| word.strip_prefix("0x"))?;
+        let [r, g, b, _] = u32::from_str_radix(value, 16).ok()?.to_le_bytes(); // Stored as 0xAABBGGRR
+        Some(Rgb::new(r, g, b))
+    } else if cfg!(target_os = "macos") {
+        // The key is only written once another accent than the default blue is picked
+        let choice = read_setting("defaults", &["read", "-g", "AppleAccentColor"]).map_or(Some(4), |value| value.parse().ok());
+        let index = usize::try_from(choice? + 1).ok()?;
+        MACOS_ACCENTS.get(index).copied()
+    } else if cfg!(all(unix, not(target_os = "macos"))) {
+        let gnome = read_setting("gsettings", &["get", "org.gnome.desktop.interface", "accent-color"]).and_then(|name| {
+            let name = name.trim_matches('\'');
+            GNOME_ACCENTS.iter().find(|(key, _)| *key == name).map(|&(_, color)| color)
+        });
+        gnome.or_else(|| {
+            let kde = ["kreadconfig6", "kreadconfig5"]
+                .into_iter()
+                .find_map(|program| read_setting(program, &["--group", "General", "--key", "AccentColor"]))?;
+            let channels: Vec<u8> = kde.split(',').map(|channel| channel.trim().parse().ok()).collect::<Option<_>>()?;
+            match channels[..] {
+                [r, g, b] | [r, g, b, _] => Some(Rgb::new(r, g, b)),
+                _ => None,
+            }
+        })
+    } else {
+        None
+    }
+}
+
+// Run a tool that prints a system setting, returning what it printed, trimmed
+//
+// A missing tool or key just means the setting isn't available here.
+fn read_setting(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok().filter(|output| output.status.success())?;
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+// Decide whether to display the dark theme
+//
+// A manual Light/Dark choice always wins, then the detected system preference, and
+// finally light when following the system but detection failed.
+pub fn is_dark(choice: ThemeChoice, system: Option<SystemTheme>) -> bool {
+    match choice {
+        ThemeChoice::Light => false,
+        ThemeChoice::Dark => true,
+        ThemeChoice::System | ThemeChoice::SystemAccent => system == Some(SystemTheme::Dark),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_manual_choice_ignores_the_system() {
+        for system in [None, Some(SystemTheme::Light), Some(SystemTheme::Dark)] {
+            assert!(!is_dark(ThemeChoice::Light, system));
+            assert!(is_dark(ThemeChoice::Dark, system));
+        }
+    }
+
+    #[test]
+    fn following_the_system_uses_what_it_reports() {
+        for choice in [ThemeChoice::System, ThemeChoice::SystemAccent] {
+            assert!(is_dark(choice, Some(SystemTheme::Dark)));
+            assert!(!is_dark(choice, Some(SystemTheme::Light)));
+        }
+    }
+
+    #[test]
+    fn following_the_system_falls_back_to_light_when_it_cant_be_told() {
+        assert!(!is_dark(ThemeChoice::System, None));
+        assert!(!is_dark(ThemeChoice::SystemAccent, None));
+    }
+
+    #[test]
+    fn only_the_system_choices_need_watching() {
+        let watched: Vec<ThemeChoice> = ThemeChoice::ALL.into_iter().filter(|choice| choice.follows_system()).collect();
+        assert_eq!(watched, [ThemeChoice::System, ThemeChoice::SystemAccent]);
+    }
+
+    #[test]
+    fn theme_choices_round_trip_through_their_keys() {
+        for choice in ThemeChoice::ALL {
+            assert_eq!(ThemeChoice::from_key(choice.key()), Some(choice));
+        }
+        assert_eq!(ThemeChoice::from_key("Dark"), None, "keys are lowercase");
+    }
+}
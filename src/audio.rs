@@ -0,0 +1,84 @@
+// Short generated tones, played in the background
+
+use std::thread;
+use std::time::Duration;
+
+use rodio::{source::SineWave, OutputStream, Sink, Source};
+
+// Pitch of the tone for a guess as far from the secret as the range allows
+pub const FAR_FREQUENCY: f32 = 200.0;
+
+// Pitch of the tone for a guess within `CLOSE_DISTANCE` of the secret
+pub const CLOSE_FREQUENCY: f32 = 1000.0;
+
+// Guesses this close to the secret all get the top pitch
+pub const CLOSE_DISTANCE: u32 = 2;
+
+// How long each tone lasts, and how loud it is at full scale
+const TONE_LENGTH: Duration = Duration::from_millis(150);
+const TONE_VOLUME: f32 = 0.2;
+
+// The pitch for a guess `distance` away from the secret, in a range `span` values wide
+//
+// The pitch rises continuously from `FAR_FREQUENCY` for the furthest possible guess to
+// `CLOSE_FREQUENCY` within `CLOSE_DISTANCE`, and never leaves that band.
+pub fn proximity_frequency(distance: u32, span: u32) -> f32 {
+    if distance <= CLOSE_DISTANCE || span <= CLOSE_DISTANCE {
+        return CLOSE_FREQUENCY;
+    }
+
+    let closeness = 1.0 - (distance - CLOSE_DISTANCE) as f32 / (span - CLOSE_DISTANCE) as f32;
+    FAR_FREQUENCY + (CLOSE_FREQUENCY - FAR_FREQUENCY) * closeness.clamp(0.0, 1.0)
+}
+
+// Play a sine tone without blocking the UI, logging rather than failing if there's no audio device
+pub fn play_tone(frequency: f32) {
+    thread::spawn(move || {
+        // The stream has to outlive the sink, so both live on this thread until the tone ends
+        let (_stream, handle) = match OutputStream::try_default() {
+            Ok(output) => output,
+            Err(error) => return tracing::warn!(%error, "Could not open the audio output"),
+        };
+        let sink = match Sink::try_new(&handle) {
+            Ok(sink) => sink,
+            Err(error) => return tracing::warn!(%error, "Could not play a tone"),
+        };
+
+        sink.append(SineWave::new(frequency).take_duration(TONE_LENGTH).amplify(TONE_VOLUME));
+        sink.sleep_until_end();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_pitch_spans_the_band_from_far_to_close() {
+        assert_eq!(proximity_frequency(99, 99), FAR_FREQUENCY);
+        assert_eq!(proximity_frequency(CLOSE_DISTANCE, 99), CLOSE_FREQUENCY);
+        assert_eq!(proximity_frequency(0, 99), CLOSE_FREQUENCY);
+        assert_eq!(proximity_frequency(CLOSE_DISTANCE + 1, 99), FAR_FREQUENCY + (CLOSE_FREQUENCY - FAR_FREQUENCY) * (96.0 / 97.0));
+    }
+
+    #[test]
+    fn the_pitch_rises_as_guesses_get_closer() {
+        for span in [3, 10, 99, 999, u32::MAX] {
+            let mut previous = FAR_FREQUENCY;
+            for distance in (0..=span.min(2000)).rev() {
+                let frequency = proximity_frequency(distance, span);
+                assert!((FAR_FREQUENCY..=CLOSE_FREQUENCY).contains(&frequency), "{} of {}", distance, span);
+                assert!(frequency >= previous, "{} of {} fell to {}", distance, span, frequency);
+                previous = frequency;
+            }
+        }
+    }
+
+    #[test]
+    fn odd_inputs_stay_within_the_band() {
+        assert_eq!(proximity_frequency(500, 99), FAR_FREQUENCY, "further than the range clamps to the far pitch");
+        assert_eq!(proximity_frequency(5, 2), CLOSE_FREQUENCY, "a range this narrow is always close");
+        assert_eq!(proximity_frequency(0, 0), CLOSE_FREQUENCY);
+        assert_eq!(proximity_frequency(u32::MAX, u32::MAX), FAR_FREQUENCY);
+    }
+}
@@ -0,0 +1,129 @@
+// Command-line benchmarks of the computer player's strategies and of the guess review
+
+use std::cmp::Ordering;
+use std::time::{Duration, Instant};
+
+use crate::analysis;
+use crate::format::Locale;
+use crate::history;
+use crate::random::seeded_secret;
+use crate::solver::{Answer, BotGame, BotStrategy};
+use crate::viewmodel;
+
+// Games played by each strategy; every strategy faces the same seeded secrets
+const GAMES: u32 = 10_000;
+
+// Guesses in the history the review is benchmarked on, and the scroll positions it's drawn at
+const HISTORY: usize = 10_000;
+const FRAMES: usize = 200;
+
+// The slowest a frame's rows may take to build before the review would feel sluggish
+const FRAME_BUDGET: Duration = Duration::from_millis(16);
+
+// Play every registered strategy against the same secrets and print how many guesses they took
+pub fn run(min: u32, max: u32) {
+    println!("{} games per strategy, secrets from {} to {}", GAMES, min, max);
+
+    for &strategy in BotStrategy::ALL {
+        let mut attempts = Vec::with_capacity(GAMES as usize);
+        let mut failures = 0;
+
+        for game in 0..GAMES {
+            // Seed the bot apart from the secret, or a random bot would simply draw the secret
+            let secret = seeded_secret(game, min, max);
+            match play(strategy, min, max, secret, u64::from(GAMES + game)) {
+                Some(count) => attempts.push(count),
+                None => failures += 1,
+            }
+        }
+
+        attempts.sort_unstable();
+        let percentile = |p: usize| attempts.get(attempts.len().saturating_sub(1) * p / 100).copied().unwrap_or(0);
+        let mean = attempts.iter().map(|&count| f64::from(count)).sum::<f64>() / attempts.len().max(1) as f64;
+
+        println!(
+            "{:<8} mean {:>7.2}  p50 {:>4}  p90 {:>4}  p99 {:>4}  max {:>4}  failures {}",
+            strategy.to_string(),
+            mean,
+            percentile(50),
+            percentile(90),
+            percentile(99),
+            percentile(100),
+            failures
+        );
+    }
+}
+
+// Scroll the review of a very long history from top to bottom and print what each frame's rows
+// cost to build, windowed as the game builds them and, for comparison, all at once
+pub fn run_history(min: u32, max: u32) {
+    let secret = seeded_secret(0, min, max);
+    let guesses: Vec<u32> = (0..HISTORY as u32).map(|i| seeded_secret(i + 1, min, max)).collect();
+    let reviews = analysis::review_guesses(min, max, secret, &guesses);
+    let locale = Locale::from_env();
+    let row = |i: usize| viewmodel::review_row(locale, i, &reviews[i], None, Some(Duration::from_millis(1500)));
+    println!("{} guesses, {} frames scrolled from top to bottom", HISTORY, FRAMES);
+
+    let mut slowest = Duration::ZERO;
+    let started = Instant::now();
+    for frame in 0..FRAMES {
+        let offset = frame as f32 / (FRAMES - 1) as f32;
+        let frame_started = Instant::now();
+        let rows: Vec<_> = history::visible(reviews.len(), offset).map(row).collect();
+        slowest = slowest.max(frame_started.elapsed());
+        std::hint::black_box(rows);
+    }
+    let windowed = started.elapsed() / FRAMES as u32;
+
+    let started = Instant::now();
+    let rows: Vec<_> = (0..reviews.len()).map(row).collect();
+    let full = started.elapsed();
+    std::hint::black_box(rows);
+
+    println!("windowed  mean {:>10.3?}  slowest {:>10.3?}", windowed, slowest);
+    println!("every row once {:>10.3?}", full);
+    if slowest > FRAME_BUDGET {
+        println!("slowest frame is over the {:?} budget", FRAME_BUDGET);
+        std::process::exit(1);
+    }
+}
+
+// Answer the bot truthfully until it finds `secret`, returning the guesses it took
+//
+// Returns `None` if the strategy ever guesses outside the range still possible, or
+// fails to converge within as many guesses as there are values.
+fn play(strategy: BotStrategy, min: u32, max: u32, secret: u32, seed: u64) -> Option<u32> {
+    let limit = u64::from(max - min) + 1;
+    let mut bot = BotGame::new(strategy, min, max, seed);
+
+    loop {
+        if bot.guess < bot.low || bot.guess > bot.high || u64::from(bot.attempts()) > limit {
+            return None;
+        }
+
+        let answer = match bot.guess.cmp(&secret) {
+            Ordering::Less => Answer::TooSmall,
+            Ordering::Greater => Answer::TooBig,
+            Ordering::Equal => return Some(bot.attempts()),
+        };
+        bot.answer(answer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn play_counts_the_guesses_to_the_secret() {
+        let strategy = |key| BotStrategy::from_key(key).unwrap();
+        assert_eq!(play(strategy("linear"), 1, 100, 42, 0), Some(42));
+        assert_eq!(play(strategy("binary"), 1, 100, 50, 0), Some(1));
+        for secret in [1, 2, 50, 99, 100] {
+            for &strategy in BotStrategy::ALL {
+                let attempts = play(strategy, 1, 100, secret, 5).unwrap();
+                assert!((1..=100).contains(&attempts));
+            }
+        }
+    }
+}
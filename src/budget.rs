@@ -0,0 +1,87 @@
+// A session-wide allowance of guesses, so every guess has to count
+
+use std::time::{Duration, Instant};
+
+// Guesses available at the start of a session, and the most the budget can hold
+pub const CAPACITY: u32 = 25;
+
+// How long it takes for one spent guess to come back
+pub const REFILL_INTERVAL: Duration = Duration::from_secs(60);
+
+// Guesses given back for winning a game
+pub const WIN_BONUS: u32 = 3;
+
+// The guesses left this session
+//
+// Each valid guess costs one. While below capacity, one guess is given back every
+// `REFILL_INTERVAL`, and each win gives back `WIN_BONUS` at once; neither goes past
+// `CAPACITY`. At zero, guessing is blocked until the next refill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuessBudget {
+    remaining: u32,                  // Guesses that can still be made
+    refilling_since: Option<Instant>, // Start of the current refill interval, while below capacity
+}
+
+impl Default for GuessBudget {
+    fn default() -> GuessBudget {
+        GuessBudget { remaining: CAPACITY, refilling_since: None }
+    }
+}
+
+impl GuessBudget {
+    // Guesses that can still be made
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
+
+    // Whether guessing is blocked until the next refill
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+
+    // Whether spent guesses are still coming back
+    pub fn is_refilling(&self) -> bool {
+        self.refilling_since.is_some()
+    }
+
+    // Spend a guess at `now`, returning `false` if none are left
+    pub fn spend(&mut self, now: Instant) -> bool {
+        if self.is_exhausted() {
+            return false;
+        }
+
+        self.remaining -= 1;
+        self.refilling_since.get_or_insert(now); // Only a budget that was full starts a fresh interval
+        true
+    }
+
+    // Give back the guesses for a win
+    pub fn reward_win(&mut self) {
+        self.remaining = (self.remaining + WIN_BONUS).min(CAPACITY);
+        if self.remaining == CAPACITY {
+            self.refilling_since = None;
+        }
+    }
+
+    // Give back every guess whose refill interval has passed by `now`
+    pub fn refill(&mut self, now: Instant) {
+        let Some(since) = self.refilling_since else {
+            return;
+        };
+
+        let intervals = now.saturating_duration_since(since).as_secs() / REFILL_INTERVAL.as_secs();
+        let intervals = u32::try_from(intervals).unwrap_or(u32::MAX); // Clamped anyway below
+        self.remaining = self.remaining.saturating_add(intervals).min(CAPACITY);
+        self.refilling_since = if self.remaining == CAPACITY {
+            None
+        } else {
+            Some(since + REFILL_INTERVAL * intervals) // Keep any progress towards the next refill
+        };
+    }
+
+    // Time until the next guess comes back, or `None` when the budget is full
+    pub fn next_refill_in(&self, now: Instant) -> Option<Duration> {
+        self.refilling_since
+            .map(|since| REFILL_INTERVAL.saturating_sub(now.saturating_duration_since(since)))
+    }
+}
@@ -0,0 +1,264 @@
+// Shareable result cards, drawn off-screen as PNG images
+//
+// A card is composed pixel by pixel with `imageproc`, never captured from the window, so
+// it looks the same whatever the window's size, theme or layout.
+
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ab_glyph::FontRef;
+use image::{Rgba, RgbaImage};
+use imageproc::drawing::{draw_filled_rect_mut, draw_text_mut, text_size};
+use imageproc::rect::Rect;
+
+use crate::color::Rgb;
+use crate::fonts;
+use crate::storage;
+
+// Width of every card; the height grows with the guess track
+pub const WIDTH: u32 = 600;
+
+// Space kept clear around the edge
+const MARGIN: u32 = 32;
+
+// Text heights, in pixels
+const TITLE_SIZE: f32 = 40.0;
+const LINE_SIZE: f32 = 24.0;
+const SYMBOL_SIZE: f32 = 28.0;
+
+// Vertical space each line takes, text plus the gap below it
+const TITLE_HEIGHT: u32 = 56;
+const LINE_HEIGHT: u32 = 34;
+
+// Space each symbol of the track takes, across and down
+const SYMBOL_ADVANCE: u32 = 36;
+
+// Gap between the text and the track
+const TRACK_GAP: u32 = 16;
+
+// Card colors
+const BACKGROUND: Rgba<u8> = Rgba([0x1E, 0x1E, 0x24, 0xFF]);
+const TEXT: Rgba<u8> = Rgba([0xF4, 0xF4, 0xF6, 0xFF]);
+
+// How saving the current game's card is going
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveState {
+    Saving,
+    Saved(PathBuf), // Where the card was written
+    Failed,         // Why is in the log
+}
+
+// One guess on the track
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Higher, // The guess was too small, so the secret lay higher
+    Lower,  // The guess was too big, so the secret lay lower
+    Win,
+}
+
+impl Step {
+    // The symbol drawn for the step; each has its own shape, so color is never needed to tell them apart
+    fn symbol(self) -> &'static str {
+        match self {
+            Step::Higher => "▲",
+            Step::Lower => "▼",
+            Step::Win => "●",
+        }
+    }
+
+    // The color of the step's symbol, from a palette safe for color vision deficiency if asked
+    fn color(self, accent: Rgb, colorblind: bool) -> Rgba<u8> {
+        let Rgb { r, g, b } = match (self, colorblind) {
+            (Step::Win, _) => accent,
+            (Step::Higher, false) => Rgb { r: 0x4C, g: 0x8B, b: 0xF5 }, // Blue
+            (Step::Lower, false) => Rgb { r: 0xE0, g: 0x4F, b: 0x4F },  // Red
+            (Step::Higher, true) => Rgb { r: 0x56, g: 0xB4, b: 0xE9 },  // Okabe-Ito sky blue
+            (Step::Lower, true) => Rgb { r: 0xE6, g: 0x9F, b: 0x00 },   // Okabe-Ito orange
+        };
+        Rgba([r, g, b, 0xFF])
+    }
+}
+
+// Everything a card shows, already worded and formatted for the locale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultCard {
+    pub lines: Vec<String>, // The range, guesses, time and grade, one per line under the title
+    pub track: Vec<Step>,   // One step per guess, oldest first
+    pub accent: Rgb,        // Color of the winning step
+    pub colorblind: bool,   // Whether to draw the track in the colorblind-safe palette
+}
+
+// Where everything on a card goes, worked out before drawing so nothing can clip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub height: u32,     // Height of the whole card
+    pub track_top: u32,  // Top of the first row of the track
+    pub per_row: usize,  // Track symbols that fit across the card
+    pub rows: usize,     // Rows the track wraps onto
+}
+
+impl Layout {
+    // Lay out a card with `lines` lines of text under the title and a track of `steps` guesses
+    pub fn new(lines: usize, steps: usize) -> Layout {
+        let per_row = ((WIDTH - 2 * MARGIN) / SYMBOL_ADVANCE).max(1) as usize;
+        let rows = steps.div_ceil(per_row).max(1); // An empty track still keeps its row
+        let track_top = MARGIN + TITLE_HEIGHT + lines as u32 * LINE_HEIGHT + TRACK_GAP;
+
+        Layout { height: track_top + rows as u32 * SYMBOL_ADVANCE + MARGIN, track_top, per_row, rows }
+    }
+
+    // The top-left corner of the `index`th track symbol, wrapping onto a new row when one fills
+    pub fn symbol_position(&self, index: usize) -> (u32, u32) {
+        let (row, column) = (index / self.per_row, index % self.per_row);
+        (MARGIN + column as u32 * SYMBOL_ADVANCE, self.track_top + row as u32 * SYMBOL_ADVANCE)
+    }
+}
+
+// The text size to draw a line at so it fits `available` pixels, shrinking it only if it has to
+pub fn fit_scale(text_width: u32, available: u32, size: f32) -> f32 {
+    if text_width <= available || text_width == 0 {
+        size
+    } else {
+        size * available as f32 / text_width as f32
+    }
+}
+
+// Draw the card as an image
+pub fn render(card: &ResultCard) -> RgbaImage {
+    let font = FontRef::try_from_slice(fonts::SANS_BYTES).expect("the bundled font is valid");
+    let layout = Layout::new(card.lines.len(), card.track.len());
+    let mut image = RgbaImage::from_pixel(WIDTH, layout.height, BACKGROUND);
+
+    // A strip of the accent along the top, tying the card to the player's colors
+    let Rgb { r, g, b } = card.accent;
+    draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(WIDTH, 8), Rgba([r, g, b, 0xFF]));
+
+    // Draw each line of text, shrunk to fit if a long locale or number would run off the edge
+    let available = WIDTH - 2 * MARGIN;
+    let mut draw_line = |text: &str, size: f32, top: u32| {
+        let (width, _) = text_size(size, &font, text);
+        let size = fit_scale(width, available, size);
+        draw_text_mut(&mut image, TEXT, MARGIN as i32, top as i32, size, &font, text);
+    };
+    draw_line("Guessing Game", TITLE_SIZE, MARGIN);
+    for (i, line) in card.lines.iter().enumerate() {
+        draw_line(line, LINE_SIZE, MARGIN + TITLE_HEIGHT + i as u32 * LINE_HEIGHT);
+    }
+
+    for (i, step) in card.track.iter().enumerate() {
+        let (x, y) = layout.symbol_position(i);
+        let color = step.color(card.accent, card.colorblind);
+        draw_text_mut(&mut image, color, x as i32, y as i32, SYMBOL_SIZE, &font, step.symbol());
+    }
+
+    image
+}
+
+// Render the card and save it where the player will find it, returning the path
+pub fn save(card: ResultCard) -> Option<PathBuf> {
+    let time = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+    let path = output_dir().join(format!("guessing-game-{}.png", time));
+
+    match render(&card).save(&path) {
+        Ok(()) => Some(path),
+        Err(error) => {
+            tracing::warn!(path = %path.display(), %error, "Could not save the result card");
+            None
+        }
+    }
+}
+
+// The Pictures folder if the player has one, otherwise their home folder
+fn output_dir() -> PathBuf {
+    match storage::home_dir() {
+        Some(home) if home.join("Pictures").is_dir() => home.join("Pictures"),
+        Some(home) => home,
+        None => PathBuf::from("."), // Next to wherever the game was started from
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The right and bottom edges of everything on the card must stay inside the margin
+    fn assert_fits(layout: &Layout, steps: usize) {
+        for index in 0..steps {
+            let (x, y) = layout.symbol_position(index);
+            assert!(x >= MARGIN && x + SYMBOL_ADVANCE <= WIDTH - MARGIN, "symbol {} at x {}", index, x);
+            assert!(y >= layout.track_top && y + SYMBOL_ADVANCE <= layout.height - MARGIN, "symbol {} at y {}", index, y);
+        }
+    }
+
+    #[test]
+    fn a_short_game_fits_on_one_row() {
+        let layout = Layout::new(4, 7);
+        assert_eq!(layout.per_row, 14);
+        assert_eq!(layout.rows, 1);
+        assert_eq!(layout.track_top, MARGIN + TITLE_HEIGHT + 4 * LINE_HEIGHT + TRACK_GAP);
+        assert_eq!(layout.height, layout.track_top + SYMBOL_ADVANCE + MARGIN);
+        assert_eq!(layout.symbol_position(0), (MARGIN, layout.track_top));
+        assert_fits(&layout, 7);
+    }
+
+    #[test]
+    fn a_long_game_wraps_onto_more_rows() {
+        for steps in [14, 15, 28, 29, 100, 1000] {
+            let layout = Layout::new(4, steps);
+            assert_eq!(layout.rows, steps.div_ceil(14), "{} steps", steps);
+            assert_fits(&layout, steps);
+        }
+        let layout = Layout::new(4, 15);
+        assert_eq!(layout.symbol_position(13).1, layout.track_top);
+        assert_eq!(layout.symbol_position(14), (MARGIN, layout.track_top + SYMBOL_ADVANCE));
+    }
+
+    #[test]
+    fn an_empty_track_still_keeps_its_row() {
+        assert_eq!(Layout::new(4, 0).rows, 1);
+        assert_eq!(Layout::new(4, 0).height, Layout::new(4, 1).height);
+    }
+
+    #[test]
+    fn every_line_pushes_the_track_down() {
+        assert_eq!(Layout::new(5, 3).track_top - Layout::new(4, 3).track_top, LINE_HEIGHT);
+        assert_eq!(Layout::new(0, 3).track_top, MARGIN + TITLE_HEIGHT + TRACK_GAP);
+    }
+
+    #[test]
+    fn text_only_shrinks_when_it_would_run_over() {
+        assert_eq!(fit_scale(300, 536, LINE_SIZE), LINE_SIZE);
+        assert_eq!(fit_scale(536, 536, LINE_SIZE), LINE_SIZE);
+        assert_eq!(fit_scale(1072, 536, LINE_SIZE), LINE_SIZE / 2.0);
+        assert_eq!(fit_scale(0, 536, LINE_SIZE), LINE_SIZE);
+        assert!(fit_scale(537, 536, LINE_SIZE) < LINE_SIZE);
+    }
+
+    #[test]
+    fn every_step_has_its_own_symbol_and_colors() {
+        let steps = [Step::Higher, Step::Lower, Step::Win];
+        let accent = Rgb { r: 1, g: 2, b: 3 };
+        for (i, a) in steps.iter().enumerate() {
+            for b in &steps[i + 1..] {
+                assert_ne!(a.symbol(), b.symbol());
+                for colorblind in [false, true] {
+                    assert_ne!(a.color(accent, colorblind), b.color(accent, colorblind), "{:?} {:?}", a, b);
+                }
+            }
+        }
+        assert_eq!(Step::Win.color(accent, true), Rgba([1, 2, 3, 0xFF]));
+        assert_ne!(Step::Higher.color(accent, false), Step::Higher.color(accent, true));
+    }
+
+    #[test]
+    fn the_image_is_the_size_of_its_layout() {
+        let card = ResultCard {
+            lines: vec!["Range 1–100".to_string(); 4],
+            track: vec![Step::Higher; 30],
+            accent: Rgb { r: 0x80, g: 0x40, b: 0xC0 },
+            colorblind: false,
+        };
+        let image = render(&card);
+        assert_eq!((image.width(), image.height()), (WIDTH, Layout::new(4, 30).height));
+    }
+}
@@ -0,0 +1,184 @@
+// Short codes that let friends play the exact same game
+
+use std::fmt;
+
+// URL-safe base64 digits, so codes survive being pasted into chats and links
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// Bytes in a decoded code: seed, minimum and maximum, then a checksum
+const CODE_BYTES: usize = 13;
+
+// Everything needed to recreate a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Challenge {
+    pub seed: u32, // Seed the secret is derived from
+    pub min: u32,  // Smallest possible secret
+    pub max: u32,  // Largest possible secret
+}
+
+// Why a pasted code couldn't be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeError {
+    Empty,           // Nothing was pasted
+    WrongLength,     // Too short or too long to be a code
+    InvalidCharacter, // Contains something other than code characters
+    Corrupted,       // The checksum doesn't match, so a character was changed or mistyped
+    InvalidRange,    // The range is empty
+    UnsupportedRange, // The range is valid but isn't one this version can play
+}
+
+impl fmt::Display for ChallengeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ChallengeError::Empty => "Paste a challenge code first",
+            ChallengeError::WrongLength => "That code is the wrong length",
+            ChallengeError::InvalidCharacter => "That code contains characters challenge codes never use",
+            ChallengeError::Corrupted => "That code looks mistyped - check it and try again",
+            ChallengeError::InvalidRange => "That code describes an impossible range",
+            ChallengeError::UnsupportedRange => "That code is for a number range this version can't play",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl Challenge {
+    // Encode the challenge as an 18-character code
+    pub fn encode(&self) -> String {
+        let mut bytes = [0; CODE_BYTES];
+        bytes[0..4].copy_from_slice(&self.seed.to_be_bytes());
+        bytes[4..8].copy_from_slice(&self.min.to_be_bytes());
+        bytes[8..12].copy_from_slice(&self.max.to_be_bytes());
+        bytes[12] = checksum(&bytes[..12]);
+
+        // Emit six bits at a time, padding the final partial group with zeros
+        let bits: Vec<u8> = bytes.iter().flat_map(|byte| (0..8).rev().map(move |bit| (byte >> bit) & 1)).collect();
+        bits.chunks(6)
+            .map(|group| {
+                let value = group.iter().fold(0, |value, &bit| (value << 1) | bit) << (6 - group.len());
+                char::from(ALPHABET[usize::from(value)])
+            })
+            .collect()
+    }
+
+    // Decode a code made by `encode`, ignoring surrounding whitespace
+    pub fn decode(code: &str) -> Result<Challenge, ChallengeError> {
+        let code = code.trim();
+        if code.is_empty() {
+            return Err(ChallengeError::Empty);
+        }
+        if code.len() != (CODE_BYTES * 8).div_ceil(6) {
+            return Err(ChallengeError::WrongLength);
+        }
+
+        // Turn each character back into its six bits, then regroup them into bytes
+        let mut bits = Vec::with_capacity(code.len() * 6);
+        for character in code.bytes() {
+            let value = ALPHABET
+                .iter()
+                .position(|&digit| digit == character)
+                .ok_or(ChallengeError::InvalidCharacter)?;
+            bits.extend((0..6).rev().map(|bit| ((value >> bit) & 1) as u8));
+        }
+        let bytes: Vec<u8> = bits
+            .chunks_exact(8)
+            .map(|byte| byte.iter().fold(0, |value, &bit| (value << 1) | bit))
+            .collect();
+
+        if bytes[12] != checksum(&bytes[..12]) {
+            return Err(ChallengeError::Corrupted);
+        }
+
+        let word = |start: usize| u32::from_be_bytes([bytes[start], bytes[start + 1], bytes[start + 2], bytes[start + 3]]);
+        let challenge = Challenge { seed: word(0), min: word(4), max: word(8) };
+        if challenge.min > challenge.max {
+            return Err(ChallengeError::InvalidRange);
+        }
+        Ok(challenge)
+    }
+}
+
+// A one-byte checksum that catches nearly every mistyped character
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0x5A, |sum: u8, &byte| sum.rotate_left(3) ^ byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLASSIC: Challenge = Challenge { seed: 90_210, min: 1, max: 100 };
+
+    #[test]
+    fn codes_decode_to_the_challenge_they_were_made_from() {
+        for challenge in [
+            CLASSIC,
+            Challenge { seed: 0, min: 0, max: 0 },
+            Challenge { seed: u32::MAX, min: 0, max: u32::MAX },
+            Challenge { seed: 7, min: 1_000, max: 1_999 },
+        ] {
+            let code = challenge.encode();
+            assert_eq!(code.len(), 18);
+            assert!(code.bytes().all(|c| ALPHABET.contains(&c)), "{}", code);
+            assert_eq!(Challenge::decode(&code), Ok(challenge));
+        }
+    }
+
+    #[test]
+    fn pasted_whitespace_is_ignored() {
+        assert_eq!(Challenge::decode(&format!("  {}\n", CLASSIC.encode())), Ok(CLASSIC));
+    }
+
+    #[test]
+    fn every_kind_of_bad_code_is_told_apart() {
+        let code = CLASSIC.encode();
+        assert_eq!(Challenge::decode(""), Err(ChallengeError::Empty));
+        assert_eq!(Challenge::decode("   "), Err(ChallengeError::Empty));
+        assert_eq!(Challenge::decode(&code[..17]), Err(ChallengeError::WrongLength));
+        assert_eq!(Challenge::decode(&format!("{}A", code)), Err(ChallengeError::WrongLength));
+        assert_eq!(Challenge::decode(&format!("{}+", &code[..17])), Err(ChallengeError::InvalidCharacter));
+        assert_eq!(
+            Challenge::decode(&format!("{}é", &code[..16])),
+            Err(ChallengeError::InvalidCharacter),
+            "a two-byte character fills the length without being a digit"
+        );
+        let backwards = Challenge { seed: 1, min: 100, max: 1 }.encode();
+        assert_eq!(Challenge::decode(&backwards), Err(ChallengeError::InvalidRange));
+    }
+
+    #[test]
+    fn nearly_every_mistyped_character_is_caught() {
+        let code = CLASSIC.encode();
+        let mut typos = 0;
+        let mut missed = 0;
+        // Every other digit in every place but the last, whose low bits are only padding
+        for place in 0..17 {
+            for &digit in ALPHABET.iter().filter(|&&digit| digit != code.as_bytes()[place]) {
+                let mut typo = code.clone().into_bytes();
+                typo[place] = digit;
+                typos += 1;
+                // The checksum is a single byte, so a few slip through
+                if Challenge::decode(&String::from_utf8(typo).unwrap()) != Err(ChallengeError::Corrupted) {
+                    missed += 1;
+                }
+            }
+        }
+        assert!(missed * 100 <= typos, "{} of {} typos got past the checksum", missed, typos);
+    }
+
+    #[test]
+    fn every_error_explains_itself() {
+        let errors = [
+            ChallengeError::Empty,
+            ChallengeError::WrongLength,
+            ChallengeError::InvalidCharacter,
+            ChallengeError::Corrupted,
+            ChallengeError::InvalidRange,
+            ChallengeError::UnsupportedRange,
+        ];
+        let messages: Vec<String> = errors.iter().map(ChallengeError::to_string).collect();
+        assert_eq!(messages[0], "Paste a challenge code first");
+        for (i, message) in messages.iter().enumerate() {
+            assert!(!messages[..i].contains(message), "{} is said twice", message);
+        }
+    }
+}
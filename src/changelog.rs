@@ -0,0 +1,47 @@
+// What changed in each release, for the "what's new" panel
+
+// The version of this build
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// The notable changes in one release
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Release {
+    pub version: &'static str,          // Version number, as in Cargo.toml
+    pub changes: &'static [&'static str], // One short line per change
+}
+
+// Every release, newest first; add an entry here whenever the version is bumped
+pub const RELEASES: &[Release] = &[Release {
+    version: "0.1.0",
+    changes: &[
+        "Numbers are formatted the way your system does it",
+        "Follow the system's dark mode, or pick a theme and accent color",
+        "See how many candidates remain and get graded on your strategy",
+        "Review the guesses that strayed from the best choice",
+        "Let the computer guess your number at three difficulties",
+        "Compact, left-handed and always-on-top layouts",
+        "The timer pauses while you are away",
+    ],
+}];
+
+// Split a version like "1.2.3" into numbers that compare in release order
+fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version.trim().split('.').map(|part| part.parse().ok());
+    let version = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(version)
+}
+
+// The releases the user hasn't seen, newest first
+//
+// When no version has been recorded, or it can't be read, only the current release
+// is shown rather than the full history.
+pub fn unseen(last_seen: Option<&str>) -> Vec<Release> {
+    match last_seen.and_then(parse_version) {
+        Some(seen) => RELEASES
+            .iter()
+            .filter(|release| parse_version(release.version).is_some_and(|version| version > seen))
+            .copied()
+            .collect(),
+        None => RELEASES.iter().take(1).copied().collect(),
+    }
+}
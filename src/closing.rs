@@ -0,0 +1,68 @@
+// Deciding what closing the window does, so a game in progress isn't lost to a stray click
+
+// What to do when the window's close button is pressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseAction {
+    Close,   // Nothing would be lost, so close straight away
+    Confirm, // Ask first, since a game is part-way through
+}
+
+// The player's answer to the confirmation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuitChoice {
+    Quit,         // Close, abandoning the game
+    SaveForLater, // Close, keeping the game to pick up next time
+    KeepPlaying,  // Stay open, as if the close button was never pressed
+}
+
+impl QuitChoice {
+    // The value written to session recordings
+    pub fn key(self) -> &'static str {
+        match self {
+            QuitChoice::Quit => "quit",
+            QuitChoice::SaveForLater => "save",
+            QuitChoice::KeepPlaying => "keep",
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<QuitChoice> {
+        [QuitChoice::Quit, QuitChoice::SaveForLater, QuitChoice::KeepPlaying]
+            .into_iter()
+            .find(|choice| choice.key() == key)
+    }
+}
+
+// What pressing close should do for a game with `guesses` made so far, over once won or given up
+//
+// A game nobody has guessed in yet loses nothing, and one that's over has nothing left
+// to lose, so only a game part-way through asks.
+pub fn on_close_requested(guesses: usize, over: bool) -> CloseAction {
+    if guesses > 0 && !over {
+        CloseAction::Confirm
+    } else {
+        CloseAction::Close
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_a_game_part_way_through_asks_first() {
+        assert_eq!(on_close_requested(0, false), CloseAction::Close, "nothing guessed yet");
+        assert_eq!(on_close_requested(1, false), CloseAction::Confirm);
+        assert_eq!(on_close_requested(40, false), CloseAction::Confirm);
+        assert_eq!(on_close_requested(3, true), CloseAction::Close, "won or given up");
+        assert_eq!(on_close_requested(0, true), CloseAction::Close);
+    }
+
+    #[test]
+    fn choices_round_trip_through_their_keys() {
+        for choice in [QuitChoice::Quit, QuitChoice::SaveForLater, QuitChoice::KeepPlaying] {
+            assert_eq!(QuitChoice::from_key(choice.key()), Some(choice));
+        }
+        assert_eq!(QuitChoice::from_key("close"), None);
+    }
+}
@@ -0,0 +1,239 @@
+// Color parsing and the contrast math used to derive shades from the accent color
+
+use iced::Color;
+
+// An opaque 8-bit-per-channel color, as entered by the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+// Pure black and white, the two candidates for text drawn on top of a color
+const BLACK: Rgb = Rgb::new(0x00, 0x00, 0x00);
+const WHITE: Rgb = Rgb::new(0xFF, 0xFF, 0xFF);
+
+// The predefined accent colors offered as swatches, starting with iced's own blue
+pub const ACCENT_SWATCHES: [Rgb; 8] = [
+    Rgb::new(0x5E, 0x7C, 0xE2), // Blue
+    Rgb::new(0x14, 0xB8, 0xA6), // Teal
+    Rgb::new(0x22, 0xC5, 0x5E), // Green
+    Rgb::new(0xF5, 0x9E, 0x0B), // Amber
+    Rgb::new(0xF9, 0x73, 0x16), // Orange
+    Rgb::new(0xEF, 0x44, 0x44), // Red
+    Rgb::new(0xEC, 0x48, 0x99), // Pink
+    Rgb::new(0x8B, 0x5C, 0xF6), // Purple
+];
+
+// Proximity tints from far to close: deep blue, warming through amber to near-red
+const PROXIMITY_STOPS: [Rgb; 3] = [Rgb::new(0x1E, 0x3A, 0x8A), Rgb::new(0xF5, 0x9E, 0x0B), Rgb::new(0xDC, 0x26, 0x26)];
+
+// The same scale for colorblind players, from viridis: purple, teal, then yellow, so the
+// steps differ in lightness as well as hue
+const PROXIMITY_STOPS_COLORBLIND: [Rgb; 3] =
+    [Rgb::new(0x44, 0x01, 0x54), Rgb::new(0x21, 0x91, 0x8C), Rgb::new(0xFD, 0xE7, 0x25)];
+
+// The accent used until the user picks another
+pub const DEFAULT_ACCENT: Rgb = ACCENT_SWATCHES[0];
+
+impl Rgb {
+    pub const fn new(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb { r, g, b }
+    }
+
+    // Parse "#RRGGBB" or the "#RGB" shorthand, with or without the leading '#'
+    pub fn from_hex(input: &str) -> Option<Rgb> {
+        let hex = input.trim();
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None; // Also rules out multi-byte characters before slicing below
+        }
+
+        // Each channel is either two digits, or one digit repeated in the shorthand form
+        let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+        match hex.len() {
+            6 => Some(Rgb::new(channel(&hex[0..2])?, channel(&hex[2..4])?, channel(&hex[4..6])?)),
+            3 => {
+                let expand = |i: usize| channel(&hex[i..=i]).map(|v| v * 0x11);
+                Some(Rgb::new(expand(0)?, expand(1)?, expand(2)?))
+            }
+            _ => None,
+        }
+    }
+
+    // Format as "#RRGGBB", the form written to the preferences file
+    pub fn to_hex(self) -> String {
+        format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+    }
+
+    // WCAG relative luminance, from 0.0 for black to 1.0 for white
+    pub fn relative_luminance(self) -> f32 {
+        // Undo the sRGB transfer curve so channels can be weighted linearly
+        let linear = |channel: u8| {
+            let c = channel as f32 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    // WCAG contrast ratio between two colors, from 1.0 (identical) to 21.0 (black on white)
+    pub fn contrast_ratio(self, other: Rgb) -> f32 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+        (lighter + 0.05) / (darker + 0.05)
+    }
+
+    // Black or white, whichever stands out more against this color
+    pub fn readable_text(self) -> Rgb {
+        if self.contrast_ratio(BLACK) >= self.contrast_ratio(WHITE) {
+            BLACK
+        } else {
+            WHITE
+        }
+    }
+
+    // Blend towards another color, `amount` 0.0 keeping this one and 1.0 giving the other
+    pub fn mix(self, other: Rgb, amount: f32) -> Rgb {
+        let amount = amount.clamp(0.0, 1.0);
+        let blend = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * amount).round() as u8;
+        Rgb::new(blend(self.r, other.r), blend(self.g, other.g), blend(self.b, other.b))
+    }
+
+    // The shade shown while hovering: pushed towards the text color so it stays distinct
+    // on both light accents (which darken) and dark accents (which lighten)
+    pub fn hovered(self) -> Rgb {
+        self.mix(self.readable_text(), 0.15)
+    }
+
+    // The shade shown while pressed, a stronger version of the hover shift
+    pub fn pressed(self) -> Rgb {
+        self.mix(self.readable_text(), 0.3)
+    }
+}
+
+// The tint for a guess `proximity` of the way to the secret, 0.0 as far as possible and 1.0 on it
+//
+// The tint runs evenly through the stops of the chosen scale, so 0.5 is exactly the middle one.
+pub fn proximity_tint(proximity: f32, colorblind: bool) -> Rgb {
+    let [far, middle, close] = if colorblind { PROXIMITY_STOPS_COLORBLIND } else { PROXIMITY_STOPS };
+    let proximity = proximity.clamp(0.0, 1.0);
+
+    if proximity <= 0.5 {
+        far.mix(middle, proximity * 2.0)
+    } else {
+        middle.mix(close, (proximity - 0.5) * 2.0)
+    }
+}
+
+// Round a theme color to the nearest 8-bit channels, dropping any transparency
+impl From<Color> for Rgb {
+    fn from(color: Color) -> Rgb {
+        let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Rgb::new(channel(color.r), channel(color.g), channel(color.b))
+    }
+}
+
+impl From<Rgb> for Color {
+    fn from(rgb: Rgb) -> Color {
+        Color::from_rgb8(rgb.r, rgb.g, rgb.b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_parses_in_the_long_and_short_forms() {
+        assert_eq!(Rgb::from_hex("#5E7CE2"), Some(Rgb::new(0x5E, 0x7C, 0xE2)));
+        assert_eq!(Rgb::from_hex("5e7ce2"), Some(Rgb::new(0x5E, 0x7C, 0xE2)));
+        assert_eq!(Rgb::from_hex("  #fa0 "), Some(Rgb::new(0xFF, 0xAA, 0x00)));
+        assert_eq!(Rgb::from_hex("#000"), Some(BLACK));
+    }
+
+    #[test]
+    fn anything_else_isnt_a_color() {
+        for input in ["", "#", "#12", "#1234", "#12345", "#1234567", "#gg0000", "##fff", "#+12", "ü12", "#ffé"] {
+            assert_eq!(Rgb::from_hex(input), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        for color in ACCENT_SWATCHES.into_iter().chain([BLACK, WHITE]) {
+            assert_eq!(Rgb::from_hex(&color.to_hex()), Some(color));
+        }
+        assert_eq!(Rgb::new(0x0A, 0xBC, 0xFF).to_hex(), "#0ABCFF");
+    }
+
+    #[test]
+    fn contrast_runs_from_identical_to_black_on_white() {
+        assert!((BLACK.contrast_ratio(WHITE) - 21.0).abs() < 0.01);
+        assert!((WHITE.contrast_ratio(BLACK) - 21.0).abs() < 0.01, "the order doesn't matter");
+        assert!((DEFAULT_ACCENT.contrast_ratio(DEFAULT_ACCENT) - 1.0).abs() < f32::EPSILON);
+        assert_eq!(BLACK.relative_luminance(), 0.0);
+        assert!((WHITE.relative_luminance() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn text_on_a_color_is_whichever_of_black_and_white_stands_out() {
+        assert_eq!(WHITE.readable_text(), BLACK);
+        assert_eq!(BLACK.readable_text(), WHITE);
+        assert_eq!(Rgb::new(0xF5, 0x9E, 0x0B).readable_text(), BLACK, "amber is light");
+        assert_eq!(Rgb::new(0x1E, 0x3A, 0x8A).readable_text(), WHITE, "navy is dark");
+        for swatch in ACCENT_SWATCHES {
+            let text = swatch.readable_text();
+            let other = if text == BLACK { WHITE } else { BLACK };
+            assert!(swatch.contrast_ratio(text) >= swatch.contrast_ratio(other), "{}", swatch.to_hex());
+        }
+    }
+
+    #[test]
+    fn mixing_clamps_its_amount() {
+        assert_eq!(BLACK.mix(WHITE, 0.0), BLACK);
+        assert_eq!(BLACK.mix(WHITE, 1.0), WHITE);
+        assert_eq!(BLACK.mix(WHITE, 0.5), Rgb::new(0x80, 0x80, 0x80));
+        assert_eq!(BLACK.mix(WHITE, -3.0), BLACK);
+        assert_eq!(BLACK.mix(WHITE, 7.0), WHITE);
+    }
+
+    #[test]
+    fn the_proximity_tint_runs_through_its_stops() {
+        assert_eq!(proximity_tint(0.0, false), PROXIMITY_STOPS[0]);
+        assert_eq!(proximity_tint(0.5, false), PROXIMITY_STOPS[1]);
+        assert_eq!(proximity_tint(1.0, false), PROXIMITY_STOPS[2]);
+        assert_eq!(proximity_tint(0.25, false), PROXIMITY_STOPS[0].mix(PROXIMITY_STOPS[1], 0.5));
+        assert_eq!(proximity_tint(0.0, true), PROXIMITY_STOPS_COLORBLIND[0]);
+        assert_eq!(proximity_tint(0.5, true), PROXIMITY_STOPS_COLORBLIND[1]);
+        assert_eq!(proximity_tint(1.0, true), PROXIMITY_STOPS_COLORBLIND[2]);
+    }
+
+    #[test]
+    fn proximity_outside_the_scale_is_clamped() {
+        assert_eq!(proximity_tint(-1.0, false), PROXIMITY_STOPS[0]);
+        assert_eq!(proximity_tint(2.5, false), PROXIMITY_STOPS[2]);
+    }
+
+    #[test]
+    fn the_colorblind_scale_gets_lighter_as_guesses_get_closer() {
+        let lightness: Vec<f32> = (0..=10).map(|step| proximity_tint(step as f32 / 10.0, true).relative_luminance()).collect();
+        assert!(lightness.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", lightness);
+    }
+
+    #[test]
+    fn hover_and_press_shades_move_towards_the_text_color() {
+        for swatch in ACCENT_SWATCHES.into_iter().chain([BLACK, WHITE]) {
+            let text = swatch.readable_text();
+            let (hovered, pressed) = (swatch.hovered(), swatch.pressed());
+            assert_ne!(hovered, swatch, "{}", swatch.to_hex());
+            assert!(pressed.contrast_ratio(swatch) > hovered.contrast_ratio(swatch), "{}", swatch.to_hex());
+            assert!(pressed.contrast_ratio(text) < swatch.contrast_ratio(text), "{}", swatch.to_hex());
+        }
+    }
+}
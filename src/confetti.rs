@@ -0,0 +1,139 @@
+// Confetti falling across the window, in a band above the summary, when a game is won
+//
+// Every piece's path is worked out from the moment of the burst rather than stepped frame by
+// frame, so the clock tick only has to redraw, and a slow frame never makes pieces jump. The
+// pieces come from a seeded source, so the same win always throws the same confetti and a
+// replayed session draws no extra secrets. iced 0.9 can't layer a canvas over other widgets,
+// so the band takes its own place in the layout instead of covering the game.
+
+use std::f32::consts::TAU;
+use std::time::{Duration, Instant};
+
+use iced::widget::canvas::{self, Cursor, Frame, Geometry, Path};
+use iced::{Color, Point, Rectangle, Size, Theme, Vector};
+
+use crate::random::{NumberSource, SeededSource};
+
+// The height of the band the confetti falls through, in logical pixels
+pub const HEIGHT: f32 = 220.0;
+
+// How long the burst lasts, the pieces fading out over the last part of it
+const LIFETIME: Duration = Duration::from_millis(2500);
+const FADE: Duration = Duration::from_millis(1000);
+
+// Pieces thrown in one burst
+const PIECES: usize = 80;
+
+// How fast the pieces fall faster, in pixels per second per second
+const GRAVITY: f32 = 140.0;
+
+// Colors for the pieces besides the accent, bright on either theme
+const PALETTE: [Color; 5] = [
+    Color { r: 0.95, g: 0.33, b: 0.31, a: 1.0 },
+    Color { r: 0.99, g: 0.76, b: 0.18, a: 1.0 },
+    Color { r: 0.30, g: 0.75, b: 0.42, a: 1.0 },
+    Color { r: 0.25, g: 0.56, b: 0.95, a: 1.0 },
+    Color { r: 0.67, g: 0.40, b: 0.90, a: 1.0 },
+];
+
+// One piece of confetti as it was thrown
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Particle {
+    x: f32,               // Where it starts across the band, from 0 (left) to 1 (right)
+    y: f32,               // Where it starts down the band, in pixels; above the top is fine
+    drift: f32,           // How fast it moves across, in band widths per second
+    fall: f32,            // How fast it starts falling, in pixels per second
+    spin: f32,            // How fast it turns, in radians per second
+    color: Option<usize>, // Which palette color it is, or `None` for the accent
+}
+
+// A burst of confetti, from the moment of the win
+#[derive(Debug, Clone, PartialEq)]
+pub struct Confetti {
+    started: Instant,
+    particles: Vec<Particle>,
+}
+
+impl Confetti {
+    // Throw a burst at `started`, the same one every time for the same `seed`
+    pub fn burst(seed: u32, started: Instant) -> Confetti {
+        let mut source = SeededSource::new(u64::from(seed));
+        let mut unit = || source.secret(0, 10_000) as f32 / 10_000.0; // From 0 to 1
+        let particles = (0..PIECES)
+            .map(|_| Particle {
+                x: unit(),
+                y: -40.0 * unit(),
+                drift: (unit() - 0.5) * 0.3,
+                fall: 30.0 + 90.0 * unit(),
+                spin: (unit() - 0.5) * 12.0,
+                color: {
+                    let pick = (unit() * (PALETTE.len() + 1) as f32) as usize;
+                    (pick < PALETTE.len()).then_some(pick)
+                },
+            })
+            .collect();
+        Confetti { started, particles }
+    }
+
+    // Whether every piece has faded out by `now`
+    pub fn is_finished(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.started) >= LIFETIME
+    }
+
+    // The burst as it stands at `now`, ready to draw with the accent color in `accent`
+    pub fn frame(&self, now: Instant, accent: Color) -> ConfettiFrame {
+        let elapsed = now.saturating_duration_since(self.started);
+        let t = elapsed.as_secs_f32();
+        let left = LIFETIME.saturating_sub(elapsed).as_secs_f32();
+        let alpha = (left / FADE.as_secs_f32()).clamp(0.0, 1.0);
+
+        let pieces = self
+            .particles
+            .iter()
+            .map(|particle| Piece {
+                x: particle.x + particle.drift * t,
+                y: particle.y + particle.fall * t + GRAVITY * t * t / 2.0,
+                angle: (particle.spin * t) % TAU,
+                color: Color { a: alpha, ..particle.color.map_or(accent, |index| PALETTE[index]) },
+            })
+            .collect();
+        ConfettiFrame { pieces }
+    }
+}
+
+// One piece where it is now
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Piece {
+    x: f32,       // Across the band, from 0 to 1; off either side is simply not seen
+    y: f32,       // Down the band, in pixels
+    angle: f32,   // How far it has turned
+    color: Color, // Faded as the burst ends
+}
+
+// The widget, drawing every piece at one moment of the burst; empty once it's over
+#[derive(Debug, Clone, Default)]
+pub struct ConfettiFrame {
+    pieces: Vec<Piece>,
+}
+
+impl<Message> canvas::Program<Message> for ConfettiFrame {
+    type State = ();
+
+    fn draw(&self, _state: &(), _theme: &Theme, bounds: Rectangle, _cursor: Cursor) -> Vec<Geometry> {
+        let mut frame = Frame::new(bounds.size());
+        let width = frame.width();
+
+        // Little rectangles, each turned about its own middle
+        let size = Size::new(8.0, 4.0);
+        let piece = Path::rectangle(Point::new(-size.width / 2.0, -size.height / 2.0), size);
+        for p in &self.pieces {
+            frame.with_save(|frame| {
+                frame.translate(Vector::new(p.x * width, p.y));
+                frame.rotate(p.angle);
+                frame.fill(&piece, p.color);
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
@@ -0,0 +1,152 @@
+// Reporting panics somewhere the user can find them
+//
+// The release build has no console on Windows, so without this a panic closes the
+// window without a word.
+
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::panic;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::changelog::CURRENT_VERSION;
+use crate::storage;
+
+// File crash reports are appended to, inside the data directory
+const FILE_NAME: &str = "crash.log";
+
+// Set once a panic has been reported in a dialog, so a cascade of them only shows one
+static DIALOG_SHOWN: AtomicBool = AtomicBool::new(false);
+
+// Report every panic in a crash log, and in a message box on Windows, before the default handling
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        default_hook(info); // Still print to the console where there is one
+
+        // Every panic is logged, but only the first gets a dialog: another thread failing
+        // while the first unwinds would otherwise stack up boxes the user has to dismiss
+        let location = info.location().map_or_else(|| "unknown location".to_string(), ToString::to_string);
+        let report = crash_report(panic_message(info.payload()), &location, &Backtrace::force_capture());
+        let log = match write_crash_log(&report) {
+            Ok(path) => Some(path),
+            Err(error) => {
+                // Nothing here may panic again, so failures are only mentioned on stderr
+                let _ = writeln!(io::stderr(), "Could not write the crash log: {}", error);
+                None
+            }
+        };
+        if !DIALOG_SHOWN.swap(true, Ordering::SeqCst) {
+            show_crash_dialog(log);
+        }
+    }));
+}
+
+// The message a panic was raised with
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    // Panics carry either a static message or a formatted one
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("(no message)")
+}
+
+// Describe a panic along with the app version and platform it happened on
+fn crash_report(message: &str, location: &str, backtrace: &Backtrace) -> String {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+
+    format!(
+        "=== Crash at unix time {} ===\nGuessingGame {} on {} ({})\nPanicked at {}: {}\n\nBacktrace:\n{}\n\n",
+        timestamp,
+        CURRENT_VERSION,
+        env::consts::OS,
+        env::consts::ARCH,
+        location,
+        message,
+        backtrace
+    )
+}
+
+// Append a report to the crash log, returning where it was written
+fn write_crash_log(report: &str) -> io::Result<PathBuf> {
+    let dir = storage::data_dir().ok_or_else(storage::no_data_dir)?;
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(FILE_NAME);
+    OpenOptions::new().create(true).append(true).open(&path)?.write_all(report.as_bytes())?;
+    Ok(path)
+}
+
+// Tell the user the game crashed and where the details are
+//
+// Only Windows needs this, since everywhere else the default hook's output reaches the
+// terminal or system log.
+#[cfg(windows)]
+fn show_crash_dialog(log: Option<PathBuf>) {
+    let description = match log {
+        Some(path) => format!("Guessing Game crashed. The details were saved to:\n{}", path.display()),
+        None => "Guessing Game crashed, and the details could not be saved.".to_string(),
+    };
+
+    rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Error)
+        .set_title("Guessing Game crashed")
+        .set_description(&description)
+        .set_buttons(rfd::MessageButtons::Ok)
+        .show();
+}
+
+#[cfg(not(windows))]
+fn show_crash_dialog(_log: Option<PathBuf>) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panic_messages_are_read_from_either_payload() {
+        let payload = |f: fn()| panic::catch_unwind(f).unwrap_err();
+        assert_eq!(panic_message(&*payload(|| panic!("static"))), "static");
+        assert_eq!(panic_message(&*payload(|| panic!("formatted {}", 7))), "formatted 7");
+        assert_eq!(panic_message(&*payload(|| panic::panic_any(7_u8))), "(no message)");
+    }
+
+    #[test]
+    fn a_report_names_the_version_platform_and_place() {
+        let report = crash_report("it broke", "src/main.rs:1:2", &Backtrace::disabled());
+        assert!(report.starts_with("=== Crash at unix time "));
+        assert!(report.contains(&format!("GuessingGame {} on {} ({})", CURRENT_VERSION, env::consts::OS, env::consts::ARCH)));
+        assert!(report.contains("Panicked at src/main.rs:1:2: it broke\n"));
+        assert!(report.contains("Backtrace:\n"));
+    }
+
+    #[test]
+    fn reports_are_appended_to_the_log() {
+        let _ = fs::remove_file(storage::data_dir().unwrap().join(FILE_NAME));
+        let path = write_crash_log("first\n").unwrap();
+        assert_eq!(path, storage::data_dir().unwrap().join(FILE_NAME));
+        write_crash_log("second\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first\nsecond\n");
+    }
+
+    #[test]
+    fn a_log_that_cannot_be_written_is_an_error_not_a_panic() {
+        storage::set_detached();
+        assert_eq!(write_crash_log("lost\n").unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn a_log_blocked_by_a_directory_is_an_error_not_a_panic() {
+        let path = storage::data_dir().unwrap().join(FILE_NAME);
+        let _ = fs::remove_file(&path);
+        fs::create_dir_all(&path).unwrap();
+        assert!(write_crash_log("lost\n").is_err());
+        fs::remove_dir(&path).unwrap();
+    }
+}
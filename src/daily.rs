@@ -0,0 +1,225 @@
+// The daily challenge, shared by every player on the same day, and the days it was solved
+//
+// Days follow UTC, so everyone changes over to the next challenge at the same moment
+// wherever they are.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::migration::{self, Schema};
+use crate::storage;
+
+// File the solved days are stored in, inside the data directory
+const FILE_NAME: &str = "daily.txt";
+
+// How the file has changed between versions
+const SCHEMA: Schema = Schema { file: FILE_NAME, name: "daily challenge", legacy_key: None, steps: &[] };
+
+// Seconds in a day, ignoring leap seconds like Unix time does
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+// Month names, January first
+const MONTH_NAMES: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+// A day in the proleptic Gregorian calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32, // 1 for January
+    pub day: u32,   // 1 for the first of the month
+}
+
+impl Date {
+    // Today's date in UTC
+    pub fn today() -> Date {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+        Date::from_days((seconds / SECONDS_PER_DAY) as i64)
+    }
+
+    // The date `days` after 1970-01-01, which may be negative
+    //
+    // This and `days` are Howard Hinnant's civil calendar algorithms, which count in
+    // 400-year eras so that every leap year rule falls out of the arithmetic.
+    pub fn from_days(days: i64) -> Date {
+        let days = days + 719_468; // Count from 0000-03-01, so leap days fall at the end of a year
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153; // 0 for March
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Date { year: year as i32, month: month as u32, day: day as u32 }
+    }
+
+    // Days since 1970-01-01, negative before it
+    pub fn days(self) -> i64 {
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let shifted_month = i64::from((self.month + 9) % 12); // 0 for March
+        let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    // The day before
+    pub fn previous(self) -> Date {
+        Date::from_days(self.days() - 1)
+    }
+
+    // The month the date falls in
+    pub fn month(self) -> Month {
+        Month { year: self.year, month: self.month }
+    }
+
+    // The value written to the daily file, such as "2026-10-14"
+    pub fn key(self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    // Read a value written by `key`, rejecting dates that don't exist
+    pub fn from_key(key: &str) -> Option<Date> {
+        let mut parts = key.trim().splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+
+        let month_valid = (1..=12).contains(&month);
+        (month_valid && day >= 1 && day <= (Month { year, month }).length()).then_some(Date { year, month, day })
+    }
+
+    // The seed of this day's challenge, the same for everyone
+    pub fn seed(self) -> u32 {
+        // Spread consecutive days far apart, so neighbouring days don't get similar secrets
+        (self.days() as u32).wrapping_mul(0x9E37_79B9) ^ 0x2545_F491
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.day, MONTH_NAMES[self.month as usize - 1], self.year)
+    }
+}
+
+// A calendar month
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Month {
+    pub year: i32,
+    pub month: u32, // 1 for January
+}
+
+impl Month {
+    // The first day of the month
+    pub fn first(self) -> Date {
+        Date { year: self.year, month: self.month, day: 1 }
+    }
+
+    // Days in the month
+    pub fn length(self) -> u32 {
+        let next = self.next().first();
+        (next.days() - self.first().days()) as u32
+    }
+
+    // The month before
+    pub fn previous(self) -> Month {
+        match self.month {
+            1 => Month { year: self.year - 1, month: 12 },
+            month => Month { year: self.year, month: month - 1 },
+        }
+    }
+
+    // The month after
+    pub fn next(self) -> Month {
+        match self.month {
+            12 => Month { year: self.year + 1, month: 1 },
+            month => Month { year: self.year, month: month + 1 },
+        }
+    }
+
+    // The month laid out in weeks from Monday to Sunday, with `None` padding either end
+    pub fn weeks(self) -> Vec<[Option<Date>; 7]> {
+        // 1970-01-01 was a Thursday, the fourth day of a Monday-first week
+        let lead = (self.first().days() + 3).rem_euclid(7) as usize;
+        let mut cells: Vec<Option<Date>> = vec![None; lead];
+        cells.extend((1..=self.length()).map(|day| Some(Date { year: self.year, month: self.month, day })));
+        cells.resize(cells.len().div_ceil(7) * 7, None);
+
+        cells
+            .chunks_exact(7)
+            .map(|week| [week[0], week[1], week[2], week[3], week[4], week[5], week[6]])
+            .collect()
+    }
+}
+
+impl fmt::Display for Month {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", MONTH_NAMES[self.month as usize - 1], self.year)
+    }
+}
+
+// The days the daily challenge was solved, saved after every change
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Completions {
+    solved: BTreeSet<Date>,
+}
+
+impl Completions {
+    // Load the solved days, starting afresh if there are none or they can't be read
+    pub fn load() -> Completions {
+        let values = match migration::load(&SCHEMA) {
+            Ok(Some(values)) => values,
+            Ok(None) => return Completions::default(), // No daily challenge solved yet
+            Err(error) => {
+                tracing::warn!(%error, "Could not read the daily challenge history");
+                return Completions::default();
+            }
+        };
+
+        let solved = values
+            .get("solved")
+            .map(|list| list.split(',').filter_map(Date::from_key).collect())
+            .unwrap_or_default();
+        Completions { solved }
+    }
+
+    // Save the solved days, logging rather than failing if the file can't be written
+    fn save(&self) {
+        let solved: Vec<String> = self.solved.iter().map(|date| date.key()).collect();
+        let contents = storage::write_key_values([("solved", solved.join(","))]);
+
+        if let Err(error) = migration::save(&SCHEMA, &contents) {
+            tracing::warn!(%error, "Could not save the daily challenge history");
+        }
+    }
+
+    // Whether the challenge for `date` was solved
+    pub fn is_solved(&self, date: Date) -> bool {
+        self.solved.contains(&date)
+    }
+
+    // Note the challenge for `date` as solved
+    pub fn record(&mut self, date: Date) {
+        if self.solved.insert(date) {
+            self.save();
+        }
+    }
+
+    // Days in a row solved up to `today`, still counting yesterday's run while today is open
+    pub fn streak(&self, today: Date) -> u32 {
+        let mut day = if self.is_solved(today) { today } else { today.previous() };
+        let mut streak = 0;
+        while self.is_solved(day) {
+            streak += 1;
+            day = day.previous();
+        }
+        streak
+    }
+}
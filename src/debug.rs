@@ -0,0 +1,80 @@
+// The developer overlay, and the record of recent messages it shows
+
+use std::collections::VecDeque;
+
+// Messages the overlay lists
+pub const RECENT_MESSAGES: usize = 5;
+
+// The last `capacity` items pushed, dropping the oldest to make room
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingBuffer<T> {
+    items: VecDeque<T>, // Oldest first
+    capacity: usize,
+}
+
+impl<T> RingBuffer<T> {
+    // An empty buffer holding at most `capacity` items
+    pub fn new(capacity: usize) -> RingBuffer<T> {
+        RingBuffer { items: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    // Add an item, dropping the oldest once the buffer is full
+    pub fn push(&mut self, item: T) {
+        if self.capacity == 0 {
+            return; // Nothing is ever kept
+        }
+        if self.items.len() == self.capacity {
+            self.items.pop_front();
+        }
+        self.items.push_back(item);
+    }
+
+    // The items kept, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kept(buffer: &RingBuffer<u32>) -> Vec<u32> {
+        buffer.iter().copied().collect()
+    }
+
+    #[test]
+    fn items_are_kept_oldest_first_until_full() {
+        let mut buffer = RingBuffer::new(3);
+        assert_eq!(kept(&buffer), []);
+        buffer.push(1);
+        buffer.push(2);
+        assert_eq!(kept(&buffer), [1, 2]);
+        buffer.push(3);
+        assert_eq!(kept(&buffer), [1, 2, 3]);
+    }
+
+    #[test]
+    fn the_oldest_item_makes_room_for_the_newest() {
+        let mut buffer = RingBuffer::new(RECENT_MESSAGES);
+        for n in 0..12 {
+            buffer.push(n);
+        }
+        assert_eq!(kept(&buffer), [7, 8, 9, 10, 11]);
+    }
+
+    #[test]
+    fn a_buffer_of_one_keeps_only_the_latest() {
+        let mut buffer = RingBuffer::new(1);
+        buffer.push(1);
+        buffer.push(2);
+        assert_eq!(kept(&buffer), [2]);
+    }
+
+    #[test]
+    fn a_buffer_of_none_keeps_nothing() {
+        let mut buffer = RingBuffer::new(0);
+        buffer.push(1);
+        assert_eq!(kept(&buffer), []);
+    }
+}
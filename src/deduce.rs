@@ -0,0 +1,227 @@
+// Deducing the player's number from a batch of true statements about it, for the computer-guesses mode
+//
+// Rather than answering the bot one guess at a time, the player can state everything at once,
+// as in ">10, <80, even", and the computer works out what's left by checking every number in the
+// range against every statement. When nothing is left the statements can't all be true, and the
+// computer points at the fewest of them that can't hold together.
+
+use std::fmt;
+
+// Numbers listed in a result before the rest are only counted
+const LISTED: usize = 15;
+
+// One statement about the number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    Above(u32),      // ">n"
+    Below(u32),      // "<n"
+    AtLeast(u32),    // ">=n"
+    AtMost(u32),     // "<=n"
+    Is(u32),         // "=n"
+    IsNot(u32),      // "!=n"
+    Even,            // "even"
+    Odd,             // "odd"
+    MultipleOf(u32), // "multiple of n", never of 0
+}
+
+impl Constraint {
+    // Whether `number` makes the statement true
+    pub fn holds(self, number: u32) -> bool {
+        match self {
+            Constraint::Above(bound) => number > bound,
+            Constraint::Below(bound) => number < bound,
+            Constraint::AtLeast(bound) => number >= bound,
+            Constraint::AtMost(bound) => number <= bound,
+            Constraint::Is(value) => number == value,
+            Constraint::IsNot(value) => number != value,
+            Constraint::Even => number.is_multiple_of(2),
+            Constraint::Odd => !number.is_multiple_of(2),
+            Constraint::MultipleOf(factor) => number.is_multiple_of(factor),
+        }
+    }
+
+    // Read one statement, as in ">=10" or "multiple of 3"
+    fn parse(clause: &str) -> Option<Constraint> {
+        let clause = clause.trim().to_lowercase();
+        let number = |rest: &str| rest.trim().parse::<u32>().ok();
+        // The two-character operators go first, so ">=" isn't read as ">" followed by "=10"
+        let constraint = if let Some(rest) = clause.strip_prefix(">=") {
+            Constraint::AtLeast(number(rest)?)
+        } else if let Some(rest) = clause.strip_prefix("<=") {
+            Constraint::AtMost(number(rest)?)
+        } else if let Some(rest) = clause.strip_prefix("!=") {
+            Constraint::IsNot(number(rest)?)
+        } else if let Some(rest) = clause.strip_prefix('>') {
+            Constraint::Above(number(rest)?)
+        } else if let Some(rest) = clause.strip_prefix('<') {
+            Constraint::Below(number(rest)?)
+        } else if let Some(rest) = clause.strip_prefix('=') {
+            Constraint::Is(number(rest)?)
+        } else if let Some(rest) = clause.strip_prefix("multiple of") {
+            Constraint::MultipleOf(number(rest).filter(|&factor| factor > 0)?)
+        } else {
+            match clause.as_str() {
+                "even" => Constraint::Even,
+                "odd" => Constraint::Odd,
+                _ => return None,
+            }
+        };
+        Some(constraint)
+    }
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constraint::Above(bound) => write!(f, ">{}", bound),
+            Constraint::Below(bound) => write!(f, "<{}", bound),
+            Constraint::AtLeast(bound) => write!(f, ">={}", bound),
+            Constraint::AtMost(bound) => write!(f, "<={}", bound),
+            Constraint::Is(value) => write!(f, "={}", value),
+            Constraint::IsNot(value) => write!(f, "!={}", value),
+            Constraint::Even => write!(f, "even"),
+            Constraint::Odd => write!(f, "odd"),
+            Constraint::MultipleOf(factor) => write!(f, "multiple of {}", factor),
+        }
+    }
+}
+
+// Why a batch of statements couldn't be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeduceError {
+    Empty,                              // Nothing was typed
+    Unreadable(String),                 // A statement, as typed, that isn't one of the forms understood
+    Impossible(Constraint),             // A statement no number in the range makes true
+    Clash(Constraint, Constraint),      // Two statements that can't both be true
+    Together(usize),                    // This many statements that can't all be true, though any two can
+}
+
+impl fmt::Display for DeduceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeduceError::Empty => write!(f, "Type what's true about your number, like >10, <80, even"),
+            DeduceError::Unreadable(clause) => write!(
+                f,
+                "Couldn't read \"{}\" - use >, <, >=, <=, = or != and a number, even, odd or multiple of a number",
+                clause
+            ),
+            DeduceError::Impossible(constraint) => write!(f, "No number in the range fits \"{}\"", constraint),
+            DeduceError::Clash(first, second) => {
+                write!(f, "Those can't all be true: \"{}\" and \"{}\" contradict each other", first, second)
+            }
+            DeduceError::Together(count) => {
+                write!(f, "Those can't all be true: no number fits all {} at once, though any two can hold together", count)
+            }
+        }
+    }
+}
+
+// What the statements leave of the range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deduction {
+    pub remaining: usize,   // Numbers that make every statement true
+    pub low: u32,           // The smallest of them
+    pub high: u32,          // The largest of them
+    pub listed: Vec<u32>,   // The first few of them, smallest first
+    pub everything: bool,   // Whether the statements rule nothing out
+}
+
+// Read a comma-separated batch of statements, as in ">10, <80, even"
+pub fn parse(text: &str) -> Result<Vec<Constraint>, DeduceError> {
+    let clauses: Vec<&str> = text.split(',').map(str::trim).filter(|clause| !clause.is_empty()).collect();
+    if clauses.is_empty() {
+        return Err(DeduceError::Empty);
+    }
+    clauses
+        .into_iter()
+        .map(|clause| Constraint::parse(clause).ok_or_else(|| DeduceError::Unreadable(clause.to_string())))
+        .collect()
+}
+
+// Work out which numbers in `min..=max` make every statement true, or why none can
+pub fn deduce(constraints: &[Constraint], min: u32, max: u32) -> Result<Deduction, DeduceError> {
+    let fits = |number: u32, constraints: &[Constraint]| constraints.iter().all(|constraint| constraint.holds(number));
+    let remaining: Vec<u32> = (min..=max).filter(|&number| fits(number, constraints)).collect();
+
+    if let (Some(&low), Some(&high)) = (remaining.first(), remaining.last()) {
+        return Ok(Deduction {
+            remaining: remaining.len(),
+            low,
+            high,
+            listed: remaining.iter().copied().take(LISTED).collect(),
+            everything: remaining.len() as u64 == u64::from(max - min) + 1,
+        });
+    }
+
+    // Name the smallest set of statements that's to blame: one alone, then a pair, then all of them
+    let none_fit = |constraints: &[Constraint]| !(min..=max).any(|number| fits(number, constraints));
+    if let Some(&constraint) = constraints.iter().find(|&&constraint| none_fit(&[constraint])) {
+        return Err(DeduceError::Impossible(constraint));
+    }
+    for (i, &first) in constraints.iter().enumerate() {
+        if let Some(&second) = constraints[i + 1..].iter().find(|&&second| none_fit(&[first, second])) {
+            return Err(DeduceError::Clash(first, second));
+        }
+    }
+    Err(DeduceError::Together(constraints.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Constraint::*;
+
+    #[test]
+    fn statements_are_read_in_every_form() {
+        let cases = [
+            (">10, <80, even", vec![Above(10), Below(80), Even]),
+            ("multiple of 3", vec![MultipleOf(3)]),
+            (" >= 5 , != 7 ,", vec![AtLeast(5), IsNot(7)]),
+            ("<=50, =42, ODD", vec![AtMost(50), Is(42), Odd]),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse(input), Ok(expected), "{}", input);
+        }
+    }
+
+    #[test]
+    fn statements_that_cant_be_read_are_named() {
+        let cases = [
+            ("", DeduceError::Empty),
+            (" , ", DeduceError::Empty),
+            (">10, bigger than 5", DeduceError::Unreadable("bigger than 5".to_string())),
+            (">ten", DeduceError::Unreadable(">ten".to_string())),
+            ("multiple of 0", DeduceError::Unreadable("multiple of 0".to_string())),
+            ("=> 5", DeduceError::Unreadable("=> 5".to_string())),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse(input), Err(expected), "{}", input);
+        }
+    }
+
+    #[test]
+    fn the_smallest_set_to_blame_is_named() {
+        let cases = [
+            (">100", DeduceError::Impossible(Above(100))),
+            ("even, <1", DeduceError::Impossible(Below(1))),
+            (">50, even, <20", DeduceError::Clash(Above(50), Below(20))),
+            ("=7, even", DeduceError::Clash(Is(7), Even)),
+            ("odd, >=2, <=2", DeduceError::Together(3)), // Any two leave 1, 2 or 3
+        ];
+        for (input, expected) in cases {
+            assert_eq!(deduce(&parse(input).unwrap(), 1, 100), Err(expected), "{}", input);
+        }
+    }
+
+    #[test]
+    fn statements_narrow_the_range_down() {
+        let deduction = deduce(&parse(">40, <43, even").unwrap(), 1, 100).unwrap();
+        assert_eq!(deduction, Deduction { remaining: 1, low: 42, high: 42, listed: vec![42], everything: false });
+
+        let deduction = deduce(&parse("multiple of 3").unwrap(), 1, 100).unwrap();
+        assert_eq!((deduction.remaining, deduction.low, deduction.high), (33, 3, 99));
+        assert_eq!(deduction.listed.len(), LISTED, "only the first few are listed");
+
+        assert!(deduce(&parse(">=1").unwrap(), 1, 100).unwrap().everything);
+    }
+}
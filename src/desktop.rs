@@ -0,0 +1,12 @@
+// Window manager features that not every platform provides
+
+use std::env;
+
+// Whether the window manager honours requests to keep a window above all others
+//
+// Wayland compositors ignore always-on-top requests from clients, and iced gives no
+// feedback when a request is ignored, so this has to be decided up front.
+pub fn always_on_top_supported() -> bool {
+    let wayland = cfg!(all(unix, not(target_os = "macos"))) && env::var_os("WAYLAND_DISPLAY").is_some();
+    !wayland
+}
@@ -0,0 +1,154 @@
+// Endless mode: a fresh secret straight after every win, until the player stops
+
+use std::io;
+
+use crate::migration::{self, Schema};
+use crate::storage;
+
+// File the endless records are stored in, inside the data directory
+const FILE_NAME: &str = "endless.txt";
+
+// How the file has changed between versions
+const SCHEMA: Schema = Schema { file: FILE_NAME, name: "endless mode records", legacy_key: None, steps: &[] };
+
+// The session being played, carried over from one secret to the next
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Session {
+    pub solved: u32,  // Secrets found so far
+    pub guesses: u32, // Guesses spent on the secrets found, not counting the one in play
+}
+
+impl Session {
+    // Bank a secret found in `attempts` guesses, before the next one is drawn
+    pub fn solve(&mut self, attempts: u32) {
+        self.solved += 1;
+        self.guesses += attempts;
+    }
+
+    // Guesses across the whole session, counting `attempts` made on the secret in play
+    pub fn total_guesses(&self, attempts: u32) -> u32 {
+        self.guesses + attempts
+    }
+}
+
+// Totals across every endless session, kept apart from the single-game stats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Records {
+    pub sessions: u32,    // Sessions played to the end
+    pub best_solved: u32, // Most secrets found in one session
+    pub solved: u32,      // Secrets found, ever
+    pub guesses: u32,     // Guesses made, ever, including on secrets left unfound
+}
+
+impl Records {
+    // Load the records, starting afresh if there are none or they can't be read
+    pub fn load() -> Records {
+        let values = match migration::load(&SCHEMA) {
+            Ok(Some(values)) => values,
+            Ok(None) => return Records::default(), // No endless sessions yet
+            Err(error) => {
+                tracing::warn!(%error, "Could not read the endless mode records");
+                return Records::default();
+            }
+        };
+
+        let count = |key: &str| values.get(key).and_then(|value| value.parse().ok()).unwrap_or(0);
+        Records {
+            sessions: count("sessions"),
+            best_solved: count("best_solved"),
+            solved: count("solved"),
+            guesses: count("guesses"),
+        }
+    }
+
+    // Save the records, logging rather than failing if the file can't be written
+    fn save(&self) {
+        let contents = storage::write_key_values([
+            ("sessions", self.sessions.to_string()),
+            ("best_solved", self.best_solved.to_string()),
+            ("solved", self.solved.to_string()),
+            ("guesses", self.guesses.to_string()),
+        ]);
+
+        if let Err(error) = migration::save(&SCHEMA, &contents) {
+            tracing::warn!(%error, "Could not save the endless mode records");
+        }
+    }
+
+    // Add a finished session, with `attempts` made on the secret it stopped in, and save;
+    // a session stopped before any guess isn't worth a record
+    pub fn record(&mut self, session: Session, attempts: u32) {
+        let guesses = session.total_guesses(attempts);
+        if guesses == 0 {
+            return;
+        }
+
+        self.sessions += 1;
+        self.best_solved = self.best_solved.max(session.solved);
+        self.solved = self.solved.saturating_add(session.solved);
+        self.guesses = self.guesses.saturating_add(guesses);
+        self.save();
+    }
+
+    // Forget every session and delete the saved file; they're forgotten even if that fails
+    pub fn reset(&mut self) -> io::Result<()> {
+        *self = Records::default();
+        migration::remove(&SCHEMA)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_session_banks_each_secret_found() {
+        let mut session = Session::default();
+        assert_eq!(session.total_guesses(3), 3);
+        session.solve(4);
+        session.solve(6);
+        assert_eq!(session, Session { solved: 2, guesses: 10 });
+        assert_eq!(session.total_guesses(0), 10);
+        assert_eq!(session.total_guesses(2), 12, "guesses on the secret in play count too");
+    }
+
+    #[test]
+    fn finished_sessions_add_up_and_keep_the_best() {
+        let mut records = Records::default();
+        records.record(Session { solved: 3, guesses: 15 }, 2);
+        records.record(Session { solved: 5, guesses: 30 }, 0);
+        records.record(Session { solved: 1, guesses: 4 }, 7);
+        assert_eq!(records, Records { sessions: 3, best_solved: 5, solved: 9, guesses: 58 });
+    }
+
+    #[test]
+    fn a_session_stopped_before_any_guess_is_not_recorded() {
+        let mut records = Records::default();
+        records.record(Session::default(), 0);
+        assert_eq!(records, Records::default());
+
+        records.record(Session::default(), 1);
+        assert_eq!(records, Records { sessions: 1, best_solved: 0, solved: 0, guesses: 1 }, "a guess without a find still counts");
+    }
+
+    #[test]
+    fn totals_saturate_rather_than_wrap() {
+        let mut records = Records { sessions: 1, best_solved: 1, solved: u32::MAX, guesses: u32::MAX - 1 };
+        records.record(Session { solved: 2, guesses: 5 }, 0);
+        assert_eq!((records.solved, records.guesses, records.best_solved), (u32::MAX, u32::MAX, 2));
+    }
+
+    #[test]
+    fn records_are_saved_and_loaded_back() {
+        Records::default().reset().unwrap();
+        assert_eq!(Records::load(), Records::default());
+
+        let mut records = Records::default();
+        records.record(Session { solved: 2, guesses: 9 }, 3);
+        assert_eq!(Records::load(), records);
+
+        records.reset().unwrap();
+        assert_eq!(records, Records::default());
+        assert_eq!(Records::load(), Records::default());
+    }
+}
@@ -0,0 +1,241 @@
+// A tiny evaluator for guesses typed as sums, such as "2^6" or "100/2 - 3"
+//
+// Only whole numbers are involved: +, −, ×, / and ^ with the usual precedence, ^ binding
+// tightest and grouping to the right, and parentheses. Division has to come out exactly,
+// since a guess is always a whole number. Everything is worked out in `i64` with checked
+// arithmetic, so a sum that runs away is reported rather than wrapping round.
+
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+// Why a typed sum couldn't be turned into a guess
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExprError {
+    Syntax,         // Not a sum the evaluator understands, such as "5 +" or "(2"
+    Fraction,       // A division or negative power that doesn't come out whole
+    DivisionByZero, // Something was divided by zero
+    Overflow,       // A step, or the answer, is too big to work with
+    Negative,       // The answer is below zero, so it can't be a guess
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExprError::Syntax => write!(f, "Please enter a valid number, or a sum such as 50+13."),
+            ExprError::Fraction => write!(f, "That sum doesn't come out to a whole number - division has to be exact."),
+            ExprError::DivisionByZero => write!(f, "That sum divides by zero."),
+            ExprError::Overflow => write!(f, "That sum is too big to work out."),
+            ExprError::Negative => write!(f, "That sum comes out below zero, and guesses can't be negative."),
+        }
+    }
+}
+
+// Whether `input` has an operator in it, so it's worth evaluating rather than just parsing
+pub fn looks_like_sum(input: &str) -> bool {
+    input.chars().any(|c| operator(c).is_some() || c == '(' || c == ')')
+}
+
+// Evaluate `input` to the guess it stands for
+pub fn evaluate(input: &str) -> Result<u32, ExprError> {
+    let mut parser = Parser { chars: input.chars().peekable() };
+    let value = parser.sum()?;
+    parser.skip_spaces();
+    if parser.chars.peek().is_some() {
+        return Err(ExprError::Syntax); // Something left over, such as an unmatched ")"
+    }
+
+    if value < 0 {
+        return Err(ExprError::Negative);
+    }
+    u32::try_from(value).map_err(|_| ExprError::Overflow)
+}
+
+// The operator a character stands for, accepting the typographic signs as well as ASCII
+fn operator(c: char) -> Option<char> {
+    match c {
+        '+' => Some('+'),
+        '-' | '−' => Some('-'),
+        '*' | '×' => Some('*'),
+        '/' | '÷' => Some('/'),
+        '^' => Some('^'),
+        _ => None,
+    }
+}
+
+// A recursive-descent parser that evaluates as it goes
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_spaces(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    // The next operator, if it's one of `wanted`, consuming it
+    fn next_operator(&mut self, wanted: &[char]) -> Option<char> {
+        self.skip_spaces();
+        let op = operator(*self.chars.peek()?).filter(|op| wanted.contains(op))?;
+        self.chars.next();
+        Some(op)
+    }
+
+    // sum := product (("+" | "-") product)*
+    fn sum(&mut self) -> Result<i64, ExprError> {
+        let mut value = self.product()?;
+        while let Some(op) = self.next_operator(&['+', '-']) {
+            let right = self.product()?;
+            value = if op == '+' { value.checked_add(right) } else { value.checked_sub(right) }.ok_or(ExprError::Overflow)?;
+        }
+        Ok(value)
+    }
+
+    // product := unary (("*" | "/") unary)*
+    fn product(&mut self) -> Result<i64, ExprError> {
+        let mut value = self.unary()?;
+        while let Some(op) = self.next_operator(&['*', '/']) {
+            let right = self.unary()?;
+            value = if op == '*' { value.checked_mul(right).ok_or(ExprError::Overflow)? } else { divide(value, right)? };
+        }
+        Ok(value)
+    }
+
+    // unary := "-" unary | power, so -2^2 is -(2^2)
+    fn unary(&mut self) -> Result<i64, ExprError> {
+        if self.next_operator(&['-']).is_some() {
+            return self.unary()?.checked_neg().ok_or(ExprError::Overflow);
+        }
+        self.power()
+    }
+
+    // power := atom ("^" unary)?, so 2^3^2 is 2^9
+    fn power(&mut self) -> Result<i64, ExprError> {
+        let base = self.atom()?;
+        if self.next_operator(&['^']).is_none() {
+            return Ok(base);
+        }
+        let exponent = self.unary()?;
+        raise(base, exponent)
+    }
+
+    // atom := digits | "(" sum ")"
+    fn atom(&mut self) -> Result<i64, ExprError> {
+        self.skip_spaces();
+        if self.chars.next_if_eq(&'(').is_some() {
+            let value = self.sum()?;
+            self.skip_spaces();
+            return self.chars.next_if_eq(&')').map(|_| value).ok_or(ExprError::Syntax);
+        }
+
+        let mut value: Option<i64> = None;
+        while let Some(digit) = self.chars.peek().and_then(|c| c.to_digit(10)) {
+            self.chars.next();
+            let sofar = value.unwrap_or(0);
+            value = Some(sofar.checked_mul(10).and_then(|v| v.checked_add(i64::from(digit))).ok_or(ExprError::Overflow)?);
+        }
+        value.ok_or(ExprError::Syntax)
+    }
+}
+
+// `left / right`, only when it comes out whole
+fn divide(left: i64, right: i64) -> Result<i64, ExprError> {
+    if right == 0 {
+        return Err(ExprError::DivisionByZero);
+    }
+    if left.checked_rem(right).ok_or(ExprError::Overflow)? != 0 {
+        return Err(ExprError::Fraction);
+    }
+    left.checked_div(right).ok_or(ExprError::Overflow)
+}
+
+// `base ^ exponent`; a negative power is only whole for a base of 1 or -1
+fn raise(base: i64, exponent: i64) -> Result<i64, ExprError> {
+    if exponent < 0 {
+        return match base {
+            0 => Err(ExprError::DivisionByZero),
+            1 => Ok(1),
+            -1 => Ok(if exponent % 2 == 0 { 1 } else { -1 }),
+            _ => Err(ExprError::Fraction),
+        };
+    }
+    let exponent = u32::try_from(exponent).map_err(|_| ExprError::Overflow)?;
+    base.checked_pow(exponent).ok_or(ExprError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_follow_the_usual_precedence() {
+        let cases = [
+            ("42", 42),
+            ("2 + 3 * 4", 14),
+            ("(2 + 3) * 4", 20),
+            ("100/2 - 3", 47),
+            ("20 - 5 - 3", 12),    // Subtraction groups to the left
+            ("64 / 4 / 2", 8),     // So does division
+            ("2 * 3 ^ 2", 18),     // ^ binds tighter than *
+            ("2^3^2", 512),        // ^ groups to the right: 2^9, not 8^2
+            ("(2^3)^2", 64),
+            ("10 - -2^2 - 6", 8),  // -2^2 is -(2^2), so this is 10 + 4 - 6
+            ("(-2)^2", 4),
+            ("-(3 - 10)", 7),
+            ("1^-3", 1),
+            ("(-1)^-2", 1),
+            (" ( 7 ) ", 7),
+            ("2^31 + 2^31 - 1", u32::MAX),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(evaluate(input), Ok(expected), "{}", input);
+        }
+    }
+
+    #[test]
+    fn typographic_operators_work_like_ascii() {
+        let cases = [("10 − 3", 7), ("6 × 7", 42), ("84 ÷ 2", 42), ("2 × 3 − 4 ÷ 2", 4), ("−2 × −3", 6)];
+        for (input, expected) in cases {
+            assert_eq!(evaluate(input), Ok(expected), "{}", input);
+        }
+    }
+
+    #[test]
+    fn every_error_has_a_sum_that_causes_it() {
+        let cases = [
+            ("", ExprError::Syntax),
+            ("5 +", ExprError::Syntax),
+            ("(2", ExprError::Syntax),
+            ("2)", ExprError::Syntax),
+            ("2 3", ExprError::Syntax),
+            ("5 ** 2", ExprError::Syntax),
+            ("two + 2", ExprError::Syntax),
+            ("7 / 2", ExprError::Fraction),
+            ("2^-1", ExprError::Fraction),
+            ("5 / 0", ExprError::DivisionByZero),
+            ("5 / (3 - 3)", ExprError::DivisionByZero),
+            ("0^-1", ExprError::DivisionByZero),
+            ("2^64", ExprError::Overflow),
+            ("2^32", ExprError::Overflow),                 // Fine to work with, but too big for a guess
+            ("2^31 * 2^31 * 2^31", ExprError::Overflow),
+            ("99999999999999999999", ExprError::Overflow), // More digits than any number holds
+            ("1 + 99999999999999999999", ExprError::Overflow),
+            ("3 - 5", ExprError::Negative),
+            ("-2^2", ExprError::Negative),
+            ("-1", ExprError::Negative),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(evaluate(input), Err(expected), "{}", input);
+        }
+    }
+
+    #[test]
+    fn only_input_with_an_operator_looks_like_a_sum() {
+        assert!(looks_like_sum("50+13"));
+        assert!(looks_like_sum("6×7"));
+        assert!(looks_like_sum("(5)"));
+        assert!(!looks_like_sum("50"));
+        assert!(!looks_like_sum("1,000"));
+        assert!(!looks_like_sum("abc"));
+    }
+}
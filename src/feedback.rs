@@ -0,0 +1,161 @@
+// Feedback shown to the player, kept as data until it is rendered for display
+
+use crate::expression::ExprError;
+use crate::format::{format_number, Locale, ParseError};
+use crate::rules::Outcome;
+
+// Every kind of feedback message the game can show
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Feedback {
+    Welcome { min: u32, max: u32 }, // Initial prompt describing the range
+    InvalidNumber(ParseError),      // The input could not be parsed, and why
+    BadSum(ExprError),              // The input was a sum that couldn't be worked out
+    TooSmall(u32),                  // The guess was below the secret
+    TooBig(u32),                    // The guess was above the secret
+    Win { secret: u32 },            // The secret was found
+    Solved { secret: u32, solved: u32 }, // A secret was found in endless mode, and the next one drawn
+    GaveUp { secret: u32 },         // The guesser gave up on a secret another player set
+    TooClose { guess: u32, previous: u32, delta: u32 }, // The guess didn't move far enough from the last one
+    TooSlow,                        // The time for a guess ran out, wasting the attempt
+    WarmUp { guess: u32, outcome: Outcome }, // The round's free guess, which doesn't count whatever it earned
+    Swapped { guess: u32, outcome: Outcome }, // A wrong guess, after which the secret changed to another that fits
+}
+
+impl Feedback {
+    // Render the feedback as text, formatting any numbers for the given locale
+    pub fn render(&self, locale: Locale) -> String {
+        self.render_with(&|value| format_number(locale, value))
+    }
+
+    // Render the feedback as text, formatting any numbers with `n`, such as to fit a narrow space
+    pub fn render_with(&self, n: &dyn Fn(u32) -> String) -> String {
+        match self {
+            Feedback::Welcome { min, max } => {
+                format!("Welcome to the Guessing Game! Pick a number between {} and {}.", n(*min), n(*max))
+            }
+            Feedback::InvalidNumber(error) => match error {
+                ParseError::Empty => String::from("Type a number to guess first."),
+                ParseError::Negative => String::from("The secret is never negative - please enter a number without a minus sign."),
+                ParseError::InvalidCharacter(c) => format!("\"{}\" isn't a digit - please enter a whole number.", c),
+                ParseError::Misgrouped => format!("Thousands separators go between groups of three digits, as in {}.", n(1000)),
+                ParseError::TooBig => format!("That number is too big - guesses go up to {}.", n(u32::MAX)),
+            },
+            Feedback::BadSum(error) => error.to_string(),
+            Feedback::TooSmall(guess) => format!("{} is too small!", n(*guess)),
+            Feedback::TooBig(guess) => format!("{} is too big!", n(*guess)),
+            Feedback::Win { secret } => format!("You win! 🎉 The number was {}.", n(*secret)),
+            Feedback::Solved { secret, solved } => {
+                format!("Solved! The number was {} - that's {} so far. Here's the next one.", n(*secret), n(*solved))
+            }
+            Feedback::GaveUp { secret } => format!("The number was {}. The player who set it wins!", n(*secret)),
+            Feedback::TooSlow => String::from("Too slow! That attempt ran out of time."),
+            Feedback::WarmUp { guess, outcome } => match outcome {
+                Outcome::TooSmall => format!("Warm-up: {} is too small. That one doesn't count.", n(*guess)),
+                Outcome::TooBig => format!("Warm-up: {} is too big. That one doesn't count.", n(*guess)),
+                Outcome::Win => format!("Warm-up: {} would have won! That one doesn't count, so guess it for real.", n(*guess)),
+            },
+            Feedback::Swapped { guess, outcome } => {
+                let direction = if *outcome == Outcome::TooSmall { "small" } else { "big" };
+                format!(
+                    "{} is too {}. Surprise swap! The number just changed to another that fits every clue so far.",
+                    n(*guess),
+                    direction
+                )
+            }
+            Feedback::TooClose { guess, previous, delta } => format!(
+                "{} is too close to your last guess, {} - each guess must change by at least {}.",
+                n(*guess),
+                n(*previous),
+                n(*delta)
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::parse_number;
+
+    // The message shown for `input` typed as a guess in `locale`
+    fn message(locale: Locale, input: &str) -> String {
+        let error = parse_number(locale, input).expect_err(input);
+        Feedback::InvalidNumber(error).render(locale)
+    }
+
+    #[test]
+    fn an_empty_guess_asks_for_a_number() {
+        assert_eq!(parse_number(Locale::English, "  "), Err(ParseError::Empty));
+        assert_eq!(message(Locale::English, ""), "Type a number to guess first.");
+    }
+
+    #[test]
+    fn a_negative_guess_asks_for_no_minus_sign() {
+        assert_eq!(parse_number(Locale::English, "-5"), Err(ParseError::Negative));
+        assert_eq!(
+            message(Locale::English, "-5"),
+            "The secret is never negative - please enter a number without a minus sign."
+        );
+    }
+
+    #[test]
+    fn an_invalid_character_is_quoted_back() {
+        assert_eq!(parse_number(Locale::English, "5x"), Err(ParseError::InvalidCharacter('x')));
+        assert_eq!(message(Locale::English, "5x"), "\"x\" isn't a digit - please enter a whole number.");
+    }
+
+    #[test]
+    fn a_misgrouped_number_shows_the_locale_grouping() {
+        assert_eq!(parse_number(Locale::English, "1,00"), Err(ParseError::Misgrouped));
+        assert_eq!(
+            message(Locale::English, "1,00"),
+            "Thousands separators go between groups of three digits, as in 1,000."
+        );
+        assert_eq!(
+            message(Locale::German, "1.00"),
+            "Thousands separators go between groups of three digits, as in 1.000."
+        );
+    }
+
+    #[test]
+    fn a_number_too_big_gives_the_largest_guess() {
+        assert_eq!(parse_number(Locale::English, "4294967296"), Err(ParseError::TooBig));
+        assert_eq!(message(Locale::English, "4294967296"), "That number is too big - guesses go up to 4,294,967,295.");
+    }
+
+    #[test]
+    fn every_number_in_every_message_goes_through_the_formatter() {
+        let marked = |value: u32| format!("<{}>", value);
+        let cases = [
+            (Feedback::Welcome { min: 1, max: 100 }, vec![1, 100]),
+            (Feedback::InvalidNumber(ParseError::Misgrouped), vec![1000]),
+            (Feedback::InvalidNumber(ParseError::TooBig), vec![u32::MAX]),
+            (Feedback::TooSmall(5), vec![5]),
+            (Feedback::TooBig(4_000_000_000), vec![4_000_000_000]),
+            (Feedback::Win { secret: 95 }, vec![95]),
+            (Feedback::Solved { secret: 95, solved: 3 }, vec![95, 3]),
+            (Feedback::GaveUp { secret: 12 }, vec![12]),
+            (Feedback::TooClose { guess: 50, previous: 48, delta: 5 }, vec![50, 48, 5]),
+            (Feedback::WarmUp { guess: 40, outcome: Outcome::Win }, vec![40]),
+            (Feedback::Swapped { guess: 60, outcome: Outcome::TooBig }, vec![60]),
+        ];
+        for (feedback, numbers) in cases {
+            let text = feedback.render_with(&marked);
+            for number in numbers {
+                assert!(text.contains(&marked(number)), "{:?} in {:?}", number, text);
+            }
+            let unmarked = text.split(['<', '>']).step_by(2).collect::<String>();
+            assert!(!unmarked.chars().any(|c| c.is_ascii_digit()), "a raw number in {:?}", text);
+        }
+
+        for feedback in [Feedback::TooSlow, Feedback::BadSum(ExprError::DivisionByZero)] {
+            assert_eq!(feedback.render_with(&marked), feedback.render(Locale::English));
+        }
+    }
+
+    #[test]
+    fn large_numbers_are_grouped_for_the_locale() {
+        assert_eq!(Feedback::TooBig(4_000_000_000).render(Locale::English), "4,000,000,000 is too big!");
+        assert_eq!(Feedback::Win { secret: 1_234_567 }.render(Locale::Swiss), "You win! 🎉 The number was 1'234'567.");
+    }
+}
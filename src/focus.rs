@@ -0,0 +1,189 @@
+// Keyboard focus order for the text fields and the settings panel
+//
+// iced 0.9 can only focus text inputs, so those get real widget focus. The other settings
+// controls get a focus the game keeps itself: Tab moves onto them like onto a field, and
+// Space and the arrow keys then act on whichever one it rests on.
+
+use iced::widget::text_input;
+
+// A text field that Tab can move to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Guess,     // The guess input
+    Challenge, // The challenge code input
+    Practice,  // The practice range input
+    Scorecard, // The path of a friend's scorecard to load
+    Replay,    // The path of a replay file to watch
+    Constraints, // The statements for the computer to deduce the player's number from
+    Accent,    // The hex accent input in the settings panel
+    PassSecret, // The hidden secret input when setting up pass and play
+    Setting(Setting), // A settings control that isn't a text input
+}
+
+// A settings control that Tab can move to, in the order the panel shows them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    ShowCandidates,
+    ShowBases,
+    MysteryMode,
+    CloseEnough,
+    MinChange,
+    SurpriseSwap,
+    TimedGuesses,
+    GhostRace,
+    GuessBudget,
+    MercyHints,
+    AssistHints,
+    GuessAssist,
+    ProfileMode, // The mode whose profile the next picker shows
+    Profile,
+    ProfileSave, // Only on screen while the mode has a profile
+    ProximityTones,
+    IdleTimeout,
+    Startup,
+    Motion,
+    SpeakFeedback,
+    SpeechRate,  // Only on screen where the engine's rate can be changed
+    SpeechVoice, // Only on screen where there are voices to pick from
+    NotifyOnWin,
+    SpectatorFeed,
+    AlwaysOnTop,
+    Compact,
+    LeftHanded,
+    OneKey,
+    OneKeyTiming,
+    Theme,
+    Font,
+    NumberFormat,
+    MonospaceDigits,
+    Colorblind,
+    Swatches, // The row of accent swatches, stepped through like a picker
+    Analytics,
+    Insights, // Only on screen while statistics are kept
+    DeleteStatistics,
+    ResetRecords,
+    WhatsNew,
+}
+
+impl Setting {
+    // The widget ID the control is created with
+    pub fn key(self) -> &'static str {
+        match self {
+            Setting::ShowCandidates => "setting_show_candidates",
+            Setting::ShowBases => "setting_show_bases",
+            Setting::MysteryMode => "setting_mystery_mode",
+            Setting::CloseEnough => "setting_close_enough",
+            Setting::MinChange => "setting_min_change",
+            Setting::SurpriseSwap => "setting_surprise_swap",
+            Setting::TimedGuesses => "setting_timed_guesses",
+            Setting::GhostRace => "setting_ghost_race",
+            Setting::GuessBudget => "setting_guess_budget",
+            Setting::MercyHints => "setting_mercy_hints",
+            Setting::AssistHints => "setting_assist_hints",
+            Setting::GuessAssist => "setting_guess_assist",
+            Setting::ProfileMode => "setting_profile_mode",
+            Setting::Profile => "setting_profile",
+            Setting::ProfileSave => "setting_profile_save",
+            Setting::ProximityTones => "setting_proximity_tones",
+            Setting::IdleTimeout => "setting_idle_timeout",
+            Setting::Startup => "setting_startup",
+            Setting::Motion => "setting_motion",
+            Setting::SpeakFeedback => "setting_speak_feedback",
+            Setting::SpeechRate => "setting_speech_rate",
+            Setting::SpeechVoice => "setting_speech_voice",
+            Setting::NotifyOnWin => "setting_notify_on_win",
+            Setting::SpectatorFeed => "setting_spectator_feed",
+            Setting::AlwaysOnTop => "setting_always_on_top",
+            Setting::Compact => "setting_compact",
+            Setting::LeftHanded => "setting_left_handed",
+            Setting::OneKey => "setting_one_key",
+            Setting::OneKeyTiming => "setting_one_key_timing",
+            Setting::Theme => "setting_theme",
+            Setting::Font => "setting_font",
+            Setting::NumberFormat => "setting_number_format",
+            Setting::MonospaceDigits => "setting_monospace_digits",
+            Setting::Colorblind => "setting_colorblind",
+            Setting::Swatches => "setting_swatches",
+            Setting::Analytics => "setting_analytics",
+            Setting::Insights => "setting_insights",
+            Setting::DeleteStatistics => "setting_delete_statistics",
+            Setting::ResetRecords => "setting_reset_records",
+            Setting::WhatsNew => "setting_whats_new",
+        }
+    }
+}
+
+// A key pressed while a setting has focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKey {
+    Activate, // Space: toggle a checkbox or press a button
+    Previous, // Left or Up: the previous choice of a picker
+    Next,     // Right or Down: the next choice of a picker
+}
+
+impl SettingKey {
+    // The value written to session recordings
+    pub fn key(self) -> &'static str {
+        match self {
+            SettingKey::Activate => "activate",
+            SettingKey::Previous => "previous",
+            SettingKey::Next => "next",
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<SettingKey> {
+        [SettingKey::Activate, SettingKey::Previous, SettingKey::Next]
+            .into_iter()
+            .find(|setting_key| setting_key.key() == key)
+    }
+}
+
+impl Field {
+    // The widget ID the field is created with, so focus commands can find it
+    //
+    // No text input has a setting's ID, so focusing one takes focus away from every text
+    // input, leaving Space and the arrow keys free for the setting.
+    pub fn id(self) -> text_input::Id {
+        text_input::Id::new(match self {
+            Field::Guess => "guess",
+            Field::Challenge => "challenge",
+            Field::Practice => "practice",
+            Field::Scorecard => "scorecard",
+            Field::Replay => "replay",
+            Field::Constraints => "constraints",
+            Field::Accent => "accent",
+            Field::PassSecret => "pass_secret",
+            Field::Setting(setting) => setting.key(),
+        })
+    }
+}
+
+// The choice after (or before, going backwards) `current` in `all`, stopping at either end
+pub fn step<T: Clone + PartialEq>(all: &[T], current: T, forward: bool) -> T {
+    let position = all.iter().position(|choice| *choice == current);
+    let index = match (position, forward) {
+        (None, _) => 0, // Not one of the choices, so start from the first
+        (Some(position), true) => (position + 1).min(all.len() - 1),
+        (Some(position), false) => position.saturating_sub(1),
+    };
+    all[index].clone()
+}
+
+// The field Tab (or Shift+Tab, going backwards) moves to from `current`
+//
+// `visible` lists the fields on screen in Tab order. Focus wraps around at either end,
+// and starts from the first field when nothing known has focus.
+pub fn next(visible: &[Field], current: Option<Field>, forward: bool) -> Option<Field> {
+    let position = current.and_then(|current| visible.iter().position(|&field| field == current));
+    let count = visible.len();
+
+    let index = match (position, forward) {
+        (_, _) if count == 0 => return None,
+        (Some(position), true) => (position + 1) % count,
+        (Some(position), false) => (position + count - 1) % count,
+        (None, true) => 0,
+        (None, false) => count - 1,
+    };
+    Some(visible[index])
+}
@@ -0,0 +1,78 @@
+// The bundled fonts the user can choose between
+
+use std::fmt;
+
+use iced::Font;
+
+// The DejaVu Sans font file, also used to draw result cards
+pub const SANS_BYTES: &[u8] = include_bytes!("../assets/fonts/DejaVuSans.ttf");
+
+// DejaVu Sans, a clean sans-serif with clearly distinct digits
+const SANS: Font = Font::External {
+    name: "DejaVu Sans",
+    bytes: SANS_BYTES,
+};
+
+// DejaVu Serif, for players who find serifs easier to tell apart
+const SERIF: Font = Font::External {
+    name: "DejaVu Serif",
+    bytes: include_bytes!("../assets/fonts/DejaVuSerif.ttf"),
+};
+
+// DejaVu Sans Mono, where every digit has the same width and a distinct shape
+pub const MONO: Font = Font::External {
+    name: "DejaVu Sans Mono",
+    bytes: include_bytes!("../assets/fonts/DejaVuSansMono.ttf"),
+};
+
+// Which font the UI text is drawn in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FontChoice {
+    #[default]
+    Default, // iced's built-in font
+    Sans,
+    Serif,
+    Mono,
+}
+
+impl FontChoice {
+    // Every choice, in the order shown in the font picker
+    pub const ALL: [FontChoice; 4] = [FontChoice::Default, FontChoice::Sans, FontChoice::Serif, FontChoice::Mono];
+
+    // The iced font to attach to text widgets
+    pub fn font(self) -> Font {
+        match self {
+            FontChoice::Default => Font::Default,
+            FontChoice::Sans => SANS,
+            FontChoice::Serif => SERIF,
+            FontChoice::Mono => MONO,
+        }
+    }
+
+    // The value written to the preferences file
+    pub fn key(self) -> &'static str {
+        match self {
+            FontChoice::Default => "default",
+            FontChoice::Sans => "sans",
+            FontChoice::Serif => "serif",
+            FontChoice::Mono => "mono",
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<FontChoice> {
+        FontChoice::ALL.into_iter().find(|choice| choice.key() == key)
+    }
+}
+
+impl fmt::Display for FontChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            FontChoice::Default => "Default",
+            FontChoice::Sans => "DejaVu Sans",
+            FontChoice::Serif => "DejaVu Serif",
+            FontChoice::Mono => "DejaVu Sans Mono",
+        };
+        write!(f, "{}", label)
+    }
+}
@@ -0,0 +1,406 @@
+// Locale-aware formatting and parsing of the numbers shown in the game
+
+use std::env;
+use std::fmt;
+use std::time::Duration;
+
+// The number formatting conventions the game knows about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    English, // 1,000,000
+    German,  // 1.000.000
+    French,  // 1 000 000 (no-break space)
+    Swiss,   // 1'000'000
+}
+
+impl Locale {
+    // Detect the locale from the usual POSIX environment variables, falling back to English
+    pub fn from_env() -> Locale {
+        ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|key| env::var(key).ok())
+            .find(|value| !value.is_empty())
+            .map(|value| Locale::from_tag(&value))
+            .unwrap_or(Locale::English)
+    }
+
+    // Map a locale tag such as "de_DE.UTF-8" or "fr-CH" onto one of the known conventions
+    pub fn from_tag(tag: &str) -> Locale {
+        let tag = tag.to_ascii_lowercase().replace('-', "_");
+        let mut parts = tag.split(['_', '.', '@']);
+        let language = parts.next().unwrap_or("");
+        let region = parts.next().unwrap_or("");
+
+        match (language, region) {
+            (_, "ch") | (_, "li") => Locale::Swiss, // Switzerland and Liechtenstein group with apostrophes
+            ("de", _) | ("nl", _) | ("it", _) | ("es", _) | ("pt", _) | ("da", _) | ("id", _) => {
+                Locale::German
+            }
+            ("fr", _) | ("ru", _) | ("pl", _) | ("cs", _) | ("sv", _) | ("nb", _) | ("fi", _) | ("uk", _) => {
+                Locale::French
+            }
+            _ => Locale::English,
+        }
+    }
+
+    // A tag that `from_tag` maps back onto this locale
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::German => "de",
+            Locale::French => "fr",
+            Locale::Swiss => "de_CH",
+        }
+    }
+
+    // The character placed between groups of three digits
+    pub fn group_separator(self) -> char {
+        match self {
+            Locale::English => ',',
+            Locale::German => '.',
+            Locale::French => '\u{a0}', // No-break space so numbers never wrap across lines
+            Locale::Swiss => '\'',
+        }
+    }
+
+    // The character between the whole and fractional parts of a number
+    pub fn decimal_separator(self) -> char {
+        match self {
+            Locale::English | Locale::Swiss => '.',
+            Locale::German | Locale::French => ',',
+        }
+    }
+
+    // Whether a character typed by the user counts as this locale's group separator
+    fn is_group_separator(self, c: char) -> bool {
+        match self {
+            // Nobody can easily type a no-break space, so accept the plain and narrow variants too
+            Locale::French => matches!(c, ' ' | '\u{a0}' | '\u{202f}'),
+            // Some keyboards produce a typographic apostrophe instead of the ASCII one
+            Locale::Swiss => matches!(c, '\'' | '\u{2019}'),
+            _ => c == self.group_separator(),
+        }
+    }
+}
+
+// Which conventions numbers are shown and typed in: the system's, or one picked in the settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LocaleChoice {
+    #[default]
+    System,         // Whatever the environment says, as detected on launch
+    Fixed(Locale),
+}
+
+impl LocaleChoice {
+    // Every choice, in the order shown in the picker
+    pub const ALL: [LocaleChoice; 5] = [
+        LocaleChoice::System,
+        LocaleChoice::Fixed(Locale::English),
+        LocaleChoice::Fixed(Locale::German),
+        LocaleChoice::Fixed(Locale::French),
+        LocaleChoice::Fixed(Locale::Swiss),
+    ];
+
+    // The conventions to use, given the ones detected from the system
+    pub fn resolve(self, system: Locale) -> Locale {
+        match self {
+            LocaleChoice::System => system,
+            LocaleChoice::Fixed(locale) => locale,
+        }
+    }
+
+    // The value written to the preferences file; a fixed choice is written as its locale's tag
+    pub fn key(self) -> &'static str {
+        match self {
+            LocaleChoice::System => "system",
+            LocaleChoice::Fixed(locale) => locale.tag(),
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<LocaleChoice> {
+        LocaleChoice::ALL.into_iter().find(|choice| choice.key() == key)
+    }
+}
+
+// A fixed choice is shown as a sample number, which says more than the name of a country
+impl fmt::Display for LocaleChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LocaleChoice::System => write!(f, "System"),
+            LocaleChoice::Fixed(locale) => write!(f, "{}", format_number(*locale, 1_234_567u32)),
+        }
+    }
+}
+
+// Abbreviations for numbers too wide to show in full, largest first
+const MAGNITUDES: [(u64, char); 4] = [(1_000_000_000_000, 'T'), (1_000_000_000, 'B'), (1_000_000, 'M'), (1_000, 'K')];
+
+// Format a number with the thousands separator of the given locale
+//
+// Takes anything that widens to `u64`, so every number shown goes through here whatever
+// its type.
+pub fn format_number(locale: Locale, n: impl Into<u64>) -> String {
+    let digits = n.into().to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (i, digit) in digits.chars().enumerate() {
+        // Insert a separator whenever the remaining digit count is a multiple of three
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(locale.group_separator());
+        }
+        formatted.push(digit);
+    }
+
+    formatted
+}
+
+// Format a number in at most `max_chars` characters, abbreviating it as in "4.2B" when the
+// full form is too wide for where it's shown
+//
+// The abbreviation keeps one decimal and rounds down, so it never claims more than the
+// number is. Anything still too wide, given a tiny `max_chars`, is returned anyway rather
+// than cut mid-number.
+pub fn format_number_within(locale: Locale, n: impl Into<u64>, max_chars: usize) -> String {
+    let n = n.into();
+    let full = format_number(locale, n);
+    if full.chars().count() <= max_chars {
+        return full;
+    }
+
+    match MAGNITUDES.iter().find(|&&(unit, _)| n >= unit) {
+        Some(&(unit, suffix)) => {
+            let tenths = n / (unit / 10);
+            let whole = format_number(locale, tenths / 10);
+            match tenths % 10 {
+                0 => format!("{}{}", whole, suffix),
+                tenth => format!("{}{}{}{}", whole, locale.decimal_separator(), tenth, suffix),
+            }
+        }
+        None => full, // Below a thousand there's nothing to abbreviate
+    }
+}
+
+// Why typed text isn't a number the game can use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,                // Nothing was typed, or only spaces
+    Negative,             // A number with a minus sign, which no secret ever is
+    InvalidCharacter(char), // The first character that isn't a digit or a separator
+    Misgrouped,           // Thousands separators between groups of the wrong size
+    TooBig,               // All digits, but more than a guess can hold
+}
+
+// Parse a number the user typed, accepting the locale's thousands separators
+//
+// Separators are optional, but when present they must sit between correctly sized groups,
+// so "1.000" is rejected for English rather than being misread as one.
+pub fn parse_number(locale: Locale, input: &str) -> Result<u32, ParseError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    // Only call it negative when the rest is a number, so "-2+10" is just an odd character
+    if let Some(rest) = input.strip_prefix(['-', '−']) {
+        return Err(match parse_number(locale, rest) {
+            Ok(_) | Err(ParseError::TooBig) => ParseError::Negative,
+            Err(_) => ParseError::InvalidCharacter('-'),
+        });
+    }
+
+    // Reject signs, decimals and stray characters that `parse` would otherwise allow
+    let stray = input.chars().find(|&c| !c.is_ascii_digit() && !locale.is_group_separator(c));
+    if let Some(stray) = stray {
+        return Err(ParseError::InvalidCharacter(stray));
+    }
+
+    // The first group holds one to three digits, every following group exactly three
+    let groups: Vec<&str> = input.split(|c| locale.is_group_separator(c)).collect();
+    if groups.len() > 1 {
+        let first_ok = (1..=3).contains(&groups[0].len());
+        let rest_ok = groups[1..].iter().all(|group| group.len() == 3);
+        if !first_ok || !rest_ok {
+            return Err(ParseError::Misgrouped);
+        }
+    }
+
+    groups.concat().parse().map_err(|_| ParseError::TooBig) // Only digits are left, so it can only be too big
+}
+
+// Format a duration as minutes and seconds, e.g. "1:05"
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+// Format the time taken over a guess, e.g. "+8.2s", or "+14s" from ten seconds up where tenths stop mattering
+pub fn format_thinking_time(locale: Locale, duration: Duration) -> String {
+    format!("+{}", format_seconds(locale, duration))
+}
+
+// Format a short time in seconds, e.g. "8.2s", or "14s" from ten seconds up
+pub fn format_seconds(locale: Locale, duration: Duration) -> String {
+    let tenths = (duration.as_millis() + 50) / 100; // Rounded, so 9.96s shows as "10s" rather than "10.0s"
+    if tenths < 100 {
+        format!("{}{}{}s", tenths / 10, locale.decimal_separator(), tenths % 10)
+    } else {
+        format!("{}s", format_number(locale, duration.as_secs_f64().round() as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCALES: [Locale; 4] = [Locale::English, Locale::German, Locale::French, Locale::Swiss];
+
+    #[test]
+    fn numbers_are_grouped_per_locale() {
+        assert_eq!(format_number(Locale::English, 1_234_567u32), "1,234,567");
+        assert_eq!(format_number(Locale::German, 1_234_567u32), "1.234.567");
+        assert_eq!(format_number(Locale::French, 1_234_567u32), "1\u{a0}234\u{a0}567");
+        assert_eq!(format_number(Locale::Swiss, 1_234_567u32), "1'234'567");
+        assert_eq!(format_number(Locale::English, 999u32), "999");
+        assert_eq!(format_number(Locale::English, 1_000u32), "1,000");
+        assert_eq!(format_number(Locale::English, 0u32), "0");
+        assert_eq!(format_number(Locale::English, u64::MAX), "18,446,744,073,709,551,615");
+        assert_eq!(format_number(Locale::German, u32::MAX), "4.294.967.295");
+    }
+
+    #[test]
+    fn formatted_numbers_parse_back_in_every_locale() {
+        for locale in LOCALES {
+            for n in [0, 7, 999, 1_000, 12_345, 100_000, 1_234_567, u32::MAX] {
+                assert_eq!(parse_number(locale, &format_number(locale, n)), Ok(n), "{:?} {}", locale, n);
+                assert_eq!(parse_number(locale, &n.to_string()), Ok(n), "separators are optional");
+            }
+        }
+    }
+
+    #[test]
+    fn typed_separators_are_accepted_where_keyboards_differ() {
+        assert_eq!(parse_number(Locale::French, "1 000"), Ok(1_000));
+        assert_eq!(parse_number(Locale::French, "1\u{202f}000"), Ok(1_000));
+        assert_eq!(parse_number(Locale::Swiss, "1\u{2019}000"), Ok(1_000));
+        assert_eq!(parse_number(Locale::English, " 1,000 "), Ok(1_000));
+    }
+
+    #[test]
+    fn another_locales_separator_is_not_a_digit() {
+        assert_eq!(parse_number(Locale::English, "1.000"), Err(ParseError::InvalidCharacter('.')));
+        assert_eq!(parse_number(Locale::German, "1,000"), Err(ParseError::InvalidCharacter(',')));
+        assert_eq!(parse_number(Locale::Swiss, "1 000"), Err(ParseError::InvalidCharacter(' ')));
+    }
+
+    #[test]
+    fn every_parse_error_has_an_input_that_causes_it() {
+        let cases = [
+            ("", ParseError::Empty),
+            ("   ", ParseError::Empty),
+            ("-5", ParseError::Negative),
+            ("−1,000", ParseError::Negative),
+            ("-99999999999", ParseError::Negative), // Still negative, however big
+            ("-2+10", ParseError::InvalidCharacter('-')),
+            ("12a", ParseError::InvalidCharacter('a')),
+            ("+5", ParseError::InvalidCharacter('+')),
+            ("1,00", ParseError::Misgrouped),
+            ("1000,000", ParseError::Misgrouped),
+            (",100", ParseError::Misgrouped),
+            ("100,", ParseError::Misgrouped),
+            ("1,,000", ParseError::Misgrouped),
+            ("4294967296", ParseError::TooBig),
+            ("4,294,967,296", ParseError::TooBig),
+            ("99999999999999999999999", ParseError::TooBig),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_number(Locale::English, input), Err(expected), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn numbers_that_fit_are_shown_in_full() {
+        assert_eq!(format_number_within(Locale::English, 1_234u32, 5), "1,234");
+        assert_eq!(format_number_within(Locale::English, 999u32, 1), "999", "nothing to abbreviate below a thousand");
+    }
+
+    #[test]
+    fn numbers_too_wide_are_abbreviated_rounding_down() {
+        let cases = [
+            (Locale::English, 4_200_000_000u64, "4.2B"),
+            (Locale::German, 4_200_000_000, "4,2B"),
+            (Locale::French, 4_299_999_999, "4,2B"),
+            (Locale::Swiss, 4_294_967_295, "4.2B"),
+            (Locale::English, 1_000_000, "1M"),
+            (Locale::English, 1_999_999, "1.9M"),
+            (Locale::English, 12_345, "12.3K"),
+            (Locale::English, 3_000_000_000_000, "3T"),
+            (Locale::English, 1_500_000_000_000_000, "1,500T"),
+        ];
+        for (locale, n, expected) in cases {
+            assert_eq!(format_number_within(locale, n, 4), expected, "{:?} {}", locale, n);
+        }
+    }
+
+    #[test]
+    fn locale_tags_map_onto_the_known_conventions() {
+        let cases = [
+            ("de_DE.UTF-8", Locale::German),
+            ("fr-CH", Locale::Swiss),
+            ("it_CH", Locale::Swiss),
+            ("fr_FR", Locale::French),
+            ("ru", Locale::French),
+            ("es_MX.utf8", Locale::German),
+            ("en_US", Locale::English),
+            ("C", Locale::English),
+            ("", Locale::English),
+        ];
+        for (tag, expected) in cases {
+            assert_eq!(Locale::from_tag(tag), expected, "{}", tag);
+        }
+        for locale in LOCALES {
+            assert_eq!(Locale::from_tag(locale.tag()), locale);
+        }
+    }
+
+    #[test]
+    fn locale_choices_resolve_and_round_trip_through_their_keys() {
+        assert_eq!(LocaleChoice::System.resolve(Locale::French), Locale::French);
+        assert_eq!(LocaleChoice::Fixed(Locale::Swiss).resolve(Locale::French), Locale::Swiss);
+        for choice in LocaleChoice::ALL {
+            assert_eq!(LocaleChoice::from_key(choice.key()), Some(choice));
+        }
+        assert_eq!(LocaleChoice::from_key("nl"), None);
+        assert_eq!(LocaleChoice::Fixed(Locale::German).to_string(), "1.234.567");
+    }
+
+    #[test]
+    fn seconds_show_tenths_until_ten() {
+        assert_eq!(format_seconds(Locale::English, Duration::from_millis(8_240)), "8.2s");
+        assert_eq!(format_seconds(Locale::German, Duration::from_millis(8_250)), "8,3s");
+        assert_eq!(format_seconds(Locale::English, Duration::from_millis(9_960)), "10s");
+        assert_eq!(format_thinking_time(Locale::English, Duration::from_secs(14)), "+14s");
+        assert_eq!(format_duration(Duration::from_secs(65)), "1:05");
+    }
+
+    #[test]
+    fn thinking_times_switch_to_whole_seconds_at_ten() {
+        let cases = [
+            (0, "+0.0s"),
+            (49, "+0.0s"),
+            (50, "+0.1s"),
+            (950, "+1.0s"),
+            (9_949, "+9.9s"),
+            (9_950, "+10s"),
+            (10_000, "+10s"),
+            (14_499, "+14s"),
+            (14_500, "+15s"),
+            (3_600_000, "+3,600s"),
+        ];
+        for (ms, expected) in cases {
+            assert_eq!(format_thinking_time(Locale::English, Duration::from_millis(ms)), expected, "{}ms", ms);
+        }
+        assert_eq!(format_thinking_time(Locale::French, Duration::from_millis(8_240)), "+8,2s");
+        assert_eq!(format_thinking_time(Locale::Swiss, Duration::from_millis(8_240)), "+8.2s");
+    }
+}
@@ -0,0 +1,89 @@
+// The demo gallery: every bot strategy playing the same seeded secret side by side, on a loop
+//
+// Nothing in it can be played: each panel answers its own bot truthfully, a guess at a time,
+// and once every bot has found the secret the boards stay up for a moment before the next
+// seed in the list starts them all over. After the last seed it goes back to the first.
+
+use std::cmp::Ordering;
+use std::time::Duration;
+
+use crate::random::seeded_secret;
+use crate::solver::{Answer, BotGame, BotStrategy};
+
+// The seeds shown, in order; picked so the secrets land low, high and near the middle
+pub const SEEDS: [u32; 4] = [7, 1_000, 424_242, 90_210];
+
+// How often every unsolved bot makes its next guess
+pub const STEP_INTERVAL: Duration = Duration::from_millis(300);
+
+// Steps to linger on the finished boards before the next seed
+const REST_STEPS: u32 = 8;
+
+// One bot playing the current seed
+pub struct Panel {
+    pub game: BotGame, // The bot's round, answered by the panel itself
+    pub secret: u32,   // The number it's looking for, drawn from the seed
+}
+
+// The gallery as it's playing
+pub struct Gallery {
+    pub round: usize,       // Which of `SEEDS` is being played
+    pub panels: Vec<Panel>, // One for every strategy, weakest first
+    rested: u32,            // Steps spent on the finished boards so far
+    min: u32,               // Smallest possible secret
+    max: u32,               // Largest possible secret
+}
+
+impl Gallery {
+    // Start on the first seed over `min..=max`
+    pub fn new(min: u32, max: u32) -> Gallery {
+        Gallery {
+            round: 0,
+            panels: panels(SEEDS[0], min, max),
+            rested: 0,
+            min,
+            max,
+        }
+    }
+
+    // The seed being played
+    pub fn seed(&self) -> u32 {
+        SEEDS[self.round]
+    }
+
+    // Whether every bot has found the secret
+    pub fn is_finished(&self) -> bool {
+        self.panels.iter().all(|panel| panel.game.solved)
+    }
+
+    // Let every unsolved bot make its next guess, or move on to the next seed once they've rested
+    pub fn advance(&mut self) {
+        if self.is_finished() {
+            self.rested += 1;
+            if self.rested > REST_STEPS {
+                self.round = (self.round + 1) % SEEDS.len();
+                self.panels = panels(self.seed(), self.min, self.max);
+                self.rested = 0;
+            }
+            return;
+        }
+
+        for panel in self.panels.iter_mut().filter(|panel| !panel.game.solved) {
+            let answer = match panel.game.guess.cmp(&panel.secret) {
+                Ordering::Less => Answer::TooSmall,
+                Ordering::Greater => Answer::TooBig,
+                Ordering::Equal => Answer::Correct,
+            };
+            panel.game.answer(answer); // Always truthful, so never contradicts itself
+        }
+    }
+}
+
+// A fresh panel for every strategy, all looking for the secret `seed` draws
+fn panels(seed: u32, min: u32, max: u32) -> Vec<Panel> {
+    let secret = seeded_secret(seed, min, max);
+    BotStrategy::ALL
+        .iter()
+        .map(|&strategy| Panel { game: BotGame::new(strategy, min, max, u64::from(seed)), secret })
+        .collect()
+}
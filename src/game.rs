@@ -0,0 +1,109 @@
+// The rules of one game, as plain state and a pure step from one state to the next
+//
+// The window keeps far more than this (clocks, hints, animations, the boards), but whether a
+// guess wins and how it narrows the range is decided here, so another front end playing a
+// `State` forward with `step` judges every guess the same way the window does.
+
+use std::cmp::Ordering;
+
+use crate::rules::{Exact, Outcome, WinRule};
+use crate::secret::Secret;
+
+// Where a game stands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    pub secret: Secret,        // The number being looked for, kept masked
+    pub min: u32,              // Smallest possible secret in the game's range
+    pub max: u32,              // Largest possible secret in the game's range
+    pub low: u32,              // Smallest value that could still be the secret
+    pub high: u32,             // Largest value that could still be the secret
+    pub attempts: u32,         // Guesses made so far, repeats included
+    pub history: Vec<u32>,     // Every guess, in the order they were made
+    pub last: Option<Outcome>, // What the latest guess earned, or `None` before the first
+}
+
+impl State {
+    // A new game over `min..=max` looking for `secret`
+    pub fn new(secret: u32, min: u32, max: u32) -> State {
+        State {
+            secret: Secret::new(secret),
+            min,
+            max,
+            low: min,
+            high: max,
+            attempts: 0,
+            history: Vec::new(),
+            last: None,
+        }
+    }
+
+    // Whether the secret has been found, after which `step` leaves the game as it is
+    pub fn won(&self) -> bool {
+        self.last == Some(Outcome::Win)
+    }
+}
+
+// Something the player can do to a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Guess(u32),
+}
+
+// Judge `guess` against `secret` under the exact rule, where only the secret itself wins
+pub fn check_guess(secret: &Secret, guess: u32) -> Outcome {
+    secret.judge(guess, &Exact)
+}
+
+/// The game after `input`, under the exact rule
+///
+/// ```
+/// use guessing_game::{step, Input, Outcome, State};
+///
+/// let state = State::new(37, 1, 100);
+/// let state = step(state, Input::Guess(50));
+/// assert_eq!(state.last, Some(Outcome::TooBig));
+/// assert_eq!((state.low, state.high), (1, 49));
+///
+/// let state = step(state, Input::Guess(37));
+/// assert!(state.won());
+/// assert_eq!((state.attempts, state.history), (2, vec![50, 37]));
+/// ```
+pub fn step(state: State, input: Input) -> State {
+    step_under(state, input, &Exact)
+}
+
+// The game after `input`, with guesses judged by `rule`
+//
+// The rule decides the win and the feedback, but the bounds are always narrowed on the raw
+// numbers, the same as `narrow`. A game that's already won takes no more guesses.
+pub fn step_under(mut state: State, input: Input, rule: &dyn WinRule) -> State {
+    if state.won() {
+        return state;
+    }
+    match input {
+        Input::Guess(guess) => {
+            let outcome = state.secret.judge(guess, rule);
+            state.attempts += 1;
+            state.history.push(guess);
+            state.last = Some(outcome);
+            (state.low, state.high) = if outcome == Outcome::Win {
+                let secret = state.secret.reveal(); // Only the secret itself remains
+                (secret, secret)
+            } else {
+                narrow(state.low, state.high, guess, state.secret.compare(guess))
+            };
+        }
+    }
+    state
+}
+
+// The bounds `low..=high` after a wrong guess, where `ordering` is the guess against the secret
+pub fn narrow(low: u32, high: u32, guess: u32, ordering: Ordering) -> (u32, u32) {
+    match ordering {
+        // Nothing at or below a guess under the secret can be the answer
+        Ordering::Less => (low.max(guess + 1), high),
+        // Nothing at or above a guess over the secret can be the answer
+        Ordering::Greater => (low, high.min(guess - 1)),
+        Ordering::Equal => (guess, guess),
+    }
+}
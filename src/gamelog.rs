@@ -0,0 +1,343 @@
+// A readable log of every game played, kept apart from the diagnostics log
+//
+// Each line is `time|event|fields`, with the time in seconds since the Unix epoch:
+//
+//     1700000000|start|classic|1|100        mode, smallest and largest possible secret
+//     1700000005|guess|42|too_big           the guess and the feedback it got
+//     1700000009|hint|bought|parity         who gave the hint, and which one
+//     1700000020|end|won|37|5               how the game ended, the secret and the guesses
+//
+// Scripts read this file, so the format only ever grows: new events and new trailing
+// fields may be added, but existing fields never move or change meaning. The secret of a
+// game that wasn't finished is written as `?`, so the log never gives away an answer
+// that could still be played for, such as one restored after a crash.
+
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::rules::Outcome;
+use crate::storage;
+
+// File the lines are appended to, inside the data directory
+pub const FILE_NAME: &str = "games.log";
+
+// What the log is renamed to when it grows too large, replacing the one before
+const ROTATED_NAME: &str = "games.log.1";
+
+// Size past which the log is rotated before more is written
+const MAX_BYTES: u64 = 1024 * 1024;
+
+// Lines are written once none have arrived for this long, or once this many bytes wait
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+const BUFFER_BYTES: usize = 16 * 1024;
+
+// Longest a final flush may hold up closing the window
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(1);
+
+// Something that happened in a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Started { mode: &'static str, min: u32, max: u32 }, // The mode's key, such as "classic"
+    Guessed { guess: u32, outcome: Outcome },           // The guess and what the win rule made of it
+    Hinted { free: bool, hint: &'static str },          // Whether it was volunteered, and its key
+    TimedOut { attempts: u32 },                         // A guess ran out of time; attempts so far, counting it
+    Ended { end: End, secret: u32, attempts: u32 },     // The secret is redacted unless finished
+}
+
+// How a game ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum End {
+    Won,
+    GaveUp,    // The pass-and-play guesser gave up, which reveals the secret
+    Abandoned, // A new game was started, or the window closed, part-way through
+}
+
+impl End {
+    // The value written to the log
+    fn key(self) -> &'static str {
+        match self {
+            End::Won => "won",
+            End::GaveUp => "gave_up",
+            End::Abandoned => "abandoned",
+        }
+    }
+
+    // Whether the secret was shown to the player, so it's safe to write down
+    fn finished(self) -> bool {
+        self != End::Abandoned
+    }
+}
+
+// Work for the writer thread
+enum Command {
+    Line(String),      // A line to buffer
+    Flush(Sender<()>), // Write everything buffered, then answer
+}
+
+// The writer thread's queue, started by the first event
+static WRITER: OnceLock<Sender<Command>> = OnceLock::new();
+
+// Queue an event for writing; this never blocks, so it's safe to call from `update`
+pub fn record(event: Event) {
+    let time = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+    let line = format_line(time, event);
+
+    // Sending only fails if the writer thread couldn't start, which has already been logged
+    let _ = WRITER.get_or_init(start_writer).send(Command::Line(line));
+}
+
+// Write out everything queued so far, waiting briefly; used when the window closes
+pub fn flush() {
+    let Some(writer) = WRITER.get() else {
+        return; // Nothing has been logged this session
+    };
+
+    let (done, finished) = mpsc::channel();
+    if writer.send(Command::Flush(done)).is_ok() && finished.recv_timeout(FLUSH_TIMEOUT).is_err() {
+        tracing::warn!("Timed out writing the game log");
+    }
+}
+
+// Render an event as one line of the log, including the newline
+pub fn format_line(time: u64, event: Event) -> String {
+    let fields = match event {
+        Event::Started { mode, min, max } => format!("start|{}|{}|{}", mode, min, max),
+        Event::Guessed { guess, outcome } => {
+            let outcome = match outcome {
+                Outcome::TooSmall => "too_small",
+                Outcome::TooBig => "too_big",
+                Outcome::Win => "correct",
+            };
+            format!("guess|{}|{}", guess, outcome)
+        }
+        Event::Hinted { free, hint } => format!("hint|{}|{}", if free { "free" } else { "bought" }, hint),
+        Event::TimedOut { attempts } => format!("timeout|{}", attempts),
+        Event::Ended { end, secret, attempts } => {
+            let secret = if end.finished() { secret.to_string() } else { "?".to_string() };
+            format!("end|{}|{}|{}", end.key(), secret, attempts)
+        }
+    };
+    format!("{}|{}\n", time, fields)
+}
+
+// A won game as read back from the log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Win {
+    pub time: u64,     // When it was won, in seconds since the Unix epoch
+    pub min: u32,      // Smallest possible secret in its range
+    pub max: u32,      // Largest possible secret in its range
+    pub attempts: u32, // Guesses it took
+    pub took: u64,     // Seconds from its start line to its end, pauses included
+}
+
+// Read the won games from the log's lines, oldest first
+//
+// Only the mode's range, when it started and the end are needed, so a win is paired with
+// the start line before it; a win whose start was lost to rotation is skipped, its range unknown.
+pub fn parse_wins(contents: &str) -> Vec<Win> {
+    let mut start: Option<(u32, u32, u64)> = None;
+    let mut wins = Vec::new();
+    for line in contents.lines() {
+        let fields: Vec<&str> = line.trim().split('|').collect();
+        match fields[..] {
+            [time, "start", _, min, max, ..] => {
+                start = match (min.parse(), max.parse(), time.parse()) {
+                    (Ok(min), Ok(max), Ok(time)) => Some((min, max, time)),
+                    _ => None,
+                };
+            }
+            [time, "end", end, _, attempts, ..] => {
+                if let (Some((min, max, started)), "won", Ok(time), Ok(attempts)) = (start, end, time.parse::<u64>(), attempts.parse()) {
+                    wins.push(Win { time, min, max, attempts, took: time.saturating_sub(started) });
+                }
+                start = None; // Every game has its own start line
+            }
+            _ => {}
+        }
+    }
+    wins
+}
+
+// Read every win still in the log, including the part rotated out, oldest first
+pub fn load_wins() -> Vec<Win> {
+    flush(); // The writer may still hold the latest game
+    [ROTATED_NAME, FILE_NAME]
+        .into_iter()
+        .flat_map(|name| match storage::load(name) {
+            Ok(contents) => parse_wins(&contents.unwrap_or_default()),
+            Err(error) => {
+                tracing::warn!(%error, name, "Could not read the game log");
+                Vec::new()
+            }
+        })
+        .collect()
+}
+
+// Whether the log has to be rotated before `incoming` more bytes are written to it
+//
+// A log that is still empty is never rotated, so one oversized write can't leave an
+// empty file behind in place of the previous log.
+pub fn needs_rotation(current: u64, incoming: usize, max: u64) -> bool {
+    current > 0 && current.saturating_add(incoming as u64) > max
+}
+
+// Start the thread that writes the log, returning its queue
+fn start_writer() -> Sender<Command> {
+    let (sender, receiver) = mpsc::channel();
+    if let Err(error) = thread::Builder::new().name("game-log".to_string()).spawn(move || run_writer(receiver)) {
+        tracing::warn!(%error, "Could not start the game log writer");
+    }
+    sender
+}
+
+// Buffer lines as they arrive, writing them out once things go quiet or the buffer fills
+fn run_writer(receiver: Receiver<Command>) {
+    let mut buffer = String::new();
+    loop {
+        match receiver.recv_timeout(FLUSH_INTERVAL) {
+            Ok(Command::Line(line)) => {
+                buffer.push_str(&line);
+                if buffer.len() >= BUFFER_BYTES {
+                    write_buffer(&mut buffer);
+                }
+            }
+            Ok(Command::Flush(done)) => {
+                write_buffer(&mut buffer);
+                let _ = done.send(()); // The caller may have stopped waiting
+            }
+            Err(RecvTimeoutError::Timeout) => write_buffer(&mut buffer),
+            Err(RecvTimeoutError::Disconnected) => {
+                write_buffer(&mut buffer);
+                return;
+            }
+        }
+    }
+}
+
+// Append the buffered lines, rotating the log first if they would take it over the cap
+fn write_buffer(buffer: &mut String) {
+    if buffer.is_empty() {
+        return;
+    }
+
+    match storage::size(FILE_NAME) {
+        Ok(size) if needs_rotation(size, buffer.len(), MAX_BYTES) => {
+            if let Err(error) = storage::rename(FILE_NAME, ROTATED_NAME) {
+                tracing::warn!(%error, "Could not rotate the game log");
+            }
+        }
+        Ok(_) => {}
+        Err(error) => tracing::warn!(%error, "Could not check the size of the game log"),
+    }
+
+    if let Err(error) = storage::append(FILE_NAME, buffer) {
+        tracing::warn!(%error, "Could not write the game log");
+    }
+    buffer.clear(); // Dropped even on failure, so a missing directory can't grow it forever
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The lines of one game from `start` to its end, `attempts` guesses later
+    fn game(start: u64, min: u32, max: u32, end: End, attempts: u32) -> String {
+        format_line(start, Event::Started { mode: "classic", min, max })
+            + &format_line(start + 3, Event::Guessed { guess: 42, outcome: Outcome::TooBig })
+            + &format_line(start + 9, Event::Ended { end, secret: 37, attempts })
+    }
+
+    #[test]
+    fn every_event_has_its_line() {
+        let cases = [
+            (Event::Started { mode: "classic", min: 1, max: 100 }, "start|classic|1|100"),
+            (Event::Guessed { guess: 42, outcome: Outcome::TooBig }, "guess|42|too_big"),
+            (Event::Guessed { guess: 12, outcome: Outcome::TooSmall }, "guess|12|too_small"),
+            (Event::Guessed { guess: 37, outcome: Outcome::Win }, "guess|37|correct"),
+            (Event::Hinted { free: false, hint: "parity" }, "hint|bought|parity"),
+            (Event::Hinted { free: true, hint: "range" }, "hint|free|range"),
+            (Event::TimedOut { attempts: 3 }, "timeout|3"),
+            (Event::Ended { end: End::Won, secret: 37, attempts: 5 }, "end|won|37|5"),
+            (Event::Ended { end: End::GaveUp, secret: 37, attempts: 5 }, "end|gave_up|37|5"),
+        ];
+        for (event, fields) in cases {
+            assert_eq!(format_line(1_700_000_000, event), format!("1700000000|{}\n", fields));
+        }
+    }
+
+    #[test]
+    fn an_unfinished_game_never_gives_its_secret_away() {
+        let line = format_line(1_700_000_000, Event::Ended { end: End::Abandoned, secret: 37, attempts: 2 });
+        assert_eq!(line, "1700000000|end|abandoned|?|2\n");
+        assert!(!line.contains("37"));
+    }
+
+    #[test]
+    fn wins_read_back_what_was_written() {
+        let log = game(1_700_000_000, 1, 100, End::Won, 5) + &game(1_700_000_100, 1, 1_000, End::Won, 9);
+        assert_eq!(
+            parse_wins(&log),
+            [
+                Win { time: 1_700_000_009, min: 1, max: 100, attempts: 5, took: 9 },
+                Win { time: 1_700_000_109, min: 1, max: 1_000, attempts: 9, took: 9 },
+            ]
+        );
+    }
+
+    #[test]
+    fn a_win_took_from_its_own_start_line_to_its_end() {
+        let log = game(100, 1, 100, End::Abandoned, 2) + &game(1_000, 1, 100, End::Won, 3);
+        assert_eq!(parse_wins(&log)[0].took, 9, "the abandoned game's start doesn't count");
+    }
+
+    #[test]
+    fn a_win_ending_before_its_start_took_no_time() {
+        // The clock was set back between the two lines
+        assert_eq!(parse_wins("500|start|classic|1|100\n400|end|won|37|3\n")[0].took, 0);
+    }
+
+    #[test]
+    fn only_won_games_are_read_back() {
+        let log = game(100, 1, 100, End::Abandoned, 2) + &game(200, 1, 100, End::GaveUp, 4) + &game(300, 1, 100, End::Won, 6);
+        let wins = parse_wins(&log);
+        assert_eq!(wins.len(), 1);
+        assert_eq!(wins[0].attempts, 6);
+    }
+
+    #[test]
+    fn a_win_without_its_start_line_is_skipped() {
+        // The start went with the part rotated out, and the end of the next game follows straight on
+        let log = "100|guess|42|too_big\n105|end|won|37|3\n".to_string() + &game(200, 1, 100, End::Won, 4) + "300|end|won|37|2\n";
+        let wins = parse_wins(&log);
+        assert_eq!(wins.len(), 1);
+        assert_eq!(wins[0].time, 209);
+    }
+
+    #[test]
+    fn malformed_and_unknown_lines_are_ignored() {
+        let log = "garbage\n\n|||\n100|start|classic|x|100\n110|end|won|37|3\n".to_string()
+            + "200|start|classic|1|100|a_field_added_later\n"
+            + "201|comet|1\n"
+            + "205|end|won|37|4|another\n";
+        assert_eq!(parse_wins(&log), [Win { time: 205, min: 1, max: 100, attempts: 4, took: 5 }]);
+    }
+
+    #[test]
+    fn an_empty_log_has_no_wins() {
+        assert!(parse_wins("").is_empty());
+    }
+
+    #[test]
+    fn rotation_waits_until_a_write_would_go_past_the_cap() {
+        assert!(!needs_rotation(0, 10, 100), "an empty log is never rotated");
+        assert!(!needs_rotation(0, 1_000, 100), "not even for an oversized write");
+        assert!(!needs_rotation(90, 10, 100), "reaching the cap exactly is fine");
+        assert!(needs_rotation(90, 11, 100));
+        assert!(needs_rotation(101, 0, 100), "already over");
+        assert!(!needs_rotation(100, 0, 100));
+        assert!(needs_rotation(u64::MAX - 1, usize::MAX, u64::MAX - 1), "no overflow near the top");
+        assert!(!needs_rotation(u64::MAX - 1, 1, u64::MAX));
+    }
+}
@@ -0,0 +1,107 @@
+// The ghost: the pace of the fastest win on the time-attack board, raced in later games
+//
+// Only the game clock's reading at each of that win's guesses is kept, which is all it takes to
+// say how far along the ghost would be at any moment of a new game, and whether each guess came
+// sooner or later than the ghost's guess of the same number. Nothing about its secret is kept,
+// since the new game's secret is a different one anyway.
+
+use std::io;
+use std::time::Duration;
+
+use crate::migration::{self, Schema};
+use crate::storage;
+
+// File the ghost is stored in, inside the data directory
+const FILE_NAME: &str = "ghost.txt";
+
+// How the file has changed between versions
+const SCHEMA: Schema = Schema { file: FILE_NAME, name: "ghost", legacy_key: None, steps: &[] };
+
+// The fastest win's pace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ghost {
+    splits: Vec<Duration>, // The game clock's reading at each guess, the last one winning
+}
+
+// How the player stands against the ghost at their latest guess
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Split {
+    Ahead(Duration),  // Made sooner than the ghost's guess of the same number, by this much
+    Behind(Duration), // Made later, by this much
+    Past,             // More guesses than the ghost needed to win
+}
+
+impl Ghost {
+    // The pace of a win from how long each of its guesses took, if every one of them was timed
+    pub fn from_times(times: &[Option<Duration>]) -> Option<Ghost> {
+        let splits: Vec<Duration> = times
+            .iter()
+            .scan(Duration::ZERO, |clock, time| {
+                *clock += (*time)?;
+                Some(*clock)
+            })
+            .collect();
+        (!splits.is_empty() && splits.len() == times.len()).then_some(Ghost { splits })
+    }
+
+    // Load the ghost, or `None` if no win has set one or it can't be read
+    pub fn load() -> Option<Ghost> {
+        let values = match migration::load(&SCHEMA) {
+            Ok(values) => values?,
+            Err(error) => {
+                tracing::warn!(%error, "Could not read the ghost");
+                return None;
+            }
+        };
+
+        let splits: Option<Vec<Duration>> = values
+            .get("splits")?
+            .split(',')
+            .map(|millis| millis.trim().parse().ok().map(Duration::from_millis))
+            .collect();
+        let splits = splits?;
+        // A hand-edited file could leave the clock running backwards, which no real game does
+        let ordered = splits.windows(2).all(|pair| pair[0] <= pair[1]);
+        (!splits.is_empty() && ordered).then_some(Ghost { splits })
+    }
+
+    // Save the ghost over the last one, logging rather than failing if the file can't be written
+    pub fn save(&self) {
+        let splits: Vec<String> = self.splits.iter().map(|split| split.as_millis().to_string()).collect();
+        let contents = storage::write_key_values([("splits", splits.join(","))]);
+
+        if let Err(error) = migration::save(&SCHEMA, &contents) {
+            tracing::warn!(%error, "Could not save the ghost");
+        }
+    }
+
+    // Delete the saved ghost, as when the records it came from are reset
+    pub fn remove() -> io::Result<()> {
+        migration::remove(&SCHEMA)
+    }
+
+    // The guesses the ghost had made when the game clock read `clock`
+    pub fn guesses_by(&self, clock: Duration) -> usize {
+        self.splits.partition_point(|&split| split <= clock)
+    }
+
+    // The guesses the ghost needed to win
+    pub fn guesses(&self) -> usize {
+        self.splits.len()
+    }
+
+    // The game clock's reading when the ghost won
+    pub fn finish(&self) -> Duration {
+        self.splits.last().copied().unwrap_or_default()
+    }
+
+    // How guess `guesses` (from 1), made at `clock`, compares with the ghost's guess of the same number
+    pub fn split(&self, guesses: usize, clock: Duration) -> Option<Split> {
+        let index = guesses.checked_sub(1)?;
+        Some(match self.splits.get(index) {
+            Some(&theirs) if clock <= theirs => Split::Ahead(theirs - clock),
+            Some(&theirs) => Split::Behind(clock - theirs),
+            None => Split::Past,
+        })
+    }
+}
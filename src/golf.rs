@@ -0,0 +1,158 @@
+// Golf scoring: guesses counted as strokes against the range's par, over a course of holes
+
+use crate::analysis;
+
+// Holes on a course, each one a classic game
+pub const HOLES: u32 = 9;
+
+// Par for a range: the guesses binary search needs in the worst case, so 7 for 1–100
+pub fn par(min: u32, max: u32) -> u32 {
+    analysis::optimal_guesses(min, max)
+}
+
+// The golf name for taking `strokes` on a hole of `par`
+pub fn term(strokes: u32, par: u32) -> &'static str {
+    if strokes == 1 {
+        return "hole in one"; // Named for itself whatever the par, as on the course
+    }
+    match i64::from(strokes) - i64::from(par) {
+        i64::MIN..=-4 => "condor",
+        -3 => "albatross",
+        -2 => "eagle",
+        -1 => "birdie",
+        0 => "par",
+        1 => "bogey",
+        2 => "double bogey",
+        3 => "triple bogey",
+        _ => "over par",
+    }
+}
+
+// A score relative to par as golfers write it: "E" for even, otherwise "−2" or "+3"
+pub fn relative(to_par: i64) -> String {
+    match to_par {
+        0 => "E".to_string(),
+        below if below < 0 => format!("−{}", below.unsigned_abs()), // A true minus sign, as on a leaderboard
+        above => format!("+{}", above),
+    }
+}
+
+// A hole's result as shown, such as "birdie (−1)"
+pub fn describe(strokes: u32, par: u32) -> String {
+    format!("{} ({})", term(strokes, par), relative(i64::from(strokes) - i64::from(par)))
+}
+
+// A course in progress, or just finished and showing its scorecard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Course {
+    pub par: u32,          // Par for every hole, since they're all played over the same range
+    pub strokes: Vec<u32>, // Guesses taken on each hole finished so far, in order
+}
+
+impl Course {
+    // A course at `par` per hole, none played yet
+    pub fn new(par: u32) -> Course {
+        Course { par, strokes: Vec::new() }
+    }
+
+    // The number of the hole being played, from 1, or of the last one once all are finished
+    pub fn current(&self) -> u32 {
+        (self.strokes.len() as u32 + 1).min(HOLES)
+    }
+
+    // Add a finished hole's strokes
+    pub fn record(&mut self, strokes: u32) {
+        if !self.is_finished() {
+            self.strokes.push(strokes);
+        }
+    }
+
+    // Whether every hole has been played
+    pub fn is_finished(&self) -> bool {
+        self.strokes.len() as u32 >= HOLES
+    }
+
+    // Strokes across the holes finished so far
+    pub fn total(&self) -> u32 {
+        self.strokes.iter().sum()
+    }
+
+    // The running total against par for the holes finished so far
+    pub fn to_par(&self) -> i64 {
+        i64::from(self.total()) - i64::from(self.par) * self.strokes.len() as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn par_is_the_worst_case_for_binary_search() {
+        assert_eq!(par(1, 100), 7);
+        assert_eq!(par(1, 1000), 10);
+        assert_eq!(par(5, 5), 1);
+        assert_eq!(par(0, u32::MAX), 33);
+    }
+
+    #[test]
+    fn every_score_against_par_has_its_name() {
+        let cases = [
+            (1, 7, "hole in one"),
+            (1, 1, "hole in one"),
+            (2, 7, "condor"),
+            (3, 7, "condor"),
+            (4, 7, "albatross"),
+            (5, 7, "eagle"),
+            (6, 7, "birdie"),
+            (7, 7, "par"),
+            (8, 7, "bogey"),
+            (9, 7, "double bogey"),
+            (10, 7, "triple bogey"),
+            (11, 7, "over par"),
+            (u32::MAX, 7, "over par"),
+            (2, 5, "albatross"),
+        ];
+        for (strokes, par, expected) in cases {
+            assert_eq!(term(strokes, par), expected, "{} on a par {}", strokes, par);
+        }
+    }
+
+    #[test]
+    fn results_are_written_relative_to_par() {
+        assert_eq!(relative(0), "E");
+        assert_eq!(relative(-2), "−2");
+        assert_eq!(relative(3), "+3");
+        assert_eq!(relative(i64::MIN), "−9223372036854775808");
+        assert_eq!(describe(6, 7), "birdie (−1)");
+        assert_eq!(describe(9, 7), "double bogey (+2)");
+        assert_eq!(describe(1, 7), "hole in one (−6)");
+        assert_eq!(describe(7, 7), "par (E)");
+    }
+
+    #[test]
+    fn a_course_totals_its_holes_against_par() {
+        let mut course = Course::new(7);
+        assert_eq!((course.current(), course.total(), course.to_par()), (1, 0, 0));
+
+        for strokes in [6, 9, 1] {
+            course.record(strokes);
+        }
+        assert_eq!((course.current(), course.total(), course.to_par()), (4, 16, 16 - 21));
+        assert!(!course.is_finished());
+    }
+
+    #[test]
+    fn a_finished_course_takes_no_more_holes() {
+        let mut course = Course::new(7);
+        for _ in 0..HOLES {
+            course.record(8);
+        }
+        assert!(course.is_finished());
+        assert_eq!(course.current(), HOLES, "the last hole stays current");
+
+        course.record(1);
+        assert_eq!(course.strokes.len() as u32, HOLES);
+        assert_eq!(course.to_par(), i64::from(HOLES));
+    }
+}
@@ -0,0 +1,212 @@
+// Hints the player can buy with points, each narrower than the last
+
+use crate::format::{format_number, Locale};
+use crate::score::HINT_COSTS;
+use crate::secret::Secret;
+use crate::skill::Strength;
+
+// A hint, in the order they are given
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    Digits,    // How many digits the secret has, which tells most in the widest ranges
+    Parity,    // Whether the secret is even or odd
+    LastDigit, // The secret's last digit, which settles its parity and rules out nine numbers in ten
+    Half,      // Which half of the remaining range the secret is in, or quarter for generous hints
+    Decade,    // Which run of ten the secret is in, shorter for generous hints and longer for strict ones
+}
+
+impl Hint {
+    // Every hint, cheapest first; a game can take each once
+    pub const ALL: [Hint; 5] = [Hint::Digits, Hint::Parity, Hint::LastDigit, Hint::Half, Hint::Decade];
+
+    // The hint after `taken` have been given, or `None` once they're all used
+    pub fn next(taken: usize) -> Option<Hint> {
+        Hint::ALL.get(taken).copied()
+    }
+
+    // The name written to the game log
+    pub fn key(self) -> &'static str {
+        match self {
+            Hint::Digits => "digits",
+            Hint::Parity => "parity",
+            Hint::LastDigit => "last_digit",
+            Hint::Half => "half",
+            Hint::Decade => "decade",
+        }
+    }
+
+    // The points this hint costs
+    pub fn cost(self) -> u32 {
+        let tier = Hint::ALL.iter().position(|&hint| hint == self).unwrap_or_default();
+        HINT_COSTS[tier]
+    }
+
+    // What the hint says about the secret, and the range left once it's taken into account
+    //
+    // Hints are the one place the secret is decoded mid-game, each only as far as it tells.
+    // `strength` widens or narrows the ranges the last two hints give, for assist mode.
+    pub fn give(self, secret: &Secret, low: u32, high: u32, strength: Strength, locale: Locale) -> (String, u32, u32) {
+        let n = |value: u32| format_number(locale, value);
+        let value = secret.reveal();
+        match self {
+            Hint::Digits => {
+                let digits = digit_count(value);
+                let (start, end) = digit_interval(digits);
+                let noun = if digits == 1 { "digit" } else { "digits" };
+                (format!("The number has {} {}", n(digits), noun), low.max(start), high.min(end))
+            }
+            Hint::Parity => {
+                let parity = if value.is_multiple_of(2) { "even" } else { "odd" };
+                (format!("The number is {}", parity), low, high)
+            }
+            Hint::LastDigit => {
+                // The bounds move in to the nearest numbers ending the same way, the secret at worst
+                let digit = value % 10;
+                let start = low + (digit + 10 - low % 10) % 10;
+                let end = high - (high % 10 + 10 - digit) % 10;
+                (format!("The number ends in {}", digit), start, end)
+            }
+            Hint::Half => {
+                // Split into equal parts, widened so the last one never falls short of `high`
+                let width = (u64::from(high - low) + 1).div_ceil(u64::from(strength.parts()));
+                let part = (u64::from(value - low) / width) as u32;
+                let start = low + (u64::from(part) * width) as u32;
+                let end = (u64::from(start) + width - 1).min(u64::from(high)) as u32;
+                (format!("The number is between {} and {}", n(start), n(end)), start, end)
+            }
+            Hint::Decade => {
+                let span = strength.span();
+                let start = value - value % span;
+                let end = start.saturating_add(span - 1);
+                (format!("The number is between {} and {}", n(start), n(end)), low.max(start), high.min(end))
+            }
+        }
+    }
+}
+
+// How many decimal digits `value` has, counting 0 as one digit
+pub fn digit_count(value: u32) -> u32 {
+    value.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+// The values with exactly `digits` decimal digits, from the smallest to the largest
+//
+// One digit starts at 0 rather than 1, and the widest count that fits stops at `u32::MAX`.
+pub fn digit_interval(digits: u32) -> (u32, u32) {
+    let start = if digits <= 1 { 0 } else { 10u32.checked_pow(digits - 1).unwrap_or(u32::MAX) };
+    let end = 10u32.checked_pow(digits).map_or(u32::MAX, |next| next - 1);
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hints_come_in_order_and_cost_their_tier() {
+        let given: Vec<Hint> = (0..).map_while(Hint::next).collect();
+        assert_eq!(given, Hint::ALL);
+        assert_eq!(Hint::next(Hint::ALL.len()), None);
+
+        let costs: Vec<u32> = Hint::ALL.iter().map(|hint| hint.cost()).collect();
+        assert_eq!(costs, HINT_COSTS);
+    }
+
+    #[test]
+    fn parity_tells_without_narrowing() {
+        let (text, low, high) = Hint::Parity.give(&Secret::new(95), 10, 99, Strength::Normal, Locale::English);
+        assert_eq!((text.as_str(), low, high), ("The number is odd", 10, 99));
+        let (text, ..) = Hint::Parity.give(&Secret::new(0), 0, 9, Strength::Normal, Locale::English);
+        assert_eq!(text, "The number is even");
+    }
+
+    #[test]
+    fn every_hint_keeps_the_secret_in_range() {
+        for secret in 1..=100 {
+            for strength in [Strength::Generous, Strength::Normal, Strength::Strict] {
+                let (mut low, mut high) = (1, 100);
+                for hint in Hint::ALL {
+                    (_, low, high) = hint.give(&Secret::new(secret), low, high, strength, Locale::English);
+                    assert!((low..=high).contains(&secret), "{:?} for {} gave {}..={}", hint, secret, low, high);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn log_keys_are_unique() {
+        let mut keys: Vec<&str> = Hint::ALL.iter().map(|hint| hint.key()).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        assert_eq!(keys.len(), Hint::ALL.len());
+    }
+
+    #[test]
+    fn digits_are_counted_right_at_every_power_of_ten() {
+        assert_eq!(digit_count(0), 1);
+        for digits in 1..=9 {
+            let power = 10u32.pow(digits);
+            assert_eq!(digit_count(power - 1), digits, "{}", power - 1);
+            assert_eq!(digit_count(power), digits + 1, "{}", power);
+            assert_eq!(digit_count(power + 1), digits + 1, "{}", power + 1);
+        }
+        assert_eq!(digit_count(u32::MAX), 10);
+    }
+
+    #[test]
+    fn each_digit_count_covers_its_own_values() {
+        assert_eq!(digit_interval(1), (0, 9));
+        assert_eq!(digit_interval(2), (10, 99));
+        assert_eq!(digit_interval(6), (100_000, 999_999));
+        assert_eq!(digit_interval(10), (1_000_000_000, u32::MAX));
+        for digits in 1..=10 {
+            let (start, end) = digit_interval(digits);
+            assert_eq!((digit_count(start), digit_count(end)), (digits, digits));
+            if digits < 10 {
+                assert_eq!(digit_interval(digits + 1).0, end + 1, "the intervals meet");
+            }
+        }
+    }
+
+    #[test]
+    fn the_digit_hint_tightens_the_bounds_to_its_interval() {
+        let give = |secret, low, high| Hint::Digits.give(&Secret::new(secret), low, high, Strength::Normal, Locale::English);
+        assert_eq!(give(100_000, 1, 1_000_000), ("The number has 6 digits".to_string(), 100_000, 999_999));
+        assert_eq!(give(99_999, 1, 1_000_000), ("The number has 5 digits".to_string(), 10_000, 99_999));
+        assert_eq!(give(1_000_000, 1, 1_000_000), ("The number has 7 digits".to_string(), 1_000_000, 1_000_000));
+        assert_eq!(give(10, 1, 100), ("The number has 2 digits".to_string(), 10, 99));
+        assert_eq!(give(9, 1, 100), ("The number has 1 digit".to_string(), 1, 9), "never below the range");
+        assert_eq!(give(50, 40, 60), ("The number has 2 digits".to_string(), 40, 60), "never wider than it was");
+    }
+
+    #[test]
+    fn the_range_hints_follow_the_strength() {
+        let give = |hint: Hint, strength| {
+            let (_, low, high) = hint.give(&Secret::new(95), 1, 100, strength, Locale::English);
+            (low, high)
+        };
+        assert_eq!(give(Hint::Half, Strength::Generous), (76, 100));
+        assert_eq!(give(Hint::Half, Strength::Normal), (51, 100));
+        assert_eq!(give(Hint::Half, Strength::Strict), (51, 100));
+        assert_eq!(give(Hint::Decade, Strength::Generous), (95, 99));
+        assert_eq!(give(Hint::Decade, Strength::Normal), (90, 99));
+        assert_eq!(give(Hint::Decade, Strength::Strict), (75, 99));
+    }
+
+    #[test]
+    fn the_last_digit_moves_the_bounds_in_to_numbers_ending_the_same_way() {
+        let give = |secret, low, high| Hint::LastDigit.give(&Secret::new(secret), low, high, Strength::Normal, Locale::English);
+        assert_eq!(give(95, 10, 99), ("The number ends in 5".to_string(), 15, 95));
+        assert_eq!(give(37, 1, 100), ("The number ends in 7".to_string(), 7, 97));
+        assert_eq!(give(40, 1, 100), ("The number ends in 0".to_string(), 10, 100));
+        assert_eq!(give(13, 13, 19), ("The number ends in 3".to_string(), 13, 13), "only the secret is left");
+        assert_eq!(give(u32::MAX, 0, u32::MAX), ("The number ends in 5".to_string(), 5, u32::MAX));
+        assert_eq!(give(4, 0, u32::MAX), ("The number ends in 4".to_string(), 4, u32::MAX - 1));
+
+        for secret in 1..=100 {
+            let (_, low, high) = give(secret, 1, 100);
+            assert_eq!((low % 10, high % 10), (secret % 10, secret % 10), "{}", secret);
+            assert!(low < 11 && high > 90, "{} gave {}..={}", secret, low, high);
+        }
+    }
+}
@@ -0,0 +1,178 @@
+// The post-game review as a window onto the guess history, building only the rows in view
+//
+// Endless rounds and long games can leave thousands of guesses to review, and laying out a
+// widget for every one of them on every frame makes the whole window crawl. The review scrolls
+// at a fixed row height instead, so how far down it has been scrolled tells exactly which rows
+// are on screen: only those and a few either side are built, and spaces of the right height
+// stand in for the rest, so the scrollbar still covers the whole history.
+
+use std::ops::Range;
+
+// The height of every row, in logical pixels; rows are clipped to it so the sums stay exact
+pub const ROW_HEIGHT: f32 = 22.0;
+
+// The most the review takes up on screen before it scrolls
+pub const VIEWPORT_HEIGHT: f32 = 320.0;
+
+// Rows built beyond each edge of the viewport, so a quick scroll doesn't show blank space first
+const OVERSCAN: usize = 8;
+
+// What a row of the review stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Entry {
+    Guess(usize), // The guess at that place in the history, from 0
+    TimedOut,     // An attempt that ran out of time, which has no guess
+}
+
+// How the rows fade with age: the latest guess is at full strength, each one before it a step
+// fainter, down to a floor that keeps even the oldest rows easy to read
+const FADE_STEP: f32 = 0.06;
+const FADE_FLOOR: f32 = 0.45;
+
+// The rows to build for a review of `total` rows scrolled `offset` of the way down, from 0 to 1
+pub fn visible(total: usize, offset: f32) -> Range<usize> {
+    let hidden = (height(total) - VIEWPORT_HEIGHT).max(0.0); // How far the review can scroll
+    let top = (offset.clamp(0.0, 1.0) * hidden / ROW_HEIGHT).floor() as usize;
+    let shown = (VIEWPORT_HEIGHT / ROW_HEIGHT).ceil() as usize;
+    let start = top.saturating_sub(OVERSCAN).min(total);
+    let end = (top + shown + OVERSCAN).min(total);
+    start..end
+}
+
+// What row `row` of the review stands for, with the timed-out attempts at `timeouts`
+//
+// Each timeout is kept as how many guesses came before it, in order, so timeout `j` sits at
+// row `timeouts[j] + j` and the guesses fill the rows in between. Those rows only ever go up,
+// so a binary search finds any row without walking the history.
+pub fn entry(row: usize, timeouts: &[usize]) -> Entry {
+    let (mut low, mut high) = (0, timeouts.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        if timeouts[mid] + mid < row {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    // `low` timeouts come before the row, so it's that one or a guess after them
+    match timeouts.get(low) {
+        Some(&place) if place + low == row => Entry::TimedOut,
+        _ => Entry::Guess(row - low),
+    }
+}
+
+// How strongly to draw row `index` of `total`, from `FADE_FLOOR` for old guesses to 1 for the latest
+pub fn recency(index: usize, total: usize) -> f32 {
+    let age = total.saturating_sub(index + 1);
+    (1.0 - age as f32 * FADE_STEP).max(FADE_FLOOR)
+}
+
+// The height of `rows` rows, for the spaces standing in for the rows that aren't built
+pub fn height(rows: usize) -> f32 {
+    rows as f32 * ROW_HEIGHT
+}
+
+// The height of the scrollable: the full viewport, or just enough for a review that fits in it
+pub fn viewport(total: usize) -> f32 {
+    height(total).min(VIEWPORT_HEIGHT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every row of a review of `guesses` guesses with timeouts at `timeouts`
+    fn rows(guesses: usize, timeouts: &[usize]) -> Vec<Entry> {
+        (0..guesses + timeouts.len()).map(|row| entry(row, timeouts)).collect()
+    }
+
+    #[test]
+    fn without_timeouts_every_row_is_its_guess() {
+        assert_eq!(rows(3, &[]), [Entry::Guess(0), Entry::Guess(1), Entry::Guess(2)]);
+    }
+
+    #[test]
+    fn a_timeout_before_any_guess_comes_first() {
+        assert_eq!(rows(2, &[0]), [Entry::TimedOut, Entry::Guess(0), Entry::Guess(1)]);
+    }
+
+    #[test]
+    fn timeouts_in_the_same_place_follow_each_other() {
+        assert_eq!(
+            rows(3, &[1, 1, 2]),
+            [Entry::Guess(0), Entry::TimedOut, Entry::TimedOut, Entry::Guess(1), Entry::TimedOut, Entry::Guess(2)]
+        );
+    }
+
+    #[test]
+    fn timeouts_after_the_last_guess_close_the_review() {
+        assert_eq!(rows(2, &[2, 2]), [Entry::Guess(0), Entry::Guess(1), Entry::TimedOut, Entry::TimedOut]);
+    }
+
+    #[test]
+    fn a_review_of_only_timeouts_has_no_guesses() {
+        assert_eq!(rows(0, &[0, 0]), [Entry::TimedOut, Entry::TimedOut]);
+    }
+
+    #[test]
+    fn a_review_that_fits_is_built_whole_at_any_offset() {
+        for offset in [0.0, 0.5, 1.0, -3.0, 7.0] {
+            assert_eq!(visible(0, offset), 0..0);
+            assert_eq!(visible(5, offset), 0..5);
+        }
+        assert_eq!(viewport(5), 5.0 * ROW_HEIGHT);
+        assert_eq!(viewport(10_000), VIEWPORT_HEIGHT);
+    }
+
+    #[test]
+    fn a_long_review_builds_only_the_rows_around_the_viewport() {
+        let total = 10_000;
+        let shown = (VIEWPORT_HEIGHT / ROW_HEIGHT).ceil() as usize;
+        assert_eq!(visible(total, 0.0), 0..shown + OVERSCAN);
+        assert_eq!(visible(total, 1.0).end, total, "the bottom reaches the last row");
+        assert_eq!(visible(total, 2.0), visible(total, 1.0), "offsets past the end are clamped");
+
+        let hidden = height(total) - VIEWPORT_HEIGHT;
+        for step in 0..=1_000 {
+            let offset = step as f32 / 1_000.0;
+            let rows = visible(total, offset);
+            assert!(rows.len() <= shown + 2 * OVERSCAN, "{} rows built at {}", rows.len(), offset);
+
+            // Every row at least partly on screen is built, and the spaces add up to the rest
+            let top = offset * hidden;
+            let first = (top / ROW_HEIGHT).floor() as usize;
+            let last = (((top + VIEWPORT_HEIGHT) / ROW_HEIGHT).ceil() as usize).min(total);
+            assert!(rows.start <= first && last <= rows.end, "{:?} misses {}..{} at {}", rows, first, last, offset);
+            assert_eq!(height(rows.start) + height(rows.len()) + height(total - rows.end), height(total));
+        }
+    }
+
+    #[test]
+    fn every_row_of_a_long_review_with_timeouts_is_found() {
+        let guesses = 10_000;
+        let timeouts: Vec<usize> = (0..guesses).step_by(7).chain([guesses, guesses]).collect();
+        let mut next_guess = 0;
+        let mut timed_out = 0;
+        for (row, found) in rows(guesses, &timeouts).into_iter().enumerate() {
+            match found {
+                Entry::Guess(i) => {
+                    assert_eq!(i, next_guess, "row {}", row);
+                    next_guess += 1;
+                }
+                Entry::TimedOut => {
+                    assert_eq!(timeouts[timed_out], next_guess, "row {}", row);
+                    timed_out += 1;
+                }
+            }
+        }
+        assert_eq!((next_guess, timed_out), (guesses, timeouts.len()));
+    }
+
+    #[test]
+    fn rows_fade_with_age_down_to_the_floor() {
+        assert_eq!(recency(9, 10), 1.0);
+        assert!((recency(8, 10) - (1.0 - FADE_STEP)).abs() < 1e-6);
+        assert_eq!(recency(0, 10_000), FADE_FLOOR);
+        assert!((0..20).all(|i| recency(i, 20) <= recency(i + 1, 20)));
+    }
+}
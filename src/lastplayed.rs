@@ -0,0 +1,275 @@
+// The configuration of the last game actually played, offered again at the next launch
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::analysis;
+use crate::migration::{self, Schema};
+use crate::solver::BotStrategy;
+use crate::storage;
+
+// File the configuration is stored in, inside the data directory
+const FILE_NAME: &str = "last_played.txt";
+
+// How the file has changed between versions
+const SCHEMA: Schema = Schema { file: FILE_NAME, name: "last played game", legacy_key: None, steps: &[] };
+
+// The ways a game can be played
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayMode {
+    Classic,         // The player guesses a random secret
+    Daily,           // The player guesses the day's shared secret
+    PassAndPlay,     // One player sets the secret for another
+    ComputerGuesses, // The bot guesses the player's number
+}
+
+impl PlayMode {
+    // Every mode that can be played again
+    pub const ALL: [PlayMode; 4] = [PlayMode::Classic, PlayMode::Daily, PlayMode::PassAndPlay, PlayMode::ComputerGuesses];
+
+    // The value written to the file
+    pub fn key(self) -> &'static str {
+        match self {
+            PlayMode::Classic => "classic",
+            PlayMode::Daily => "daily",
+            PlayMode::PassAndPlay => "pass_and_play",
+            PlayMode::ComputerGuesses => "computer_guesses",
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<PlayMode> {
+        PlayMode::ALL.into_iter().find(|mode| mode.key() == key)
+    }
+}
+
+// What was played, exactly enough to set the same game up again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LastPlayed {
+    pub mode: PlayMode,
+    pub min: u32,                  // Smallest possible secret
+    pub max: u32,                  // Largest possible secret
+    pub mystery: bool,             // Whether guesses went through a mystery operation
+    pub close_enough: bool,        // Whether close guesses won
+    pub bot_strategy: BotStrategy, // How the bot guessed, in the computer-guesses mode
+}
+
+// Why a stored configuration couldn't be restored as it was
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fallback {
+    UnknownMode,      // The mode was removed, or the file came from a newer version
+    UnsupportedRange, // This version can't play the stored range
+    UnknownBot,       // The bot's strategy was removed
+}
+
+impl fmt::Display for Fallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Fallback::UnknownMode => "The mode you last played isn't available any more, so Same again plays classic",
+            Fallback::UnsupportedRange => "The range you last played isn't available any more, so Same again plays classic",
+            Fallback::UnknownBot => "The bot difficulty you last played isn't available any more, so it's back to the default",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl LastPlayed {
+    // Read a configuration from the file's values, for a version that plays `min..=max`
+    //
+    // Anything that can't be restored falls back rather than failing: an unknown mode or a
+    // range this version can't play gives classic over the supported range, and an unknown
+    // bot the default one. The fallback is returned so the player can be told.
+    pub fn from_values(values: &BTreeMap<String, String>, min: u32, max: u32) -> Option<(LastPlayed, Option<Fallback>)> {
+        let flag = |key: &str| values.get(key).and_then(|value| value.parse().ok()).unwrap_or(false);
+        let number = |key: &str| values.get(key).and_then(|value| value.parse::<u32>().ok());
+
+        let mut fallback = None;
+        let mut last = LastPlayed {
+            mode: PlayMode::Classic,
+            min,
+            max,
+            mystery: flag("mystery"),
+            close_enough: flag("close_enough"),
+            bot_strategy: BotStrategy::default(),
+        };
+
+        match values.get("bot_strategy").map(|key| BotStrategy::from_key(key)) {
+            Some(Some(strategy)) => last.bot_strategy = strategy,
+            Some(None) => fallback = Some(Fallback::UnknownBot),
+            None => {}
+        }
+
+        match PlayMode::from_key(values.get("mode")?) {
+            Some(mode) => last.mode = mode,
+            None => return Some((last, Some(Fallback::UnknownMode))),
+        }
+        if (number("min"), number("max")) != (Some(min), Some(max)) {
+            last.mode = PlayMode::Classic;
+            return Some((last, Some(Fallback::UnsupportedRange)));
+        }
+
+        Some((last, fallback))
+    }
+
+    // Load the stored configuration, if a game has been played before
+    pub fn load(min: u32, max: u32) -> Option<(LastPlayed, Option<Fallback>)> {
+        match migration::load(&SCHEMA) {
+            Ok(values) => LastPlayed::from_values(&values?, min, max),
+            Err(error) => {
+                tracing::warn!(%error, "Could not read the last played configuration");
+                None
+            }
+        }
+    }
+
+    // Save the configuration, logging rather than failing if the file can't be written
+    pub fn save(&self) {
+        let contents = storage::write_key_values([
+            ("mode", self.mode.key().to_string()),
+            ("min", self.min.to_string()),
+            ("max", self.max.to_string()),
+            ("mystery", self.mystery.to_string()),
+            ("close_enough", self.close_enough.to_string()),
+            ("bot_strategy", self.bot_strategy.key().to_string()),
+        ]);
+
+        if let Err(error) = migration::save(&SCHEMA, &contents) {
+            tracing::warn!(%error, "Could not save the last played configuration");
+        }
+    }
+}
+
+// A summary such as "Computer guesses (Hard), Medium (1–100)"
+impl fmt::Display for LastPlayed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = vec![match self.mode {
+            PlayMode::Classic => "Classic".to_string(),
+            PlayMode::Daily => "Daily challenge".to_string(),
+            PlayMode::PassAndPlay => "Pass and play".to_string(),
+            PlayMode::ComputerGuesses => format!("Computer guesses ({})", self.bot_strategy),
+        }];
+        if self.mode != PlayMode::ComputerGuesses {
+            if self.mystery {
+                parts.push("mystery operation".to_string());
+            }
+            if self.close_enough {
+                parts.push("close enough".to_string());
+            }
+        }
+        parts.push(analysis::difficulty_label(self.min, self.max));
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|&(key, value)| (key.to_string(), value.to_string())).collect()
+    }
+
+    fn hard_bot() -> BotStrategy {
+        BotStrategy::from_key("binary").unwrap()
+    }
+
+    #[test]
+    fn a_stored_game_is_restored_as_it_was() {
+        let stored = values(&[
+            ("mode", "computer_guesses"),
+            ("min", "1"),
+            ("max", "100"),
+            ("mystery", "true"),
+            ("close_enough", "true"),
+            ("bot_strategy", "binary"),
+        ]);
+        let expected = LastPlayed {
+            mode: PlayMode::ComputerGuesses,
+            min: 1,
+            max: 100,
+            mystery: true,
+            close_enough: true,
+            bot_strategy: hard_bot(),
+        };
+        assert_eq!(LastPlayed::from_values(&stored, 1, 100), Some((expected, None)));
+    }
+
+    #[test]
+    fn an_unknown_mode_falls_back_to_classic() {
+        let stored = values(&[("mode", "speedrun"), ("min", "1"), ("max", "100"), ("mystery", "true")]);
+        let (last, fallback) = LastPlayed::from_values(&stored, 1, 100).unwrap();
+        assert_eq!(fallback, Some(Fallback::UnknownMode));
+        assert_eq!(last.mode, PlayMode::Classic);
+        assert!(last.mystery, "the rest of the game is kept");
+    }
+
+    #[test]
+    fn a_range_this_version_cannot_play_falls_back_to_classic_over_its_own() {
+        for (min, max) in [("1", "1000"), ("0", "100"), ("1", "lots"), ("", "")] {
+            let stored = values(&[("mode", "daily"), ("min", min), ("max", max)]);
+            let (last, fallback) = LastPlayed::from_values(&stored, 1, 100).unwrap();
+            assert_eq!(fallback, Some(Fallback::UnsupportedRange), "{}–{}", min, max);
+            assert_eq!((last.mode, last.min, last.max), (PlayMode::Classic, 1, 100));
+        }
+        let stored = values(&[("mode", "daily")]);
+        assert_eq!(LastPlayed::from_values(&stored, 1, 100).unwrap().1, Some(Fallback::UnsupportedRange));
+    }
+
+    #[test]
+    fn an_unknown_bot_falls_back_to_the_default() {
+        let stored = values(&[("mode", "computer_guesses"), ("min", "1"), ("max", "100"), ("bot_strategy", "genius")]);
+        let (last, fallback) = LastPlayed::from_values(&stored, 1, 100).unwrap();
+        assert_eq!(fallback, Some(Fallback::UnknownBot));
+        assert_eq!((last.mode, last.bot_strategy), (PlayMode::ComputerGuesses, BotStrategy::default()));
+    }
+
+    #[test]
+    fn unreadable_flags_are_off_and_a_missing_mode_is_nothing_played() {
+        let stored = values(&[("mode", "classic"), ("min", "1"), ("max", "100"), ("mystery", "yes")]);
+        assert!(!LastPlayed::from_values(&stored, 1, 100).unwrap().0.mystery);
+        assert_eq!(LastPlayed::from_values(&values(&[("min", "1"), ("max", "100")]), 1, 100), None);
+    }
+
+    #[test]
+    fn a_saved_game_loads_back() {
+        migration::remove(&SCHEMA).unwrap();
+        assert_eq!(LastPlayed::load(1, 100), None);
+
+        let last = LastPlayed {
+            mode: PlayMode::PassAndPlay,
+            min: 1,
+            max: 100,
+            mystery: false,
+            close_enough: true,
+            bot_strategy: hard_bot(),
+        };
+        last.save();
+        assert_eq!(LastPlayed::load(1, 100), Some((last, None)));
+        assert_eq!(LastPlayed::load(1, 1000).unwrap().1, Some(Fallback::UnsupportedRange));
+        migration::remove(&SCHEMA).unwrap();
+    }
+
+    #[test]
+    fn modes_round_trip_through_their_keys() {
+        for mode in PlayMode::ALL {
+            assert_eq!(PlayMode::from_key(mode.key()), Some(mode));
+        }
+        assert_eq!(PlayMode::from_key("Classic"), None);
+    }
+
+    #[test]
+    fn the_summary_names_the_mode_its_options_and_the_range() {
+        let classic = LastPlayed {
+            mode: PlayMode::Classic,
+            min: 1,
+            max: 100,
+            mystery: true,
+            close_enough: true,
+            bot_strategy: BotStrategy::default(),
+        };
+        assert_eq!(classic.to_string(), "Classic, mystery operation, close enough, Medium (1–100)");
+
+        let bot = LastPlayed { mode: PlayMode::ComputerGuesses, bot_strategy: hard_bot(), ..classic };
+        assert_eq!(bot.to_string(), "Computer guesses (Hard), Medium (1–100)", "the bot ignores the guessing options");
+    }
+}
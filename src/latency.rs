@@ -0,0 +1,70 @@
+// Timing how long `update` and `view` take, for catching a change that makes the game slow to respond
+//
+// A developer tool, never shown to players: it's only switched on by setting the environment
+// variable below, and then a small panel in the window's corner keeps a rolling average over
+// the last few hundred messages and frames, with the slowest message among them.
+
+use std::env;
+use std::time::Duration;
+
+use crate::debug::RingBuffer;
+
+// The environment variable that switches the stats on; anything but empty or "0" will do
+pub const ENV_VAR: &str = "GUESSING_GAME_LATENCY";
+
+// Messages and frames the averages cover
+const SAMPLES: usize = 300;
+
+// Whether the stats were asked for when the game was started
+pub fn enabled() -> bool {
+    env::var_os(ENV_VAR).is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+// The recent timings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Latency {
+    updates: RingBuffer<(&'static str, Duration)>, // Each message's name and how long handling it took
+    views: RingBuffer<Duration>,                   // How long each frame's widgets took to build
+}
+
+impl Default for Latency {
+    fn default() -> Latency {
+        Latency { updates: RingBuffer::new(SAMPLES), views: RingBuffer::new(SAMPLES) }
+    }
+}
+
+impl Latency {
+    // Note that handling the message `name` took `took`
+    pub fn record_update(&mut self, name: &'static str, took: Duration) {
+        self.updates.push((name, took));
+    }
+
+    // Note that building a frame's widgets took `took`
+    pub fn record_view(&mut self, took: Duration) {
+        self.views.push(took);
+    }
+
+    // The lines the panel shows, as in "update 41µs avg, slowest 2.1ms (GuessButtonPressed)"
+    pub fn lines(&self) -> [String; 2] {
+        let update = match self.updates.iter().max_by_key(|(_, took)| *took) {
+            Some((name, slowest)) => format!(
+                "update {:.1?} avg, slowest {:.1?} ({})",
+                average(self.updates.iter().map(|(_, took)| *took)),
+                slowest,
+                name
+            ),
+            None => "update: no messages yet".to_string(),
+        };
+        let view = match self.views.iter().max() {
+            Some(slowest) => format!("view {:.1?} avg, slowest {:.1?}", average(self.views.iter().copied()), slowest),
+            None => "view: no frames yet".to_string(),
+        };
+        [update, view]
+    }
+}
+
+// The mean of `durations`, or zero for none
+fn average(durations: impl Iterator<Item = Duration>) -> Duration {
+    let (total, count) = durations.fold((Duration::ZERO, 0u32), |(total, count), took| (total + took, count + 1));
+    total.checked_div(count).unwrap_or_default()
+}
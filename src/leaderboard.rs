@@ -0,0 +1,354 @@
+// Personal best boards, one ranked by guesses and one by time, fed by the same wins
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+use crate::daily::Date;
+use crate::migration::{self, Schema};
+use crate::storage;
+
+// File the boards are stored in, inside the data directory
+const FILE_NAME: &str = "leaderboard.txt";
+
+// How the file has changed between versions
+const SCHEMA: Schema = Schema { file: FILE_NAME, name: "leaderboard", legacy_key: None, steps: &[] };
+
+// Entries kept on each board
+pub const MAX_ENTRIES: usize = 10;
+
+// The least a win must take to go on the time-attack board. A challenge code or a replayed seed
+// can tell a player the secret, and typing it straight in would otherwise top the board with a
+// time no real game could beat. Three seconds is less than anyone takes to read the prompt and
+// make two guesses, so it only ever turns away wins where the answer was known beforehand.
+pub const MIN_TIMED_ELAPSED: Duration = Duration::from_secs(3);
+pub const MIN_TIMED_GUESSES: u32 = 2;
+
+// What a board ranks wins by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Attempts, // Fewest guesses first, the faster win breaking a tie
+    Time,     // Fastest win first, the one with fewer guesses breaking a tie
+}
+
+impl Metric {
+    // Every board, in the order the picker offers them
+    pub const ALL: [Metric; 2] = [Metric::Attempts, Metric::Time];
+
+    // The key the board is stored under, and written to session recordings
+    pub fn key(self) -> &'static str {
+        match self {
+            Metric::Attempts => "attempts",
+            Metric::Time => "time",
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<Metric> {
+        Metric::ALL.into_iter().find(|metric| metric.key() == key)
+    }
+
+    // How `a` ranks against `b` on this board, better first
+    fn compare(self, a: &Entry, b: &Entry) -> Ordering {
+        match self {
+            Metric::Attempts => a.attempts.cmp(&b.attempts).then(a.elapsed.cmp(&b.elapsed)),
+            Metric::Time => a.elapsed.cmp(&b.elapsed).then(a.attempts.cmp(&b.attempts)),
+        }
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Metric::Attempts => "Fewest guesses",
+            Metric::Time => "Time attack",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+// Why a win was kept off the time-attack board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ineligible {
+    TooFast,       // It took less than `MIN_TIMED_ELAPSED` on the clock
+    TooFewGuesses, // It took fewer than `MIN_TIMED_GUESSES` guesses
+    Idled,         // The clock paused while the player was away, so the time leaves some of the game out
+}
+
+impl fmt::Display for Ineligible {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ineligible::TooFast => write!(
+                f,
+                "Too quick for the time-attack board - times there need at least {} seconds on the clock",
+                MIN_TIMED_ELAPSED.as_secs()
+            ),
+            Ineligible::TooFewGuesses => write!(
+                f,
+                "Not on the time-attack board - times there need at least {} guesses, so a known secret can't top it",
+                MIN_TIMED_GUESSES
+            ),
+            Ineligible::Idled => write!(
+                f,
+                "Not on the time-attack board - the clock paused while you were away, and time spent thinking counts there"
+            ),
+        }
+    }
+}
+
+// Whether `entry` may go on the time-attack board, or why not; the guesses board takes every win
+//
+// `idled` is whether the clock paused for inactivity during the game, which leaves the time short.
+pub fn time_eligibility(entry: &Entry, idled: bool) -> Result<(), Ineligible> {
+    if entry.attempts < MIN_TIMED_GUESSES {
+        Err(Ineligible::TooFewGuesses)
+    } else if entry.elapsed < MIN_TIMED_ELAPSED {
+        Err(Ineligible::TooFast)
+    } else if idled {
+        Err(Ineligible::Idled)
+    } else {
+        Ok(())
+    }
+}
+
+// One win on a board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub attempts: u32,     // Guesses the win took
+    pub elapsed: Duration, // Time on the game clock, excluding pauses
+    pub date: Date,        // The day it was won
+}
+
+impl Entry {
+    // The value written to the file, such as "7@41250@2026-10-14"
+    fn key(&self) -> String {
+        format!("{}@{}@{}", self.attempts, self.elapsed.as_millis(), self.date.key())
+    }
+
+    // Read a value written by `key`
+    fn from_key(key: &str) -> Option<Entry> {
+        let mut parts = key.trim().splitn(3, '@');
+        let attempts = parts.next()?.parse().ok()?;
+        let elapsed = Duration::from_millis(parts.next()?.parse().ok()?);
+        let date = Date::from_key(parts.next()?)?;
+        Some(Entry { attempts, elapsed, date })
+    }
+}
+
+// Both boards, saved after every win that makes either
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Leaderboards {
+    attempts: Vec<Entry>, // Best first
+    time: Vec<Entry>,     // Best first
+}
+
+impl Leaderboards {
+    // Load the boards, starting afresh if there are none or they can't be read
+    pub fn load() -> Leaderboards {
+        let values = match migration::load(&SCHEMA) {
+            Ok(Some(values)) => values,
+            Ok(None) => return Leaderboards::default(), // No games won yet
+            Err(error) => {
+                tracing::warn!(%error, "Could not read the leaderboards");
+                return Leaderboards::default();
+            }
+        };
+
+        // Re-rank what was read, so a hand-edited file can't leave a board out of order
+        let mut boards = Leaderboards::default();
+        for metric in Metric::ALL {
+            let list = values.get(metric.key()).map_or("", String::as_str);
+            for entry in list.split(',').filter_map(Entry::from_key) {
+                insert(boards.board_mut(metric), metric, entry);
+            }
+        }
+        boards
+    }
+
+    // Save both boards in one atomic write, logging rather than failing if the file can't be written
+    fn save(&self) {
+        let list = |entries: &[Entry]| entries.iter().map(Entry::key).collect::<Vec<_>>().join(",");
+        let contents = storage::write_key_values(Metric::ALL.map(|metric| (metric.key(), list(self.board(metric)))));
+
+        if let Err(error) = migration::save(&SCHEMA, &contents) {
+            tracing::warn!(%error, "Could not save the leaderboards");
+        }
+    }
+
+    // The entries on the board for `metric`, best first
+    pub fn board(&self, metric: Metric) -> &[Entry] {
+        match metric {
+            Metric::Attempts => &self.attempts,
+            Metric::Time => &self.time,
+        }
+    }
+
+    // The board for `metric`, to insert into
+    fn board_mut(&mut self, metric: Metric) -> &mut Vec<Entry> {
+        match metric {
+            Metric::Attempts => &mut self.attempts,
+            Metric::Time => &mut self.time,
+        }
+    }
+
+    // Offer a win to every board it's eligible for, returning its place on each, from 1, where it made the board
+    pub fn record(&mut self, entry: Entry, idled: bool) -> Vec<Placement> {
+        let placed: Vec<Placement> = Metric::ALL
+            .into_iter()
+            .filter(|&metric| metric != Metric::Time || time_eligibility(&entry, idled).is_ok())
+            .filter_map(|metric| insert(self.board_mut(metric), metric, entry))
+            .collect();
+
+        if !placed.is_empty() {
+            self.save();
+        }
+        placed
+    }
+
+    // Empty both boards and delete the saved file; the boards are emptied even if that fails
+    pub fn reset(&mut self) -> io::Result<()> {
+        *self = Leaderboards::default();
+        migration::remove(&SCHEMA)
+    }
+}
+
+// Where a new entry went on a board, and what making room for it did to the rest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub metric: Metric,
+    pub place: usize,           // The new entry's place, from 1
+    pub shifted: usize,         // How many entries below it moved down a place
+    pub dropped: Option<Entry>, // The entry a full board pushed off the bottom
+}
+
+impl Placement {
+    // Whether the entry at `place` moved down to make room
+    pub fn shifted_down(&self, place: usize) -> bool {
+        place > self.place && place <= self.place + self.shifted
+    }
+}
+
+// Insert `entry` into a board ranked by `metric`, keeping the best `MAX_ENTRIES`
+//
+// An entry tied with earlier ones goes after them, so the first to set a result keeps it.
+// Returns where it went, or `None` if it didn't make the board.
+fn insert(board: &mut Vec<Entry>, metric: Metric, entry: Entry) -> Option<Placement> {
+    let index = board.partition_point(|existing| metric.compare(existing, &entry) != Ordering::Greater);
+    if index >= MAX_ENTRIES {
+        return None;
+    }
+    board.insert(index, entry);
+    let dropped = (board.len() > MAX_ENTRIES).then(|| board.pop()).flatten();
+    Some(Placement {
+        metric,
+        place: index + 1,
+        shifted: board.len() - index - 1,
+        dropped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(attempts: u32, seconds: u64) -> Entry {
+        Entry { attempts, elapsed: Duration::from_secs(seconds), date: Date { year: 2026, month: 10, day: 14 } }
+    }
+
+    #[test]
+    fn a_time_needs_both_the_guesses_and_the_seconds() {
+        let at = |attempts, elapsed| time_eligibility(&Entry { elapsed, ..entry(attempts, 0) }, false);
+        assert_eq!(at(MIN_TIMED_GUESSES, MIN_TIMED_ELAPSED), Ok(()));
+        assert_eq!(at(MIN_TIMED_GUESSES, MIN_TIMED_ELAPSED - Duration::from_millis(1)), Err(Ineligible::TooFast));
+        assert_eq!(at(MIN_TIMED_GUESSES - 1, Duration::from_secs(600)), Err(Ineligible::TooFewGuesses));
+        assert_eq!(at(1, Duration::ZERO), Err(Ineligible::TooFewGuesses), "too few guesses is the reason given first");
+        assert!(Ineligible::TooFast.to_string().contains("at least 3 seconds"));
+        assert!(Ineligible::TooFewGuesses.to_string().contains("at least 2 guesses"));
+    }
+
+    #[test]
+    fn a_win_the_clock_paused_for_idling_makes_only_the_guesses_board() {
+        let idled = |attempts, elapsed| time_eligibility(&Entry { elapsed, ..entry(attempts, 0) }, true);
+        assert_eq!(idled(MIN_TIMED_GUESSES, Duration::from_secs(600)), Err(Ineligible::Idled));
+        assert_eq!(idled(1, Duration::from_secs(600)), Err(Ineligible::TooFewGuesses));
+
+        let mut boards = Leaderboards::default();
+        let placed = boards.record(entry(4, 12), true);
+        assert_eq!(placed.iter().map(|placement| placement.metric).collect::<Vec<_>>(), [Metric::Attempts]);
+        assert!(boards.board(Metric::Time).is_empty());
+    }
+
+    #[test]
+    fn an_ineligible_win_still_makes_the_guesses_board() {
+        let mut boards = Leaderboards::default();
+        let placed = boards.record(entry(1, 1), false);
+        assert_eq!(placed.iter().map(|placement| placement.metric).collect::<Vec<_>>(), [Metric::Attempts]);
+        assert_eq!(boards.board(Metric::Attempts), [entry(1, 1)]);
+        assert!(boards.board(Metric::Time).is_empty());
+
+        let placed = boards.record(entry(2, 30), false);
+        assert_eq!(placed.iter().map(|placement| placement.metric).collect::<Vec<_>>(), Metric::ALL);
+        assert_eq!(boards.board(Metric::Time), [entry(2, 30)]);
+    }
+
+    #[test]
+    fn boards_rank_by_their_metric_ties_going_to_the_first() {
+        let mut board = Vec::new();
+        for win in [entry(5, 20), entry(3, 40), entry(5, 10), entry(3, 40)] {
+            insert(&mut board, Metric::Attempts, win);
+        }
+        assert_eq!(board, [entry(3, 40), entry(3, 40), entry(5, 10), entry(5, 20)]);
+
+        let tied = Entry { date: Date { year: 2027, month: 1, day: 1 }, ..entry(3, 40) };
+        let placement = insert(&mut board, Metric::Attempts, tied).unwrap();
+        assert_eq!((placement.place, placement.shifted), (3, 2));
+        assert!(placement.shifted_down(4) && placement.shifted_down(5) && !placement.shifted_down(3));
+
+        let mut times = Vec::new();
+        for win in [entry(5, 20), entry(3, 40), entry(4, 20)] {
+            insert(&mut times, Metric::Time, win);
+        }
+        assert_eq!(times, [entry(4, 20), entry(5, 20), entry(3, 40)]);
+    }
+
+    #[test]
+    fn a_full_board_drops_its_worst_or_turns_the_win_away() {
+        let mut board = Vec::new();
+        for attempts in 1..=MAX_ENTRIES as u32 {
+            insert(&mut board, Metric::Attempts, entry(attempts, 10));
+        }
+        assert_eq!(insert(&mut board, Metric::Attempts, entry(MAX_ENTRIES as u32, 10)), None, "a tie with the last doesn't push it off");
+
+        let placement = insert(&mut board, Metric::Attempts, entry(2, 5)).unwrap();
+        assert_eq!(placement.place, 2);
+        assert_eq!(placement.dropped, Some(entry(MAX_ENTRIES as u32, 10)));
+        assert_eq!(board.len(), MAX_ENTRIES);
+    }
+
+    #[test]
+    fn boards_survive_a_save_and_load_but_not_a_reset() {
+        let mut boards = Leaderboards::default();
+        boards.reset().unwrap();
+        boards.record(entry(4, 12), false);
+        boards.record(entry(1, 1), false);
+        boards.record(entry(6, 9), false);
+        assert_eq!(Leaderboards::load(), boards);
+
+        boards.reset().unwrap();
+        assert_eq!(boards, Leaderboards::default());
+        assert_eq!(Leaderboards::load(), Leaderboards::default());
+    }
+
+    #[test]
+    fn entries_and_metrics_read_back_from_their_keys() {
+        let win = Entry { elapsed: Duration::from_millis(41_250), ..entry(7, 0) };
+        assert_eq!(win.key(), "7@41250@2026-10-14");
+        assert_eq!(Entry::from_key(&win.key()), Some(win));
+        assert_eq!(Entry::from_key("7@41250@2026-02-30"), None);
+        assert_eq!(Entry::from_key("7@soon@2026-10-14"), None);
+        for metric in Metric::ALL {
+            assert_eq!(Metric::from_key(metric.key()), Some(metric));
+        }
+    }
+}
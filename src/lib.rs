@@ -0,0 +1,15 @@
+// The game's core, usable without the window: the rules, the secret and a game's state
+//
+// The binary is a GUI shell over this library, and any other front end can play the same
+// game with it: start a `State`, feed it `Input`s through `step`, and read the bounds and
+// `Outcome`s back. Everything here is plain data and pure functions, apart from drawing
+// fresh secrets and masks, so it needs no window, files or clock.
+
+pub mod game;    // A game's state, and the pure step from one state to the next
+pub mod mystery; // Hidden operations for the mystery mode
+pub mod random;  // Sources of secret numbers
+pub mod rules;   // Rules deciding which guesses win
+pub mod secret;  // Masked storage for the secret number
+
+pub use game::{check_guess, step, step_under, Input, State};
+pub use rules::Outcome;
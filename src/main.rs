@@ -3,133 +3,5621 @@
 // Import necessary modules and components from the `iced` crate for building the GUI
 use iced::{
     alignment::Alignment,              // For aligning widgets within containers
+    event,                             // For telling whether a widget already handled an event
+    keyboard::{self, KeyCode},         // For keyboard shortcuts
     theme::Theme,                      // Theme for styling the application
-    widget::{Button, Column, Container, Text, TextInput}, // UI widgets
+    theme::Palette,                    // Base colors the custom theme is built from
+    widget::{text_input, tooltip, Button, Canvas, Checkbox, Column, Container, PickList, Row, Scrollable, Space, Text, TextInput, Tooltip}, // UI widgets
+    window,                            // For tracking whether the window has focus
     Application,                       // Trait for building the main application
+    Color,                             // For coloring text
     Command,                           // For handling side effects
     Element,                           // Represents UI elements
+    Font,                              // For drawing text in the chosen font
+    Event,                             // Window and input events from the runtime
     Length,                            // For sizing widgets
     Settings,                          // Application settings
+    Subscription,                      // For listening to external events such as timers
 };
 
-// Import the `rand` crate for generating random numbers
-use rand::Rng;
+// Import the game's core from the library, keeping its modules at their old paths
+use guessing_game::{game, mystery, random, rules, secret};
+// Import the source of secret numbers
+use random::{seeded_secret, NumberSource, SeededSource, ThreadRngSource};
+use mystery::MysteryOperation;
+use passplay::PassSetup;
+use recovery::Snapshot;
+// Import session recording and replay
+use replay::{Recorder, Recording};
+use secret::Secret;
+// Import the feedback messages and locale-aware number handling
+use feedback::Feedback;
+use format::{format_duration, format_number, format_seconds, format_thinking_time, parse_number, Locale, LocaleChoice, ParseError};
+// Import achievements and the stats they are judged on
+use achievements::{Achievement, GameResult, Progress, ACHIEVEMENTS};
+// Import the persisted preferences, theme resolution, and accent styling
+use analytics::Insights;
+use animation::{CountTo, Fade, Gauge};
+use appearance::SystemTheme;
+use budget::GuessBudget;
+use card::{ResultCard, SaveState, Step};
+use changelog::Release;
+use closing::{CloseAction, QuitChoice};
+use daily::{Completions, Date, Month};
+use confetti::{Confetti, ConfettiFrame};
+use debug::RingBuffer;
+use endless::Records;
+use gallery::Gallery;
+use ghost::Ghost;
+use golf::Course;
+use wheel::WheelArea;
+use numberline::NumberLine;
+use onekey::OneKey;
+use skill::{Skill, Strength};
+use speech::{SpeechRate, VoiceChoice};
+use color::{proximity_tint, Rgb, ACCENT_SWATCHES};
+use focus::{Field, Setting, SettingKey};
+use gamelog::End;
+use hints::Hint;
+use lastplayed::{LastPlayed, PlayMode};
+use leaderboard::{Leaderboards, Metric, Placement};
+use fonts::FontChoice;
+use preferences::{IdleTimeout, MotionChoice, Preferences, Startup, ThemeChoice};
+use profiles::{Choice, Profiles};
+use rules::{MinChange, Outcome, WinRule};
+use swap::SurpriseSwap;
+use playback::{GameReplay, Playback, ReplayFileError};
+use scorecard::{Scorecard, ScorecardError};
+// Import challenge codes for sharing games
+use challenge::{Challenge, ChallengeError};
+// Import the bot that plays the computer-guesses mode
+use solver::{Answer, BotGame, BotStrategy};
+use style::{AccentButton, DayCell, DebugPanel, FocusRing, ProximityInput, Swatch};
 // Import the `Ordering` enum for comparing numbers
 use std::cmp::Ordering;
+// Import `Cow` so text helpers accept both borrowed and owned strings
+use std::borrow::Cow;
+// Import `Cell` for counting frames from `view`, which only borrows the game, and `RefCell` for timing it
+use std::cell::{Cell, RefCell};
+// Import `BTreeMap` for the sums guesses were typed as
+use std::collections::BTreeMap;
+// Import `PathBuf` for the recording and replay file arguments
+use std::path::{Path, PathBuf};
+// Import `Rc` so the latency stats can be updated while the game handles a message
+use std::rc::Rc;
+// Import `Duration` and `Instant` for polling intervals and game timing
+use std::time::{Duration, Instant};
+use thermometer::Thermometer;
+use trend::{Trend, TrendChart};
+use timer::Stopwatch;
+use tournament::{Round, Tournament};
+// Import the log filter configured through `RUST_LOG`
+use tracing_subscriber::EnvFilter;
+// Import the view model the game screen is drawn from
+use viewmodel::{CourseViewModel, GameViewModel, GradeExplanation, ReviewList, Severity, StatusBar, SummaryViewModel, TournamentViewModel};
+
+mod achievements; // Unlockable badges and lifetime stats
+mod analysis;    // Post-game grading of the player's strategy
+mod analytics;   // Opt-in local play statistics
+mod animation;   // Tick-driven numeric animations
+mod appearance;  // Choosing between the light and dark themes
+mod audio;       // Generated tones
+mod bench;       // Command-line benchmarks of the bot's strategies and the guess review
+mod budget;      // The session-wide guess budget
+mod card;        // Shareable result card images
+mod challenge;   // Shareable codes that recreate a game
+mod changelog;   // Release notes for the "what's new" panel
+mod closing;     // Confirming before closing on a game in progress
+mod color;       // Accent color parsing and contrast math
+mod confetti;    // Confetti falling when a game is won
+mod crash;       // Crash logs for panics
+mod daily;       // The daily challenge and the days it was solved
+mod debug;       // The developer overlay
+mod deduce;      // Deducing the player's number from statements about it
+mod desktop;     // Window manager features that vary by platform
+mod endless;     // Endless mode, a new secret straight after every win
+mod expression;  // Working out guesses typed as sums, such as "50+13"
+mod feedback;    // Feedback messages and their rendering
+mod focus;       // Tab order for the text fields and settings
+mod fonts;       // Bundled fonts the UI can be drawn in
+mod gallery;     // The demo gallery of the bots playing seeded secrets on a loop
+mod hints;       // Hints bought with points
+mod history;     // Building only the rows of a long guess review that are in view
+mod lastplayed;  // The configuration of the last game played, offered again
+mod latency;     // Developer stats on how long update and view take
+mod leaderboard; // Personal best boards by guesses and by time
+mod format;      // Locale-aware number formatting and parsing
+mod ghost;       // The pace of the fastest win, raced in later games
+mod gamelog;     // The readable log of games played
+mod golf;        // Golf scoring against par, over a course of holes
+mod mercy;       // Free hints volunteered after repeated wrong guesses
+mod migration;   // Versions of the saved files, and upgrading old ones
+mod notification; // Desktop notifications
+mod numberline;  // The clickable number line, greying out ruled-out numbers
+mod onekey;      // Playing with Space alone, for one-handed players
+mod passplay;    // Setting up games where one player picks the secret for another
+mod playback;    // Replay files of single rounds, and watching them
+mod practice;    // Drills over a part of the range typed in short
+mod preferences; // User preferences saved between runs
+mod profiles;    // Settings profiles that modes switch to
+mod recovery;    // Snapshots for recovering from a crash
+mod replay;      // Recording sessions and replaying them
+mod score;       // Points for a won game, and what hints cost
+mod scorecard;   // Exported results for friends to play and beat
+mod skill;       // Estimating the player's skill for assist mode's hints
+mod solver;      // The bot that guesses the player's number
+mod spectator;   // The feed viewers of a stream see, kept in a text file
+mod speech;      // Reading the feedback aloud
+mod stepper;     // Stepping the typed guess with the +/− buttons and the mouse wheel
+mod storage;     // Reading and writing files in the data directory
+mod style;       // Custom widget styles driven by the accent color
+mod swap;        // The surprise swap of the secret partway through a round
+mod thermometer; // The proximity thermometer beside the guess input
+mod timer;       // Pausable stopwatch for timing games
+mod tournament;  // Rounds played back to back with their scores added up
+mod trend;       // The trend chart of guesses per win over time
+mod viewmodel;   // What the game screen shows, decided apart from its widgets
+mod wheel;       // Turning the mouse wheel into a message over one widget
+
+// The inclusive range the secret number is drawn from
+const MIN_NUMBER: u32 = 1;
+const MAX_NUMBER: u32 = 100;
+
+// Window sizes for the full layout and the compact strip
+const FULL_WINDOW_SIZE: (u32, u32) = (1024, 768);
+const COMPACT_WINDOW_SIZE: (u32, u32) = (480, 70);
+
+// How often the game clock ticks while it is running
+const CLOCK_TICK_INTERVAL: Duration = Duration::from_millis(250);
+
+// How often to re-check the operating system's theme while following it
+const SYSTEM_THEME_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+// How long an achievement's unlock toast stays up
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+// How long a peek shows the secret for
+const PEEK_DURATION: Duration = Duration::from_millis(500);
+
+// How often to save a recovery snapshot while the game has changed since the last one
+const RECOVERY_SAVE_INTERVAL: Duration = Duration::from_secs(3);
 
 // Entry point of the application
 pub fn main() -> iced::Result {
-    // Run the `GuessingGame` application with default settings
-    GuessingGame::run(Settings::default())
+    // Make sure a panic leaves a trace, since there is no console on Windows
+    crash::install_panic_hook();
+    init_logging();
+
+    // Handle the command-line modes; on Windows, redirect their output to a file to see it,
+    // since the app has no console there
+    let mut launch = Launch::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(argument) = args.next() {
+        match argument.as_str() {
+            // Compare the bot's strategies instead of opening the window
+            "--bench-strategies" => {
+                bench::run(MIN_NUMBER, MAX_NUMBER);
+                return Ok(());
+            }
+            // Time building the guess review of a very long history instead of opening the window
+            "--bench-history" => {
+                bench::run_history(MIN_NUMBER, MAX_NUMBER);
+                return Ok(());
+            }
+            // Play a recorded session back without a window and check where it ends up
+            "--replay" => match args.next() {
+                Some(path) => run_replay(Path::new(&path)),
+                None => exit_with_error("--replay needs the recording to play"),
+            },
+            // Record every message of this session, for reproducing a bug
+            "--record" => match args.next() {
+                Some(path) => launch.record = Some(PathBuf::from(path)),
+                None => exit_with_error("--record needs a file to write to"),
+            },
+            // Open with the developer overlay showing, secret and all
+            "--debug" => launch.debug_overlay = true,
+            _ => {}
+        }
+    }
+
+    // Only the first copy opened saves; this one still plays, starting from the files as they are now
+    if storage::lock_data_dir() == storage::DirLock::HeldElsewhere {
+        tracing::warn!("Another copy of the game is running, so this one won't save");
+        launch.shared_data = true;
+    }
+
+    // Run the `GuessingGame` application, letting the window shrink down to the compact strip
+    GuessingGame::run(Settings {
+        flags: launch,
+        window: window::Settings {
+            min_size: Some(COMPACT_WINDOW_SIZE),
+            ..window::Settings::default()
+        },
+        exit_on_close_request: false, // Close the window ourselves, after tidying up
+        ..Settings::default()
+    })
+}
+
+// Feed a recording back through `update` as fast as possible and compare the final state
+//
+// Exits with status 1 if the state's digest differs from the recorded one, so a folder of
+// recordings can be replayed as regression tests.
+fn run_replay(path: &Path) -> ! {
+    let recording = match Recording::load(path, Instant::now()) {
+        Ok(recording) => recording,
+        Err(error) => exit_with_error(&format!("Could not load the recording: {}", error)),
+    };
+
+    // Stay out of the player's data directory altogether, reading nothing and writing nothing
+    storage::set_detached();
+
+    let length = recording.messages.last().map_or(Duration::ZERO, |&(elapsed, _)| elapsed);
+    let count = recording.messages.len();
+    let expected = recording.digest;
+    let digest = replay(recording);
+    println!("Replayed {} messages covering {}", count, format_duration(length));
+    println!("Final state digest: {:016x}", digest);
+
+    match expected {
+        Some(recorded) if recorded == digest => {
+            println!("Matches the recording");
+            std::process::exit(0)
+        }
+        Some(recorded) => {
+            println!("MISMATCH: the recording ended at {:016x}", recorded);
+            std::process::exit(1)
+        }
+        None => {
+            println!("The recorded session didn't exit cleanly, so there is no digest to compare");
+            std::process::exit(0)
+        }
+    }
+}
+
+// Play a recording's messages through a fresh game and return the digest it ends on
+//
+// The game starts from the recorded seed, locale and preferences, with nothing saved: no
+// records, profiles or game to restore, as on a first launch. A session that leaned on the
+// player's saved files (a profile, Same again) can end elsewhere when replayed.
+fn replay(recording: Recording) -> u64 {
+    let numbers = Box::new(SeededSource::new(u64::from(recording.seed)));
+    let mut game = GuessingGame::with_setup(numbers, recording.preferences, recording.locale, Saved::default());
+    game.replaying = true;
+
+    for (_, message) in recording.messages {
+        let _ = game.update(message); // Commands need the window, and don't change the state
+    }
+    game.digest()
+}
+
+// Send log output to stderr, filtered by `RUST_LOG`
+//
+// Only warnings are shown by default, so a normal run stays as quiet as before; set
+// `RUST_LOG=debug` to follow each guess and save. The GUI never reads stderr, so logging
+// can't get in its way.
+fn init_logging() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn"));
+    tracing_subscriber::fmt().with_env_filter(filter).with_writer(std::io::stderr).init();
+}
+
+// Report a command-line problem and stop
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(2)
 }
 
 // Define the main application structure
 struct GuessingGame {
-    secret_number: u32, // The randomly generated number the user needs to guess
+    secret_number: Secret, // The randomly generated number the user needs to guess, kept masked
+    operation: Option<MysteryOperation>, // This round's hidden operation, in the mystery mode
+    rule: Box<dyn WinRule>,              // How this round's guesses are judged
+    seed: u32,          // Seed the secret was derived from, shared through challenge codes
+    numbers: Box<dyn NumberSource>, // Where secret numbers are drawn from
     guess: String,      // The current guess input by the user
-    message: String,    // Feedback message displayed to the user
+    feedback: Feedback, // Feedback displayed to the user
+    locale: Locale,     // Number formatting conventions used for display and parsing
+    system_locale: Locale, // The conventions detected from the system, used unless the settings pick others
+    low: u32,           // Smallest value still consistent with the feedback so far
+    high: u32,          // Largest value still consistent with the feedback so far
+    attempts: u32,      // Number of valid guesses made this game
+    history: Vec<u32>,  // Every valid guess made this game, in order
+    guess_times: Vec<Option<Duration>>, // How long the player took over each guess in the history, where known
+    sums: BTreeMap<usize, String>, // What guesses typed as sums were typed as, by their place in the history
+    show_review: bool,  // Whether the post-game guess review is expanded
+    grade_breakdown: Option<analysis::GradeBreakdown>, // Why the game got its grade, while that's shown
+    reviews: Vec<analysis::StepReview>, // Each guess of the won game reviewed, worked out once when the review opens
+    review_offset: f32, // How far down the review is scrolled, from 0 to 1
+    won: bool,          // Whether the secret has been found, ending the game
+    proximity: Option<f32>, // How close the last wrong guess was, from 0.0 (far) to 1.0 (adjacent)
+    reveal: Option<CountTo>, // The counter running up to the secret at the end of a game
+    gauge: Gauge,       // The thermometer's level, gliding to each guess's proximity
+    hints: Vec<String>, // Hints given this game, bought or automatic, as shown to the player
+    hints_bought: usize, // How many of those were bought, which decides the next tier and its price
+    recent_wins: Vec<u32>, // Guesses taken by this session's latest wins that count, oldest first, for assist mode
+    points_spent: u32,  // Points spent on hints and the peek
+    hint_refused: bool, // Whether the last hint was refused for lack of points
+    peeked: bool,       // Whether this game's one peek has been used
+    warmup_used: bool,  // Whether this round's free warm-up guess has been taken
+    swapped: bool,      // Whether this round's secret has had its surprise swap
+    peek_until: Option<Instant>, // When the secret hides again, while a peek is showing it
+    wrong_guesses: u32, // Distinct wrong guesses this game, which automatic hints are counted on
+    mercy_given: usize, // Automatic hints volunteered this game
+    mercy_announcement: Option<String>, // The automatic hint the last guess brought, shown under the feedback
+    round: u32,         // Games started this session, numbering the log spans
+    round_span: tracing::Span, // Log span covering everything that happens in the current game
+    gave_up: bool,      // Whether the guesser gave up on a pass-and-play secret, ending the game
+    pass_and_play: bool, // Whether another player set this game's secret
+    retry: bool,        // Whether this game replays a secret already given up on, so it can't count
+    practice: Option<(u32, u32)>, // The part of the range this game drills, so it can't count either
+    pass_setup: Option<PassSetup>, // The pass-and-play setup screen, while it is open
+    stopwatch: Stopwatch, // Time spent on this game, excluding pauses
+    attempt_started: Duration, // The game clock's reading when the current guess's time began
+    thinking_since: Duration, // The game clock's reading at the last feedback, which a timeout doesn't move
+    timeouts: Vec<usize>, // Attempts this game wasted by running out of time, as how many guesses came before each
+    now: Instant,       // Time of the latest clock tick, used when drawing the timer
+    last_interaction: Instant, // When the player last did anything
+    budget: GuessBudget,      // Guesses left this session, spent only while the budget setting is on
+    idle_paused: bool,  // Whether the stopwatch was paused because the player went idle
+    idled: bool,        // Whether it paused for that at any point this game, keeping the win off the time board
+    preferences: Preferences, // Settings chosen by the user, saved whenever they change
+    system_theme: Option<SystemTheme>, // Last detected OS light/dark preference, if detection worked
+    system_accent: Option<Rgb>, // Last detected OS accent color, while the theme follows it and one could be read
+    system_reduce_motion: Option<bool>, // The OS reduce-motion setting at launch, if it could be read
+    show_settings: bool,      // Whether the settings panel is open
+    accent_input: String,     // Text in the hex accent field
+    speech: Option<Result<speech::Engine, String>>, // The speech engine once it's been started, or why it couldn't be
+    accent_input_invalid: bool, // Whether the last submitted hex value was rejected
+    window_focused: bool,     // Whether the game window currently has focus
+    bot_game: Option<BotGame>, // The computer-guesses round, while that mode is active
+    bot_contradiction: bool,  // Whether the player's last answer to the bot was impossible
+    constraints_input: String, // Statements about the player's number, typed for the computer to deduce from
+    deduction: Option<Result<deduce::Deduction, deduce::DeduceError>>, // What they left of the range, once asked
+    whats_new: Vec<Release>,  // Releases shown in the "what's new" panel; empty while it's closed
+    recovery_dirty: bool,     // Whether the game changed since the last recovery snapshot
+    pending_recovery: Option<Snapshot>, // A game left behind by a crash, until restored or declined
+    insights: Option<Insights>, // Play statistics shown in the insights panel, while it's open
+    challenge_input: String,  // Text in the challenge code field
+    challenge_error: Option<ChallengeError>, // Why the last pasted code was rejected
+    practice_input: String,   // Text in the practice range field
+    practice_error: Option<practice::RangeError>, // Why the last typed range was rejected
+    scorecard_input: String,  // Text in the scorecard path field
+    scorecard_error: Option<ScorecardError>, // Why the last scorecard couldn't be loaded
+    opponent: Option<Scorecard>, // The friend's result this game is played against, if one was loaded
+    tournament: Option<Tournament>, // The tournament this game is a round of, or that just finished
+    next_round_at: Option<Instant>, // When the next tournament round starts by itself, after a round is won
+    confetti: Option<Confetti>, // The celebration falling after a win, until it fades out
+    endless: Option<endless::Session>, // The endless session this game is part of, if one is on
+    course: Option<Course>,   // The golf course this game is a hole of, or that just finished
+    endless_records: Records, // Totals across every endless session played
+    scorecard_save: Option<SaveState>, // How exporting this game's scorecard went, once asked for
+    replay_save: Option<SaveState>, // How exporting this game's replay file went, once asked for
+    history_save: Option<SaveState>, // How exporting this game's history as CSV went, once asked for
+    show_replays: bool,       // Whether the panel for opening a replay file is open
+    replay_input: String,     // Text in the replay path field
+    replay_error: Option<ReplayFileError>, // Why the last replay file couldn't be opened
+    playback: Option<Playback>, // The replay being watched, which takes the game's place while open
+    challenge_copied: bool,   // Whether this game's code was just copied to the clipboard
+    recorder: Option<Recorder>, // Where every message goes when the session is being recorded
+    replaying: bool,          // Whether messages come from a recording rather than the player
+    focused_field: Option<Field>, // The text field last given focus by Tab or typed into
+    progress: Progress,       // Lifetime stats and unlocked achievements
+    toasts: Vec<(&'static Achievement, Instant)>, // Recent unlocks still on screen, with when they unlocked
+    show_achievements: bool,  // Whether the badges panel is open
+    trend_wins: Vec<gamelog::Win>, // Wins read from the game log for the trend chart, when the panel opened
+    trend_window: trend::Window, // How far back the trend chart goes
+    leaderboards: Leaderboards, // The best wins, by guesses and by time
+    ghost: Option<Ghost>,     // The pace of the fastest win, if one has been timed
+    one_key: OneKey,          // Where Space is in a tap, double-tap or hold, in one-key play
+    one_key_undo: String,     // The guess as it was before the last tap, put back if that tap starts a double-tap
+    profiles: Profiles,       // The settings profiles, and which mode switches to which
+    profile_mode: profiles::Mode, // The mode the settings panel is showing the profile of
+    leaderboard: Option<Metric>, // The board shown, while the leaderboard is open
+    gallery: Option<Gallery>, // The demo gallery, while it's being watched
+    board_changes: Vec<Placement>, // Where the latest win went on the boards, highlighted until the leaderboard next closes
+    board_fade: Option<Fade>, // The highlight's fade, from when the changes were first on screen
+    daily: Option<Date>,      // The day whose challenge is being played, if this game is one
+    completions: Completions, // The days the daily challenge was solved
+    calendar: Option<Month>,  // The month the streak calendar shows, while it's open
+    card_save: Option<SaveState>, // How saving this game's result card went, once asked for
+    last_played: Option<LastPlayed>, // The last game actually played, offered again with Same again
+    last_played_notice: Option<lastplayed::Fallback>, // Why that game couldn't be restored as it was
+    confirm_close: bool,      // Whether closing is waiting on the player to confirm
+    tournament_exit: Option<Box<Message>>, // What would end the tournament in progress, waiting on the player to confirm
+    confirm_reset: bool,      // Whether wiping the records is waiting on the player to confirm
+    reset_error: Option<String>, // Which records couldn't be deleted from disk by the last reset
+    debug_overlay: bool,      // Whether the developer overlay is showing
+    debug_voided: bool,       // Whether the overlay was shown during this game, so it can't count
+    time_refused: Option<leaderboard::Ineligible>, // Why this game's win was kept off the time-attack board, if it was
+    recent_messages: RingBuffer<&'static str>, // Names of the last few messages, for the overlay
+    updates: u64,             // Messages handled this session
+    frames: Cell<u64>,        // Times the window was laid out this session
+    latency: Option<Rc<RefCell<latency::Latency>>>, // Recent update and view timings, when asked for by the environment
+    newer_files: Vec<migration::NewerVersion>, // Saved files left alone because a newer version wrote them
+    shared_data: bool,        // Whether another copy of the game holds the data directory, so this one doesn't save
+    spectator_written: Option<String>, // What the spectator feed was last written with, to skip unchanged writes
+}
+
+// Options from the command line that the window needs when it opens
+#[derive(Debug, Default)]
+struct Launch {
+    record: Option<PathBuf>, // Where to record the session, if anywhere
+    debug_overlay: bool,     // Whether to open with the developer overlay showing
+    shared_data: bool,       // Whether another copy of the game is already running on the same files
 }
 
 // Define the different messages/events that can occur in the application
 #[derive(Debug, Clone)]
 pub enum Message {
     GuessInputChanged(String), // Triggered when the user changes the input in the text field
+    GuessStepped(i32),         // Triggered by the steppers or the wheel over the input, moving the guess by that much
+    NumberPicked(u32),         // Triggered by clicking a number on the number line, putting it in the input
     GuessButtonPressed,        // Triggered when the user presses the "Guess" button
+    HintRequested,             // Triggered when the user buys the next hint
+    PeekRequested,             // Triggered when the user spends the game's peek at the secret
+    NewGame,                   // Triggered when the user starts another game after winning
+    TournamentStarted(u32),    // Triggered when the user starts a tournament of that many rounds
+    TournamentAborted,         // Triggered when the user abandons the tournament part-way through
+    TournamentExitConfirmed(bool), // Triggered when the user answers whether to give up the tournament, `true` to leave
+    EndlessStarted,            // Triggered when the user starts an endless session
+    CourseStarted,             // Triggered when the user starts a golf course
+    CourseAborted,             // Triggered when the user walks off the course part-way through
+    EndlessStopped,            // Triggered when the user ends the endless session
+    ReviewToggled,             // Triggered when the user expands or collapses the guess review
+    GradeExplained,            // Triggered when the user opens the breakdown of their grade, or goes back from it
+    ReviewScrolled(f32),       // Triggered when the guess review is scrolled, with how far down it now is
+    ShowCandidatesToggled(bool), // Triggered when the user toggles the remaining-candidates readout
+    ShowBasesToggled(bool),    // Triggered when the user toggles the binary and hex readout
+    ThemeSelected(ThemeChoice), // Triggered when the user picks a theme
+    SystemThemePoll,           // Triggered periodically to re-check the OS theme
+    SystemThemeDetected(Option<SystemTheme>), // Triggered when an OS theme check finishes
+    SystemAccentDetected(Option<Rgb>), // Triggered when an OS accent color check finishes
+    SettingsToggled,           // Triggered when the user opens or closes the settings panel
+    AchievementsToggled,       // Triggered when the user opens or closes the badges panel
+    TrendWindowSelected(trend::Window), // Triggered when the user changes how far back the trend chart goes
+    LeaderboardToggled,        // Triggered when the user opens or closes the leaderboard
+    LeaderboardMetricSelected(Metric), // Triggered when the user switches which board is shown
+    ProfileModeSelected(profiles::Mode), // Triggered when the user picks which mode's profile to show
+    ProfileChosen(Choice),     // Triggered when the user sets the shown mode's profile
+    ProfileSaved,              // Triggered when the user saves the current settings into the shown mode's profile
+    DailyChallengeStarted(Date), // Triggered when the user starts the given day's challenge
+    CalendarToggled,           // Triggered when the user opens or closes the streak calendar
+    CalendarMonthMoved(bool),  // Triggered to show the next (true) or previous (false) month
+    AccentSelected(Rgb),       // Triggered when the user clicks an accent swatch
+    AccentHexChanged(String),  // Triggered when the user edits the hex accent field
+    AccentHexSubmitted,        // Triggered when the user presses Enter in the hex accent field
+    NotifyOnWinToggled(bool),  // Triggered when the user toggles win notifications
+    SpeakFeedbackToggled(bool), // Triggered when the user toggles reading the feedback aloud
+    SpeechRateSelected(SpeechRate), // Triggered when the user picks how fast the feedback is read
+    SpeechVoiceSelected(VoiceChoice), // Triggered when the user picks the voice the feedback is read in
+    FeedbackSpoken(Result<(), String>), // Triggered when the engine has taken an announcement, or failed to
+    WinNotificationAnswered(bool), // Triggered when the win notification goes away, `true` if it was clicked
+    MysteryModeToggled(bool),  // Triggered when the user toggles the mystery operation mode
+    TimedGuessesToggled(bool), // Triggered when the user toggles the per-guess time limit
+    GhostRaceToggled(bool),    // Triggered when the user toggles racing the fastest win's ghost
+    GuessBudgetToggled(bool),  // Triggered when the user toggles the session guess budget
+    MercyHintsToggled(bool),   // Triggered when the user toggles automatic hints
+    AssistHintsToggled(bool),  // Triggered when the user toggles assist mode's adaptive hints
+    GuessAssistToggled(bool),  // Triggered when the user toggles greying out ruled-out numbers
+    CloseEnoughToggled(bool),  // Triggered when the user toggles close guesses winning
+    ProximityTonesToggled(bool), // Triggered when the user toggles the proximity tones
+    ChallengeCodeCopied,       // Triggered when the user copies the current game's challenge code
+    ChallengeInputChanged(String), // Triggered when the user edits the challenge code field
+    ChallengeSubmitted,        // Triggered when the user plays the pasted challenge code
+    PracticeInputChanged(String), // Triggered when the user edits the practice range field
+    PracticeSubmitted,         // Triggered when the user starts a drill over the typed range
+    ScorecardPathChanged(String), // Triggered when the user edits the path of a friend's scorecard
+    ScorecardImported,         // Triggered when the user loads that scorecard to play its game
+    ScorecardExportRequested,  // Triggered when the user exports a scorecard for the won game
+    ScorecardExported(Option<PathBuf>), // Triggered once the scorecard is written, with where, or `None` if it failed
+    ReplayExportRequested,     // Triggered when the user exports a replay file of the finished game
+    ReplayExported(Option<PathBuf>), // Triggered once the replay file is written, with where, or `None` if it failed
+    HistoryExportRequested,    // Triggered when the user exports the finished game's history as CSV
+    HistoryExported(Option<PathBuf>), // Triggered once the CSV file is written, with where, or `None` if it failed
+    ReplaysToggled,            // Triggered when the user opens or closes the panel for opening replay files
+    ReplayPathChanged(String), // Triggered when the user edits the path of a replay file
+    ReplayOpened,              // Triggered when the user opens that replay file to watch it
+    PlaybackStepped(bool),     // Triggered when the user steps the replay forward (true) or back (false)
+    PlaybackToggled,           // Triggered when the user plays or pauses the replay
+    PlaybackAdvanced,          // Triggered periodically while the replay plays by itself
+    PlaybackClosed,            // Triggered when the user stops watching the replay
+    GalleryOpened,             // Triggered when the user opens the demo gallery
+    GalleryAdvanced,           // Triggered periodically while the demo gallery plays
+    GalleryClosed,             // Triggered when the user leaves the demo gallery
+    AnalyticsToggled(bool),    // Triggered when the user opts in or out of play statistics
+    AnalyticsDeleted,          // Triggered when the user deletes the recorded statistics
+    RecordsResetRequested,     // Triggered when the user asks to wipe the leaderboard, achievements and endless records
+    RecordsResetConfirmed(bool), // Triggered when the user answers whether to wipe them, `true` to go ahead
+    InsightsToggled,           // Triggered when the user opens or closes the insights panel
+    WindowFocusChanged(bool),  // Triggered when the window gains or loses focus
+    FontSelected(FontChoice),  // Triggered when the user picks a UI font
+    NumberFormatSelected(LocaleChoice), // Triggered when the user picks how numbers are grouped
+    MonospaceDigitsToggled(bool), // Triggered when the user toggles monospaced numbers
+    ColorblindToggled(bool),   // Triggered when the user toggles colorblind-friendly colors
+    AlwaysOnTopToggled(bool),  // Triggered when the user toggles keeping the window on top
+    AlwaysOnTopShortcut,       // Triggered by the Ctrl+Shift+P shortcut for always-on-top
+    CompactToggled(bool),      // Triggered when the user switches the compact layout on or off
+    CompactShortcut,           // Triggered by the Ctrl+Shift+M shortcut for the compact layout
+    LeftHandedToggled(bool),   // Triggered when the user toggles the mirrored layout
+    OneKeyToggled(bool),       // Triggered when the user toggles playing with Space alone
+    OneKeyTimingSelected(onekey::Timing), // Triggered when the user picks how quick one-key taps and holds are
+    OneKeyPressed(Instant),    // Triggered when Space goes down in one-key play, or repeats while held
+    OneKeyReleased(Instant),   // Triggered when Space comes back up in one-key play
+    IdleTimeoutSelected(IdleTimeout), // Triggered when the user picks the idle pause threshold
+    StartupSelected(Startup),  // Triggered when the user picks whether to start fresh or resume on launch
+    MinChangeSelected(MinChange), // Triggered when the user picks how far each guess must move
+    SurpriseSwapSelected(SurpriseSwap), // Triggered when the user picks when the secret swaps, if ever
+    MotionSelected(MotionChoice), // Triggered when the user picks animated or instant feedback
+    PassAndPlayOpened,         // Triggered when the user starts setting up a pass-and-play game
+    PassSecretChanged(String), // Triggered when the setter edits the hidden secret
+    PassSecretSubmitted,       // Triggered when the setter confirms the secret
+    PassReady,                 // Triggered when the guesser has the device and starts the game
+    PassCancelled,             // Triggered when the user backs out of the pass-and-play setup
+    ResultCardRequested,       // Triggered when the user saves a result card for the won game
+    ResultCardSaved(Option<PathBuf>), // Triggered once the card is written, with where, or `None` if it failed
+    WarmUpGuessed,             // Triggered when the round's free warm-up guess is made
+    GaveUp,                    // Triggered when the guesser gives up on a pass-and-play secret
+    RetryRequested,            // Triggered when the user replays the secret they just gave up on
+    BotModeToggled(bool),      // Triggered when the user enters or leaves the computer-guesses mode
+    BotAnswered(Answer),       // Triggered when the user answers one of the bot's guesses
+    BotStrategySelected(BotStrategy), // Triggered when the user picks the bot's difficulty
+    NewBotGame,                // Triggered when the user starts another round for the bot
+    ConstraintsChanged(String), // Triggered when the user edits the statements for the computer to deduce from
+    ConstraintsSubmitted,      // Triggered when the user asks the computer to deduce their number from them
+    SameAgain,                 // Triggered when the user starts the same kind of game as last time
+    WhatsNewOpened,            // Triggered when the user reopens the release notes from settings
+    WhatsNewDismissed,         // Triggered when the user closes the "what's new" panel
+    RecoveryAutosave,          // Triggered periodically to save a recovery snapshot
+    SpectatorRefresh,          // Triggered periodically to bring the spectator feed up to date
+    SpectatorFeedToggled(bool), // Triggered when the user toggles the spectator feed
+    RecoveryRestored,          // Triggered when the user restores the game left behind by a crash
+    RecoveryDeclined,          // Triggered when the user throws that game away
+    CloseRequested,            // Triggered when the user closes the window
+    CloseConfirmed(QuitChoice), // Triggered when the user answers whether to close mid-game
+    FocusMoved(bool),          // Triggered by Tab (true) or Shift+Tab (false) to move between fields
+    SettingKey(SettingKey),    // Triggered by Space or an arrow key, acting on the focused setting
+    DebugOverlayToggled,       // Triggered by Ctrl+Shift+D to show or hide the developer overlay
+    Tick(Instant),             // Triggered periodically while the game clock runs
 }
 
-// Implement the `Application` trait for `GuessingGame`
-impl Application for GuessingGame {
-    // Specify the executor type for handling asynchronous tasks
-    type Executor = iced::executor::Default;
-    // Define the type of messages the application will handle
-    type Message = Message;
-    // Define the theme for the application
-    type Theme = Theme; // Ensure Theme is imported correctly
-    // Define any flags that might be passed when initializing the application
-    type Flags = ();
+impl Message {
+    // Whether the message comes from the player rather than a timer or the window system
+    fn is_interaction(&self) -> bool {
+        !matches!(
+            self,
+            Message::Tick(_)
+                | Message::SystemThemePoll
+                | Message::SystemThemeDetected(_)
+                | Message::SystemAccentDetected(_)
+                | Message::WindowFocusChanged(_)
+                | Message::RecoveryAutosave
+                | Message::SpectatorRefresh
+                | Message::PlaybackAdvanced
+                | Message::GalleryAdvanced
+                | Message::FeedbackSpoken(_)
+                | Message::WinNotificationAnswered(_)
+                | Message::CloseRequested
+        )
+    }
 
-    // Method to initialize the application
-    fn new(_flags: Self::Flags) -> (Self, Command<Self::Message>) {
-        // Generate a random secret number between 1 and 100
-        let secret_number = rand::thread_rng().gen_range(1..=100);
-        (
-            GuessingGame {
-                secret_number,
-                guess: String::new(),                        // Initialize guess as an empty string
-                message: String::from("Welcome to the Guessing Game!"), // Initial welcome message
-            },
-            Command::none(), // No initial commands to run
+    // Whether the message sets up another game over the current one, losing a tournament in progress
+    fn ends_tournament(&self) -> bool {
+        matches!(
+            self,
+            Message::TournamentAborted
+                | Message::CourseStarted
+                | Message::DailyChallengeStarted(_)
+                | Message::ChallengeSubmitted
+                | Message::PracticeSubmitted
+                | Message::SameAgain
+                | Message::PassAndPlayOpened
+                | Message::ScorecardImported
+                | Message::RecoveryRestored
         )
     }
+}
 
-    // Method to set the window title
-    fn title(&self) -> String {
-        String::from("Guessing Game") // Title displayed on the application window
+// Everything the game reads back from the data directory when it starts
+#[derive(Default)]
+struct Saved {
+    recovery: Option<Snapshot>,                       // A game left behind by a crash
+    last_played: Option<LastPlayed>,                  // The last game actually played
+    last_played_notice: Option<lastplayed::Fallback>, // Why that game couldn't be restored as it was
+    endless_records: Records,                         // Totals across every endless session
+    progress: Progress,                               // Lifetime stats and unlocked achievements
+    leaderboards: Leaderboards,                       // The best wins
+    ghost: Option<Ghost>,                             // The pace of the fastest timed win
+    profiles: Profiles,                               // The settings profiles
+    completions: Completions,                         // The days the daily challenge was solved
+    newer_files: Vec<migration::NewerVersion>,        // Files left alone because a newer version wrote them
+}
+
+impl Saved {
+    // Read every saved file, falling back to an empty one for each that's missing or unreadable
+    fn load() -> Saved {
+        // Offer the last game played again, with a notice if it had to fall back
+        let (last_played, last_played_notice) = match LastPlayed::load(MIN_NUMBER, MAX_NUMBER) {
+            Some((last, fallback)) => (Some(last), fallback),
+            None => (None, None),
+        };
+        if let Some(fallback) = last_played_notice {
+            tracing::warn!(?fallback, "The last played configuration could not be restored as it was");
+        }
+
+        Saved {
+            // A game left behind by a crash, ignoring one from a different range
+            recovery: recovery::load().filter(|snapshot| snapshot.low >= MIN_NUMBER && snapshot.high <= MAX_NUMBER),
+            last_played,
+            last_played_notice,
+            endless_records: Records::load(),
+            progress: Progress::load(),
+            leaderboards: Leaderboards::load(),
+            ghost: Ghost::load(),
+            profiles: Profiles::load(),
+            completions: Completions::load(),
+            newer_files: migration::newer_files(), // Last, once every file above has been read
+        }
     }
+}
 
-    // Method to handle updates based on incoming messages/events
-    fn update(&mut self, message: Message) -> Command<Message> {
-        match message {
-            // Handle changes in the guess input field
-            Message::GuessInputChanged(value) => {
-                self.guess = value; // Update the current guess with the new input
+impl GuessingGame {
+    // Create the game, drawing secrets from the given source
+    fn with_number_source(numbers: Box<dyn NumberSource>) -> GuessingGame {
+        GuessingGame::with_setup(numbers, Preferences::load(), Locale::from_env(), Saved::load())
+    }
+
+    // Create the game with the given secrets, preferences, number conventions and saved files
+    fn with_setup(mut numbers: Box<dyn NumberSource>, preferences: Preferences, locale: Locale, saved: Saved) -> GuessingGame {
+        // Draw a seed and derive the secret from it, so the game can be shared as a code
+        let seed = numbers.secret(0, u32::MAX);
+        let secret_number = Secret::new(seeded_secret(seed, MIN_NUMBER, MAX_NUMBER));
+
+        // Detect the OS theme up front so the first frame already uses the right one
+        let system_theme = appearance::detect_system_theme();
+        if system_theme.is_none() {
+            tracing::warn!("Could not detect the system theme, defaulting to light");
+        }
+
+        // Read the OS accent too when the theme is built around it, falling back to the player's own
+        let system_accent = (preferences.theme == ThemeChoice::SystemAccent).then(appearance::detect_accent_color).flatten();
+        if preferences.theme == ThemeChoice::SystemAccent && system_accent.is_none() {
+            tracing::warn!("Could not read the system accent color, using the chosen accent instead");
+        }
+
+        // Pre-fill the hex field with the saved accent
+        let accent_input = preferences.accent.to_hex();
+
+        // Start the speech engine now only if it's going to be needed
+        let speech_engine = preferences.speak_feedback.then(speech::Engine::start);
+        let operation = preferences.mystery_mode.then(|| MysteryOperation::random(numbers.as_mut()));
+        let rule = rules::for_mode(operation, preferences.close_enough);
+
+        // Show what changed since the last version the user dismissed the notes for
+        let whats_new = changelog::unseen(preferences.last_seen_version.as_deref());
+
+        GuessingGame {
+            secret_number,
+            operation,
+            rule,
+            seed,
+            numbers,
+            guess: String::new(), // Initialize guess as an empty string
+            feedback: Feedback::Welcome { min: MIN_NUMBER, max: MAX_NUMBER }, // Initial welcome message
+            locale: preferences.number_format.resolve(locale), // The system's way, unless the settings say otherwise
+            system_locale: locale,
+            low: MIN_NUMBER,            // Every value in the range starts out possible
+            high: MAX_NUMBER,
+            attempts: 0,
+            history: Vec::new(),
+            guess_times: Vec::new(),
+            sums: BTreeMap::new(),
+            show_review: false,
+            grade_breakdown: None,
+            reviews: Vec::new(),
+            review_offset: 0.0,
+            won: false,
+            proximity: None, // Neutral until the first guess
+            reveal: None,
+            gauge: Gauge::default(), // Empty until the first guess
+            hints: Vec::new(),
+            hints_bought: 0,
+            recent_wins: Vec::new(),
+            points_spent: 0,
+            hint_refused: false,
+            peeked: false,
+            warmup_used: false,
+            swapped: false,
+            peek_until: None,
+            wrong_guesses: 0,
+            mercy_given: 0,
+            mercy_announcement: None,
+            round: 1,
+            round_span: tracing::info_span!(
+                "round",
+                number = 1,
+                difficulty = %analysis::difficulty_label(MIN_NUMBER, MAX_NUMBER),
+                mystery = operation.is_some()
+            ),
+            gave_up: false,
+            pass_and_play: false,
+            retry: false,
+            practice: None,
+            pass_setup: None,
+            stopwatch: Stopwatch::default(),
+            attempt_started: Duration::ZERO,
+            thinking_since: Duration::ZERO,
+            timeouts: Vec::new(),
+            now: Instant::now(),
+            last_interaction: Instant::now(),
+            idle_paused: false,
+            idled: false,
+            preferences,
+            system_theme,
+            system_accent,
+            system_reduce_motion: appearance::detect_reduced_motion(),
+            show_settings: false,
+            accent_input,
+            speech: speech_engine,
+            accent_input_invalid: false,
+            window_focused: true, // The window starts out in the foreground
+            bot_game: None,       // Start in the classic mode where the player guesses
+            bot_contradiction: false,
+            constraints_input: String::new(),
+            deduction: None,
+            whats_new,
+            recovery_dirty: false,
+            pending_recovery: saved.recovery,
+            insights: None,
+            challenge_input: String::new(),
+            challenge_error: None,
+            practice_input: String::new(),
+            practice_error: None,
+            scorecard_input: String::new(),
+            scorecard_error: None,
+            opponent: None,
+            tournament: None,
+            next_round_at: None,
+            confetti: None,
+            endless: None,
+            course: None,
+            endless_records: saved.endless_records,
+            scorecard_save: None,
+            replay_save: None,
+            history_save: None,
+            show_replays: false,
+            replay_input: String::new(),
+            replay_error: None,
+            playback: None,
+            challenge_copied: false,
+            recorder: None,
+            replaying: false,
+            focused_field: Some(Field::Guess), // Focused as soon as the window opens
+            progress: saved.progress,
+            budget: GuessBudget::default(),
+            toasts: Vec::new(),
+            show_achievements: false,
+            trend_wins: Vec::new(),
+            trend_window: trend::Window::default(),
+            leaderboards: saved.leaderboards,
+            ghost: saved.ghost,
+            one_key: OneKey::default(),
+            one_key_undo: String::new(),
+            profiles: saved.profiles,
+            profile_mode: profiles::Mode::default(),
+            leaderboard: None,
+            gallery: None,
+            board_changes: Vec::new(),
+            board_fade: None,
+            daily: None,
+            completions: saved.completions,
+            calendar: None,
+            card_save: None,
+            last_played: saved.last_played,
+            last_played_notice: saved.last_played_notice,
+            confirm_close: false,
+            tournament_exit: None,
+            confirm_reset: false,
+            reset_error: None,
+            debug_overlay: false,
+            debug_voided: false,
+            time_refused: None,
+            recent_messages: RingBuffer::new(debug::RECENT_MESSAGES),
+            updates: 0,
+            frames: Cell::new(0),
+            latency: latency::enabled().then(Rc::default),
+            newer_files: saved.newer_files,
+            shared_data: false,
+            spectator_written: None,
+        }
+    }
+
+    // Count the integers still consistent with every piece of feedback given so far
+    fn candidates_left(&self) -> u32 {
+        self.high - self.low + 1 // The bounds are inclusive and never cross
+    }
+
+    // Whether to show every change instantly; every animation must check this before playing
+    fn reduce_motion(&self) -> bool {
+        self.preferences.motion.reduce_motion(self.system_reduce_motion)
+    }
+
+    // Give the automatic hint that has fallen due, if mercy hints are on
+    fn volunteer_mercy_hint(&mut self) {
+        if !self.preferences.mercy_hints {
+            return;
+        }
+
+        if let Some(hint) = mercy::due(self.wrong_guesses, self.mercy_given) {
+            let text = hint.text(&self.secret_number, self.locale);
+            self.hints.push(format!("{} (automatic)", text)); // Listed with the bought hints
+            self.mercy_announcement = Some(text);
+            self.mercy_given += 1;
+            gamelog::record(gamelog::Event::Hinted { free: true, hint: hint.key() });
+            tracing::debug!(?hint, wrong_guesses = self.wrong_guesses, "Automatic hint given");
+        }
+    }
+
+    // Count from `from` up or down to the secret in the feedback area, unless motion is reduced
+    fn start_reveal(&mut self, from: u32) {
+        if !self.reduce_motion() {
+            let now = Instant::now();
+            self.now = now; // Draw the first frame from the start rather than a stale tick
+            self.reveal = Some(CountTo::new(from, self.secret_number.reveal(), now));
+            self.confetti = Some(Confetti::burst(self.seed ^ self.attempts, now));
+        }
+    }
+
+    // The font chosen for UI text
+    fn font(&self) -> Font {
+        self.preferences.font.font()
+    }
+
+    // The font for numeric displays, monospaced when the user wants digits easy to tell apart
+    fn digits_font(&self) -> Font {
+        if self.preferences.monospace_digits {
+            fonts::MONO
+        } else {
+            self.font()
+        }
+    }
+
+    // Create text in the chosen font
+    fn text<'a>(&self, content: impl Into<Cow<'a, str>>) -> Text<'a> {
+        Text::new(content).font(self.font())
+    }
+
+    // Create text that is mostly numbers, in the numeric font
+    fn digits_text<'a>(&self, content: impl Into<Cow<'a, str>>) -> Text<'a> {
+        Text::new(content).font(self.digits_font())
+    }
+
+    // Create the text input field for the user's guess, tinted by how close the last guess was
+    fn guess_input(&self, model: &GameViewModel) -> TextInput<Message> {
+        let input = TextInput::new("Enter your guess...", &self.guess); // Placeholder and current value
+        let input = match model.input_tint {
+            Some(tint) => input.style(style::custom_text_input(ProximityInput(tint))),
+            None => input,
+        };
+
+        input
+            .id(Field::Guess.id())                // Let Tab and the startup focus find it
+            .on_input(Message::GuessInputChanged) // Define the message to send on input change
+            .padding(10)                          // Add padding inside the text field
+            .size(20)                             // Set the font size
+            .font(self.digits_font())             // Draw the digits in the chosen numeric font
+    }
+
+    // Flank the guess input with − and + steppers, and let the wheel step it while the cursor is over it
+    fn stepped_guess_input(&self, model: &GameViewModel) -> Element<Message> {
+        let stepper = |label: &'static str, by: i32| {
+            let button = Button::new(self.digits_text(label)).padding([10, 12]);
+            if model.game_over { button } else { button.on_press(Message::GuessStepped(by)) }
+        };
+        let input: Element<Message> = WheelArea::new(self.guess_input(model), |notches, shift| {
+            Message::GuessStepped(notches * stepper::stride(shift) as i32)
+        })
+        .into();
+
+        // Explain the bounds over the input while what's typed falls outside them
+        let input = match &model.range_tip {
+            Some(tip) => Tooltip::new(input, tip, tooltip::Position::Top)
+                .font(self.font())
+                .gap(6)
+                .padding(6)
+                .style(iced::theme::Container::Box)
+                .into(),
+            None => input,
+        };
+
+        Row::new()
+            .push(stepper("−", -(stepper::STEP as i32)))
+            .push(input)
+            .push(stepper("+", stepper::STEP as i32))
+            .spacing(4)
+            .align_items(Alignment::Center)
+            .into()
+    }
+
+    // Create the button that submits the guess, filled with the accent color
+    fn guess_button(&self, model: &GameViewModel) -> Button<Message> {
+        let guess_button = Button::new(self.text("Guess"))
+            .style(style::custom_button(AccentButton(self.accent())));
+
+        if model.guess_enabled {
+            guess_button.on_press(Message::GuessButtonPressed)
+        } else {
+            guess_button
+        }
+    }
+
+    // Lay out elements from left to right, or right to left for left-handed players
+    fn mirrored_row<'a>(&self, mut children: Vec<Element<'a, Message>>) -> Row<'a, Message> {
+        if self.preferences.left_handed {
+            children.reverse();
+        }
+        Row::with_children(children)
+    }
+
+    // Lay out the compact strip: input, Guess button, and a one-line readout
+    fn compact_view(&self) -> Element<Message> {
+        let model = GameViewModel::new(self);
+
+        // Offer a new game in place of the disabled Guess button once the game is over
+        let action = if model.game_over {
+            Button::new(self.text("Play again")).on_press(Message::NewGame)
+        } else {
+            self.guess_button(&model)
+        };
+        let readout = self.digits_text(model.compact_readout.clone());
+
+        // A small button to get back to the full layout without remembering the shortcut
+        let expand = Button::new(self.text("Expand")).on_press(Message::CompactToggled(false));
+
+        let strip = self
+            .mirrored_row(vec![
+                self.guess_input(&model).width(Length::Fixed(140.0)).into(),
+                action.into(),
+                readout.into(),
+                expand.into(),
+            ])
+            .spacing(8)
+            .padding(8)
+            .align_items(Alignment::Center);
+
+        Container::new(strip)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_y()
+            .into()
+    }
+
+    // Reset everything about the current game and draw a fresh secret
+    fn start_new_game(&mut self) {
+        let seed = self.numbers.secret(0, u32::MAX);
+        self.start_seeded_game(seed);
+    }
+
+    // Reset everything about the current game and derive the secret from `seed`
+    fn start_seeded_game(&mut self, seed: u32) {
+        self.reset_game(seed);
+        self.announce_game();
+    }
+
+    // Start another try at the secret just given up on, with the same range and operation
+    fn start_retry(&mut self) {
+        let (secret, operation, pass_and_play) = (self.secret_number, self.operation, self.pass_and_play);
+        self.reset_game(self.seed);
+        self.secret_number = secret; // A pass-and-play secret was typed, so the seed can't recreate it
+        self.operation = operation;
+        self.rule = rules::for_mode(operation, self.preferences.close_enough);
+        self.pass_and_play = pass_and_play;
+        self.retry = true;
+        self.announce_game();
+    }
+
+    // Clear the previous game and draw the secret for `seed`, without announcing the new game
+    fn reset_game(&mut self, seed: u32) {
+        // Walking away from a game part-way through ends the winning streak, unless it couldn't
+        // count anyway; nor does leaving one behind for the pass-and-play setup
+        if !self.won && !self.history.is_empty() && self.can_count() && self.pass_setup.is_none() {
+            self.progress.record_abandoned();
+        }
+        self.log_abandoned();
+
+        // Leaving an endless session any way but rolling over to its next secret ends it,
+        // counting the guesses made on the secret in play before they're cleared
+        if let Some(session) = self.endless.take() {
+            tracing::info!(solved = session.solved, guesses = session.total_guesses(self.attempts), "Endless session ended");
+            self.endless_records.record(session, self.attempts);
+        }
+
+        self.seed = seed;
+        self.secret_number = Secret::new(seeded_secret(seed, MIN_NUMBER, MAX_NUMBER));
+        self.challenge_copied = false;
+        self.operation = self.preferences.mystery_mode.then(|| MysteryOperation::random(self.numbers.as_mut()));
+        self.rule = rules::for_mode(self.operation, self.preferences.close_enough);
+        self.guess.clear();
+        self.feedback = Feedback::Welcome { min: MIN_NUMBER, max: MAX_NUMBER };
+        self.low = MIN_NUMBER;
+        self.high = MAX_NUMBER;
+        self.attempts = 0;
+        self.timeouts.clear();
+        self.attempt_started = Duration::ZERO;
+        self.thinking_since = Duration::ZERO; // The first guess is timed from the start of the game
+        self.history.clear();
+        self.guess_times.clear();
+        self.sums.clear();
+        self.show_review = false;
+        self.grade_breakdown = None;
+        self.reviews.clear();
+        self.review_offset = 0.0;
+        self.won = false;
+        self.gave_up = false;
+        self.pass_and_play = false;
+        self.retry = false;
+        self.practice = None;
+        self.reveal = None;
+        self.confetti = None; // A new round starts clean, even mid-burst
+        self.gauge = Gauge::default();
+        self.proximity = None;
+        self.hints.clear();
+        self.hints_bought = 0;
+        self.points_spent = 0;
+        self.hint_refused = false;
+        self.peeked = false;
+        self.warmup_used = false;
+        self.swapped = false;
+        self.peek_until = None;
+        self.wrong_guesses = 0;
+        self.mercy_given = 0;
+        self.mercy_announcement = None;
+        self.stopwatch.reset();
+        self.idle_paused = false;
+        self.idled = false;
+        self.debug_voided = self.debug_overlay; // Left open, it shows the new secret too
+        self.time_refused = None;
+        self.daily = None; // Set again by the caller when this is a daily challenge
+        self.card_save = None;
+        self.scorecard_save = None;
+        self.replay_save = None;
+        self.history_save = None;
+        self.opponent = None; // Set again by the caller when a scorecard was loaded
+        self.tournament = None; // Put back by the caller when this is the next round
+        self.next_round_at = None;
+        self.course = None; // Put back by the caller when this is the next hole
+    }
+
+    // Start a drill over `low..=high`, drawing the secret from there and narrowing the bounds to match
+    fn start_practice(&mut self, low: u32, high: u32) {
+        let seed = self.numbers.secret(0, u32::MAX);
+        self.reset_game(seed);
+        self.secret_number = Secret::new(seeded_secret(seed, low, high));
+        self.feedback = Feedback::Welcome { min: low, max: high };
+        self.low = low;
+        self.high = high;
+        self.practice = Some((low, high));
+        self.announce_game();
+    }
+
+    // Start the tournament's next round, keeping its results so far
+    fn start_tournament_round(&mut self) {
+        let tournament = self.tournament.take();
+        let seed = self.numbers.secret(0, u32::MAX);
+        self.reset_game(seed);
+        self.tournament = tournament;
+        self.announce_game();
+    }
+
+    // Start the course's next hole, keeping the scorecard so far
+    fn start_course_hole(&mut self) {
+        let course = self.course.take();
+        let seed = self.numbers.secret(0, u32::MAX);
+        self.reset_game(seed);
+        self.course = course;
+        self.announce_game();
+    }
+
+    // Bank the secret just found in the endless session and draw the next one straight away,
+    // keeping the session's counters while everything about the round starts afresh
+    fn start_endless_round(&mut self) {
+        let Some(mut session) = self.endless.take() else {
+            return;
+        };
+        let secret = self.secret_number.reveal();
+        session.solve(self.attempts);
+
+        let seed = self.numbers.secret(0, u32::MAX);
+        self.reset_game(seed);
+        self.endless = Some(session);
+        self.feedback = Feedback::Solved { secret, solved: session.solved };
+        self.announce_game();
+    }
+
+    // Record and log the start of the game just set up
+    fn announce_game(&mut self) {
+        self.apply_profile();
+        self.track(analytics::Event::GameStarted(self.mode_key()));
+
+        self.open_round_span();
+        self.round_span.in_scope(|| tracing::info!("Game started"));
+        self.log_started();
+    }
+
+    // Group the current game's log lines under a span of their own
+    fn open_round_span(&mut self) {
+        self.round += 1;
+        self.round_span = tracing::info_span!(
+            "round",
+            number = self.round,
+            difficulty = %analysis::difficulty_label(MIN_NUMBER, MAX_NUMBER),
+            mystery = self.operation.is_some()
+        );
+    }
+
+    // Switch to the settings profile the current mode is set to, if it has one
+    fn apply_profile(&mut self) {
+        let Some(settings) = profiles::Mode::from_key(self.mode_key()).and_then(|mode| self.profiles.settings_for(mode)) else {
+            return;
+        };
+        if settings != profiles::Settings::of(&self.preferences) {
+            tracing::info!(mode = self.mode_key(), "Switched to the mode's settings profile");
+            self.update_preferences(|preferences| settings.apply(preferences));
+            self.rule = rules::for_mode(self.operation, self.preferences.close_enough); // Nothing is guessed yet
+        }
+    }
+
+    // The speech engine, if it's running
+    fn speech_engine(&self) -> Option<&speech::Engine> {
+        self.speech.as_ref()?.as_ref().ok()
+    }
+
+    // Read the feedback aloud when that's on, as the screen words it, from an async command
+    // so the window never waits on the engine
+    fn speak_feedback(&self) -> Command<Message> {
+        let Some(engine) = self.speech_engine().filter(|_| self.preferences.speak_feedback && !self.replaying) else {
+            return Command::none();
+        };
+        let mut text = speech::spoken(&self.feedback.render(self.locale));
+        if self.won {
+            let noun = if self.attempts == 1 { "guess" } else { "guesses" };
+            text = format!("{} {} {}.", text, format_number(self.locale, self.attempts), noun);
+        }
+        let (rate, voice) = (self.preferences.speech_rate, self.preferences.speech_voice.clone());
+        Command::perform(speech::say(engine.clone(), text, rate, voice), Message::FeedbackSpoken)
+    }
+
+    // Note the start of the current game in the game log
+    fn log_started(&self) {
+        let (min, max) = self.practice.unwrap_or((MIN_NUMBER, MAX_NUMBER));
+        gamelog::record(gamelog::Event::Started { mode: self.mode_key(), min, max });
+    }
+
+    // Note the end of the current game in the game log
+    fn log_ended(&self, end: End) {
+        gamelog::record(gamelog::Event::Ended { end, secret: self.secret_number.reveal(), attempts: self.attempts });
+    }
+
+    // Note the current game as abandoned, if it was started and hasn't ended
+    fn log_abandoned(&self) {
+        if !self.won && !self.gave_up && !self.history.is_empty() {
+            self.log_ended(End::Abandoned);
+        }
+    }
+
+    // Lay out the quick-start controls: copy this game's code, paste one to play, or drill part of the range
+    fn challenge_view(&self) -> Element<Message> {
+        // A code only carries the full range, so it can't recreate a drill's secret
+        let mut copy_row = Row::new().spacing(10).align_items(Alignment::Center);
+        if self.practice.is_none() {
+            copy_row = copy_row.push(Button::new(self.text("Copy challenge code")).on_press(Message::ChallengeCodeCopied));
+        }
+        if self.challenge_copied {
+            copy_row = copy_row.push(self.digits_text(format!("Copied {}", self.current_challenge().encode())));
+        }
+
+        let input = TextInput::new("Paste a challenge code", &self.challenge_input)
+            .id(Field::Challenge.id())
+            .on_input(Message::ChallengeInputChanged)
+            .on_submit(Message::ChallengeSubmitted)
+            .padding(5)
+            .width(Length::Fixed(220.0))
+            .font(fonts::MONO); // Codes mix look-alike characters such as l, I and 1
+        let play = Button::new(self.text("Play code")).on_press(Message::ChallengeSubmitted);
+
+        let practice_input = TextInput::new("Practice a range, like 40-60", &self.practice_input)
+            .id(Field::Practice.id())
+            .on_input(Message::PracticeInputChanged)
+            .on_submit(Message::PracticeSubmitted)
+            .padding(5)
+            .width(Length::Fixed(220.0));
+        let drill = Button::new(self.text("Practice")).on_press(Message::PracticeSubmitted);
+
+        // A friend's scorecard plays the same game, then compares the results
+        let scorecard_input = TextInput::new("Path to a friend's scorecard", &self.scorecard_input)
+            .id(Field::Scorecard.id())
+            .on_input(Message::ScorecardPathChanged)
+            .on_submit(Message::ScorecardImported)
+            .padding(5)
+            .width(Length::Fixed(220.0));
+        let load = Button::new(self.text("Load scorecard")).on_press(Message::ScorecardImported);
+
+        let danger = self.theme().palette().danger;
+        let mut column = Column::new()
+            .push(copy_row)
+            .push(self.mirrored_row(vec![input.into(), play.into()]).spacing(10))
+            .spacing(10)
+            .align_items(Alignment::Center);
+        if let Some(error) = self.challenge_error {
+            column = column.push(self.text(error.to_string()).style(danger));
+        }
+        column = column.push(self.mirrored_row(vec![practice_input.into(), drill.into()]).spacing(10));
+        if let Some(error) = self.practice_error {
+            column = column.push(self.text(error.to_string()).style(danger));
+        }
+        column = column.push(self.mirrored_row(vec![scorecard_input.into(), load.into()]).spacing(10));
+        if let Some(error) = self.scorecard_error {
+            column = column.push(self.text(error.to_string()).style(danger));
+        }
+
+        column.into()
+    }
+
+    // The challenge describing the current game
+    fn current_challenge(&self) -> Challenge {
+        Challenge { seed: self.seed, min: MIN_NUMBER, max: MAX_NUMBER }
+    }
+
+    // The current game's result as a scorecard; only meaningful once it's won
+    fn scorecard(&self) -> Scorecard {
+        Scorecard {
+            challenge: self.current_challenge(),
+            guesses: self.attempts,
+            score: score::score(self.attempts, self.points_spent),
+            seconds: self.stopwatch.elapsed(self.now).as_secs(),
+        }
+    }
+
+    // The current round as a replay file; only meaningful once it's over
+    fn game_replay(&self) -> GameReplay {
+        let (min, max) = self.practice.unwrap_or((MIN_NUMBER, MAX_NUMBER)); // A drill replays over its own range
+        GameReplay {
+            min,
+            max,
+            secret: self.secret_number.reveal(),
+            guesses: self.history.clone(),
+            times: self.guess_times.clone(),
+            timeouts: self.timeouts.clone(),
+            won: self.won,
+        }
+    }
+
+    // A digest of everything a replay has to reproduce, leaving out timing-dependent state
+    fn digest(&self) -> u64 {
+        let bot = self.bot_game.as_ref().map(|bot| (bot.strategy.key(), bot.low, bot.high, &bot.history, bot.solved));
+        let state = format!(
+            "seed={} secret={} operation={:?} low={} high={} history={:?} won={} guess={:?} feedback={:?} bot={:?} preferences={:?}",
+            self.seed,
+            self.secret_number.reveal(),
+            self.operation,
+            self.low,
+            self.high,
+            self.history,
+            self.won,
+            self.guess,
+            self.feedback,
+            bot,
+            self.preferences.to_text()
+        );
+        replay::digest(&state)
+    }
+
+    // The text fields currently on screen, in Tab order
+    fn visible_fields(&self) -> Vec<Field> {
+        let mut fields = Vec::new();
+        if self.pass_setup.is_some() && !self.preferences.compact {
+            // The setup screen replaces the game, leaving only its own field
+            if let Some(PassSetup::Entering { .. }) = self.pass_setup {
+                fields.push(Field::PassSecret);
             }
-            // Handle the event when the "Guess" button is pressed
-            Message::GuessButtonPressed => {
-                // Attempt to parse the guess input into an unsigned integer
-                let guess: u32 = match self.guess.trim().parse() {
-                    Ok(num) => num, // Successfully parsed number
-                    Err(_) => {
-                        // If parsing fails, update the message to prompt for a valid number
-                        self.message = String::from("Please enter a valid number.");
-                        return Command::none(); // Exit the update without further processing
-                    }
-                };
+            if self.show_settings {
+                fields.extend(self.settings_fields());
+            }
+            return fields;
+        }
+        if self.bot_game.is_none() {
+            fields.push(Field::Guess);
+        } else {
+            fields.push(Field::Constraints);
+        }
+        if !self.preferences.compact {
+            // The compact strip only has the guess input
+            if self.bot_game.is_none() && !self.pass_and_play {
+                fields.push(Field::Challenge);
+                fields.push(Field::Practice);
+                fields.push(Field::Scorecard);
+            }
+            if self.show_replays {
+                fields.push(Field::Replay);
+            }
+            if self.show_settings {
+                fields.extend(self.settings_fields());
+            }
+        }
+        fields
+    }
 
-                // Compare the user's guess with the secret number
-                match guess.cmp(&self.secret_number) {
-                    Ordering::Less => self.message = String::from("Too small!"), // Guess is lower than secret
-                    Ordering::Greater => self.message = String::from("Too big!"), // Guess is higher than secret
-                    Ordering::Equal => {
-                        // Correct guess; inform the user of their success
-                        self.message = String::from("You win! 🎉");
-                        // Optionally, you could reset the game here by generating a new secret number
-                    }
+    // The settings panel's controls, in Tab order
+    fn settings_fields(&self) -> Vec<Field> {
+        let mut settings = vec![
+            Setting::ShowCandidates,
+            Setting::ShowBases,
+            Setting::MysteryMode,
+            Setting::CloseEnough,
+            Setting::MinChange,
+            Setting::SurpriseSwap,
+            Setting::TimedGuesses,
+            Setting::GhostRace,
+            Setting::GuessBudget,
+            Setting::MercyHints,
+            Setting::AssistHints,
+            Setting::GuessAssist,
+            Setting::ProfileMode,
+            Setting::Profile,
+        ];
+        if self.profiles.choice(self.profile_mode).0.is_some() {
+            settings.push(Setting::ProfileSave);
+        }
+        settings.extend([
+            Setting::ProximityTones,
+            Setting::IdleTimeout,
+            Setting::Startup,
+            Setting::Motion,
+            Setting::SpeakFeedback,
+        ]);
+        if let Some(engine) = self.speech_engine() {
+            if engine.can_set_rate {
+                settings.push(Setting::SpeechRate);
+            }
+            if engine.voices.len() > 1 {
+                settings.push(Setting::SpeechVoice);
+            }
+        }
+        settings.extend([
+            Setting::NotifyOnWin,
+            Setting::SpectatorFeed,
+            Setting::AlwaysOnTop,
+            Setting::Compact,
+            Setting::LeftHanded,
+            Setting::OneKey,
+            Setting::OneKeyTiming,
+            Setting::Theme,
+            Setting::Font,
+            Setting::NumberFormat,
+            Setting::MonospaceDigits,
+            Setting::Colorblind,
+            Setting::Swatches,
+        ]);
+        let mut fields: Vec<Field> = settings.drain(..).map(Field::Setting).collect();
+        fields.push(Field::Accent); // The hex field comes right after the swatches it complements
+
+        settings.push(Setting::Analytics);
+        if self.preferences.analytics {
+            settings.push(Setting::Insights);
+        }
+        settings.extend([Setting::DeleteStatistics, Setting::ResetRecords, Setting::WhatsNew]);
+        fields.extend(settings.into_iter().map(Field::Setting));
+        fields
+    }
+
+    // The message a key pressed on the focused setting stands for, as if its control was used
+    //
+    // Checkboxes and buttons answer Space; pickers and the swatches step with the arrow keys,
+    // stopping at either end rather than wrapping round to a surprising choice.
+    fn setting_message(&self, key: SettingKey) -> Option<Message> {
+        let Some(Field::Setting(setting)) = self.focused_field else {
+            return None;
+        };
+        if !self.show_settings || self.preferences.compact {
+            return None; // The panel was closed since, taking the setting off screen
+        }
+
+        let preferences = &self.preferences;
+        if key != SettingKey::Activate {
+            let forward = key == SettingKey::Next;
+            return match setting {
+                Setting::IdleTimeout => {
+                    Some(Message::IdleTimeoutSelected(focus::step(&IdleTimeout::ALL, preferences.idle_timeout, forward)))
+                }
+                Setting::Startup => Some(Message::StartupSelected(focus::step(&Startup::ALL, preferences.startup, forward))),
+                Setting::OneKeyTiming => {
+                    Some(Message::OneKeyTimingSelected(focus::step(&onekey::Timing::ALL, preferences.one_key_timing, forward)))
+                }
+                Setting::MinChange => {
+                    let choices = MinChange::choices(MIN_NUMBER, MAX_NUMBER);
+                    Some(Message::MinChangeSelected(focus::step(&choices, preferences.min_change, forward)))
+                }
+                Setting::SurpriseSwap => {
+                    Some(Message::SurpriseSwapSelected(focus::step(&SurpriseSwap::ALL, preferences.surprise_swap, forward)))
+                }
+                Setting::Motion => Some(Message::MotionSelected(focus::step(&MotionChoice::ALL, preferences.motion, forward))),
+                Setting::SpeechRate => {
+                    Some(Message::SpeechRateSelected(focus::step(&SpeechRate::ALL, preferences.speech_rate, forward)))
+                }
+                Setting::SpeechVoice => self.speech_engine().map(|engine| {
+                    Message::SpeechVoiceSelected(focus::step(&engine.voices, engine.voice(&preferences.speech_voice), forward))
+                }),
+                Setting::Theme => Some(Message::ThemeSelected(focus::step(&ThemeChoice::ALL, preferences.theme, forward))),
+                Setting::Font => Some(Message::FontSelected(focus::step(&FontChoice::ALL, preferences.font, forward))),
+                Setting::NumberFormat => {
+                    Some(Message::NumberFormatSelected(focus::step(&LocaleChoice::ALL, preferences.number_format, forward)))
+                }
+                Setting::Swatches => Some(Message::AccentSelected(focus::step(&ACCENT_SWATCHES, preferences.accent, forward))),
+                Setting::ProfileMode => {
+                    Some(Message::ProfileModeSelected(focus::step(&profiles::Mode::ALL, self.profile_mode, forward)))
+                }
+                Setting::Profile => {
+                    let choices = self.profiles.choices();
+                    Some(Message::ProfileChosen(focus::step(&choices, self.profiles.choice(self.profile_mode), forward)))
                 }
+                _ => None,
+            };
+        }
 
-                // Clear the input field after processing the guess
-                self.guess.clear();
+        match setting {
+            Setting::ShowCandidates => Some(Message::ShowCandidatesToggled(!preferences.show_candidates)),
+            Setting::ShowBases => Some(Message::ShowBasesToggled(!preferences.show_bases)),
+            Setting::MysteryMode => Some(Message::MysteryModeToggled(!preferences.mystery_mode)),
+            Setting::CloseEnough => Some(Message::CloseEnoughToggled(!preferences.close_enough)),
+            Setting::TimedGuesses => Some(Message::TimedGuessesToggled(!preferences.timed_guesses)),
+            Setting::GhostRace => Some(Message::GhostRaceToggled(!preferences.ghost_race)),
+            Setting::GuessBudget => Some(Message::GuessBudgetToggled(!preferences.guess_budget)),
+            Setting::MercyHints => Some(Message::MercyHintsToggled(!preferences.mercy_hints)),
+            Setting::AssistHints => Some(Message::AssistHintsToggled(!preferences.assist_hints)),
+            Setting::GuessAssist => Some(Message::GuessAssistToggled(!preferences.guess_assist)),
+            Setting::ProximityTones => Some(Message::ProximityTonesToggled(!preferences.proximity_tones)),
+            Setting::NotifyOnWin => Some(Message::NotifyOnWinToggled(!preferences.notify_on_win)),
+            Setting::SpeakFeedback => Some(Message::SpeakFeedbackToggled(!preferences.speak_feedback)),
+            Setting::SpectatorFeed => Some(Message::SpectatorFeedToggled(!preferences.spectator_feed)),
+            Setting::AlwaysOnTop => Some(Message::AlwaysOnTopToggled(!preferences.always_on_top)),
+            Setting::Compact => Some(Message::CompactToggled(!preferences.compact)),
+            Setting::LeftHanded => Some(Message::LeftHandedToggled(!preferences.left_handed)),
+            Setting::OneKey => Some(Message::OneKeyToggled(!preferences.one_key)),
+            Setting::MonospaceDigits => Some(Message::MonospaceDigitsToggled(!preferences.monospace_digits)),
+            Setting::Colorblind => Some(Message::ColorblindToggled(!preferences.colorblind)),
+            Setting::Analytics => Some(Message::AnalyticsToggled(!preferences.analytics)),
+            Setting::Insights => preferences.analytics.then_some(Message::InsightsToggled),
+            Setting::ProfileSave => self.profiles.choice(self.profile_mode).0.is_some().then_some(Message::ProfileSaved),
+            Setting::DeleteStatistics => Some(Message::AnalyticsDeleted),
+            Setting::ResetRecords => Some(Message::RecordsResetRequested),
+            Setting::WhatsNew => Some(Message::WhatsNewOpened),
+            Setting::MinChange
+            | Setting::SurpriseSwap
+            | Setting::IdleTimeout
+            | Setting::Startup
+            | Setting::OneKeyTiming
+            | Setting::Motion
+            | Setting::Theme
+            | Setting::Font
+            | Setting::NumberFormat
+            | Setting::Swatches
+            | Setting::ProfileMode
+            | Setting::Profile
+            | Setting::SpeechRate
+            | Setting::SpeechVoice => None,
+        }
+    }
+
+    // The message Space going down (`pressed`) or up at `at` stands for in one-key play, if any
+    //
+    // A setting with keyboard focus keeps Space for itself, and so do the screens without a
+    // guess to make. A double-tap's first tap stepped the guess, so it's put back before guessing.
+    fn one_key_message(&mut self, pressed: bool, at: Instant) -> Option<Message> {
+        let playing = self.bot_game.is_none() && self.pass_setup.is_none();
+        if !self.preferences.one_key || !playing || self.setting_message(SettingKey::Activate).is_some() {
+            return None;
+        }
+
+        let timing = self.preferences.one_key_timing;
+        let action = if pressed { self.one_key.press(at, timing) } else { self.one_key.release(at, timing) };
+        match action {
+            onekey::Action::Wait => None,
+            onekey::Action::Step => {
+                self.one_key_undo = self.guess.clone();
+                Some(Message::GuessStepped(stepper::STEP as i32))
+            }
+            onekey::Action::Guess { undo_tap } => {
+                if undo_tap {
+                    self.guess = std::mem::take(&mut self.one_key_undo);
+                }
+                Some(Message::GuessButtonPressed)
             }
         }
-        Command::none() // No additional commands to run after handling the message
     }
 
-    // Method to define the layout and appearance of the application's UI
-    fn view(&self) -> Element<Message> {
-        // Create a text input field for the user's guess
-        let guess_input = TextInput::new("Enter your guess...", &self.guess) // Placeholder and current value
-            .on_input(Message::GuessInputChanged) // Define the message to send on input change
-            .padding(10)                          // Add padding inside the text field
-            .size(20);                            // Set the font size
+    // Wrap a settings control so it shows when keyboard focus rests on it
+    fn focusable<'a>(&self, setting: Setting, control: impl Into<Element<'a, Message>>) -> Element<'a, Message> {
+        let focused = self.focused_field == Some(Field::Setting(setting));
+        Container::new(control)
+            .padding(4)
+            .style(style::custom_container(FocusRing { accent: self.accent(), focused }))
+            .into()
+    }
 
-        // Create a button that the user can press to submit their guess
-        let guess_button = Button::new(Text::new("Guess")) // Button with the label "Guess"
-            .on_press(Message::GuessButtonPressed);       // Define the message to send on button press
+    // Record a play statistic, if the user has opted in
+    fn track(&self, event: analytics::Event) {
+        if self.preferences.analytics {
+            analytics::record(event);
+        }
+    }
 
-        // Arrange the UI elements vertically in a column
-        let content = Column::new()
-            .push(Text::new(&self.message).size(30)) // Display the current message with larger text
-            .push(guess_input)                        // Add the guess input field
-            .push(guess_button)                       // Add the guess button
-            .padding(20)                              // Add padding around the column
-            .align_items(Alignment::Center);          // Center-align all items within the column
+    // How generous bought hints are: matched to the player's recent wins in assist mode, otherwise as always
+    fn hint_strength(&self) -> Strength {
+        if !self.preferences.assist_hints {
+            return Strength::Normal;
+        }
+        Skill::estimate(&self.recent_wins, analysis::optimal_guesses(MIN_NUMBER, MAX_NUMBER)).hint_strength()
+    }
 
-        // Embed the column inside a container that fills the available space
-        Container::new(content)
-            .width(Length::Fill)   // Make the container take the full available width
-            .height(Length::Fill)  // Make the container take the full available height
-            .center_x()             // Center content horizontally
-            .center_y()             // Center content vertically
-            .into()                 // Convert the container into an `Element<Message>`
+    // The accent everything is drawn in: the OS's when the theme follows it, otherwise the player's
+    fn accent(&self) -> Rgb {
+        match self.system_accent {
+            Some(accent) if self.preferences.theme == ThemeChoice::SystemAccent => accent,
+            _ => self.preferences.accent,
+        }
+    }
+
+    // Whether winning this game counts towards stats and boards; pass-and-play wins don't, since
+    // the setter could have helped, and neither do retries of a known secret, drills, or games the
+    // debug overlay showed it in
+    fn can_count(&self) -> bool {
+        !self.pass_and_play && !self.retry && self.practice.is_none() && !self.debug_voided
+    }
+
+    // The statistics key for the current classic game's mode
+    fn mode_key(&self) -> &'static str {
+        if self.retry {
+            "retry" // Kept apart from every other mode, since a retry can't count
+        } else if self.practice.is_some() {
+            "practice" // Likewise for a drill
+        } else if self.tournament.is_some() {
+            "tournament"
+        } else if self.endless.is_some() {
+            "endless"
+        } else if self.course.is_some() {
+            "golf"
+        } else if self.operation.is_some() {
+            "mystery"
+        } else {
+            "classic"
+        }
+    }
+
+    // Lay out the insights panel, summarizing the recorded statistics
+    fn insights_view(&self, insights: &Insights) -> Element<Message> {
+        let n = |value: u32| format_number(self.locale, value);
+        let mut lines = vec![format!("{} sessions and {} games recorded", n(insights.sessions), n(insights.games()))];
+
+        if let Some(average) = insights.average_session {
+            lines.push(format!("Sessions last {} on average", format_duration(average)));
+        }
+        if let Some((mode, games)) = insights.favourite_mode() {
+            lines.push(format!("You play mostly {} ({} of {} games)", analytics::label(mode), n(games), n(insights.games())));
+        }
+        if let Some(average) = insights.average_attempts {
+            lines.push(format!("You've won {} games, in {:.1} guesses on average", n(insights.wins), average));
+        }
+        if let Some((feature, uses)) = insights.favourite_feature() {
+            lines.push(format!("You use {} the most ({} times)", analytics::label(feature), n(uses)));
+        }
+        lines.push(format!("Recorded only on this computer, in {}", analytics::FILE_NAME));
+
+        lines
+            .into_iter()
+            .fold(Column::new().spacing(4).align_items(Alignment::Center), |column, line| {
+                column.push(self.digits_text(line))
+            })
+            .into()
     }
-}
\ No newline at end of file
+
+    // The feedback for a wrong guess, compared through the round's mystery operation if it has one
+    fn wrong_guess_feedback(&self, guess: u32) -> Feedback {
+        if self.secret_number.judge(guess, self.rule.as_ref()) == Outcome::TooSmall {
+            Feedback::TooSmall(guess)
+        } else {
+            Feedback::TooBig(guess)
+        }
+    }
+
+    // Swap the secret for another that fits everything the player has been told, if the round has
+    // reached its swap; a typed pass-and-play secret is the setter's and the daily one is everybody's,
+    // so neither is swapped, and a round with nothing left to swap to simply keeps its secret
+    fn surprise_swap(&mut self, guess: u32, outcome: Outcome) {
+        let due = self.preferences.surprise_swap.guesses() == Some(self.history.len());
+        if !due || self.swapped || self.pass_and_play || self.daily.is_some() {
+            return;
+        }
+        self.swapped = true;
+
+        let clues = swap::Clues::given(self.hints_bought, self.mercy_given);
+        let candidates = swap::candidates(&self.secret_number, self.low, self.high, &self.history, self.rule.as_ref(), clues);
+        let Some(secret) = swap::pick(&candidates, self.seed) else {
+            tracing::debug!("No other number fits the clues, so the secret stays");
+            return;
+        };
+        tracing::debug!(candidates = candidates.len(), "Surprise swap");
+        self.secret_number = Secret::new(secret);
+        self.feedback = Feedback::Swapped { guess, outcome };
+        // The thermometer measured the old secret, so it starts over rather than mislead
+        self.proximity = None;
+        self.gauge = Gauge::default();
+    }
+
+    // Time left for the current guess, while the per-guess limit applies and its clock has started
+    fn attempt_remaining(&self) -> Option<Duration> {
+        let applies = self.preferences.timed_guesses && self.bot_game.is_none() && !self.won && !self.gave_up;
+        (applies && self.stopwatch.has_started())
+            .then(|| timer::attempt_remaining(self.stopwatch.elapsed(self.now), self.attempt_started))
+    }
+
+    // Waste the current attempt because its time ran out, starting the next one's straight away
+    fn expire_attempt(&mut self) {
+        self.attempts += 1;
+        self.timeouts.push(self.history.len()); // Shown in the history after the guesses made so far
+        self.attempt_started += timer::ATTEMPT_LIMIT; // From when the time ran out, not when the tick noticed
+        self.feedback = Feedback::TooSlow;
+        self.recovery_dirty = true;
+        tracing::debug!(attempts = self.attempts, "An attempt ran out of time");
+        gamelog::record(gamelog::Event::TimedOut { attempts: self.attempts });
+    }
+
+    // What viewers of a stream see: the feedback and the guesses so far, never a peek or the overlay
+    fn spectator_feed(&self) -> String {
+        let n = |value: u32| format_number(self.locale, value);
+        let (feedback, progress) = match (&self.pass_setup, &self.bot_game) {
+            (Some(_), _) => ("Setting up pass and play...".to_string(), String::new()),
+            (None, Some(bot)) => {
+                let noun = if bot.attempts() == 1 { "guess" } else { "guesses" };
+                ("The computer is guessing".to_string(), format!("{} {} so far", n(bot.attempts()), noun))
+            }
+            (None, None) => {
+                let noun = if self.attempts == 1 { "guess" } else { "guesses" };
+                (self.feedback.render(self.locale), format!("{} {}", n(self.attempts), noun))
+            }
+        };
+        spectator::render(&feedback, &progress)
+    }
+
+    // Capture the game in progress for the recovery file
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            secret: self.secret_number,
+            seed: self.seed,
+            low: self.low,
+            high: self.high,
+            history: self.history.clone(),
+            times: self.guess_times.clone(),
+            timeouts: self.timeouts.clone(),
+            warmup_used: self.warmup_used,
+            swapped: self.swapped,
+            elapsed: self.stopwatch.elapsed(Instant::now()),
+            operation: self.operation,
+            saved: false,
+            practice: self.practice,
+            daily: self.daily,
+            pass_and_play: self.pass_and_play,
+            retry: self.retry,
+            voided: self.debug_voided,
+        }
+    }
+
+    // Close the window, first saving the game for later if asked, or discarding it otherwise
+    fn close(&mut self, save_for_later: bool) -> Command<Message> {
+        self.track(analytics::Event::SessionEnded);
+        if save_for_later {
+            recovery::save(&Snapshot { saved: true, ..self.snapshot() });
+        } else {
+            self.log_abandoned();
+            recovery::discard(); // Removing the snapshot marks the exit as clean
+        }
+        gamelog::flush(); // The writer thread doesn't outlive the window
+
+        let digest = self.digest();
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.finish(digest);
+        }
+        window::close()
+    }
+
+    // Put back a game from a recovery snapshot, with the clock running again from where it stopped
+    //
+    // The current game is reset rather than a new one started, which would log a start and switch
+    // to a mode's settings for a game that never gets played.
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.reset_game(snapshot.seed);
+        self.secret_number = snapshot.secret;
+        self.seed = snapshot.seed;
+        self.operation = snapshot.operation;
+        self.rule = rules::for_mode(self.operation, self.preferences.close_enough);
+        self.low = snapshot.low;
+        self.high = snapshot.high;
+        self.attempts = (snapshot.history.len() + snapshot.timeouts.len()) as u32;
+        self.timeouts = snapshot.timeouts;
+        self.warmup_used = snapshot.warmup_used;
+        self.swapped = snapshot.swapped;
+        self.stopwatch = Stopwatch::from_elapsed(snapshot.elapsed);
+        self.attempt_started = snapshot.elapsed; // A fresh start on the guess that was interrupted
+        // Time the next guess from the last one when every guess was timed, or from now for an older save
+        self.thinking_since = snapshot.times.iter().copied().sum::<Option<Duration>>().unwrap_or(snapshot.elapsed);
+        self.stopwatch.start(Instant::now());
+        self.practice = snapshot.practice;
+        self.daily = snapshot.daily;
+        self.pass_and_play = snapshot.pass_and_play;
+        self.retry = snapshot.retry;
+        self.debug_voided |= snapshot.voided;
+
+        // Repeat the feedback for the last guess, which is never the winning one, or the drill's
+        // welcome when there's none yet
+        if let Some(&last) = snapshot.history.last() {
+            self.feedback = self.wrong_guess_feedback(last);
+        } else if let Some((min, max)) = self.practice {
+            self.feedback = Feedback::Welcome { min, max };
+        }
+        self.history = snapshot.history;
+        self.guess_times = snapshot.times;
+        self.open_round_span();
+        self.round_span.in_scope(|| tracing::info!(guesses = self.history.len(), "Game restored"));
+    }
+
+    // The result card for the won game
+    fn result_card(&self) -> ResultCard {
+        let n = |value: u32| format_number(self.locale, value);
+        let optimal = analysis::optimal_guesses(MIN_NUMBER, MAX_NUMBER);
+        let noun = if self.attempts == 1 { "guess" } else { "guesses" };
+
+        // The last guess won, even when it was only close enough; the rest point to the secret
+        let last = self.history.len().saturating_sub(1);
+        let track = self
+            .history
+            .iter()
+            .enumerate()
+            .map(|(i, &guess)| match self.secret_number.compare(guess) {
+                _ if i == last => Step::Win,
+                Ordering::Less => Step::Higher,
+                _ => Step::Lower,
+            })
+            .collect();
+
+        ResultCard {
+            lines: vec![
+                format!("Range {}–{}", n(MIN_NUMBER), n(MAX_NUMBER)),
+                format!("Solved in {} {} (optimal: {})", n(self.attempts), noun, n(optimal)),
+                format!("Time: {}", format_duration(self.stopwatch.elapsed(self.now))),
+                format!("Grade {}", analysis::grade(self.attempts, optimal)),
+            ],
+            track,
+            accent: self.accent(),
+            colorblind: self.preferences.colorblind,
+        }
+    }
+
+    // What the game is doing, in the debug overlay's terms
+    fn phase(&self) -> &'static str {
+        match (&self.pass_setup, &self.bot_game) {
+            (Some(_), _) => "Setting up pass and play",
+            (None, Some(bot)) if bot.solved => "Bot round solved",
+            (None, Some(_)) => "Bot guessing",
+            (None, None) if self.won => "Won",
+            (None, None) if self.gave_up => "Gave up",
+            (None, None) if self.history.is_empty() => "Waiting for the first guess",
+            (None, None) => "Guessing",
+        }
+    }
+
+    // Lay out the developer overlay with the game's internals, secret included
+    fn debug_view(&self) -> Element<Message> {
+        let now = Instant::now();
+        let mut timers = vec![format!(
+            "clock {} ({})",
+            format_duration(self.stopwatch.elapsed(now)),
+            if self.stopwatch.is_running() { "running" } else if self.idle_paused { "idle" } else { "stopped" }
+        )];
+        if let Some(until) = self.peek_until {
+            timers.push(format!("peek {} ms", until.saturating_duration_since(now).as_millis()));
+        }
+        if self.reveal.is_some() {
+            timers.push("reveal animating".to_string());
+        }
+        if let Some(refill) = self.budget.next_refill_in(now).filter(|_| self.preferences.guess_budget) {
+            timers.push(format!("refill in {}", format_duration(refill)));
+        }
+        if !self.toasts.is_empty() {
+            timers.push(format!("{} toasts", self.toasts.len()));
+        }
+
+        let recent: Vec<&str> = self.recent_messages.iter().copied().collect();
+        let lines = [
+            "DEBUG - Ctrl+Shift+D to hide. This game won't count towards achievements.".to_string(),
+            format!("Phase: {}", self.phase()),
+            format!("Secret (cheat info): {}", format_number(self.locale, self.secret_number.reveal())),
+            format!(
+                "Bounds: {}-{}, {} attempts",
+                format_number(self.locale, self.low),
+                format_number(self.locale, self.high),
+                format_number(self.locale, self.attempts)
+            ),
+            format!("Timers: {}", timers.join(", ")),
+            format!("Last messages: {}", recent.join(", ")),
+            format!(
+                "Updates: {}, frames: {}",
+                format_number(self.locale, self.updates),
+                format_number(self.locale, self.frames.get())
+            ),
+        ];
+
+        let column = lines
+            .into_iter()
+            .fold(Column::new().spacing(4), |column, line| column.push(Text::new(line).font(fonts::MONO).size(14)));
+        Container::new(column)
+            .padding(10)
+            .style(style::custom_container(DebugPanel))
+            .into()
+    }
+
+    // Lay out the offer to restore a game left behind by a crash
+    fn recovery_view(&self, snapshot: &Snapshot) -> Element<Message> {
+        let noun = if snapshot.history.len() == 1 { "guess" } else { "guesses" };
+        let question = if snapshot.saved {
+            "Welcome back! Pick up the game you saved"
+        } else {
+            "The game didn't close properly last time. Restore your game"
+        };
+        let prompt = self.digits_text(format!(
+            "{} ({} {} in)?",
+            question,
+            format_number(self.locale, snapshot.history.len() as u32),
+            noun
+        ));
+        let restore = Button::new(self.text("Restore"))
+            .on_press(Message::RecoveryRestored)
+            .style(style::custom_button(AccentButton(self.accent())));
+        let decline = Button::new(self.text("Discard")).on_press(Message::RecoveryDeclined);
+
+        Column::new()
+            .push(prompt)
+            .push(self.mirrored_row(vec![restore.into(), decline.into()]).spacing(10))
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .into()
+    }
+
+    // Lay out the question asked when the window is closed mid-game
+    fn close_prompt_view(&self) -> Element<Message> {
+        let keep = Button::new(self.text("Keep playing"))
+            .on_press(Message::CloseConfirmed(QuitChoice::KeepPlaying))
+            .style(style::custom_button(AccentButton(self.accent())));
+        let save = Button::new(self.text("Quit and save for later")).on_press(Message::CloseConfirmed(QuitChoice::SaveForLater));
+        let quit = Button::new(self.text("Quit")).on_press(Message::CloseConfirmed(QuitChoice::Quit));
+
+        Column::new()
+            .push(self.text("Quit in the middle of this game?"))
+            .push(self.mirrored_row(vec![keep.into(), save.into(), quit.into()]).spacing(10))
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .into()
+    }
+
+    // Lay out the status bar: one line along the bottom of the window, its segments kept apart by dots
+    fn status_bar_view(&self, bar: StatusBar) -> Element<Message> {
+        let line = self.digits_text(bar.segments.join("  ·  ")).size(14);
+        Container::new(line)
+            .width(Length::Fill)
+            .padding([4, 12])
+            .center_x()
+            .style(iced::theme::Container::Box)
+            .into()
+    }
+
+    // Lay out the question about leaving the tournament, which everything else waits on
+    fn tournament_exit_view(&self) -> Element<Message> {
+        let played = self.tournament.as_ref().map_or(0, |tournament| tournament.rounds.len() as u32);
+        let warning = match played {
+            0 => "Leave the tournament? It will be lost.".to_string(),
+            1 => "Leave the tournament? The round played so far will be lost.".to_string(),
+            _ => format!("Leave the tournament? The {} rounds played so far will be lost.", format_number(self.locale, played)),
+        };
+        let keep = Button::new(self.text("Keep playing"))
+            .on_press(Message::TournamentExitConfirmed(false))
+            .style(style::custom_button(AccentButton(self.accent())));
+        let leave = Button::new(self.text("Leave tournament")).on_press(Message::TournamentExitConfirmed(true));
+
+        Column::new()
+            .push(self.digits_text(warning))
+            .push(self.mirrored_row(vec![keep.into(), leave.into()]).spacing(10))
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .into()
+    }
+
+    // Lay out the question about wiping the records, which can't be undone
+    fn records_reset_view(&self) -> Element<Message> {
+        let keep = Button::new(self.text("Keep my records"))
+            .on_press(Message::RecordsResetConfirmed(false))
+            .style(style::custom_button(AccentButton(self.accent())));
+        let reset = Button::new(self.text("Reset records")).on_press(Message::RecordsResetConfirmed(true));
+
+        Column::new()
+            .push(self.text("Reset every record? The leaderboard, achievements and endless mode records will be gone for good."))
+            .push(self.mirrored_row(vec![keep.into(), reset.into()]).spacing(10))
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .into()
+    }
+
+    // Lay out the tournament's progress, and its standings once the last round is won
+    fn tournament_view(&self, tournament: TournamentViewModel) -> Element<Message> {
+        let mut column = Column::new().push(self.digits_text(tournament.heading).size(22));
+        if tournament.next_round {
+            column = column.push(self.text("Next round starting..."));
+        }
+
+        // List every round, picking out the best, then offer another tournament of the same length
+        if let Some(standings) = tournament.standings {
+            let accent = Color::from(self.accent());
+            for (i, line) in standings.into_iter().enumerate() {
+                let line = self.digits_text(line);
+                column = column.push(if tournament.best == Some(i) { line.style(accent) } else { line });
+            }
+            let again = Button::new(self.text("New tournament"))
+                .on_press(Message::TournamentStarted(tournament.length))
+                .style(style::custom_button(AccentButton(self.accent())));
+            column = column.push(again);
+        } else {
+            column = column.push(Button::new(self.text("Abort tournament")).on_press(Message::TournamentAborted));
+        }
+
+        column.spacing(8).align_items(Alignment::Center).into()
+    }
+
+    // Lay out the course's running total, and its scorecard hole by hole once the last one is played
+    fn course_view(&self, course: CourseViewModel) -> Element<Message> {
+        let mut column = Column::new().push(self.digits_text(course.heading).size(22));
+        if let Some(holes) = course.holes {
+            for line in holes {
+                column = column.push(self.digits_text(line));
+            }
+            let again = Button::new(self.text("New course"))
+                .on_press(Message::CourseStarted)
+                .style(style::custom_button(AccentButton(self.accent())));
+            column = column.push(again);
+        } else {
+            column = column.push(Button::new(self.text("Leave the course")).on_press(Message::CourseAborted));
+        }
+
+        column.spacing(8).align_items(Alignment::Center).into()
+    }
+
+    // Lay out the breakdown of the strategy grade, in place of the rest of the summary
+    fn grade_view(&self, explanation: GradeExplanation) -> Element<Message> {
+        let back = Button::new(self.text("Back to summary"))
+            .on_press(Message::GradeExplained)
+            .style(style::custom_button(AccentButton(self.accent())));
+        let danger = self.theme().palette().danger;
+
+        let column = explanation.lines.into_iter().fold(Column::new(), |column, line| column.push(self.digits_text(line)));
+        explanation
+            .deviations
+            .into_iter()
+            .fold(column, |column, line| column.push(self.digits_text(line).size(16).style(danger)))
+            .push(back)
+            .spacing(8)
+            .align_items(Alignment::Center)
+            .into()
+    }
+
+    // Lay out the end-of-game summary with the strategy grade, which opens its breakdown when clicked
+    fn summary_view(&self, summary: SummaryViewModel) -> Element<Message> {
+        if let Some(explanation) = summary.explanation {
+            return self.grade_view(explanation);
+        }
+        let play_again = Button::new(self.text(summary.play_again_label))
+            .on_press(Message::NewGame)
+            .style(style::custom_button(AccentButton(self.accent())));
+        let review_button = Button::new(self.text(summary.review_label)).on_press(Message::ReviewToggled);
+        let mut card_button = Button::new(self.text("Save result card"));
+        if summary.card_enabled {
+            card_button = card_button.on_press(Message::ResultCardRequested);
+        }
+        let mut buttons = vec![play_again.into(), review_button.into(), card_button.into()];
+        if let Some(enabled) = summary.scorecard_enabled {
+            let export = Button::new(self.text("Export scorecard"));
+            buttons.push(if enabled { export.on_press(Message::ScorecardExportRequested) } else { export }.into());
+        }
+        let mut replay_button = Button::new(self.text("Export replay"));
+        if summary.replay_enabled {
+            replay_button = replay_button.on_press(Message::ReplayExportRequested);
+        }
+        buttons.push(replay_button.into());
+        let mut history_button = Button::new(self.text("Export history (CSV)"));
+        if summary.history_enabled {
+            history_button = history_button.on_press(Message::HistoryExportRequested);
+        }
+        buttons.push(history_button.into());
+
+        // The confetti band stays for as long as the summary does, so the layout doesn't jump when the burst ends
+        let mut summary_column = Column::new();
+        if !self.reduce_motion() {
+            let accent = Color::from(self.accent());
+            let burst = self.confetti.as_ref().map_or_else(ConfettiFrame::default, |confetti| confetti.frame(self.now, accent));
+            summary_column = summary_column.push(Canvas::new(burst).width(Length::Fill).height(Length::Fixed(confetti::HEIGHT)));
+        }
+        let result = Button::new(self.digits_text(summary.result)).on_press(Message::GradeExplained).style(iced::theme::Button::Text);
+        let mut summary_column = summary_column
+            .push(result)
+            .push(self.digits_text(summary.golf))
+            .push(self.digits_text(summary.score))
+            .push(self.mirrored_row(buttons).spacing(10))
+            .spacing(10)
+            .align_items(Alignment::Center);
+        if let Some(thinking) = summary.thinking {
+            summary_column = summary_column.push(self.digits_text(thinking));
+        }
+        if let Some(comparison) = summary.comparison {
+            summary_column = summary_column.push(self.digits_text(comparison).size(22));
+        }
+        if let Some(time_refused) = summary.time_refused {
+            summary_column = summary_column.push(self.text(time_refused).size(16));
+        }
+        if let Some(review) = summary.review {
+            summary_column = summary_column.push(self.review_view(review));
+        }
+
+        summary_column.into()
+    }
+
+    // Lay out the step-by-step review, flagging guesses that strayed from the midpoint, muting their
+    // times, and fading older guesses so the latest ones stand out
+    //
+    // Only the rows near the viewport are built; spaces the height of the rest keep the scrollbar true
+    fn review_view(&self, review: ReviewList) -> Element<Message> {
+        let palette = self.theme().palette();
+        let total = review.above + review.rows.len() + review.below;
+
+        let rows = review
+            .rows
+            .into_iter()
+            .enumerate()
+            .fold(Column::new().push(Space::with_height(history::height(review.above))), |column, (i, row)| {
+                let strength = history::recency(review.above + i, total);
+                let color = if row.suboptimal { palette.danger } else { palette.text };
+                let line = self.digits_text(row.text).style(Color { a: strength, ..color });
+                let line: Element<Message> = match row.time {
+                    Some(time) => {
+                        let muted = Color { a: 0.6 * strength, ..palette.text }; // Times are secondary to the guesses
+                        Row::new().push(line).push(self.digits_text(time).style(muted)).spacing(8).into()
+                    }
+                    None => line.into(),
+                };
+                column.push(Container::new(line).height(history::ROW_HEIGHT)) // A fixed height, so the sums stay exact
+            })
+            .push(Space::with_height(history::height(review.below)));
+        let list = Scrollable::new(rows)
+            .height(history::viewport(total))
+            .on_scroll(|offset| Message::ReviewScrolled(offset.y));
+
+        Column::new().spacing(4).push(self.text(review.header)).push(list).into()
+    }
+
+    // Lay out the classic mode: feedback, the guess controls, and the game's progress
+    fn game_view(&self) -> Element<Message> {
+        let model = GameViewModel::new(self);
+
+        // Put the input and the Guess button side by side, button on the dominant-hand side,
+        // with the time left for the guess beyond the button
+        let mut guess_controls = vec![self.stepped_guess_input(&model), self.guess_button(&model).into()];
+        if model.warmup_enabled {
+            let warmup = Button::new(self.text("Warm-up (doesn't count)")).on_press(Message::WarmUpGuessed);
+            guess_controls.push(warmup.into());
+        }
+        if let Some(level) = model.thermometer {
+            let gauge = Thermometer {
+                level,
+                fill: Color::from(proximity_tint(level, self.preferences.colorblind)),
+                outline: self.theme().palette().text,
+            };
+            let gauge = Canvas::new(gauge).width(Length::Fixed(thermometer::WIDTH)).height(Length::Fixed(thermometer::HEIGHT));
+            guess_controls.insert(0, gauge.into()); // On the input's side, away from the button
+        }
+        if let Some(timer) = model.attempt_timer.clone() {
+            guess_controls.push(self.digits_text(timer).into());
+        }
+        let guess_row = self
+            .mirrored_row(guess_controls)
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        // The number line under the controls, ruled-out numbers greyed when the assist is on
+        let number_line = model.number_line.map(|allowed| {
+            let line = NumberLine {
+                min: MIN_NUMBER,
+                max: MAX_NUMBER,
+                allowed,
+                active: Color::from(self.accent()),
+                ruled_out: Color { a: 0.2, ..self.theme().palette().text },
+                on_pick: Message::NumberPicked,
+            };
+            Canvas::new(line).width(Length::Fixed(300.0)).height(Length::Fixed(numberline::HEIGHT))
+        });
+
+        // Color the feedback by its severity: accent for a win, danger for unusable input
+        let feedback = self.text(model.feedback).size(30);
+        let feedback = match model.severity {
+            Severity::Info => feedback,
+            Severity::Error => feedback.style(self.theme().palette().danger),
+            Severity::Success => feedback.style(Color::from(self.accent())),
+        };
+
+        // Arrange the game's elements vertically in a column
+        let mut content = Column::new().push(feedback); // Display the current feedback with larger text
+        if let Some(sum) = model.worked_sum {
+            content = content.push(self.digits_text(sum)); // How a typed sum came out, right under it
+        }
+        if let Some(announcement) = model.announcement {
+            content = content.push(self.digits_text(announcement).size(22)); // An automatic hint, right under it
+        }
+        if let Some(status) = model.card_status {
+            content = content.push(self.text(status));
+        }
+        if let Some(status) = model.scorecard_status {
+            content = content.push(self.text(status));
+        }
+        if let Some(status) = model.replay_status {
+            content = content.push(self.text(status));
+        }
+        if let Some(status) = model.history_status {
+            content = content.push(self.text(status));
+        }
+        if let Some(note) = model.opponent_note {
+            content = content.push(self.digits_text(note));
+        }
+        if let Some(ghost) = model.ghost {
+            let palette = self.theme().palette();
+            content = content.push(self.digits_text(ghost.progress).size(16));
+            if let Some((split, ahead)) = ghost.split {
+                let color = if ahead { palette.success } else { palette.danger };
+                content = content.push(self.digits_text(split).size(16).style(color));
+            }
+        }
+        if let Some(tournament) = model.tournament {
+            content = content.push(self.tournament_view(tournament));
+        }
+        if let Some(course) = model.course {
+            content = content.push(self.course_view(course));
+        }
+        if let Some(endless) = model.endless {
+            let stop = Button::new(self.text("Stop")).on_press(Message::EndlessStopped);
+            let row = self.mirrored_row(vec![self.digits_text(endless).size(22).into(), stop.into()]);
+            content = content.push(row.spacing(10).align_items(Alignment::Center));
+        }
+        let mut content = content
+            .push(guess_row)                          // Add the guess input field and button
+            .spacing(10)                              // Keep the stacked controls from touching
+            .align_items(Alignment::Center);          // Center-align all items within the column
+        if let Some(line) = number_line {
+            content = content.push(line);
+        }
+        if let Some(tip) = model.range_tip {
+            content = content.push(self.digits_text(tip).size(16).style(self.theme().palette().danger)); // Also there without hovering
+        }
+        if let Some(bases) = model.bases {
+            content = content.push(self.digits_text(bases)); // Right under the input it describes
+        }
+
+        // Show the guess budget prominently, since it decides whether the player can guess
+        if let Some(budget) = model.budget {
+            content = content.push(self.digits_text(budget).size(24));
+        }
+
+        // Add whichever progress lines the view model decided to show
+        if let Some(note) = model.operation_note {
+            content = content.push(self.text(note));
+        }
+
+        // Show how the game went once it is over
+        if let Some(summary) = model.summary {
+            content = content.push(self.summary_view(summary));
+        }
+
+        // List the hints bought so far, and offer the next one with its price
+        for hint in model.hints {
+            content = content.push(self.digits_text(hint));
+        }
+        if let Some(label) = model.hint_button {
+            content = content.push(Button::new(self.text(label)).on_press(Message::HintRequested));
+        }
+        if let Some(note) = model.assist_note {
+            content = content.push(self.text(note));
+        }
+        if let Some(peek) = model.peek_button {
+            let button = Button::new(self.text(peek.label));
+            content = content.push(if peek.enabled { button.on_press(Message::PeekRequested) } else { button });
+        }
+        if let Some(refusal) = model.hint_refused {
+            content = content.push(self.text(refusal).style(self.theme().palette().danger));
+        }
+
+        // Name who set the secret in pass-and-play games, and let the guesser concede
+        if let Some(note) = model.setter_note {
+            content = content.push(self.text(note));
+        }
+        if model.give_up_enabled {
+            content = content.push(Button::new(self.text("Give up")).on_press(Message::GaveUp));
+        }
+        if model.retry_enabled {
+            content = content.push(Button::new(self.text("Retry this number")).on_press(Message::RetryRequested));
+        }
+        if let Some(note) = model.retry_note {
+            content = content.push(self.text(note));
+        }
+
+        // Before the first guess, offer to set up the last game played again
+        if let Some(last_played) = model.last_played {
+            let same_again = Button::new(self.text("Same again")).on_press(Message::SameAgain);
+            let row = self.mirrored_row(vec![self.text(last_played).into(), same_again.into()]);
+            content = content.push(row.spacing(10).align_items(Alignment::Center));
+        }
+        if let Some(notice) = model.last_played_notice {
+            content = content.push(self.text(notice));
+        }
+
+        // Offer this game's challenge code, and a field to play someone else's; a code can't
+        // recreate a secret a player typed, so pass-and-play games have none
+        if !self.pass_and_play {
+            content = content.push(self.challenge_view());
+        }
+
+        // Offer the other modes: the bot guessing, or a friend setting the secret
+        let other_modes = self
+            .mirrored_row(vec![
+                Button::new(self.text("Let the computer guess")).on_press(Message::BotModeToggled(true)).into(),
+                Button::new(self.text("Pass and play")).on_press(Message::PassAndPlayOpened).into(),
+            ])
+            .spacing(10);
+        content = content.push(other_modes);
+
+        // Offer a tournament of a few rounds back to back, or an endless session, unless one is already on
+        if self.tournament.is_none() && self.endless.is_none() && self.course.is_none() {
+            let mut lengths = vec![self.text("Tournament:").into()];
+            lengths.extend(tournament::LENGTHS.map(|length| {
+                let label = format!("{} rounds", format_number(self.locale, length));
+                Button::new(self.digits_text(label)).on_press(Message::TournamentStarted(length)).into()
+            }));
+            lengths.push(Button::new(self.text("Endless")).on_press(Message::EndlessStarted).into());
+            lengths.push(Button::new(self.text("Golf course")).on_press(Message::CourseStarted).into());
+            content = content.push(self.mirrored_row(lengths).spacing(10).align_items(Alignment::Center));
+        }
+
+        content.into()
+    }
+
+    // Lay out the replay being watched: the guess shown so far, what it narrowed the range to, and
+    // controls to step through it; nothing here touches the game behind it
+    fn playback_view(&self, playback: &Playback) -> Element<Message> {
+        let n = |value: u32| format_number(self.locale, value);
+        let replay = &playback.replay;
+        let total = playback.steps.len() as u32;
+
+        let heading = format!("Replay of a game from {} to {}", n(replay.min), n(replay.max));
+        let (progress, feedback, range) = match playback.current() {
+            Some(step) => (
+                format!("Guess {} of {}", n(playback.shown as u32), n(total)),
+                match step.thought {
+                    Some(time) => format!("{} ({})", step.feedback.render(self.locale), format_thinking_time(self.locale, time)),
+                    None => step.feedback.render(self.locale),
+                },
+                format!("Still possible: {}–{}", n(step.low), n(step.high)),
+            ),
+            None => (
+                format!("{} guesses to watch", n(total)),
+                "Press Next or Play to see the first guess".to_string(),
+                format!("Still possible: {}–{}", n(replay.min), n(replay.max)),
+            ),
+        };
+
+        let mut column = Column::new()
+            .push(self.digits_text(heading).size(22))
+            .push(self.digits_text(progress))
+            .push(self.digits_text(feedback).size(30))
+            .push(self.digits_text(range))
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        // A round that wasn't won never shows the secret in its feedback, so name it at the end
+        if playback.is_finished() && !replay.won {
+            column = column.push(self.digits_text(format!("The number was {}, and it wasn't found", n(replay.secret))));
+        }
+
+        let mut back = Button::new(self.text("Back"));
+        if playback.shown > 0 {
+            back = back.on_press(Message::PlaybackStepped(false));
+        }
+        let mut next = Button::new(self.text("Next"));
+        if !playback.is_finished() {
+            next = next.on_press(Message::PlaybackStepped(true));
+        }
+        let play = Button::new(self.text(if playback.playing { "Pause" } else { "Play" })).on_press(Message::PlaybackToggled);
+        let close = Button::new(self.text("Close replay")).on_press(Message::PlaybackClosed);
+        column
+            .push(self.mirrored_row(vec![back.into(), play.into(), next.into(), close.into()]).spacing(10))
+            .into()
+    }
+
+    // Lay out the panel for opening a replay file someone shared
+    fn replays_view(&self) -> Element<Message> {
+        let input = TextInput::new("Path to a replay file", &self.replay_input)
+            .id(Field::Replay.id())
+            .on_input(Message::ReplayPathChanged)
+            .on_submit(Message::ReplayOpened)
+            .padding(5)
+            .width(Length::Fixed(220.0));
+        let open = Button::new(self.text("Open replay")).on_press(Message::ReplayOpened);
+
+        let mut column = Column::new()
+            .push(self.mirrored_row(vec![input.into(), open.into()]).spacing(10))
+            .spacing(10)
+            .align_items(Alignment::Center);
+        if let Some(error) = self.replay_error {
+            column = column.push(self.text(error.to_string()).style(self.theme().palette().danger));
+        }
+        column.into()
+    }
+
+    // Lay out the pass-and-play setup: the hidden secret field, then the hand-off
+    fn pass_setup_view(&self, setup: &PassSetup) -> Element<Message> {
+        let n = |value: u32| format_number(self.locale, value);
+        let cancel = Button::new(self.text("Cancel")).on_press(Message::PassCancelled);
+
+        let column = match setup {
+            PassSetup::Entering { input, invalid } => {
+                let prompt = format!(
+                    "Player 1: pick a number from {} to {} without letting anyone see",
+                    n(MIN_NUMBER),
+                    n(MAX_NUMBER)
+                );
+                // Masked like a password so the guesser can't read it over the setter's shoulder
+                let secret_input = TextInput::new("Secret number", input)
+                    .id(Field::PassSecret.id())
+                    .on_input(Message::PassSecretChanged)
+                    .on_submit(Message::PassSecretSubmitted)
+                    .password()
+                    .padding(10)
+                    .size(20)
+                    .width(Length::Fixed(200.0));
+                let confirm = Button::new(self.text("Hide it"))
+                    .on_press(Message::PassSecretSubmitted)
+                    .style(style::custom_button(AccentButton(self.accent())));
+
+                let mut column = Column::new()
+                    .push(self.text(prompt))
+                    .push(self.mirrored_row(vec![secret_input.into(), confirm.into()]).spacing(10));
+                if *invalid {
+                    let danger = self.theme().palette().danger;
+                    let error = format!("Enter a whole number from {} to {}", n(MIN_NUMBER), n(MAX_NUMBER));
+                    column = column.push(self.text(error).style(danger));
+                }
+                column
+            }
+            // A separate step, so the guesser never sees the screen while the secret is on it
+            PassSetup::Handoff(_) => {
+                let ready = Button::new(self.text("Ready"))
+                    .on_press(Message::PassReady)
+                    .style(style::custom_button(AccentButton(self.accent())));
+                Column::new()
+                    .push(self.text("Secret set. Pass the device to the guesser, then press Ready.").size(24))
+                    .push(ready)
+            }
+        };
+
+        column.push(cancel).spacing(10).align_items(Alignment::Center).into()
+    }
+
+    // Remember the game being played as the one to offer again, saving it if it's new
+    fn remember_played(&mut self, mode: PlayMode) {
+        let played = LastPlayed {
+            mode,
+            min: MIN_NUMBER,
+            max: MAX_NUMBER,
+            mystery: self.operation.is_some(),
+            close_enough: self.preferences.close_enough,
+            bot_strategy: self.preferences.bot_strategy,
+        };
+        if self.last_played != Some(played) {
+            played.save();
+            self.last_played = Some(played);
+        }
+        self.last_played_notice = None; // Replaced by a configuration that can be restored
+    }
+
+    // Set up the same kind of game as the last one played, under the settings it was played with
+    fn play_same_again(&mut self, last: LastPlayed) -> Command<Message> {
+        self.update_preferences(|preferences| {
+            preferences.mystery_mode = last.mystery;
+            preferences.close_enough = last.close_enough;
+            preferences.bot_strategy = last.bot_strategy;
+        });
+        self.bot_game = None;
+        self.pass_setup = None;
+
+        match last.mode {
+            PlayMode::Classic => self.start_new_game(),
+            PlayMode::Daily => {
+                let today = Date::today(); // Today's challenge, since the one played may be over
+                self.start_seeded_game(today.seed());
+                self.daily = Some(today);
+            }
+            PlayMode::PassAndPlay => {
+                self.pass_setup = Some(PassSetup::new());
+                self.focused_field = Some(Field::PassSecret);
+                return text_input::focus(Field::PassSecret.id());
+            }
+            PlayMode::ComputerGuesses => self.start_bot_game(),
+        }
+        Command::none()
+    }
+
+    // Lay out the demo gallery: a panel for every bot on the current seed, and the way back out
+    fn gallery_view(&self, gallery: &Gallery) -> Element<Message> {
+        let n = |value: u32| format_number(self.locale, value);
+        let heading = self.text("Demo gallery - the bots play on their own, on a loop").size(22);
+        let seed = self.digits_text(format!(
+            "Seed {} ({} of {}), secret {}",
+            n(gallery.seed()),
+            n(gallery.round as u32 + 1),
+            n(gallery::SEEDS.len() as u32),
+            n(gallery.panels.first().map_or(0, |panel| panel.secret)),
+        ));
+
+        let panels = gallery.panels.iter().fold(Row::new().spacing(30), |row, panel| {
+            let game = &panel.game;
+            let guesses = format!("{} {}", n(game.attempts()), if game.attempts() == 1 { "guess" } else { "guesses" });
+            let status = if game.solved {
+                self.digits_text(format!("Found it in {}", guesses)).style(Color::from(self.accent()))
+            } else {
+                self.digits_text(format!("{} so far", guesses))
+            };
+            let column = Column::new()
+                .push(self.text(game.strategy.to_string()).size(20))
+                .push(self.text(game.strategy.description()).size(14))
+                .push(self.digits_text(n(game.guess)).size(36))
+                .push(self.digits_text(format!("Still possible: {}–{}", n(game.low), n(game.high))))
+                .push(status)
+                .spacing(6)
+                .width(Length::Fixed(200.0))
+                .align_items(Alignment::Center);
+            row.push(column)
+        });
+
+        let back = Button::new(self.text("Back to the game")).on_press(Message::GalleryClosed);
+        Column::new()
+            .push(heading)
+            .push(seed)
+            .push(panels)
+            .push(back)
+            .spacing(16)
+            .align_items(Alignment::Center)
+            .into()
+    }
+
+    // Start a computer-guesses round at the chosen difficulty
+    fn start_bot_game(&mut self) {
+        let strategy = self.preferences.bot_strategy;
+        let seed = u64::from(self.numbers.secret(0, u32::MAX));
+        self.bot_game = Some(BotGame::new(strategy, MIN_NUMBER, MAX_NUMBER, seed));
+        self.bot_contradiction = false;
+        self.track(analytics::Event::GameStarted("computer_guesses"));
+    }
+
+    // Lay out the computer-guesses mode: the bot's guess, the answer buttons, and its difficulty
+    fn bot_view(&self, bot: &BotGame) -> Element<Message> {
+        let n = |value: u32| format_number(self.locale, value);
+
+        let mut column = Column::new()
+            .push(self.text(format!("Think of a number from {} to {}", n(MIN_NUMBER), n(MAX_NUMBER))))
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        if bot.solved {
+            // Celebrate in the accent color, like the player's own wins
+            let noun = if bot.attempts() == 1 { "guess" } else { "guesses" };
+            let solved = self
+                .digits_text(format!("Got it: {} in {} {}", n(bot.guess), n(bot.attempts()), noun))
+                .size(30)
+                .style(Color::from(self.accent()));
+            let play_again = Button::new(self.text("Play again"))
+                .on_press(Message::NewBotGame)
+                .style(style::custom_button(AccentButton(self.accent())));
+            column = column.push(solved).push(play_again);
+        } else {
+            // Ask about the current guess and offer the three possible answers
+            let answers = self
+                .mirrored_row(vec![
+                    Button::new(self.text("Too small")).on_press(Message::BotAnswered(Answer::TooSmall)).into(),
+                    Button::new(self.text("Too big")).on_press(Message::BotAnswered(Answer::TooBig)).into(),
+                    Button::new(self.text("Correct!"))
+                        .on_press(Message::BotAnswered(Answer::Correct))
+                        .style(style::custom_button(AccentButton(self.accent())))
+                        .into(),
+                ])
+                .spacing(10);
+            column = column
+                .push(self.digits_text(format!("Is it {}?", n(bot.guess))).size(30))
+                .push(answers);
+
+            if self.bot_contradiction {
+                let danger = self.theme().palette().danger;
+                column = column.push(self.text("That can't be right - check your earlier answers").style(danger));
+            }
+        }
+
+        column = column.push(self.deduce_view());
+
+        // Say how the bot is playing, and let the player change it
+        let strategy = self.text(format!("Bot strategy: {} ({})", bot.strategy, bot.strategy.description()));
+        let difficulty_picker = Row::new()
+            .push(self.text("Bot difficulty:"))
+            .push(PickList::new(
+                BotStrategy::ALL,
+                Some(self.preferences.bot_strategy),
+                Message::BotStrategySelected,
+            ).font(self.font()))
+            .spacing(10)
+            .align_items(Alignment::Center);
+        let back = Button::new(self.text("Back to guessing")).on_press(Message::BotModeToggled(false));
+
+        column.push(strategy).push(difficulty_picker).push(back).into()
+    }
+
+    // Lay out the statements the computer can deduce the player's number from, and what they leave
+    fn deduce_view(&self) -> Element<Message> {
+        let n = |value: u32| format_number(self.locale, value);
+        let input = TextInput::new("Or tell it everything, like >10, <80, even", &self.constraints_input)
+            .id(Field::Constraints.id())
+            .on_input(Message::ConstraintsChanged)
+            .on_submit(Message::ConstraintsSubmitted)
+            .padding(5)
+            .width(Length::Fixed(320.0));
+        let deduce = Button::new(self.text("Deduce")).on_press(Message::ConstraintsSubmitted);
+        let mut column = Column::new()
+            .push(self.mirrored_row(vec![input.into(), deduce.into()]).spacing(10))
+            .spacing(6)
+            .align_items(Alignment::Center);
+
+        match &self.deduction {
+            None => {}
+            Some(Ok(deduction)) if deduction.remaining == 1 => {
+                let found = self.digits_text(format!("It must be {}", n(deduction.low))).size(24);
+                column = column.push(found.style(Color::from(self.accent())));
+            }
+            Some(Ok(deduction)) => {
+                let mut listed: Vec<String> = deduction.listed.iter().map(|&number| n(number)).collect();
+                if deduction.remaining > listed.len() {
+                    listed.push("…".to_string());
+                }
+                let summary = if deduction.everything {
+                    format!("That rules nothing out: all {} are still possible", n(deduction.remaining as u32))
+                } else {
+                    format!(
+                        "{} numbers fit, from {} to {}: {}",
+                        n(deduction.remaining as u32),
+                        n(deduction.low),
+                        n(deduction.high),
+                        listed.join(", ")
+                    )
+                };
+                column = column.push(self.digits_text(summary));
+            }
+            Some(Err(error)) => {
+                column = column.push(self.text(error.to_string()).style(self.theme().palette().danger));
+            }
+        }
+        column.into()
+    }
+
+    // Lay out the "what's new" panel, listing each release's changes
+    fn whats_new_view(&self) -> Element<Message> {
+        let notes = self.whats_new.iter().fold(Column::new().spacing(4), |column, release| {
+            let column = column.push(self.text(format!("What's new in {}", release.version)).size(24));
+            release
+                .changes
+                .iter()
+                .fold(column, |column, change| column.push(self.text(format!("- {}", change))))
+        });
+
+        let dismiss = Button::new(self.text("Got it")).on_press(Message::WhatsNewDismissed);
+
+        Column::new()
+            .push(notes)
+            .push(dismiss)
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .into()
+    }
+
+    // Lay out the badges panel, with unlocked achievements in the accent color
+    fn achievements_view(&self) -> Element<Message> {
+        let n = |value: u32| format_number(self.locale, value);
+        let stats = self.progress.stats;
+        let summary = self.digits_text(format!("{} wins, current streak {}", n(stats.wins), n(stats.streak)));
+
+        // Endless sessions are their own category, so their totals get a line of their own
+        let mut column = Column::new().push(summary).spacing(4).align_items(Alignment::Center);
+        let records = self.endless_records;
+        if records.sessions > 0 {
+            column = column.push(self.digits_text(format!(
+                "Endless: best {} solved, {} solved in {} guesses over {} sessions",
+                n(records.best_solved),
+                n(records.solved),
+                n(records.guesses),
+                n(records.sessions)
+            )));
+        }
+
+        // Chart the trend of guesses per win, or say why there's nothing to chart yet
+        let picker = Row::new()
+            .push(self.text("Trend:"))
+            .push(PickList::new(&trend::Window::ALL[..], Some(self.trend_window), Message::TrendWindowSelected).font(self.font()))
+            .spacing(10)
+            .align_items(Alignment::Center);
+        column = column.push(picker);
+        let trend = Trend::new(&self.trend_wins, self.trend_window);
+        column = if trend.is_empty() {
+            column.push(self.text("No wins logged yet - win a game to start the chart"))
+        } else {
+            let chart = TrendChart {
+                trend,
+                line: Color::from(self.accent()),
+                axes: self.theme().palette().text,
+            };
+            column.push(Canvas::new(chart).width(Length::Fixed(360.0)).height(Length::Fixed(trend::HEIGHT)))
+        };
+        column = column.push(self.difficulty_table());
+
+        ACHIEVEMENTS
+            .iter()
+            .fold(column, |column, achievement| {
+                let line = format!("{} - {}", achievement.name, achievement.description);
+                if self.progress.is_unlocked(achievement) {
+                    column.push(self.text(line).style(Color::from(self.accent())))
+                } else {
+                    column.push(self.text(format!("{} (locked)", line)))
+                }
+            })
+            .into()
+    }
+
+    // Lay out the logged wins by difficulty: how many, and their average guesses and time,
+    // with a dash where a difficulty has no wins to average
+    fn difficulty_table(&self) -> Element<Message> {
+        let n = |value: u32| format_number(self.locale, value);
+        let cell = |text: String| self.digits_text(text).width(Length::Fixed(90.0));
+        let header = Row::new()
+            .push(cell("Difficulty".to_string()))
+            .push(cell("Wins".to_string()))
+            .push(cell("Guesses".to_string()))
+            .push(cell("Time".to_string()));
+
+        analysis::by_difficulty(&self.trend_wins)
+            .into_iter()
+            .fold(Column::new().push(header).spacing(2), |column, stats| {
+                let attempts = stats.average_attempts.map_or("—".to_string(), |average| format!("{:.1}", average));
+                let time = stats.average_time.map_or("—".to_string(), |average| format_seconds(self.locale, average));
+                column.push(
+                    Row::new()
+                        .push(cell(stats.difficulty.to_string()))
+                        .push(cell(n(stats.wins)))
+                        .push(cell(attempts))
+                        .push(cell(time)),
+                )
+            })
+            .into()
+    }
+
+    // Lay out the board ranked by `metric`, with a picker to switch to the other
+    fn leaderboard_view(&self, metric: Metric) -> Element<Message> {
+        let picker = Row::new()
+            .push(self.text("Board:"))
+            .push(PickList::new(&Metric::ALL[..], Some(metric), Message::LeaderboardMetricSelected).font(self.font()))
+            .spacing(10)
+            .align_items(Alignment::Center);
+        let mut column = Column::new().push(picker).spacing(4).align_items(Alignment::Center);
+
+        let entries = self.leaderboards.board(metric);
+        if entries.is_empty() {
+            return column.push(self.text("No wins yet - win a game to set the first record")).into();
+        }
+
+        // Lead with what the board ranks by, so the order reads at a glance
+        let n = |value: u32| format_number(self.locale, value);
+        let line = |place: usize, entry: &leaderboard::Entry| {
+            let guesses = format!("{} {}", n(entry.attempts), if entry.attempts == 1 { "guess" } else { "guesses" });
+            let time = format_duration(entry.elapsed);
+            match metric {
+                Metric::Attempts => format!("{}. {} in {}, {}", n(place as u32), guesses, time, entry.date),
+                Metric::Time => format!("{}. {} with {}, {}", n(place as u32), time, guesses, entry.date),
+            }
+        };
+
+        // Pick out where the latest win went and the entries it moved down, in the accent color
+        // fading back to the text's, or held there when motion is reduced
+        let change = self.board_changes.iter().find(|placement| placement.metric == metric);
+        let strength = match self.board_fade {
+            Some(_) if self.reduce_motion() => 1.0,
+            Some(fade) => fade.strength(self.now),
+            None => 0.0,
+        };
+        let text = Rgb::from(self.theme().palette().text);
+        let tint = |amount: f32| Color::from(text.mix(self.accent(), amount * strength));
+
+        for (i, entry) in entries.iter().enumerate() {
+            let place = i + 1;
+            let row = match change {
+                Some(change) if strength > 0.0 && change.place == place => {
+                    self.digits_text(format!("{}  ★ new", line(place, entry))).style(tint(1.0))
+                }
+                Some(change) if strength > 0.0 && change.shifted_down(place) => {
+                    self.digits_text(format!("{}  ↓", line(place, entry))).style(tint(0.5))
+                }
+                _ => self.digits_text(line(place, entry)),
+            };
+            column = column.push(row);
+        }
+        if let Some(dropped) = change.and_then(|change| change.dropped).filter(|_| strength > 0.0) {
+            let bumped = format!("Bumped off the board: {}", line(leaderboard::MAX_ENTRIES + 1, &dropped));
+            column = column.push(self.digits_text(bumped).style(tint(0.5)));
+        }
+        column.into()
+    }
+
+    // Lay out the streak calendar for `month`, with the days the daily challenge was solved filled in
+    fn calendar_view(&self, month: Month) -> Element<Message> {
+        let today = Date::today();
+
+        let previous = Button::new(self.text("<")).on_press(Message::CalendarMonthMoved(false));
+        let mut next = Button::new(self.text(">"));
+        if month < today.month() {
+            next = next.on_press(Message::CalendarMonthMoved(true)); // Left disabled on the current month
+        }
+        let title = Container::new(self.text(month.to_string())).width(Length::Fixed(160.0)).center_x();
+        let header = Row::new().push(previous).push(title).push(next).spacing(10).align_items(Alignment::Center);
+
+        // Size every cell the same, so the weeks line up into columns
+        let cell = |content: Element<'static, Message>| {
+            Container::new(content).width(Length::Fixed(36.0)).height(Length::Fixed(28.0)).center_x().center_y()
+        };
+
+        let weekdays = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"]
+            .into_iter()
+            .fold(Row::new().spacing(4), |row, name| row.push(cell(self.text(name).into())));
+        let weeks = month.weeks().into_iter().fold(Column::new().push(weekdays).spacing(4), |column, week| {
+            let row = week.into_iter().fold(Row::new().spacing(4), |row, date| match date {
+                Some(date) => {
+                    let style = DayCell {
+                        accent: self.accent(),
+                        solved: self.completions.is_solved(date),
+                        today: date == today,
+                    };
+                    row.push(cell(self.digits_text(date.day.to_string()).into()).style(style::custom_container(style)))
+                }
+                None => row.push(cell(Space::with_width(Length::Shrink).into())),
+            });
+            column.push(row)
+        });
+
+        let streak = match self.completions.streak(today) {
+            0 => "Solve today's challenge to start a streak".to_string(),
+            1 => "Daily streak: 1 day".to_string(),
+            days => format!("Daily streak: {} days", format_number(self.locale, days)),
+        };
+
+        Column::new()
+            .push(header)
+            .push(weeks)
+            .push(self.text(streak))
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .into()
+    }
+
+    // Lay out the settings panel
+    fn settings_view(&self) -> Element<Message> {
+        // Create a checkbox that reveals how many values are still possible
+        let candidates_toggle = Checkbox::new(
+            "Show remaining candidates",
+            self.preferences.show_candidates,
+            Message::ShowCandidatesToggled,
+        )
+        .font(self.font());
+
+        // Create a checkbox for the binary and hex readout under the input
+        let bases_toggle = Checkbox::new(
+            "Show my guess in binary and hex",
+            self.preferences.show_bases,
+            Message::ShowBasesToggled,
+        )
+        .font(self.font());
+
+        // Create a picker for the theme, labelled so it reads as a setting
+        let theme_picker = Row::new()
+            .push(self.text("Theme:"))
+            .push(PickList::new(
+                &ThemeChoice::ALL[..],
+                Some(self.preferences.theme),
+                Message::ThemeSelected,
+            ).font(self.font()))
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        // Create a row of clickable accent swatches, outlining the current one
+        let swatches = ACCENT_SWATCHES.iter().fold(Row::new().spacing(8), |row, &color| {
+            row.push(
+                Button::new(Space::new(Length::Fixed(24.0), Length::Fixed(24.0)))
+                    .on_press(Message::AccentSelected(color))
+                    .padding(0)
+                    .style(style::custom_button(Swatch { color, selected: color == self.preferences.accent })),
+            )
+        });
+
+        // Create a hex field for any other accent, applied when Enter is pressed
+        let accent_input = TextInput::new("#5E7CE2", &self.accent_input)
+            .id(Field::Accent.id())
+            .on_input(Message::AccentHexChanged)
+            .on_submit(Message::AccentHexSubmitted)
+            .padding(5)
+            .width(Length::Fixed(100.0));
+        let mut accent_row = Row::new()
+            .push(self.text("Accent:"))
+            .push(accent_input)
+            .spacing(10)
+            .align_items(Alignment::Center);
+        if self.accent_input_invalid {
+            // Explain the rejected input next to the field; the current accent stays as it was
+            let danger = self.theme().palette().danger;
+            accent_row = accent_row.push(self.text("Use a hex color like #FF8800").style(danger));
+        }
+
+        // Create a checkbox for the mystery operation mode
+        let mystery_toggle = Checkbox::new(
+            "Mystery operation (from the next game)",
+            self.preferences.mystery_mode,
+            Message::MysteryModeToggled,
+        )
+        .font(self.font());
+
+        // Create a checkbox for letting close guesses win
+        let close_toggle = Checkbox::new(
+            format!("Close enough: guesses within {} win (from the next game)", rules::CLOSE_ENOUGH),
+            self.preferences.close_enough,
+            Message::CloseEnoughToggled,
+        )
+        .font(self.font());
+
+        // Create a picker for how far each guess must move, offering only what the range allows
+        let min_change_picker = Row::new()
+            .push(self.text("Each guess must change by:"))
+            .push(PickList::new(
+                MinChange::choices(MIN_NUMBER, MAX_NUMBER),
+                Some(self.preferences.min_change),
+                Message::MinChangeSelected,
+            ).font(self.font()))
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        // Create a picker for the surprise swap, saying plainly what it does
+        let swap_picker = Row::new()
+            .push(self.text("Surprise swap - the number changes once, to one that fits every clue:"))
+            .push(PickList::new(&SurpriseSwap::ALL[..], Some(self.preferences.surprise_swap), Message::SurpriseSwapSelected).font(self.font()))
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        // Create a checkbox for the per-guess time limit
+        let timed_toggle = Checkbox::new(
+            format!("Timed guesses: {} seconds for each guess, or the attempt is wasted", timer::ATTEMPT_LIMIT.as_secs()),
+            self.preferences.timed_guesses,
+            Message::TimedGuessesToggled,
+        )
+        .font(self.font());
+
+        // Create a checkbox for racing the ghost of the fastest win
+        let ghost_toggle =
+            Checkbox::new("Race the ghost of my fastest win", self.preferences.ghost_race, Message::GhostRaceToggled)
+                .font(self.font());
+
+        // Create a checkbox for the session guess budget, spelling out its rules
+        let budget_toggle = Checkbox::new(
+            format!(
+                "Guess budget ({} per session, one back every minute, {} back per win)",
+                budget::CAPACITY,
+                budget::WIN_BONUS
+            ),
+            self.preferences.guess_budget,
+            Message::GuessBudgetToggled,
+        )
+        .font(self.font());
+
+        // Create a checkbox for free hints after repeated wrong guesses, meant for children
+        let mercy_toggle = Checkbox::new(
+            format!("Mercy hints for younger players (a free clue every {} wrong guesses)", mercy::GUESSES_PER_HINT),
+            self.preferences.mercy_hints,
+            Message::MercyHintsToggled,
+        )
+        .font(self.font());
+
+        // Create a checkbox for assist mode, which competitive players may want off
+        let assist_toggle = Checkbox::new(
+            "Assist mode (hints adapt to how your recent games went)",
+            self.preferences.assist_hints,
+            Message::AssistHintsToggled,
+        )
+        .font(self.font());
+
+        // Create a checkbox for the guess assist on the number line
+        let guess_assist_toggle = Checkbox::new(
+            "Guess assist (grey out numbers already ruled out)",
+            self.preferences.guess_assist,
+            Message::GuessAssistToggled,
+        )
+        .font(self.font());
+
+        // Create the profile selector: a mode, the profile it switches to, and a way to change that profile
+        let mut profile_row = Row::new()
+            .push(self.text("Settings profile for"))
+            .push(self.focusable(
+                Setting::ProfileMode,
+                PickList::new(&profiles::Mode::ALL[..], Some(self.profile_mode), Message::ProfileModeSelected).font(self.font()),
+            ))
+            .push(self.text("games:"))
+            .push(self.focusable(
+                Setting::Profile,
+                PickList::new(self.profiles.choices(), Some(self.profiles.choice(self.profile_mode)), Message::ProfileChosen)
+                    .font(self.font()),
+            ))
+            .spacing(10)
+            .align_items(Alignment::Center);
+        if let Some(name) = self.profiles.choice(self.profile_mode).0 {
+            let save = Button::new(self.text(format!("Save these settings to {}", name))).on_press(Message::ProfileSaved);
+            profile_row = profile_row.push(self.focusable(Setting::ProfileSave, save));
+        }
+
+        // Create a checkbox for the proximity tones, warning that they're a clue in themselves
+        let tones_toggle = Checkbox::new(
+            "Proximity tones (higher pitch when closer - partly gives the answer away)",
+            self.preferences.proximity_tones,
+            Message::ProximityTonesToggled,
+        )
+        .font(self.font());
+
+        // Create the play statistics opt-in, with its insights and deletion buttons
+        let analytics_toggle = Checkbox::new(
+            "Keep play statistics on this computer",
+            self.preferences.analytics,
+            Message::AnalyticsToggled,
+        )
+        .font(self.font());
+        let mut analytics_row = Row::new()
+            .push(self.focusable(Setting::Analytics, analytics_toggle))
+            .spacing(10)
+            .align_items(Alignment::Center);
+        if self.preferences.analytics {
+            let insights_label = if self.insights.is_some() { "Hide insights" } else { "Insights" };
+            let insights_button = Button::new(self.text(insights_label)).on_press(Message::InsightsToggled);
+            analytics_row = analytics_row.push(self.focusable(Setting::Insights, insights_button));
+        }
+        let delete_button = Button::new(self.text("Delete statistics")).on_press(Message::AnalyticsDeleted);
+        analytics_row = analytics_row.push(self.focusable(Setting::DeleteStatistics, delete_button));
+
+        // Create a button for wiping the records, which asks first, and say if it last fell short
+        let reset_button = Button::new(self.text("Reset records")).on_press(Message::RecordsResetRequested);
+        let mut reset_row = Column::new()
+            .push(self.focusable(Setting::ResetRecords, reset_button))
+            .spacing(5)
+            .align_items(Alignment::Center);
+        if let Some(error) = &self.reset_error {
+            reset_row = reset_row.push(self.text(error).size(16).style(self.theme().palette().danger));
+        }
+
+        // Create a checkbox for the spectator feed, saying where streaming software can read it
+        let spectator_label = match spectator::path() {
+            Some(path) => format!("Spectator feed for streaming (written to {})", path.display()),
+            None => "Spectator feed for streaming (no folder to write it to)".to_string(),
+        };
+        let spectator_toggle =
+            Checkbox::new(spectator_label, self.preferences.spectator_feed, Message::SpectatorFeedToggled).font(self.font());
+
+        // Create a checkbox for desktop notifications on wins
+        let notify_toggle = Checkbox::new(
+            "Notify me of wins while in the background",
+            self.preferences.notify_on_win,
+            Message::NotifyOnWinToggled,
+        )
+        .font(self.font());
+
+        // Create a picker for how numbers are grouped, saying what "System" resolved to
+        let mut number_format_picker = Row::new()
+            .push(self.text("Numbers:"))
+            .push(
+                PickList::new(&LocaleChoice::ALL[..], Some(self.preferences.number_format), Message::NumberFormatSelected)
+                    .font(self.font()),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center);
+        if self.preferences.number_format == LocaleChoice::System {
+            let sample = format!("({})", LocaleChoice::Fixed(self.system_locale));
+            number_format_picker = number_format_picker.push(self.digits_text(sample));
+        }
+
+        // Create a picker for the UI font, and a checkbox for monospaced numbers
+        let font_picker = Row::new()
+            .push(self.text("Font:"))
+            .push(PickList::new(
+                &FontChoice::ALL[..],
+                Some(self.preferences.font),
+                Message::FontSelected,
+            ).font(self.font()))
+            .spacing(10)
+            .align_items(Alignment::Center);
+        let monospace_toggle = Checkbox::new(
+            "Monospace digits",
+            self.preferences.monospace_digits,
+            Message::MonospaceDigitsToggled,
+        )
+        .font(self.font());
+
+        // Create a checkbox for color scales that don't rely on telling red from green
+        let colorblind_toggle = Checkbox::new(
+            "Colorblind-friendly colors",
+            self.preferences.colorblind,
+            Message::ColorblindToggled,
+        )
+        .font(self.font());
+
+        // Create a checkbox for pinning the window, noting where the platform can't do it
+        let on_top_label = if desktop::always_on_top_supported() {
+            "Keep window on top (Ctrl+Shift+P)"
+        } else {
+            "Keep window on top (not supported here)"
+        };
+        let on_top_toggle = Checkbox::new(on_top_label, self.preferences.always_on_top, Message::AlwaysOnTopToggled)
+            .font(self.font());
+
+        // Create a checkbox for the compact strip layout
+        let compact_toggle = Checkbox::new(
+            "Compact layout (Ctrl+Shift+M)",
+            self.preferences.compact,
+            Message::CompactToggled,
+        )
+        .font(self.font());
+
+        // Create a checkbox for mirroring the controls
+        let left_handed_toggle = Checkbox::new(
+            "Left-handed layout",
+            self.preferences.left_handed,
+            Message::LeftHandedToggled,
+        )
+        .font(self.font());
+
+        // Create a checkbox for one-key play, and a picker for its timing while it's on
+        let one_key_toggle = Checkbox::new(
+            "One-key play: tap Space to count up, double-tap or hold it to guess",
+            self.preferences.one_key,
+            Message::OneKeyToggled,
+        )
+        .font(self.font());
+        let one_key_picker = Row::new()
+            .push(self.text("One-key timing:"))
+            .push(
+                PickList::new(&onekey::Timing::ALL[..], Some(self.preferences.one_key_timing), Message::OneKeyTimingSelected)
+                    .font(self.font()),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        // Create a picker for how long inactivity lasts before the clock pauses
+        let idle_picker = Row::new()
+            .push(self.text("Pause timer when idle:"))
+            .push(PickList::new(
+                &IdleTimeout::ALL[..],
+                Some(self.preferences.idle_timeout),
+                Message::IdleTimeoutSelected,
+            ).font(self.font()))
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        // Create a picker for what happens on launch with a game left unfinished
+        let startup_picker = Row::new()
+            .push(self.text("On launch:"))
+            .push(PickList::new(&Startup::ALL[..], Some(self.preferences.startup), Message::StartupSelected).font(self.font()))
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        // Create a picker for animated or instant feedback, saying what "System" resolved to
+        let mut motion_picker = Row::new()
+            .push(self.text("Feedback:"))
+            .push(PickList::new(
+                &MotionChoice::ALL[..],
+                Some(self.preferences.motion),
+                Message::MotionSelected,
+            ).font(self.font()))
+            .spacing(10)
+            .align_items(Alignment::Center);
+        if self.preferences.motion == MotionChoice::System {
+            let resolved = if self.reduce_motion() { "(reduced motion is on)" } else { "(animated)" };
+            motion_picker = motion_picker.push(self.text(resolved));
+        }
+
+        // Create the spoken feedback setting, with the rate and voice where the platform offers them;
+        // with no speech engine the setting says why and stays off
+        let speech_label = match &self.speech {
+            Some(Err(reason)) => format!("Read the feedback aloud (unavailable: {})", reason),
+            _ => "Read the feedback aloud".to_string(),
+        };
+        let speech_toggle = Checkbox::new(
+            speech_label,
+            self.preferences.speak_feedback && !matches!(self.speech, Some(Err(_))),
+            Message::SpeakFeedbackToggled,
+        )
+        .font(self.font());
+        let mut speech_controls = Column::new().push(self.focusable(Setting::SpeakFeedback, speech_toggle)).spacing(10);
+        if let Some(engine) = self.speech_engine() {
+            if engine.can_set_rate {
+                let rate_picker = Row::new()
+                    .push(self.text("Speaking rate:"))
+                    .push(PickList::new(&SpeechRate::ALL[..], Some(self.preferences.speech_rate), Message::SpeechRateSelected).font(self.font()))
+                    .spacing(10)
+                    .align_items(Alignment::Center);
+                speech_controls = speech_controls.push(self.focusable(Setting::SpeechRate, rate_picker));
+            }
+            if engine.voices.len() > 1 {
+                let voice_picker = Row::new()
+                    .push(self.text("Voice:"))
+                    .push(
+                        PickList::new(
+                            engine.voices.clone(),
+                            Some(engine.voice(&self.preferences.speech_voice)),
+                            Message::SpeechVoiceSelected,
+                        )
+                        .font(self.font()),
+                    )
+                    .spacing(10)
+                    .align_items(Alignment::Center);
+                speech_controls = speech_controls.push(self.focusable(Setting::SpeechVoice, voice_picker));
+            }
+        }
+
+        // Create a button that brings back the release notes after they were dismissed
+        let whats_new_button = Button::new(self.text("What's new")).on_press(Message::WhatsNewOpened);
+
+        // Every control but the text field is wrapped to show keyboard focus; the field has its own
+        Column::new()
+            .push(self.focusable(Setting::ShowCandidates, candidates_toggle))
+            .push(self.focusable(Setting::ShowBases, bases_toggle))
+            .push(self.focusable(Setting::MysteryMode, mystery_toggle))
+            .push(self.focusable(Setting::CloseEnough, close_toggle))
+            .push(self.focusable(Setting::MinChange, min_change_picker))
+            .push(self.focusable(Setting::SurpriseSwap, swap_picker))
+            .push(self.focusable(Setting::TimedGuesses, timed_toggle))
+            .push(self.focusable(Setting::GhostRace, ghost_toggle))
+            .push(self.focusable(Setting::GuessBudget, budget_toggle))
+            .push(self.focusable(Setting::MercyHints, mercy_toggle))
+            .push(self.focusable(Setting::AssistHints, assist_toggle))
+            .push(self.focusable(Setting::GuessAssist, guess_assist_toggle))
+            .push(profile_row)
+            .push(self.focusable(Setting::ProximityTones, tones_toggle))
+            .push(self.focusable(Setting::IdleTimeout, idle_picker))
+            .push(self.focusable(Setting::Startup, startup_picker))
+            .push(self.focusable(Setting::Motion, motion_picker))
+            .push(speech_controls)
+            .push(self.focusable(Setting::NotifyOnWin, notify_toggle))
+            .push(self.focusable(Setting::SpectatorFeed, spectator_toggle))
+            .push(self.focusable(Setting::AlwaysOnTop, on_top_toggle))
+            .push(self.focusable(Setting::Compact, compact_toggle))
+            .push(self.focusable(Setting::LeftHanded, left_handed_toggle))
+            .push(self.focusable(Setting::OneKey, one_key_toggle))
+            .push(self.focusable(Setting::OneKeyTiming, one_key_picker))
+            .push(self.focusable(Setting::Theme, theme_picker))
+            .push(self.focusable(Setting::Font, font_picker))
+            .push(self.focusable(Setting::NumberFormat, number_format_picker))
+            .push(self.focusable(Setting::MonospaceDigits, monospace_toggle))
+            .push(self.focusable(Setting::Colorblind, colorblind_toggle))
+            .push(self.focusable(Setting::Swatches, swatches))
+            .push(accent_row)
+            .push(analytics_row)
+            .push(reset_row)
+            .push(self.focusable(Setting::WhatsNew, whats_new_button))
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .into()
+    }
+
+    // Keep the window above others or release it, remembering the choice
+    fn set_always_on_top(&mut self, on_top: bool) -> Command<Message> {
+        // Leave the setting alone where the request would be silently ignored
+        if !desktop::always_on_top_supported() {
+            tracing::warn!("Always-on-top is not supported by this window manager");
+            return Command::none();
+        }
+
+        self.update_preferences(|preferences| preferences.always_on_top = on_top);
+        window::change_always_on_top(on_top)
+    }
+
+    // Switch between the full and compact layouts, resizing the window to match
+    fn set_compact(&mut self, compact: bool) -> Command<Message> {
+        self.update_preferences(|preferences| preferences.compact = compact);
+
+        let (width, height) = if compact { COMPACT_WINDOW_SIZE } else { FULL_WINDOW_SIZE };
+        window::resize(width, height)
+    }
+
+    // Record player activity, resuming the clock if it was paused for inactivity
+    fn note_interaction(&mut self) {
+        let now = Instant::now();
+        self.last_interaction = now;
+        self.now = now;
+
+        if self.idle_paused {
+            self.idle_paused = false;
+            self.stopwatch.start(now);
+        }
+    }
+
+    // Apply a change to the preferences and save them straight away
+    fn update_preferences(&mut self, change: impl FnOnce(&mut Preferences)) {
+        change(&mut self.preferences);
+        self.preferences.save();
+    }
+}
+
+// Implement the `Application` trait for `GuessingGame`
+impl Application for GuessingGame {
+    // Specify the executor type for handling asynchronous tasks
+    type Executor = iced::executor::Default;
+    // Define the type of messages the application will handle
+    type Message = Message;
+    // Define the theme for the application
+    type Theme = Theme; // Ensure Theme is imported correctly
+    // Define any flags that might be passed when initializing the application
+    type Flags = Launch; // Command-line options such as where to record the session
+
+    // Method to initialize the application
+    fn new(launch: Self::Flags) -> (Self, Command<Self::Message>) {
+        let mut game = match launch.record {
+            // Draw every random number from a recorded seed, so a replay makes the same draws
+            Some(path) => {
+                let seed = rand::random::<u32>();
+                let mut game = GuessingGame::with_number_source(Box::new(SeededSource::new(u64::from(seed))));
+                match Recorder::create(&path, seed, game.locale, &game.preferences) {
+                    Ok(recorder) => game.recorder = Some(recorder),
+                    Err(error) => tracing::error!(path = %path.display(), %error, "Could not start recording"),
+                }
+                game
+            }
+            // Draw real random secrets; other sources can be passed to `with_number_source`
+            None => GuessingGame::with_number_source(Box::new(ThreadRngSource)),
+        };
+        game.track(analytics::Event::SessionStarted);
+        game.debug_overlay = launch.debug_overlay;
+        game.debug_voided = launch.debug_overlay;
+        game.shared_data = launch.shared_data;
+
+        // Pick the unfinished game straight back up if that's how the player likes to start, and
+        // there is one; a recorded session keeps the offer instead, since its replay never sees it.
+        // Otherwise the fresh game is the one being started.
+        let resume = game.preferences.startup == Startup::Resume && game.recorder.is_none();
+        match if resume { game.pending_recovery.take() } else { None } {
+            Some(snapshot) => {
+                tracing::info!(saved = snapshot.saved, "Resuming the unfinished game");
+                game.restore(snapshot);
+            }
+            None => {
+                game.track(analytics::Event::GameStarted(game.mode_key()));
+                game.log_started();
+            }
+        }
+
+        // Re-pin the window if it was kept on top last time
+        let on_top = if game.preferences.always_on_top && desktop::always_on_top_supported() {
+            window::change_always_on_top(true)
+        } else {
+            Command::none()
+        };
+
+        // Shrink straight to the strip if the compact layout was left on
+        let compact = if game.preferences.compact {
+            window::resize(COMPACT_WINDOW_SIZE.0, COMPACT_WINDOW_SIZE.1)
+        } else {
+            Command::none()
+        };
+
+        // Start with the cursor in the guess input, ready to type
+        let focus = text_input::focus(Field::Guess.id());
+
+        (game, Command::batch([on_top, compact, focus]))
+    }
+
+    // Method to set the window title
+    fn title(&self) -> String {
+        String::from("Guessing Game") // Title displayed on the application window
+    }
+
+    // Method to handle updates based on incoming messages/events, timing each one when the
+    // latency stats are on
+    fn update(&mut self, message: Message) -> Command<Message> {
+        let Some(latency) = self.latency.clone() else {
+            return self.handle(message);
+        };
+        let name = replay::name(&message);
+        let started = Instant::now();
+        let command = self.handle(message);
+        latency.borrow_mut().record_update(name, started.elapsed());
+        command
+    }
+
+    // Method to choose the theme, honouring a manual choice over the OS preference
+    fn theme(&self) -> Theme {
+        let base = if appearance::is_dark(self.preferences.theme, self.system_theme) {
+            Palette::DARK
+        } else {
+            Palette::LIGHT
+        };
+
+        // Swap in the accent so focus rings and other primary-colored widgets follow it
+        let mut palette = Palette {
+            primary: self.accent().into(),
+            ..base
+        };
+        // Built around the OS accent, the background takes a faint tint of it too, like native themes
+        if let Some(accent) = self.system_accent.filter(|_| self.preferences.theme == ThemeChoice::SystemAccent) {
+            palette.background = Rgb::from(base.background).mix(accent, 0.05).into();
+        }
+        Theme::custom(palette)
+    }
+
+    // Method to listen for external events
+    fn subscription(&self) -> Subscription<Message> {
+        // Listen for Space going down and up while one-key play is on; the events are stamped
+        // here, so the taps are timed from when they happened rather than when they're handled
+        let one_key = if self.preferences.one_key {
+            iced::subscription::events_with(|event, _| match event {
+                Event::Keyboard(keyboard::Event::KeyPressed {
+                    key_code: KeyCode::Space,
+                    modifiers,
+                }) if modifiers.is_empty() => Some(Message::OneKeyPressed(Instant::now())),
+                Event::Keyboard(keyboard::Event::KeyReleased {
+                    key_code: KeyCode::Space,
+                    ..
+                }) => Some(Message::OneKeyReleased(Instant::now())),
+                _ => None,
+            })
+        } else {
+            Subscription::none()
+        };
+
+        // Only poll the OS theme while it actually decides what is shown
+        let theme_poll = if self.preferences.theme.follows_system() {
+            iced::time::every(SYSTEM_THEME_POLL_INTERVAL).map(|_| Message::SystemThemePoll)
+        } else {
+            Subscription::none()
+        };
+
+        // Track window focus so notifications are only sent when the game is in the background,
+        // and listen for the global keyboard shortcuts
+        let events = iced::subscription::events_with(|event, status| match event {
+            Event::Window(window::Event::Focused) => Some(Message::WindowFocusChanged(true)),
+            Event::Window(window::Event::Unfocused) => Some(Message::WindowFocusChanged(false)),
+            Event::Window(window::Event::CloseRequested) => Some(Message::CloseRequested),
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::P,
+                modifiers,
+            }) if modifiers.control() && modifiers.shift() => Some(Message::AlwaysOnTopShortcut),
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::M,
+                modifiers,
+            }) if modifiers.control() && modifiers.shift() => Some(Message::CompactShortcut),
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::Tab,
+                modifiers,
+            }) if !modifiers.control() && !modifiers.alt() => Some(Message::FocusMoved(!modifiers.shift())),
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::D,
+                modifiers,
+            }) if modifiers.control() && modifiers.shift() => Some(Message::DebugOverlayToggled),
+            // Space and the arrows act on a focused setting, unless a text input took the key
+            Event::Keyboard(keyboard::Event::KeyPressed { key_code, modifiers })
+                if status == event::Status::Ignored && !modifiers.control() && !modifiers.alt() =>
+            {
+                match key_code {
+                    KeyCode::Space => Some(Message::SettingKey(SettingKey::Activate)),
+                    KeyCode::Left | KeyCode::Up => Some(Message::SettingKey(SettingKey::Previous)),
+                    KeyCode::Right | KeyCode::Down => Some(Message::SettingKey(SettingKey::Next)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        });
+
+        // Tick the game clock while it runs, to redraw it and watch for inactivity, while
+        // toasts are up so they disappear on time, while spent guesses are coming back, while
+        // the end-of-game reveal plays, and while the next tournament round waits to start
+        let refilling = self.preferences.guess_budget && self.budget.is_refilling();
+        let animating = self.reveal.is_some()
+            || self.confetti.is_some()
+            || self.peek_until.is_some()
+            || self.next_round_at.is_some()
+            || self.gauge.is_moving(self.now)
+            || self.board_fade.is_some_and(|fade| !self.reduce_motion() && fade.is_fading(self.now));
+        let clock = if self.stopwatch.is_running() || !self.toasts.is_empty() || refilling || animating {
+            iced::time::every(CLOCK_TICK_INTERVAL).map(Message::Tick)
+        } else {
+            Subscription::none()
+        };
+
+        // Save recovery snapshots only while there is something new to save
+        let autosave = if self.recovery_dirty {
+            iced::time::every(RECOVERY_SAVE_INTERVAL).map(|_| Message::RecoveryAutosave)
+        } else {
+            Subscription::none()
+        };
+
+        // Keep the spectator feed current only while it's on
+        let spectator = if self.preferences.spectator_feed {
+            iced::time::every(spectator::REFRESH_INTERVAL).map(|_| Message::SpectatorRefresh)
+        } else {
+            Subscription::none()
+        };
+
+        // Step a replay forward only while it plays by itself
+        let playback = if self.playback.as_ref().is_some_and(|playback| playback.playing) {
+            iced::time::every(playback::STEP_INTERVAL).map(|_| Message::PlaybackAdvanced)
+        } else {
+            Subscription::none()
+        };
+
+        // Play the demo gallery on while it's open
+        let gallery = if self.gallery.is_some() {
+            iced::time::every(gallery::STEP_INTERVAL).map(|_| Message::GalleryAdvanced)
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch([theme_poll, events, one_key, clock, autosave, spectator, playback, gallery])
+    }
+
+
+    // Method to define the layout and appearance of the application's UI, timed like `update`;
+    // only building the widgets is timed, since iced lays them out and draws them afterwards
+    fn view(&self) -> Element<Message> {
+        let Some(latency) = self.latency.as_ref() else {
+            return self.layout();
+        };
+        let started = Instant::now();
+        let element = self.layout();
+        latency.borrow_mut().record_view(started.elapsed());
+        element
+    }
+}
+
+impl GuessingGame {
+    // Handle an incoming message or event; `update` wraps this to time it when asked to
+    fn handle(&mut self, message: Message) -> Command<Message> {
+        // Write the message down first when recording, exactly as it arrived
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.record(&message);
+        }
+
+        // Count the message for the debug overlay, listing all but the clock's ticks, which
+        // would push everything else out within a second
+        self.updates += 1;
+        if !matches!(message, Message::Tick(_)) {
+            self.recent_messages.push(replay::name(&message));
+        }
+
+        self.dispatch(message)
+    }
+
+    // Act on a message that has already been recorded and counted
+    fn dispatch(&mut self, message: Message) -> Command<Message> {
+        // A key pressed on a focused setting stands for the message its control sends; it's
+        // recorded as the key, so a replay works the message out again from the same state
+        let message = match message {
+            Message::SettingKey(key) => match self.setting_message(key) {
+                Some(message) => message,
+                None => return Command::none(), // Nothing focused that the key applies to
+            },
+            // Space in one-key play stands for a step or a guess in the same way, worked out
+            // from when it went down and came up
+            Message::OneKeyPressed(at) => match self.one_key_message(true, at) {
+                Some(message) => message,
+                None => return Command::none(),
+            },
+            Message::OneKeyReleased(at) => match self.one_key_message(false, at) {
+                Some(message) => message,
+                None => return Command::none(),
+            },
+            message => message,
+        };
+
+        // Anything that would throw away a tournament in progress waits for the player to say so,
+        // leaving the tournament exactly as it was until they do
+        if message.ends_tournament() && self.tournament.as_ref().is_some_and(|tournament| !tournament.is_finished()) {
+            self.tournament_exit = Some(Box::new(message));
+            return Command::none();
+        }
+
+        // Any real interaction counts as activity and ends an idle pause
+        if message.is_interaction() {
+            self.note_interaction();
+        }
+
+        // Hide a peek whose time is up before anything else, going by the real clock so that
+        // a stalled tick can never leave the secret on screen
+        if self.peek_until.is_some_and(|until| Instant::now() >= until) {
+            self.peek_until = None;
+        }
+
+        // Log everything the message leads to under the current game's span
+        let round_span = self.round_span.clone();
+        let _round = round_span.enter();
+
+        match message {
+            // Handle changes in the guess input field
+            Message::GuessInputChanged(value) => {
+                self.guess = value; // Update the current guess with the new input
+                self.focused_field = Some(Field::Guess); // Typing means the field has focus
+
+                // The clock starts with the first keystroke of a game
+                if !self.won && !self.gave_up && !self.stopwatch.has_started() {
+                    self.stopwatch.start(Instant::now());
+                }
+            }
+            // Handle the guess being stepped, which edits the field in place like typing would
+            Message::GuessStepped(by) => {
+                if self.won || self.gave_up {
+                    return Command::none();
+                }
+                self.guess = stepper::step(self.locale, &self.guess, by, self.low, self.high);
+                if !self.stopwatch.has_started() {
+                    self.stopwatch.start(Instant::now());
+                }
+            }
+            // Put a number picked on the number line in the input, ready to be guessed
+            Message::NumberPicked(number) => {
+                if self.won || self.gave_up {
+                    return Command::none();
+                }
+                self.guess = format_number(self.locale, number);
+                if !self.stopwatch.has_started() {
+                    self.stopwatch.start(Instant::now());
+                }
+            }
+            // Handle the event when the "Guess" button is pressed
+            Message::GuessButtonPressed => {
+                // The game is over once won or given up; only a new game accepts guesses again
+                if self.won || self.gave_up {
+                    return Command::none();
+                }
+                let mut notified = Command::none(); // Waits on the win notification, if one is shown
+
+                // Attempt to parse the guess input, accepting the locale's thousands separators,
+                // and failing that work it out as a sum such as "2^6"; only a stray character
+                // can be part of a sum, so emptiness, a lone minus sign and overflow are reported as they are
+                let mut sum = None;
+                let guess: u32 = match parse_number(self.locale, &self.guess) {
+                    Ok(num) => num, // Successfully parsed number
+                    Err(ParseError::InvalidCharacter(_)) if expression::looks_like_sum(&self.guess) => match expression::evaluate(&self.guess) {
+                        Ok(value) => {
+                            sum = Some(self.guess.trim().to_string());
+                            value
+                        }
+                        Err(error) => {
+                            tracing::debug!(?error, "Rejected a sum that couldn't be worked out");
+                            self.feedback = Feedback::BadSum(error);
+                            return Command::none();
+                        }
+                    },
+                    Err(error) => {
+                        // If parsing fails, update the feedback to say what's wrong with the number
+                        tracing::debug!(?error, "Rejected a guess that isn't a number");
+                        self.feedback = Feedback::InvalidNumber(error);
+                        return Command::none(); // Exit the update without further processing
+                    }
+                };
+
+                // Refuse a guess too close to the last one under the minimum-change rule, before
+                // it costs anything; a setting the range can't support is treated as off
+                let min_change = self.preferences.min_change;
+                let delta = min_change.delta().filter(|_| min_change.fits(MIN_NUMBER, MAX_NUMBER));
+                if let (Some(delta), Some(&previous)) = (delta, self.history.last()) {
+                    if guess.abs_diff(previous) < delta {
+                        tracing::debug!(guess, previous, delta, "Rejected a guess too close to the last one");
+                        self.feedback = Feedback::TooClose { guess, previous, delta };
+                        return Command::none();
+                    }
+                }
+
+                // Spend a guess from the session budget, refusing the guess once it's empty
+                if self.preferences.guess_budget && !self.budget.spend(Instant::now()) {
+                    tracing::debug!(guess, "Rejected a guess with the budget exhausted");
+                    return Command::none();
+                }
+
+                let repeated = self.history.contains(&guess); // Repeats count as guesses, but not towards automatic hints
+                let clock = self.stopwatch.elapsed(Instant::now()); // Paused time never reaches the clock
+                self.attempt_started = clock; // The next guess's time starts now
+                if let Some(sum) = sum {
+                    self.sums.insert(self.history.len(), sum);
+                }
+
+                // Play the guess through the game's rules, the same step any other front end uses:
+                // it counts the guess, judges it under this round's rule, which decides the win and
+                // the feedback, and narrows the bounds on the raw numbers. The history is lent to
+                // the step rather than copied, and comes back with the guess on the end.
+                let state = game::State {
+                    secret: self.secret_number,
+                    min: MIN_NUMBER,
+                    max: MAX_NUMBER,
+                    low: self.low,
+                    high: self.high,
+                    attempts: self.attempts,
+                    history: std::mem::take(&mut self.history),
+                    last: None, // A won game takes no more guesses, so this one isn't won yet
+                };
+                let played = game::step_under(state, game::Input::Guess(guess), self.rule.as_ref());
+                let outcome = played.last.expect("a guess in a game not yet won is always judged");
+                let (low, high) = (played.low, played.high); // Taken up below, once the feedback is settled
+                self.attempts = played.attempts;
+                self.history = played.history;
+
+                self.guess_times.push(Some(clock.saturating_sub(self.thinking_since)));
+                self.thinking_since = clock;
+                self.mercy_announcement = None;
+                self.recovery_dirty = true;
+                tracing::debug!(guess, attempts = self.attempts, ?outcome, "Guess received");
+
+                // A game counts as played once its first guess is in
+                if self.history.len() == 1 {
+                    let mode = if self.daily.is_some() {
+                        PlayMode::Daily
+                    } else if self.pass_and_play {
+                        PlayMode::PassAndPlay
+                    } else {
+                        PlayMode::Classic
+                    };
+                    self.remember_played(mode);
+                }
+                gamelog::record(gamelog::Event::Guessed { guess, outcome });
+
+                // Remember how close the guess was, for tinting the input and filling the
+                // thermometer; guesses outside the range count as being as far away as possible.
+                // Nothing moves the thermometer once the game is over, so it keeps the last reading.
+                let range = (MAX_NUMBER - MIN_NUMBER).max(1);
+                let distance = self.secret_number.distance(guess).min(range);
+                let proximity = thermometer::fill_fraction(distance, range);
+                self.proximity = Some(proximity);
+                self.gauge.set(proximity, Instant::now());
+
+                // Sound out how close a wrong guess was; wins are announced without a tone
+                if outcome != Outcome::Win && self.preferences.proximity_tones && !self.replaying {
+                    audio::play_tone(audio::proximity_frequency(distance, range));
+                }
+
+                // Count distinct wrong guesses towards automatic hints, volunteering one when due
+                if outcome != Outcome::Win && !repeated {
+                    self.wrong_guesses += 1;
+                    self.volunteer_mercy_hint();
+                }
+
+                match outcome {
+                    Outcome::TooSmall | Outcome::TooBig => {
+                        self.feedback = self.wrong_guess_feedback(guess);
+                        (self.low, self.high) = (low, high);
+                        self.surprise_swap(guess, outcome);
+                    }
+                    Outcome::Win => {
+                        // Winning guess, which may only be close to the secret; reveal the secret itself
+                        let secret = self.secret_number.reveal();
+                        self.feedback = Feedback::Win { secret };
+                        (self.low, self.high) = (low, high); // Only the secret itself remains
+                        self.won = true;
+                        self.stopwatch.pause(Instant::now()); // Freeze the clock on the final time
+                        self.start_reveal(guess);
+
+                        self.track(analytics::Event::GameWon(self.attempts));
+                        self.log_ended(End::Won);
+                        tracing::info!(attempts = self.attempts, elapsed = ?self.stopwatch.elapsed(self.now), "Game won");
+                        if self.preferences.guess_budget {
+                            self.budget.reward_win();
+                        }
+
+                        // Count the win towards achievements, announcing any it unlocks
+                        let result = GameResult {
+                            attempts: self.attempts,
+                            optimal: analysis::optimal_guesses(MIN_NUMBER, MAX_NUMBER),
+                            elapsed: self.stopwatch.elapsed(self.now),
+                        };
+                        if self.can_count() {
+                            let now = Instant::now();
+                            let unlocked = self.progress.record_win(&result);
+                            self.toasts.extend(unlocked.into_iter().map(|achievement| (achievement, now)));
+
+                            // Offer the win to both boards at once, so they never disagree about it
+                            let entry = leaderboard::Entry {
+                                attempts: result.attempts,
+                                elapsed: result.elapsed,
+                                date: Date::today(),
+                            };
+                            // A win too quick to be real, or timed with the clock paused for the player
+                            // being away, still counts for guesses, but not for time
+                            self.time_refused = leaderboard::time_eligibility(&entry, self.idled).err();
+                            if let Some(reason) = self.time_refused {
+                                tracing::info!(?reason, "Win kept off the time-attack board");
+                            }
+                            self.board_changes = self.leaderboards.record(entry, self.idled);
+                            // Already on screen, the changes start fading at once; otherwise when it's opened
+                            self.board_fade = self.leaderboard.map(|_| Fade::new(Instant::now()));
+                            for placement in &self.board_changes {
+                                tracing::info!(board = placement.metric.key(), place = placement.place, "Win made the leaderboard");
+                                // The new fastest win is the ghost to race from now on
+                                if (placement.metric, placement.place) == (Metric::Time, 1) {
+                                    if let Some(ghost) = Ghost::from_times(&self.guess_times) {
+                                        ghost.save();
+                                        self.ghost = Some(ghost);
+                                    }
+                                }
+                            }
+
+                            // Remember the win for assist mode's skill estimate
+                            self.recent_wins.push(result.attempts);
+                            if self.recent_wins.len() > skill::RECENT_GAMES {
+                                self.recent_wins.remove(0);
+                            }
+
+                            // Mark the day solved on the streak calendar
+                            if let Some(date) = self.daily {
+                                self.completions.record(date);
+                            }
+                        }
+
+                        // Bank the round in the tournament, lining up the next one if any are left
+                        let round = Round {
+                            attempts: self.attempts,
+                            score: score::score(self.attempts, self.points_spent),
+                            elapsed: result.elapsed,
+                        };
+                        if let Some(tournament) = &mut self.tournament {
+                            tournament.record(round);
+                            if !tournament.is_finished() {
+                                self.next_round_at = Some(Instant::now() + tournament::ROUND_BREAK);
+                            }
+                        }
+
+                        // Mark the hole's strokes on the course's scorecard
+                        if let Some(course) = &mut self.course {
+                            course.record(self.attempts);
+                        }
+
+                        // A finished game has nothing left to recover
+                        self.recovery_dirty = false;
+                        recovery::discard();
+
+                        // Let the player know about the win if they've switched to another window
+                        if self.preferences.notify_on_win && !self.window_focused && !self.replaying {
+                            notified = Command::perform(
+                                notification::notify_win(self.feedback.render(self.locale)),
+                                Message::WinNotificationAnswered,
+                            );
+                        }
+                    }
+                }
+
+                // Clear the input field after processing the guess
+                self.guess.clear();
+
+                // In an endless session, a win rolls straight over to the next secret
+                if self.won && self.endless.is_some() {
+                    self.start_endless_round();
+                }
+                return Command::batch([self.speak_feedback(), notified]);
+            }
+            // Handle the round's free warm-up guess: it gets feedback and narrows the bounds, but
+            // isn't an attempt, so it's never in the history, the score or the budget, and can't win
+            Message::WarmUpGuessed => {
+                if self.won || self.gave_up || self.warmup_used {
+                    return Command::none();
+                }
+                let guess = match parse_number(self.locale, &self.guess) {
+                    Ok(guess) => guess,
+                    Err(error) => {
+                        self.feedback = Feedback::InvalidNumber(error);
+                        return Command::none(); // A typo doesn't use up the warm-up
+                    }
+                };
+
+                let outcome = self.secret_number.judge(guess, self.rule.as_ref());
+                tracing::debug!(guess, ?outcome, "Warm-up guess received");
+                self.warmup_used = true;
+                self.feedback = Feedback::WarmUp { guess, outcome };
+                match self.secret_number.compare(guess) {
+                    Ordering::Less => self.low = self.low.max(guess + 1),
+                    Ordering::Greater => self.high = self.high.min(guess - 1),
+                    Ordering::Equal => {} // Saying so is enough; the real guess still has to be made
+                }
+                if !self.stopwatch.has_started() {
+                    self.stopwatch.start(Instant::now());
+                }
+                self.guess.clear();
+                self.recovery_dirty = true;
+                self.track(analytics::Event::FeatureUsed("warmup"));
+                return self.speak_feedback();
+            }
+            // Handle a hint being bought, refusing it if the best remaining score can't pay for it
+            Message::HintRequested => {
+                let Some(hint) = Hint::next(self.hints_bought).filter(|_| !self.won && !self.gave_up) else {
+                    return Command::none();
+                };
+                if !score::can_afford(self.attempts, self.points_spent, hint.cost()) {
+                    self.hint_refused = true;
+                    return Command::none();
+                }
+
+                let (text, low, high) = hint.give(&self.secret_number, self.low, self.high, self.hint_strength(), self.locale);
+                self.hints.push(text);
+                self.hints_bought += 1;
+                self.points_spent += hint.cost();
+                self.hint_refused = false;
+                self.low = low; // Hints narrow the range just like feedback does
+                self.high = high;
+                self.track(analytics::Event::FeatureUsed("hints"));
+                tracing::debug!(?hint, cost = hint.cost(), "Hint given");
+                gamelog::record(gamelog::Event::Hinted { free: false, hint: hint.key() });
+            }
+            // Handle the one peek a game allows, paid for out of the score
+            Message::PeekRequested => {
+                let over = self.won || self.gave_up;
+                if !self.peeked && !over && score::can_afford(self.attempts, self.points_spent, score::PEEK_COST) {
+                    let now = Instant::now();
+                    self.peeked = true;
+                    self.points_spent += score::PEEK_COST;
+                    self.now = now; // Draw the peek from a fresh clock rather than a stale tick
+                    self.peek_until = Some(now + PEEK_DURATION);
+                    self.track(analytics::Event::FeatureUsed("peek"));
+                    tracing::debug!("Peek used");
+                }
+            }
+            // Handle the "Play again" button shown after a win
+            Message::NewGame => {
+                // Mid-tournament or mid-course, the button moves straight on to the next round or hole
+                if self.tournament.as_ref().is_some_and(|tournament| !tournament.is_finished()) {
+                    self.start_tournament_round();
+                } else if self.course.as_ref().is_some_and(|course| !course.is_finished()) {
+                    self.start_course_hole();
+                } else {
+                    self.start_new_game();
+                }
+            }
+            // Handle a tournament being started, which begins its first round straight away
+            Message::TournamentStarted(length) => {
+                if self.pass_setup.is_some() || self.bot_game.is_some() || !tournament::LENGTHS.contains(&length) {
+                    return Command::none();
+                }
+                tracing::info!(rounds = length, "Tournament started");
+                self.tournament = Some(Tournament::new(length));
+                self.start_tournament_round();
+            }
+            // Handle the tournament being abandoned, going back to a single classic game
+            Message::TournamentAborted => {
+                if self.tournament.take().is_some() {
+                    tracing::info!("Tournament aborted");
+                    self.start_new_game();
+                }
+            }
+            // Handle the answer to giving up the tournament, going ahead with what was asked for if it's yes
+            Message::TournamentExitConfirmed(leave) => {
+                let Some(pending) = self.tournament_exit.take() else {
+                    return Command::none();
+                };
+                if !leave {
+                    return Command::none(); // The tournament carries on as if nothing happened
+                }
+                if let Some(tournament) = self.tournament.take() {
+                    tracing::info!(rounds = tournament.rounds.len(), "Tournament given up");
+                }
+                return match *pending {
+                    Message::TournamentAborted => {
+                        self.start_new_game();
+                        Command::none()
+                    }
+                    // With the tournament gone, it goes through unasked; it was recorded when it
+                    // first arrived, so a replay brings it back to the same question
+                    pending => self.dispatch(pending),
+                };
+            }
+            // Handle a golf course being started, which tees off its first hole straight away
+            Message::CourseStarted => {
+                if self.pass_setup.is_some() || self.bot_game.is_some() {
+                    return Command::none();
+                }
+                tracing::info!(holes = golf::HOLES, "Golf course started");
+                self.course = Some(Course::new(golf::par(MIN_NUMBER, MAX_NUMBER)));
+                self.start_course_hole();
+            }
+            // Handle the course being abandoned, going back to a single classic game
+            Message::CourseAborted => {
+                if self.course.take().is_some() {
+                    tracing::info!("Golf course abandoned");
+                    self.start_new_game();
+                }
+            }
+            // Handle an endless session being started, on a fresh secret
+            Message::EndlessStarted => {
+                if self.pass_setup.is_some() || self.bot_game.is_some() || self.tournament.is_some() || self.endless.is_some() {
+                    return Command::none();
+                }
+                tracing::info!("Endless session started");
+                let seed = self.numbers.secret(0, u32::MAX);
+                self.reset_game(seed);
+                self.endless = Some(endless::Session::default());
+                self.announce_game();
+            }
+            // Handle the endless session being stopped, which records it and goes back to a single classic game
+            Message::EndlessStopped => {
+                if self.endless.is_some() {
+                    self.start_new_game(); // Resetting the game records the session
+                }
+            }
+            // Handle the guess review being expanded or collapsed
+            Message::ReviewToggled => {
+                self.show_review = !self.show_review;
+                self.review_offset = 0.0;
+                if self.show_review {
+                    // The history can't change once the game is won, so it's only walked the once
+                    let secret = self.secret_number.reveal();
+                    let (min, max) = self.practice.unwrap_or((MIN_NUMBER, MAX_NUMBER));
+                    self.reviews = analysis::review_guesses(min, max, secret, &self.history);
+                    self.track(analytics::Event::FeatureUsed("review"));
+                } else {
+                    self.reviews.clear();
+                }
+            }
+            // Handle the grade's breakdown being opened from the summary, or closed to go back to it
+            Message::GradeExplained => {
+                if self.grade_breakdown.take().is_none() && self.won {
+                    let secret = self.secret_number.reveal();
+                    let (min, max) = self.practice.unwrap_or((MIN_NUMBER, MAX_NUMBER));
+                    let reviews = analysis::review_guesses(min, max, secret, &self.history);
+                    let optimal = analysis::optimal_guesses(MIN_NUMBER, MAX_NUMBER);
+                    self.grade_breakdown = Some(analysis::explain(self.attempts, optimal, &reviews));
+                    self.track(analytics::Event::FeatureUsed("grade_breakdown"));
+                }
+            }
+            // Handle the guess review being scrolled, which decides which of its rows get built
+            Message::ReviewScrolled(offset) => {
+                self.review_offset = offset;
+            }
+            // Handle the always-on-top checkbox and its keyboard shortcut
+            Message::AlwaysOnTopToggled(on_top) => {
+                return self.set_always_on_top(on_top);
+            }
+            Message::AlwaysOnTopShortcut => {
+                return self.set_always_on_top(!self.preferences.always_on_top);
+            }
+            // Handle the compact layout checkbox and its keyboard shortcut
+            Message::CompactToggled(compact) => {
+                return self.set_compact(compact);
+            }
+            Message::CompactShortcut => {
+                return self.set_compact(!self.preferences.compact);
+            }
+            // Handle the debug overlay's shortcut; showing it gives the secret away, so the
+            // game no longer counts towards achievements
+            Message::DebugOverlayToggled => {
+                self.debug_overlay = !self.debug_overlay;
+                if self.debug_overlay {
+                    self.debug_voided = true;
+                }
+            }
+            // Handle the left-handed layout setting
+            Message::LeftHandedToggled(left_handed) => {
+                self.update_preferences(|preferences| preferences.left_handed = left_handed);
+            }
+            // Handle one-key play being switched on or off, forgetting any tap half-way through
+            Message::OneKeyToggled(one_key) => {
+                self.update_preferences(|preferences| preferences.one_key = one_key);
+                self.one_key = OneKey::default();
+            }
+            // Handle how quickly one-key taps and holds have to come
+            Message::OneKeyTimingSelected(timing) => {
+                self.update_preferences(|preferences| preferences.one_key_timing = timing);
+            }
+            // Space in one-key play is translated before handling, so it never gets this far
+            Message::OneKeyPressed(_) | Message::OneKeyReleased(_) => {}
+            // Handle the idle pause threshold
+            Message::IdleTimeoutSelected(timeout) => {
+                self.update_preferences(|preferences| preferences.idle_timeout = timeout);
+            }
+            // Handle the choice of starting fresh or resuming on launch, which applies from the next launch
+            Message::StartupSelected(startup) => {
+                self.update_preferences(|preferences| preferences.startup = startup);
+            }
+            // Handle the minimum change between guesses, which applies from the next guess
+            Message::MinChangeSelected(change) => {
+                self.update_preferences(|preferences| preferences.min_change = change);
+            }
+            // Handle when the surprise swap comes, which a round already past it won't see
+            Message::SurpriseSwapSelected(swap) => {
+                self.update_preferences(|preferences| preferences.surprise_swap = swap);
+            }
+            // Handle the animated or instant feedback setting
+            Message::MotionSelected(motion) => {
+                self.update_preferences(|preferences| preferences.motion = motion);
+            }
+            // Handle the start of a pass-and-play setup
+            Message::PassAndPlayOpened => {
+                self.pass_setup = Some(PassSetup::new());
+                self.focused_field = Some(Field::PassSecret);
+                return text_input::focus(Field::PassSecret.id());
+            }
+            // Handle edits to the hidden secret, wiping the previous text rather than leaving it behind
+            Message::PassSecretChanged(value) => {
+                if let Some(PassSetup::Entering { input, invalid }) = self.pass_setup.as_mut() {
+                    passplay::wipe(input);
+                    *input = value;
+                    *invalid = false;
+                }
+                self.focused_field = Some(Field::PassSecret);
+            }
+            // Handle the setter confirming the secret, moving on to the hand-off if it's in range
+            Message::PassSecretSubmitted => {
+                if let Some(setup) = self.pass_setup.as_mut() {
+                    setup.submit(self.locale, MIN_NUMBER, MAX_NUMBER);
+                }
+            }
+            // Handle the guesser taking over, starting a normal game around the setter's secret
+            Message::PassReady => {
+                if let Some(&PassSetup::Handoff(secret)) = self.pass_setup.as_ref() {
+                    self.start_new_game(); // While still set up, so a game left behind isn't held against the streak
+                    self.pass_setup = None;
+                    self.secret_number = secret;
+                    self.pass_and_play = true;
+                    self.track(analytics::Event::FeatureUsed("pass_and_play"));
+                    self.focused_field = Some(Field::Guess);
+                    return text_input::focus(Field::Guess.id());
+                }
+            }
+            // Handle the user backing out of the setup; dropping it wipes anything typed
+            Message::PassCancelled => {
+                self.pass_setup = None;
+            }
+            // Handle the guesser giving up, revealing the secret and crediting the setter
+            Message::GaveUp => {
+                if self.pass_and_play && !self.won && !self.gave_up {
+                    self.gave_up = true;
+                    self.feedback = Feedback::GaveUp { secret: self.secret_number.reveal() };
+                    self.start_reveal(self.history.last().copied().unwrap_or(MIN_NUMBER));
+                    self.stopwatch.pause(Instant::now());
+                    self.log_ended(End::GaveUp);
+                    self.recovery_dirty = false;
+                    recovery::discard();
+                }
+            }
+            // Handle the "Retry this number" button, offered only once the secret was given up on
+            Message::RetryRequested => {
+                if self.gave_up && !self.won {
+                    self.start_retry();
+                    self.focused_field = Some(Field::Guess);
+                    return text_input::focus(Field::Guess.id());
+                }
+            }
+            // Handle a result card being asked for, drawing and saving it off the UI thread
+            Message::ResultCardRequested => {
+                if self.won && self.card_save != Some(SaveState::Saving) {
+                    self.card_save = Some(SaveState::Saving);
+                    let card = self.result_card();
+                    return Command::perform(async move { card::save(card) }, Message::ResultCardSaved);
+                }
+            }
+            // Handle the card having been written, unless a new game has started since
+            Message::ResultCardSaved(path) => {
+                if self.card_save == Some(SaveState::Saving) {
+                    self.card_save = Some(path.map_or(SaveState::Failed, SaveState::Saved));
+                }
+            }
+            // Handle entering or leaving the computer-guesses mode
+            Message::BotModeToggled(active) => {
+                if active {
+                    self.start_bot_game();
+                } else {
+                    self.bot_game = None;
+                }
+            }
+            // Handle an answer to the bot's guess, refusing answers that contradict earlier ones
+            Message::BotAnswered(answer) => {
+                if let Some(bot) = self.bot_game.as_mut() {
+                    let first = bot.attempts() == 1;
+                    self.bot_contradiction = !bot.answer(answer);
+                    if first && !self.bot_contradiction {
+                        self.remember_played(PlayMode::ComputerGuesses);
+                    }
+                }
+            }
+            // Handle a new bot difficulty, restarting any round so it plays at that level throughout
+            Message::BotStrategySelected(strategy) => {
+                self.update_preferences(|preferences| preferences.bot_strategy = strategy);
+                if self.bot_game.is_some() {
+                    self.start_bot_game();
+                }
+            }
+            // Handle the "Play again" button shown after the bot finds the number
+            Message::NewBotGame => {
+                self.start_bot_game();
+            }
+            // Handle edits to the statements, clearing the last deduction while typing
+            Message::ConstraintsChanged(value) => {
+                self.constraints_input = value;
+                self.focused_field = Some(Field::Constraints);
+                self.deduction = None;
+            }
+            // Handle the computer being asked to deduce the player's number from the statements
+            Message::ConstraintsSubmitted => {
+                let deduction = deduce::parse(&self.constraints_input)
+                    .and_then(|constraints| deduce::deduce(&constraints, MIN_NUMBER, MAX_NUMBER));
+                match &deduction {
+                    Ok(deduction) => tracing::debug!(remaining = deduction.remaining, "Deduced from constraints"),
+                    Err(error) => tracing::debug!(%error, "Constraints couldn't be deduced from"),
+                }
+                self.deduction = Some(deduction);
+                self.track(analytics::Event::FeatureUsed("deduce"));
+            }
+            // Handle the "Same again" button, repeating the last game played
+            Message::SameAgain => {
+                if let Some(last) = self.last_played {
+                    return self.play_same_again(last);
+                }
+            }
+            // Write the spectator feed if what viewers should see has changed
+            Message::SpectatorRefresh => {
+                let contents = self.spectator_feed();
+                if self.preferences.spectator_feed && self.spectator_written.as_ref() != Some(&contents) {
+                    spectator::write(&contents);
+                    self.spectator_written = Some(contents);
+                }
+            }
+            // Handle the spectator feed setting, removing the file once it's switched off
+            Message::SpectatorFeedToggled(enabled) => {
+                self.update_preferences(|preferences| preferences.spectator_feed = enabled);
+                if !enabled {
+                    spectator::clear();
+                }
+                self.spectator_written = None; // Write afresh on the next refresh
+            }
+            // Save a recovery snapshot if anything changed since the last one
+            Message::RecoveryAutosave => {
+                if self.recovery_dirty {
+                    recovery::save(&self.snapshot());
+                    self.recovery_dirty = false;
+                }
+            }
+            // Handle the player taking back the game left behind by a crash
+            Message::RecoveryRestored => {
+                if let Some(snapshot) = self.pending_recovery.take() {
+                    self.restore(snapshot);
+                }
+            }
+            // Handle the player throwing that game away
+            Message::RecoveryDeclined => {
+                self.pending_recovery = None;
+                if self.history.is_empty() {
+                    recovery::discard(); // Otherwise the current game's snapshot has replaced it
+                }
+            }
+            // Handle the window's close button, asking first if a game is part-way through
+            Message::CloseRequested => {
+                match closing::on_close_requested(self.history.len(), self.won || self.gave_up) {
+                    CloseAction::Close => return self.close(false),
+                    CloseAction::Confirm => self.confirm_close = true,
+                }
+            }
+            // Handle the answer to that question; only an explicit choice closes the window
+            Message::CloseConfirmed(choice) => {
+                self.confirm_close = false;
+                match choice {
+                    QuitChoice::Quit => return self.close(false),
+                    QuitChoice::SaveForLater => return self.close(true),
+                    QuitChoice::KeepPlaying => {}
+                }
+            }
+            // Handle the release notes being reopened, showing the full history
+            Message::WhatsNewOpened => {
+                self.whats_new = changelog::RELEASES.to_vec();
+            }
+            // Handle the "what's new" panel being closed, so it stays closed until the next update
+            Message::WhatsNewDismissed => {
+                self.whats_new.clear();
+                self.update_preferences(|preferences| {
+                    preferences.last_seen_version = Some(changelog::CURRENT_VERSION.to_string())
+                });
+            }
+            // Translated into the setting's own message before the match
+            Message::SettingKey(_) => {}
+            // Handle Tab and Shift+Tab, cycling through the fields on screen and wrapping around
+            Message::FocusMoved(forward) => {
+                if let Some(field) = focus::next(&self.visible_fields(), self.focused_field, forward) {
+                    self.focused_field = Some(field);
+                    return text_input::focus(field.id());
+                }
+            }
+            // Advance the clock, pausing it if the player has been away too long
+            Message::Tick(now) => {
+                self.now = now;
+                self.toasts.retain(|&(_, shown)| now.saturating_duration_since(shown) < TOAST_DURATION);
+                self.budget.refill(now);
+                if self.peek_until.is_some_and(|until| now >= until) {
+                    self.peek_until = None;
+                }
+                if self.reveal.is_some_and(|reveal| reveal.is_finished(now)) {
+                    self.reveal = None;
+                }
+                if self.confetti.as_ref().is_some_and(|confetti| confetti.is_finished(now)) {
+                    self.confetti = None;
+                }
+
+                let idle_for = now.saturating_duration_since(self.last_interaction);
+                let timed_out = self.preferences.idle_timeout.duration().is_some_and(|limit| idle_for >= limit);
+                if self.stopwatch.is_running() && timed_out {
+                    // Pause as of the last interaction so none of the idle time counts
+                    self.stopwatch.pause(self.last_interaction);
+                    self.idle_paused = true;
+                    self.idled = true;
+                }
+                if self.attempt_remaining() == Some(Duration::ZERO) {
+                    self.expire_attempt();
+                }
+                if self.next_round_at.is_some_and(|at| now >= at) {
+                    self.start_tournament_round();
+                }
+            }
+            // Handle the remaining-candidates checkbox
+            Message::ShowCandidatesToggled(show) => {
+                self.update_preferences(|preferences| preferences.show_candidates = show);
+                if show {
+                    self.track(analytics::Event::FeatureUsed("candidates"));
+                }
+            }
+            // Handle the binary and hex readout checkbox
+            Message::ShowBasesToggled(show) => {
+                self.update_preferences(|preferences| preferences.show_bases = show);
+            }
+            // Handle a new theme being picked
+            Message::ThemeSelected(theme) => {
+                self.update_preferences(|preferences| preferences.theme = theme);
+                if theme == ThemeChoice::SystemAccent {
+                    return Command::perform(async { appearance::detect_accent_color() }, Message::SystemAccentDetected);
+                }
+                self.system_accent = None;
+            }
+            // Check the OS theme in the background so a slow platform query never blocks the UI,
+            // and its accent color with it when the theme is built around that
+            Message::SystemThemePoll => {
+                let theme = Command::perform(async { appearance::detect_system_theme() }, Message::SystemThemeDetected);
+                if self.preferences.theme != ThemeChoice::SystemAccent {
+                    return theme;
+                }
+                let accent = Command::perform(async { appearance::detect_accent_color() }, Message::SystemAccentDetected);
+                return Command::batch([theme, accent]);
+            }
+            // Handle the result of an OS accent color check, keeping the player's accent when there's none
+            Message::SystemAccentDetected(accent) => {
+                if accent.is_none() && self.system_accent.is_some() {
+                    tracing::warn!("Lost track of the system accent color, using the chosen accent instead");
+                }
+                self.system_accent = accent.filter(|_| self.preferences.theme == ThemeChoice::SystemAccent);
+            }
+            // Handle the result of an OS theme check
+            Message::SystemThemeDetected(system_theme) => {
+                if system_theme.is_none() && self.system_theme.is_some() {
+                    tracing::warn!("Lost track of the system theme, defaulting to light");
+                }
+                self.system_theme = system_theme;
+            }
+            // Handle the settings panel being opened or closed
+            Message::SettingsToggled => {
+                self.show_settings = !self.show_settings;
+            }
+            // Handle the badges panel being opened or closed
+            Message::AchievementsToggled => {
+                self.show_achievements = !self.show_achievements;
+                if self.show_achievements {
+                    self.trend_wins = gamelog::load_wins(); // Read afresh, so the chart includes the game just won
+                }
+            }
+            // Handle the trend chart being switched to a longer or shorter history
+            Message::TrendWindowSelected(window) => {
+                self.trend_window = window;
+            }
+            // Handle the leaderboard being opened, on the guesses board, or closed
+            Message::LeaderboardToggled => {
+                self.leaderboard = match self.leaderboard {
+                    Some(_) => None,
+                    None => Some(Metric::Attempts),
+                };
+                // The latest win's changes are shown once, from when the board opens until it closes
+                if self.leaderboard.is_some() && !self.board_changes.is_empty() {
+                    self.now = Instant::now(); // Fade from a fresh clock rather than a stale tick
+                    self.board_fade = Some(Fade::new(self.now));
+                } else {
+                    self.board_changes.clear();
+                    self.board_fade = None;
+                }
+            }
+            // Handle the settings panel being switched to another mode's profile
+            Message::ProfileModeSelected(mode) => {
+                self.profile_mode = mode;
+            }
+            // Handle a mode being given a profile, or none; it's switched to when a game of the mode starts
+            Message::ProfileChosen(choice) => {
+                self.profiles.assign(self.profile_mode, choice);
+            }
+            // Handle the current settings being kept as the shown mode's profile
+            Message::ProfileSaved => {
+                if let Some(name) = self.profiles.choice(self.profile_mode).0 {
+                    self.profiles.update(&name, profiles::Settings::of(&self.preferences));
+                }
+            }
+            // Handle a switch between the guesses and time attack boards
+            Message::LeaderboardMetricSelected(metric) => {
+                if self.leaderboard.is_some() {
+                    self.leaderboard = Some(metric);
+                }
+            }
+            // Handle the daily challenge being started, with the same secret as everyone else's
+            Message::DailyChallengeStarted(date) => {
+                if self.pass_setup.is_none() {
+                    self.bot_game = None;
+                    self.start_seeded_game(date.seed());
+                    self.daily = Some(date);
+                }
+            }
+            // Handle the streak calendar being opened, on the current month, or closed
+            Message::CalendarToggled => {
+                self.calendar = match self.calendar {
+                    Some(_) => None,
+                    None => Some(Date::today().month()),
+                };
+            }
+            // Handle moving between months, never past the current one since it has nothing to show
+            Message::CalendarMonthMoved(forward) => {
+                if let Some(month) = self.calendar {
+                    let moved = if forward { month.next() } else { month.previous() };
+                    self.calendar = Some(moved.min(Date::today().month()));
+                }
+            }
+            // Handle a swatch click, mirroring the color into the hex field
+            Message::AccentSelected(accent) => {
+                self.accent_input = accent.to_hex();
+                self.accent_input_invalid = false;
+                self.update_preferences(|preferences| preferences.accent = accent);
+            }
+            // Handle edits to the hex field, clearing any earlier error while typing
+            Message::AccentHexChanged(value) => {
+                self.accent_input = value;
+                self.focused_field = Some(Field::Accent);
+                self.accent_input_invalid = false;
+            }
+            // Handle a submitted hex value, keeping the current accent if it's invalid
+            Message::AccentHexSubmitted => match Rgb::from_hex(&self.accent_input) {
+                Some(accent) => {
+                    self.accent_input = accent.to_hex();
+                    self.update_preferences(|preferences| preferences.accent = accent);
+                }
+                None => self.accent_input_invalid = true,
+            },
+            // Handle the mystery mode setting, which takes effect from the next game
+            Message::MysteryModeToggled(mystery) => {
+                self.update_preferences(|preferences| preferences.mystery_mode = mystery);
+            }
+            // Handle the proximity tones setting
+            Message::ProximityTonesToggled(tones) => {
+                self.update_preferences(|preferences| preferences.proximity_tones = tones);
+            }
+            // Handle the automatic hints setting
+            Message::MercyHintsToggled(mercy) => {
+                self.update_preferences(|preferences| preferences.mercy_hints = mercy);
+            }
+            // Handle the assist mode setting, which applies from the next hint bought
+            Message::AssistHintsToggled(assist) => {
+                self.update_preferences(|preferences| preferences.assist_hints = assist);
+            }
+            // Handle the guess assist setting, which the number line picks up straight away
+            Message::GuessAssistToggled(assist) => {
+                self.update_preferences(|preferences| preferences.guess_assist = assist);
+            }
+            // Handle the close-enough setting, which takes effect from the next game
+            Message::CloseEnoughToggled(close) => {
+                self.update_preferences(|preferences| preferences.close_enough = close);
+            }
+            // Handle racing the ghost, which only changes what's shown
+            Message::GhostRaceToggled(race) => {
+                self.update_preferences(|preferences| preferences.ghost_race = race);
+            }
+            // Handle the per-guess time limit, which restarts the current guess's time when switched on
+            Message::TimedGuessesToggled(timed) => {
+                self.update_preferences(|preferences| preferences.timed_guesses = timed);
+                self.attempt_started = self.stopwatch.elapsed(Instant::now());
+            }
+            // Handle the guess budget setting; the budget itself carries on across the toggle
+            Message::GuessBudgetToggled(budget) => {
+                self.update_preferences(|preferences| preferences.guess_budget = budget);
+            }
+            // Handle the challenge code being copied for sharing
+            Message::ChallengeCodeCopied => {
+                self.challenge_copied = true;
+                return iced::clipboard::write(self.current_challenge().encode());
+            }
+            // Handle edits to the challenge code field, clearing any earlier error while typing
+            Message::ChallengeInputChanged(value) => {
+                self.challenge_input = value;
+                self.focused_field = Some(Field::Challenge);
+                self.challenge_error = None;
+            }
+            // Handle edits to the practice range field, clearing any earlier error while typing
+            Message::PracticeInputChanged(value) => {
+                self.practice_input = value;
+                self.focused_field = Some(Field::Practice);
+                self.practice_error = None;
+            }
+            // Handle a typed practice range, starting a drill over it if it reads as one
+            Message::PracticeSubmitted => match practice::parse(&self.practice_input, MIN_NUMBER, MAX_NUMBER) {
+                Ok((low, high)) => {
+                    self.practice_input.clear();
+                    self.practice_error = None;
+                    tracing::info!(low, high, "Practice drill started");
+                    self.start_practice(low, high);
+                }
+                Err(error) => self.practice_error = Some(error),
+            },
+            // Handle a pasted challenge code, starting that exact game if it's valid
+            Message::ChallengeSubmitted => {
+                let challenge = Challenge::decode(&self.challenge_input).and_then(|challenge| {
+                    if (challenge.min, challenge.max) == (MIN_NUMBER, MAX_NUMBER) {
+                        Ok(challenge)
+                    } else {
+                        Err(ChallengeError::UnsupportedRange)
+                    }
+                });
+
+                match challenge {
+                    Ok(challenge) => {
+                        self.challenge_input.clear();
+                        self.start_seeded_game(challenge.seed);
+                    }
+                    Err(error) => self.challenge_error = Some(error),
+                }
+            }
+            // Handle edits to the scorecard path, clearing any earlier error while typing
+            Message::ScorecardPathChanged(value) => {
+                self.scorecard_input = value;
+                self.focused_field = Some(Field::Scorecard);
+                self.scorecard_error = None;
+            }
+            // Handle a friend's scorecard being loaded, starting its game to play against their result
+            Message::ScorecardImported => {
+                let path = PathBuf::from(self.scorecard_input.trim());
+                match Scorecard::load(&path, MIN_NUMBER, MAX_NUMBER) {
+                    Ok(opponent) => {
+                        self.scorecard_input.clear();
+                        self.bot_game = None;
+                        self.start_seeded_game(opponent.challenge.seed);
+                        self.opponent = Some(opponent);
+                    }
+                    Err(error) => self.scorecard_error = Some(error),
+                }
+            }
+            // Handle the request to export the won game's scorecard, writing it in the background
+            Message::ScorecardExportRequested => {
+                if self.won && !self.pass_and_play && self.scorecard_save != Some(SaveState::Saving) {
+                    self.scorecard_save = Some(SaveState::Saving);
+                    let scorecard = self.scorecard();
+                    return Command::perform(async move { scorecard::save(scorecard) }, Message::ScorecardExported);
+                }
+            }
+            // Handle the scorecard having been written, unless a new game has started since
+            Message::ScorecardExported(path) => {
+                if self.scorecard_save == Some(SaveState::Saving) {
+                    self.scorecard_save = Some(path.map_or(SaveState::Failed, SaveState::Saved));
+                }
+            }
+            // Handle the request to export the finished game's replay file, writing it in the background
+            Message::ReplayExportRequested => {
+                if (self.won || self.gave_up) && self.replay_save != Some(SaveState::Saving) {
+                    self.replay_save = Some(SaveState::Saving);
+                    let replay = self.game_replay();
+                    return Command::perform(async move { playback::save(replay) }, Message::ReplayExported);
+                }
+            }
+            // Handle the replay file having been written, unless a new game has started since
+            Message::ReplayExported(path) => {
+                if self.replay_save == Some(SaveState::Saving) {
+                    self.replay_save = Some(path.map_or(SaveState::Failed, SaveState::Saved));
+                }
+            }
+            // Handle the request to export the finished game's history as CSV, writing it in the background
+            Message::HistoryExportRequested => {
+                if (self.won || self.gave_up) && self.history_save != Some(SaveState::Saving) {
+                    self.history_save = Some(SaveState::Saving);
+                    let replay = self.game_replay();
+                    return Command::perform(async move { playback::save_csv(replay) }, Message::HistoryExported);
+                }
+            }
+            // Handle the CSV file having been written, unless a new game has started since
+            Message::HistoryExported(path) => {
+                if self.history_save == Some(SaveState::Saving) {
+                    self.history_save = Some(path.map_or(SaveState::Failed, SaveState::Saved));
+                }
+            }
+            // Handle the panel for opening replay files being opened or closed
+            Message::ReplaysToggled => {
+                self.show_replays = !self.show_replays;
+                self.replay_error = None;
+            }
+            // Handle edits to the replay path, clearing any earlier error while typing
+            Message::ReplayPathChanged(value) => {
+                self.replay_input = value;
+                self.focused_field = Some(Field::Replay);
+                self.replay_error = None;
+            }
+            // Handle a replay file being opened, which takes the game's place until it's closed
+            Message::ReplayOpened => {
+                let path = PathBuf::from(self.replay_input.trim());
+                match GameReplay::load(&path) {
+                    Ok(replay) => {
+                        tracing::info!(guesses = replay.guesses.len(), "Replay opened");
+                        self.replay_input.clear();
+                        self.show_replays = false;
+                        self.playback = Some(Playback::new(replay));
+                    }
+                    Err(error) => self.replay_error = Some(error),
+                }
+            }
+            // Handle the replay being stepped by hand, which pauses it
+            Message::PlaybackStepped(forward) => {
+                if let Some(playback) = &mut self.playback {
+                    playback.playing = false;
+                    playback.step(forward);
+                }
+            }
+            // Handle the replay being played or paused, starting again from the top if it had finished
+            Message::PlaybackToggled => {
+                if let Some(playback) = &mut self.playback {
+                    if playback.is_finished() {
+                        playback.shown = 0;
+                    }
+                    playback.playing = !playback.playing;
+                }
+            }
+            // Handle the next guess coming up while the replay plays by itself
+            Message::PlaybackAdvanced => {
+                if let Some(playback) = self.playback.as_mut().filter(|playback| playback.playing) {
+                    playback.step(true);
+                }
+            }
+            // Handle the replay being closed, going back to the game it covered
+            Message::PlaybackClosed => {
+                self.playback = None;
+            }
+            // Handle the demo gallery being opened, always from its first seed
+            Message::GalleryOpened => {
+                self.gallery = Some(Gallery::new(MIN_NUMBER, MAX_NUMBER));
+                self.track(analytics::Event::FeatureUsed("gallery"));
+            }
+            // Handle the bots in the demo gallery making their next guesses
+            Message::GalleryAdvanced => {
+                if let Some(gallery) = self.gallery.as_mut() {
+                    gallery.advance();
+                }
+            }
+            // Handle the demo gallery being left, going back to whatever was on screen before
+            Message::GalleryClosed => {
+                self.gallery = None;
+            }
+            // Handle opting in or out of play statistics; opting out stops recording at once
+            Message::AnalyticsToggled(enabled) => {
+                self.update_preferences(|preferences| preferences.analytics = enabled);
+                self.track(analytics::Event::SessionStarted); // Count the session that opted in
+                if !enabled {
+                    self.insights = None;
+                }
+            }
+            // Handle the recorded statistics being deleted
+            Message::AnalyticsDeleted => {
+                analytics::delete();
+                self.insights = self.insights.as_ref().map(|_| Insights::default());
+            }
+            // Handle the player asking to wipe their records, which waits on them confirming it
+            Message::RecordsResetRequested => {
+                self.confirm_reset = true;
+                self.reset_error = None;
+            }
+            // Handle the answer to wiping the records, clearing them on screen at once even where
+            // a file can't be deleted, and saying which ones will be back next launch
+            Message::RecordsResetConfirmed(reset) => {
+                self.confirm_reset = false;
+                if !reset {
+                    return Command::none();
+                }
+                self.board_changes.clear(); // Nothing left on the boards to point out
+                let failed: Vec<String> = [
+                    ("leaderboard", self.leaderboards.reset()),
+                    ("achievements", self.progress.reset()),
+                    ("endless mode records", self.endless_records.reset()),
+                    ("ghost", self.ghost.take().map_or(Ok(()), |_| Ghost::remove())),
+                ]
+                .into_iter()
+                .filter_map(|(records, result)| {
+                    let error = result.err()?;
+                    tracing::warn!(%error, records, "Could not delete records");
+                    Some(records.to_string())
+                })
+                .collect();
+                tracing::info!(failed = failed.len(), "Records reset");
+                if !failed.is_empty() {
+                    self.reset_error = Some(format!(
+                        "Couldn't delete the saved {}; they're cleared for now but will be back next launch",
+                        failed.join(" and ")
+                    ));
+                }
+            }
+            // Handle the insights panel being opened or closed, reading the latest statistics
+            Message::InsightsToggled => {
+                self.insights = match self.insights {
+                    Some(_) => None,
+                    None => Some(Insights::from_records(&analytics::load())),
+                };
+            }
+            // Handle the win notification setting
+            Message::NotifyOnWinToggled(notify) => {
+                self.update_preferences(|preferences| preferences.notify_on_win = notify);
+            }
+            // Handle reading the feedback aloud, starting the engine the first time it's switched on
+            Message::SpeakFeedbackToggled(speak) => {
+                if speak && self.speech.is_none() {
+                    self.speech = Some(speech::Engine::start());
+                }
+                let speak = speak && self.speech_engine().is_some(); // Stays off with no engine to speak through
+                self.update_preferences(|preferences| preferences.speak_feedback = speak);
+            }
+            // Handle the speaking rate, used from the next announcement
+            Message::SpeechRateSelected(rate) => {
+                self.update_preferences(|preferences| preferences.speech_rate = rate);
+            }
+            // Handle the speaking voice, used from the next announcement
+            Message::SpeechVoiceSelected(voice) => {
+                self.update_preferences(|preferences| preferences.speech_voice = voice.id);
+            }
+            // Handle the win notification being clicked, bringing the window back to the front
+            Message::WinNotificationAnswered(clicked) => {
+                if clicked {
+                    return window::gain_focus();
+                }
+            }
+            // Handle an announcement the engine couldn't speak, which stops any more being tried this session
+            Message::FeedbackSpoken(result) => {
+                if let Err(reason) = result {
+                    self.speech = Some(Err(reason));
+                }
+            }
+            // Handle the window gaining or losing focus
+            Message::WindowFocusChanged(focused) => {
+                self.window_focused = focused;
+                if !focused {
+                    self.peek_until = None; // Never leave the secret showing in a window left behind
+                }
+            }
+            // Handle a new UI font being picked; bundled fonts are embedded, so it applies immediately
+            Message::FontSelected(font) => {
+                self.update_preferences(|preferences| preferences.font = font);
+            }
+            // Handle a new number format, which every number on screen and every guess typed from now on uses
+            Message::NumberFormatSelected(choice) => {
+                self.update_preferences(|preferences| preferences.number_format = choice);
+                self.locale = choice.resolve(self.system_locale);
+            }
+            // Handle the monospace digits setting
+            Message::MonospaceDigitsToggled(monospace) => {
+                self.update_preferences(|preferences| preferences.monospace_digits = monospace);
+            }
+            // Handle the colorblind-friendly colors setting
+            Message::ColorblindToggled(colorblind) => {
+                self.update_preferences(|preferences| preferences.colorblind = colorblind);
+            }
+        }
+        Command::none() // No additional commands to run after handling the message
+    }
+
+
+    // Lay out the whole window; `view` wraps this to time it when asked to
+    fn layout(&self) -> Element<Message> {
+        self.frames.set(self.frames.get() + 1);
+
+        // The question about closing mid-game takes over until it's answered, even in the compact strip
+        if self.confirm_close && self.preferences.compact {
+            return self.close_prompt_view();
+        }
+
+        // The compact strip replaces everything else; the game state behind it keeps updating
+        if self.preferences.compact {
+            return self.compact_view();
+        }
+
+        // Create buttons that show or hide the settings and badges panels
+        let settings_button = Button::new(self.text(if self.show_settings { "Hide settings" } else { "Settings" }))
+            .on_press(Message::SettingsToggled);
+        let achievements_button =
+            Button::new(self.text(if self.show_achievements { "Hide achievements" } else { "Achievements" }))
+                .on_press(Message::AchievementsToggled);
+        let calendar_button = Button::new(self.text(if self.calendar.is_some() { "Hide calendar" } else { "Calendar" }))
+            .on_press(Message::CalendarToggled);
+        let leaderboard_button =
+            Button::new(self.text(if self.leaderboard.is_some() { "Hide leaderboard" } else { "Leaderboard" }))
+                .on_press(Message::LeaderboardToggled);
+        let replays_button = Button::new(self.text(if self.show_replays { "Hide replays" } else { "Replays" }))
+            .on_press(Message::ReplaysToggled);
+        let mut gallery_button = Button::new(self.text("Demo gallery"));
+        if self.gallery.is_none() {
+            gallery_button = gallery_button.on_press(Message::GalleryOpened);
+        }
+
+        // Offer today's challenge from any mode but the pass-and-play setup, which has to be finished or cancelled
+        let mut daily_button = Button::new(self.text("Daily challenge"));
+        if self.pass_setup.is_none() {
+            daily_button = daily_button.on_press(Message::DailyChallengeStarted(Date::today()));
+        }
+
+        // Arrange the UI elements vertically in a column
+        let mut content = Column::new()
+            .spacing(10)                              // Keep the stacked controls from touching
+            .padding(20)                              // Add padding around the column
+            .align_items(Alignment::Center);          // Center-align all items within the column
+
+        // Ask about closing mid-game above everything else, then about leaving the tournament or wiping the records
+        if self.confirm_close {
+            content = content.push(self.close_prompt_view());
+        }
+        if self.tournament_exit.is_some() {
+            content = content.push(self.tournament_exit_view());
+        }
+        if self.confirm_reset {
+            content = content.push(self.records_reset_view());
+        }
+
+        // Show the developer overlay above everything else, when it's been asked for
+        if self.debug_overlay {
+            content = content.push(self.debug_view());
+        }
+
+        // Warn that nothing from this window will be kept while another copy of the game runs
+        if self.shared_data {
+            let warning = "Guessing Game is already open in another window - this one won't save any progress or settings";
+            content = content.push(self.text(warning).style(self.theme().palette().danger));
+        }
+
+        // Explain any saved file that's being left alone for the newer version that wrote it
+        for newer in &self.newer_files {
+            content = content.push(self.text(newer.message()).style(self.theme().palette().danger));
+        }
+
+        // Offer to restore a game left behind by a crash, above everything else
+        if let Some(snapshot) = &self.pending_recovery {
+            content = content.push(self.recovery_view(snapshot));
+        }
+
+        // Announce newly unlocked achievements
+        for (achievement, _) in &self.toasts {
+            let toast = self.text(format!("Achievement unlocked: {}!", achievement.name));
+            content = content.push(toast.size(24).style(Color::from(self.accent())));
+        }
+
+        // Put the active mode's controls at the top of the game, or the gallery or the replay being watched in their place
+        content = content.push(match (&self.gallery, &self.playback, &self.pass_setup, &self.bot_game) {
+            (Some(gallery), _, _, _) => self.gallery_view(gallery),       // The bots playing on their own, to watch
+            (None, Some(playback), _, _) => self.playback_view(playback), // A shared round played back, read-only
+            (None, None, Some(setup), _) => self.pass_setup_view(setup),  // One player sets a secret for another
+            (None, None, None, Some(bot)) => self.bot_view(bot),          // The bot guesses the player's number
+            (None, None, None, None) => self.game_view(),                 // The player guesses the secret
+        });
+
+        // Show the release notes below the game, where they never get in the way of playing
+        if !self.whats_new.is_empty() {
+            content = content.push(self.whats_new_view());
+        }
+
+        // Add the badges and settings panels below the game when they are open
+        content = content.push(
+            self.mirrored_row(vec![
+                daily_button.into(),
+                calendar_button.into(),
+                leaderboard_button.into(),
+                replays_button.into(),
+                gallery_button.into(),
+                achievements_button.into(),
+                settings_button.into(),
+            ])
+            .spacing(10),
+        );
+        if self.show_replays {
+            content = content.push(self.replays_view());
+        }
+        if let Some(month) = self.calendar {
+            content = content.push(self.calendar_view(month));
+        }
+        if let Some(metric) = self.leaderboard {
+            content = content.push(self.leaderboard_view(metric));
+        }
+        if self.show_achievements {
+            content = content.push(self.achievements_view());
+        }
+        if self.show_settings {
+            content = content.push(self.settings_view());
+            if let Some(insights) = &self.insights {
+                content = content.push(self.insights_view(insights));
+            }
+        }
+
+        // Pin the latency stats to the top-left corner, outside the centered column, when they're on
+        let content: Element<Message> = match &self.latency {
+            Some(latency) => {
+                let lines = latency.borrow().lines();
+                let stats = lines
+                    .into_iter()
+                    .fold(Column::new().spacing(2), |column, line| column.push(Text::new(line).font(fonts::MONO).size(12)));
+                let stats = Container::new(stats).padding(6).style(style::custom_container(DebugPanel));
+                Column::new()
+                    .align_items(Alignment::Center)
+                    .push(Container::new(stats).width(Length::Fill))
+                    .push(content)
+                    .into()
+            }
+            None => content.into(),
+        };
+
+        // Embed the column inside a container that fills the available space
+        let body = Container::new(content)
+            .width(Length::Fill)   // Make the container take the full available width
+            .height(Length::Fill)  // Make the container take the full available height
+            .center_x()             // Center content horizontally
+            .center_y();            // Center content vertically
+
+        // Keep the status bar under everything else while the player is the one guessing
+        if self.gallery.is_none() && self.playback.is_none() && self.pass_setup.is_none() && self.bot_game.is_none() {
+            Column::new().push(body).push(self.status_bar_view(StatusBar::new(self))).into()
+        } else {
+            body.into() // Convert the container into an `Element<Message>`
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A game drawing from `seed`, with default preferences and nothing saved
+    fn game(seed: u64) -> GuessingGame {
+        GuessingGame::with_setup(Box::new(SeededSource::new(seed)), Preferences::default(), Locale::English, Saved::default())
+    }
+
+    // Play `messages` through a fresh game while recording it, then read the recording back
+    fn record(name: &str, seed: u32, messages: Vec<Message>) -> (Recording, u64) {
+        let path = std::env::temp_dir().join(format!("guessing-game-{}-{}.rec", name, std::process::id()));
+        let mut game = game(u64::from(seed));
+        game.recorder = Some(Recorder::create(&path, seed, game.locale, &game.preferences).expect("recording should start"));
+        for message in messages {
+            let _ = game.update(message);
+        }
+        let digest = game.digest();
+        game.recorder.take().expect("still recording").finish(digest);
+
+        let recording = Recording::load(&path, Instant::now()).expect("recording should load");
+        let _ = std::fs::remove_file(&path);
+        (recording, digest)
+    }
+
+    #[test]
+    fn a_confirmed_tournament_exit_is_recorded_once() {
+        let (recording, digest) = record(
+            "tournament-exit",
+            7,
+            vec![
+                Message::TournamentStarted(tournament::LENGTHS[0]),
+                Message::GuessInputChanged("50".to_string()),
+                Message::GuessButtonPressed,
+                Message::CourseStarted,                 // Held back while the tournament is on
+                Message::TournamentExitConfirmed(true), // Goes ahead with it
+            ],
+        );
+
+        let names: Vec<&str> = recording.messages.iter().map(|(_, message)| replay::name(message)).collect();
+        assert_eq!(
+            names,
+            ["TournamentStarted", "GuessInputChanged", "GuessButtonPressed", "CourseStarted", "TournamentExitConfirmed"]
+        );
+        assert_eq!(replay(recording), digest);
+    }
+
+    #[test]
+    fn a_confirmed_tournament_exit_goes_ahead_once() {
+        let mut game = game(7);
+        let _ = game.update(Message::TournamentStarted(tournament::LENGTHS[0]));
+        let _ = game.update(Message::CourseStarted);
+        assert!(game.tournament.is_some() && game.course.is_none(), "the course waits for the player to confirm");
+
+        let _ = game.update(Message::TournamentExitConfirmed(true));
+        assert!(game.tournament.is_none());
+        assert!(game.course.is_some());
+        assert!(game.tournament_exit.is_none());
+    }
+
+    #[test]
+    fn a_declined_tournament_exit_leaves_the_tournament_alone() {
+        let mut game = game(7);
+        let _ = game.update(Message::TournamentStarted(tournament::LENGTHS[0]));
+        let _ = game.update(Message::CourseStarted);
+        let _ = game.update(Message::TournamentExitConfirmed(false));
+        assert!(game.tournament.is_some());
+        assert!(game.course.is_none());
+    }
+
+    #[test]
+    fn a_scorecard_loaded_mid_tournament_waits_for_the_player_to_confirm() {
+        let path = std::env::temp_dir().join(format!("guessing-game-tournament-{}.scorecard", std::process::id()));
+        let challenge = Challenge { seed: 90_210, min: MIN_NUMBER, max: MAX_NUMBER };
+        std::fs::write(&path, Scorecard { challenge, guesses: 5, score: 80, seconds: 30 }.to_text()).unwrap();
+
+        let mut game = game(7);
+        let _ = game.update(Message::TournamentStarted(tournament::LENGTHS[0]));
+        let _ = game.update(Message::ScorecardPathChanged(path.display().to_string()));
+        let _ = game.update(Message::ScorecardImported);
+        assert!(game.tournament.is_some() && game.opponent.is_none(), "the scorecard waits for the player to confirm");
+
+        let _ = game.update(Message::TournamentExitConfirmed(true));
+        let _ = std::fs::remove_file(&path);
+        assert!(game.tournament.is_none());
+        assert!(game.opponent.is_some());
+        assert_eq!(game.seed, 90_210);
+    }
+
+    #[test]
+    fn a_crashed_game_restored_mid_tournament_waits_for_the_player_to_confirm() {
+        let mut crashed = game(42);
+        guess(&mut crashed, "50");
+        let snapshot = crashed.snapshot();
+
+        let mut game = game(7);
+        let _ = game.update(Message::TournamentStarted(tournament::LENGTHS[0]));
+        game.pending_recovery = Some(snapshot);
+        let _ = game.update(Message::RecoveryRestored);
+        assert!(game.tournament.is_some() && game.history.is_empty(), "the crashed game waits for the player to confirm");
+        assert!(game.pending_recovery.is_some());
+
+        let _ = game.update(Message::TournamentExitConfirmed(true));
+        assert!(game.tournament.is_none());
+        assert_eq!(game.history, [50]);
+        assert_eq!(game.seed, crashed.seed);
+    }
+
+    // Type `text` into the guess field and submit it
+    fn guess(game: &mut GuessingGame, text: &str) {
+        let _ = game.update(Message::GuessInputChanged(text.to_string()));
+        let _ = game.update(Message::GuessButtonPressed);
+    }
+
+    #[test]
+    fn repeated_submits_of_an_emptied_field_never_use_an_attempt() {
+        let mut game = game(7);
+        let wrong = if game.secret_number.reveal() == 50 { "51" } else { "50" };
+        guess(&mut game, wrong);
+        assert_eq!(game.attempts, 1);
+        assert!(game.guess.is_empty(), "a counted guess clears the field");
+
+        // Enter held down, or mashed, on the field the guess left empty
+        for _ in 0..20 {
+            let _ = game.update(Message::GuessButtonPressed);
+        }
+        assert_eq!(game.attempts, 1);
+        assert_eq!(game.history.len(), 1);
+        assert!(matches!(game.feedback, Feedback::InvalidNumber(ParseError::Empty)));
+    }
+
+    // Play one wrong guess, then start over; returns the streak that's left
+    fn streak_after_walking_away(game: &mut GuessingGame) -> u32 {
+        game.progress.stats.streak = 3;
+        let wrong = if game.secret_number.reveal() == 50 { "51" } else { "50" };
+        guess(game, wrong);
+        let _ = game.update(Message::NewGame);
+        game.progress.stats.streak
+    }
+
+    // Set `secret` for another player and hand the game over
+    fn hand_over(game: &mut GuessingGame, secret: &str) {
+        for message in [
+            Message::PassAndPlayOpened,
+            Message::PassSecretChanged(secret.to_string()),
+            Message::PassSecretSubmitted,
+            Message::PassReady,
+        ] {
+            let _ = game.update(message);
+        }
+    }
+
+    #[test]
+    fn walking_away_from_a_classic_game_breaks_the_streak() {
+        assert_eq!(streak_after_walking_away(&mut game(7)), 0);
+    }
+
+    #[test]
+    fn walking_away_from_a_pass_and_play_game_keeps_the_streak() {
+        let mut game = game(7);
+        hand_over(&mut game, "37");
+        assert!(game.pass_and_play);
+        assert_eq!(streak_after_walking_away(&mut game), 3);
+    }
+
+    #[test]
+    fn walking_away_from_a_voided_game_keeps_the_streak() {
+        let mut game = game(7);
+        let _ = game.update(Message::DebugOverlayToggled);
+        assert!(game.debug_voided);
+        assert_eq!(streak_after_walking_away(&mut game), 3);
+    }
+
+    #[test]
+    fn handing_over_for_pass_and_play_keeps_the_streak() {
+        let mut game = game(7);
+        game.progress.stats.streak = 3;
+        let wrong = if game.secret_number.reveal() == 50 { "51" } else { "50" };
+        guess(&mut game, wrong);
+        hand_over(&mut game, "37");
+        assert!(game.pass_and_play);
+        assert_eq!(game.progress.stats.streak, 3);
+    }
+
+    // A source that hands out the numbers it was given, in order
+    struct Fixed(Vec<u32>);
+
+    impl NumberSource for Fixed {
+        fn secret(&mut self, _min: u32, _max: u32) -> u32 {
+            self.0.remove(0)
+        }
+    }
+
+    #[test]
+    fn every_game_draws_its_secret_from_the_source() {
+        let source = Fixed(vec![1_000, 90_210]);
+        let mut game = GuessingGame::with_setup(Box::new(source), Preferences::default(), Locale::English, Saved::default());
+        assert_eq!(game.seed, 1_000);
+        assert_eq!(game.secret_number.reveal(), seeded_secret(1_000, MIN_NUMBER, MAX_NUMBER));
+
+        let _ = game.update(Message::NewGame);
+        assert_eq!(game.seed, 90_210);
+        assert_eq!(game.secret_number.reveal(), seeded_secret(90_210, MIN_NUMBER, MAX_NUMBER));
+    }
+
+    #[test]
+    fn a_pasted_challenge_code_starts_its_game() {
+        let mut game = game(7);
+        let code = Challenge { seed: 90_210, min: MIN_NUMBER, max: MAX_NUMBER }.encode();
+        let _ = game.update(Message::ChallengeInputChanged(format!(" {} ", code)));
+        let _ = game.update(Message::ChallengeSubmitted);
+        assert_eq!(game.challenge_error, None);
+        assert_eq!(game.seed, 90_210);
+        assert_eq!(game.secret_number.reveal(), 37);
+        assert_eq!(game.current_challenge().encode(), code, "the game shares the code it was started from");
+    }
+
+    #[test]
+    fn a_challenge_for_another_range_is_refused() {
+        let mut game = game(7);
+        let seed = game.seed;
+        let code = Challenge { seed: 90_210, min: 1, max: 1_000 }.encode();
+        let _ = game.update(Message::ChallengeInputChanged(code));
+        let _ = game.update(Message::ChallengeSubmitted);
+        assert_eq!(game.challenge_error, Some(ChallengeError::UnsupportedRange));
+        assert_eq!(game.seed, seed, "the game in play carries on");
+
+        let _ = game.update(Message::ChallengeInputChanged("not a code".to_string()));
+        let _ = game.update(Message::ChallengeSubmitted);
+        assert_eq!(game.challenge_error, Some(ChallengeError::WrongLength));
+    }
+
+    #[test]
+    fn a_damaged_snapshot_still_starts_a_fresh_game() {
+        storage::save("recovery.txt", "schema_version = 9\nmasked_secret = 12\nhistory = 5,x\n").unwrap();
+        let saved = Saved::load();
+        assert!(saved.recovery.is_none());
+        assert!(saved.newer_files.is_empty(), "a damaged file isn't mistaken for a newer one");
+
+        let mut game = GuessingGame::with_setup(Box::new(SeededSource::new(42)), Preferences::default(), Locale::English, saved);
+        assert!(game.pending_recovery.is_none());
+        guess(&mut game, "50");
+        assert_eq!(game.attempts, 1);
+        assert_eq!(storage::load("recovery.txt").unwrap(), None, "the damaged file is gone");
+    }
+
+    // A game with the per-guess time limit on
+    fn timed_game() -> GuessingGame {
+        let mut game = game(7);
+        game.preferences.timed_guesses = true;
+        game
+    }
+
+    // A guess that can't be the secret
+    fn wrong_guess(game: &GuessingGame) -> &'static str {
+        if game.secret_number.reveal() == 50 { "51" } else { "50" }
+    }
+
+    // Comfortably past the limit, however long the test took to get here
+    const LATE: Duration = Duration::from_millis(timer::ATTEMPT_LIMIT.as_millis() as u64 + 500);
+
+    #[test]
+    fn the_attempt_clock_waits_for_the_first_typed_guess() {
+        let mut game = timed_game();
+        let _ = game.update(Message::Tick(Instant::now() + Duration::from_secs(60)));
+        assert_eq!(game.attempts, 0);
+        assert!(game.timeouts.is_empty());
+        assert_eq!(game.attempt_remaining(), None);
+        assert_ne!(game.feedback, Feedback::TooSlow);
+    }
+
+    #[test]
+    fn an_attempt_left_too_long_is_wasted_once() {
+        let mut game = timed_game();
+        let typed = Instant::now();
+        let _ = game.update(Message::GuessInputChanged("5".to_string()));
+        let _ = game.update(Message::Tick(typed + LATE));
+        assert_eq!(game.attempts, 1);
+        assert_eq!(game.timeouts, [0]);
+        assert!(game.history.is_empty(), "a timeout has no guess");
+        assert_eq!(game.feedback, Feedback::TooSlow);
+
+        // The next attempt's time starts from when the last ran out, not from the tick
+        let _ = game.update(Message::Tick(typed + LATE + Duration::from_millis(100)));
+        assert_eq!(game.attempts, 1);
+        let _ = game.update(Message::Tick(typed + LATE + timer::ATTEMPT_LIMIT));
+        assert_eq!(game.attempts, 2);
+        assert_eq!(game.timeouts, [0, 0]);
+    }
+
+    #[test]
+    fn timeouts_sit_between_the_guesses_they_came_between() {
+        let mut game = timed_game();
+        let wrong = wrong_guess(&game);
+        let typed = Instant::now();
+        let _ = game.update(Message::GuessInputChanged(wrong.to_string()));
+        let _ = game.update(Message::Tick(typed + LATE));
+        let _ = game.update(Message::GuessButtonPressed);
+        assert_eq!(game.attempts, 2);
+        assert_eq!(game.history.len(), 1);
+        assert_eq!(game.timeouts, [0]);
+
+        let secret = game.secret_number.reveal().to_string();
+        guess(&mut game, &secret);
+        assert!(game.won);
+        let _ = game.update(Message::ReviewToggled);
+
+        let review = GameViewModel::new(&game).summary.and_then(|summary| summary.review).expect("the review is open");
+        let rows: Vec<&str> = review.rows.iter().map(|row| row.text.as_str()).collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], "Too slow!");
+        assert!(rows[1].starts_with(&format!("1. {} ", wrong)));
+        assert!(rows[2].starts_with(&format!("2. {} ", secret)));
+        assert!(review.header.ends_with(", 1 too slow"));
+
+        let csv = game.game_replay().to_csv();
+        assert_eq!(csv.lines().nth(1), Some("1,,Too slow!,1,100,"));
+    }
+
+    #[test]
+    fn the_attempt_clock_stops_with_an_idle_pause() {
+        let mut game = timed_game();
+        let typed = Instant::now();
+        let _ = game.update(Message::GuessInputChanged("5".to_string()));
+        // Away for longer than the idle timeout: the clock stopped when the player left
+        let away = game.preferences.idle_timeout.duration().expect("idle pauses are on by default");
+        let _ = game.update(Message::Tick(typed + away + LATE));
+        assert!(game.idle_paused);
+        assert_eq!(game.attempts, 0);
+    }
+
+    #[test]
+    fn a_won_game_never_times_out() {
+        let mut game = timed_game();
+        let typed = Instant::now();
+        let secret = game.secret_number.reveal().to_string();
+        guess(&mut game, &secret);
+        let _ = game.update(Message::Tick(typed + LATE));
+        assert!(game.won);
+        assert_eq!(game.attempts, 1);
+        assert!(game.timeouts.is_empty());
+    }
+
+    #[test]
+    fn a_restored_game_keeps_its_timeouts_in_place() {
+        let mut game = timed_game();
+        let typed = Instant::now();
+        let wrong = wrong_guess(&game);
+        let _ = game.update(Message::GuessInputChanged(wrong.to_string()));
+        let _ = game.update(Message::Tick(typed + LATE));
+        let _ = game.update(Message::GuessButtonPressed);
+
+        let snapshot = game.snapshot();
+        let mut restored = timed_game();
+        restored.restore(snapshot);
+        assert_eq!(restored.timeouts, [0]);
+        assert_eq!(restored.attempts, 2);
+    }
+
+    // Every recording checked in under `recordings/`, replayed on each test run
+    const RECORDINGS: [(&str, &str); 1] = [("classic-win", include_str!("../recordings/classic-win.rec"))];
+
+    #[test]
+    fn checked_in_recordings_replay_to_their_digest() {
+        for (name, contents) in RECORDINGS {
+            let recording = Recording::parse(contents, Instant::now()).unwrap_or_else(|error| panic!("{}: {}", name, error));
+            let expected = recording.digest.unwrap_or_else(|| panic!("{} has no digest", name));
+            assert_eq!(replay(recording), expected, "{} replayed to a different state", name);
+        }
+    }
+
+    #[test]
+    fn opting_out_stops_the_statistics_at_once() {
+        analytics::delete();
+        let mut game = game(42);
+        guess(&mut game, "50");
+        assert!(analytics::load().is_empty(), "nothing is recorded before opting in");
+
+        let _ = game.update(Message::AnalyticsToggled(true));
+        guess(&mut game, "95");
+        let events: Vec<String> = analytics::load().into_iter().map(|record| record.event).collect();
+        assert_eq!(events, ["session_started", "game_won"]);
+
+        let _ = game.update(Message::AnalyticsToggled(false));
+        let _ = game.update(Message::NewGame);
+        guess(&mut game, "50");
+        assert_eq!(analytics::load().len(), 2, "nothing more after opting out");
+
+        let _ = game.update(Message::InsightsToggled);
+        let _ = game.update(Message::AnalyticsDeleted);
+        assert!(analytics::load().is_empty());
+        assert_eq!(game.insights, Some(Insights::default()));
+    }
+
+    #[test]
+    fn a_win_unlocks_achievements_and_walking_away_ends_the_streak() {
+        let _ = Progress::default().reset();
+        let mut game = game(42);
+        guess(&mut game, "95");
+        let unlocked: Vec<&str> = game.toasts.iter().map(|(achievement, _)| achievement.key).collect();
+        assert_eq!(unlocked, ["first_win", "first_try", "five_or_fewer", "optimal", "quick"]);
+        assert_eq!(game.progress.stats, achievements::Stats { wins: 1, streak: 1 });
+
+        let _ = game.update(Message::NewGame);
+        let wrong = wrong_guess(&game);
+        guess(&mut game, wrong);
+        let _ = game.update(Message::NewGame);
+        assert_eq!(game.progress.stats, achievements::Stats { wins: 1, streak: 0 });
+        assert_eq!(Progress::load(), game.progress);
+        let _ = game.progress.reset();
+    }
+
+    #[test]
+    fn the_debug_overlay_is_off_until_asked_for() {
+        let game = game(42);
+        assert!(!game.debug_overlay);
+        assert!(!game.debug_voided);
+        assert!(game.can_count());
+    }
+
+    #[test]
+    fn a_game_seen_with_the_debug_overlay_never_counts() {
+        let _ = Progress::default().reset();
+        let mut game = game(42);
+        let _ = game.update(Message::DebugOverlayToggled);
+        let _ = game.update(Message::DebugOverlayToggled);
+        assert!(!game.debug_overlay);
+        assert!(game.debug_voided, "closing the overlay doesn't unsee the secret");
+        guess(&mut game, "95");
+        assert!(game.won);
+        assert_eq!(game.progress.stats.wins, 0);
+        assert!(game.toasts.is_empty());
+
+        let _ = game.update(Message::NewGame);
+        assert!(game.can_count(), "the next game's secret hasn't been seen");
+
+        let _ = game.update(Message::DebugOverlayToggled);
+        let _ = game.update(Message::NewGame);
+        assert!(game.debug_voided, "left open, the overlay shows the new secret too");
+    }
+
+    #[test]
+    fn the_debug_overlay_lists_recent_messages_but_not_ticks() {
+        let mut game = game(42);
+        guess(&mut game, "50");
+        let _ = game.update(Message::Tick(Instant::now()));
+        let _ = game.update(Message::DebugOverlayToggled);
+        let recent: Vec<&str> = game.recent_messages.iter().copied().collect();
+        assert_eq!(recent, ["GuessInputChanged", "GuessButtonPressed", "DebugOverlayToggled"]);
+        assert_eq!(game.updates, 4, "ticks still count as updates");
+    }
+
+    #[test]
+    fn the_result_card_tracks_every_guess_and_ends_on_the_win() {
+        let mut game = game(42);
+        for text in ["50", "99", "95"] {
+            guess(&mut game, text);
+        }
+        let card = game.result_card();
+        assert_eq!(card.track, [Step::Higher, Step::Lower, Step::Win]);
+        assert_eq!(card.lines[0], "Range 1–100");
+        assert_eq!(card.lines[1], "Solved in 3 guesses (optimal: 7)");
+        assert!(card.lines[2].starts_with("Time: "));
+        assert_eq!(card.lines[3], "Grade A");
+        assert!(!card.colorblind);
+
+        game.preferences.colorblind = true;
+        assert!(game.result_card().colorblind);
+    }
+
+    #[test]
+    fn closing_before_the_first_guess_does_not_ask() {
+        let mut game = game(42);
+        let _ = game.update(Message::CloseRequested);
+        assert!(!game.confirm_close);
+    }
+
+    #[test]
+    fn closing_mid_game_asks_and_keep_playing_stays_open() {
+        let mut game = game(42);
+        guess(&mut game, "50");
+        let _ = game.update(Message::CloseRequested);
+        assert!(game.confirm_close);
+
+        let _ = game.update(Message::CloseConfirmed(QuitChoice::KeepPlaying));
+        assert!(!game.confirm_close);
+        assert_eq!(game.history, [50], "the game goes on as it was");
+    }
+
+    #[test]
+    fn closing_a_won_game_does_not_ask() {
+        let mut game = game(42);
+        guess(&mut game, "95");
+        let _ = game.update(Message::CloseRequested);
+        assert!(!game.confirm_close);
+    }
+
+    #[test]
+    fn saving_for_later_keeps_the_game_and_quitting_drops_it() {
+        let mut saving = game(42);
+        guess(&mut saving, "50");
+        let _ = saving.update(Message::CloseRequested);
+        let _ = saving.update(Message::CloseConfirmed(QuitChoice::SaveForLater));
+        let snapshot = recovery::load().expect("the game was saved for later");
+        assert!(snapshot.saved);
+        assert_eq!(snapshot.history, [50]);
+
+        let mut quitting = game(42);
+        guess(&mut quitting, "50");
+        let _ = quitting.update(Message::CloseRequested);
+        let _ = quitting.update(Message::CloseConfirmed(QuitChoice::Quit));
+        assert_eq!(recovery::load(), None);
+    }
+
+    #[test]
+    fn the_first_guess_remembers_the_game_for_same_again() {
+        let mut first = game(42);
+        assert_eq!(first.last_played, None);
+        guess(&mut first, "50");
+        let last = first.last_played.expect("a guess makes the game played");
+        assert_eq!((last.mode, last.min, last.max), (PlayMode::Classic, MIN_NUMBER, MAX_NUMBER));
+        assert_eq!(LastPlayed::load(MIN_NUMBER, MAX_NUMBER), Some((last, None)));
+
+        let mut next = game(7);
+        next.last_played = Some(LastPlayed { mode: PlayMode::ComputerGuesses, mystery: true, ..last });
+        let _ = next.update(Message::SameAgain);
+        assert!(next.bot_game.is_some());
+        assert!(next.preferences.mystery_mode);
+    }
+
+    #[test]
+    fn an_endless_win_rolls_straight_over_to_a_fresh_round() {
+        let _ = Records::default().reset();
+        let mut game = game(42);
+        let _ = game.update(Message::EndlessStarted);
+        let first = game.secret_number.reveal();
+        let wrong = wrong_guess(&game);
+        guess(&mut game, wrong);
+        guess(&mut game, &first.to_string());
+
+        assert!(!game.won, "the next round is already on");
+        assert_eq!(game.endless, Some(endless::Session { solved: 1, guesses: 2 }));
+        assert_eq!(game.feedback, Feedback::Solved { secret: first, solved: 1 });
+        assert_eq!(game.attempts, 0);
+        assert!(game.history.is_empty());
+        assert_eq!((game.low, game.high), (MIN_NUMBER, MAX_NUMBER));
+        assert_eq!(game.stopwatch.elapsed(Instant::now()), Duration::ZERO, "the round's clock starts afresh");
+
+        let wrong = wrong_guess(&game);
+        guess(&mut game, wrong);
+        let _ = game.update(Message::EndlessStopped);
+        assert_eq!(game.endless, None);
+        assert_eq!(game.endless_records, Records { sessions: 1, best_solved: 1, solved: 1, guesses: 3 });
+        assert_eq!(Records::load(), game.endless_records);
+        let _ = game.endless_records.reset();
+    }
+
+    #[test]
+    fn stepping_edits_the_guess_within_the_range_left() {
+        let mut game = game(42);
+        let _ = game.update(Message::GuessStepped(1));
+        assert_eq!(game.guess, "50", "an empty field starts from the middle");
+        assert!(game.stopwatch.has_started());
+
+        guess(&mut game, "90");
+        let _ = game.update(Message::GuessStepped(1));
+        assert_eq!(game.guess, "95", "the cleared field starts from the middle of what's left");
+        let _ = game.update(Message::GuessInputChanged("92".to_string()));
+        let _ = game.update(Message::GuessStepped(-10));
+        assert_eq!(game.guess, "91", "nothing at or below 90 is left");
+        let _ = game.update(Message::GuessStepped(30));
+        assert_eq!(game.guess, "100");
+
+        guess(&mut game, "95");
+        let _ = game.update(Message::GuessStepped(1));
+        assert_eq!(game.guess, "", "the won game takes no more steps");
+    }
+
+    #[test]
+    fn each_guess_is_timed_from_the_one_before_and_the_first_from_the_start() {
+        let mut game = game(42);
+        game.stopwatch = Stopwatch::from_elapsed(Duration::from_millis(8_200)); // Stopped, so the clock holds still
+        guess(&mut game, "50");
+        game.stopwatch = Stopwatch::from_elapsed(Duration::from_millis(11_700));
+        guess(&mut game, "75");
+        assert_eq!(game.guess_times, [Some(Duration::from_millis(8_200)), Some(Duration::from_millis(3_500))]);
+
+        let replay = game.game_replay();
+        assert_eq!(replay.times, game.guess_times);
+        assert_eq!(game.snapshot().times, game.guess_times);
+    }
+
+    #[test]
+    fn time_away_while_the_clock_is_paused_is_not_thinking_time() {
+        let mut game = game(42);
+        let typed = Instant::now();
+        let _ = game.update(Message::GuessInputChanged("50".to_string()));
+        let away = game.preferences.idle_timeout.duration().expect("idle pauses are on by default");
+        let _ = game.update(Message::Tick(typed + away + Duration::from_secs(600)));
+        assert!(game.idle_paused);
+
+        let _ = game.update(Message::GuessButtonPressed);
+        let time = game.guess_times[0].expect("the guess was timed");
+        assert!(time < away, "{:?} counted the time away", time);
+    }
+
+    #[test]
+    fn the_trend_window_can_be_switched() {
+        let mut game = game(42);
+        assert_eq!(game.trend_window, trend::Window::Last20);
+        let _ = game.update(Message::TrendWindowSelected(trend::Window::All));
+        assert_eq!(game.trend_window, trend::Window::All);
+    }
+
+    #[test]
+    fn a_win_too_quick_to_be_real_is_kept_off_the_time_board_only() {
+        let boards = |game: &GuessingGame| game.board_changes.iter().map(|placement| placement.metric).collect::<Vec<_>>();
+
+        let mut game = game(42);
+        game.leaderboards.reset().unwrap();
+        game.stopwatch = Stopwatch::from_elapsed(Duration::from_secs(60));
+        guess(&mut game, "95");
+        assert_eq!(game.time_refused, Some(leaderboard::Ineligible::TooFewGuesses));
+        assert_eq!(boards(&game), [Metric::Attempts]);
+
+        let _ = game.update(Message::NewGame);
+        assert_eq!(game.time_refused, None);
+        game.secret_number = Secret::new(95);
+        game.stopwatch = Stopwatch::from_elapsed(Duration::from_secs(1));
+        let wrong = wrong_guess(&game);
+        guess(&mut game, wrong);
+        guess(&mut game, "95");
+        assert_eq!(game.time_refused, Some(leaderboard::Ineligible::TooFast));
+        assert_eq!(viewmodel::GameViewModel::new(&game).summary.unwrap().time_refused, game.time_refused.map(|reason| reason.to_string()));
+
+        let _ = game.update(Message::NewGame);
+        game.secret_number = Secret::new(95);
+        game.stopwatch = Stopwatch::from_elapsed(leaderboard::MIN_TIMED_ELAPSED);
+        guess(&mut game, wrong);
+        guess(&mut game, "95");
+        assert_eq!(game.time_refused, None);
+        assert_eq!(boards(&game), Metric::ALL);
+    }
+
+    #[test]
+    fn a_win_the_clock_paused_for_is_kept_off_the_time_board() {
+        let mut game = game(42);
+        game.leaderboards.reset().unwrap();
+        let typed = Instant::now();
+        let _ = game.update(Message::GuessInputChanged("50".to_string()));
+        let away = game.preferences.idle_timeout.duration().expect("idle pauses are on by default");
+        let _ = game.update(Message::Tick(typed + away + Duration::from_secs(600)));
+        let _ = game.update(Message::GuessButtonPressed);
+        assert!(!game.idle_paused, "the guess brought the player back");
+
+        game.stopwatch = Stopwatch::from_elapsed(Duration::from_secs(60));
+        guess(&mut game, "95");
+        assert_eq!(game.time_refused, Some(leaderboard::Ineligible::Idled));
+        assert!(game.leaderboards.board(Metric::Time).is_empty());
+        assert_eq!(game.leaderboards.board(Metric::Attempts).len(), 1);
+
+        let _ = game.update(Message::NewGame);
+        assert!(!game.idled, "the next game starts with a clean clock");
+    }
+
+    #[test]
+    fn the_window_plays_guesses_the_way_the_library_steps_them() {
+        let mut game = game(42);
+        let mut state = game::State::new(game.secret_number.reveal(), MIN_NUMBER, MAX_NUMBER);
+        for guess_made in [50, 80, 80, 500, 90, 99, 95] {
+            guess(&mut game, &guess_made.to_string());
+            state = game::step(state, game::Input::Guess(guess_made));
+            assert_eq!((game.low, game.high, game.attempts), (state.low, state.high, state.attempts), "after {}", guess_made);
+            assert_eq!(game.history, state.history);
+        }
+        assert!(game.won && state.won());
+        assert_eq!(state.attempts, 7, "repeats and guesses outside the range count");
+    }
+
+    #[test]
+    fn a_drill_replays_over_the_range_it_drilled() {
+        let mut game = game(42);
+        let _ = game.update(Message::PracticeInputChanged("40-60".to_string()));
+        let _ = game.update(Message::PracticeSubmitted);
+        assert_eq!(game.practice, Some((40, 60)));
+        let secret = game.secret_number.reveal().to_string();
+        guess(&mut game, &secret);
+
+        let replay = game.game_replay();
+        assert_eq!((replay.min, replay.max), (40, 60));
+        let _ = game.update(Message::NewGame);
+        assert_eq!((game.game_replay().min, game.game_replay().max), (MIN_NUMBER, MAX_NUMBER));
+    }
+
+    #[test]
+    fn a_drill_restored_after_a_crash_is_still_a_drill() {
+        let _ = Progress::default().reset();
+        let mut crashed = game(42);
+        let _ = crashed.update(Message::PracticeInputChanged("40-60".to_string()));
+        let _ = crashed.update(Message::PracticeSubmitted);
+        let wrong = wrong_guess(&crashed);
+        guess(&mut crashed, wrong);
+
+        let mut game = game(7);
+        game.pending_recovery = Some(crashed.snapshot());
+        let _ = game.update(Message::RecoveryRestored);
+        assert_eq!(game.practice, Some((40, 60)));
+        assert_eq!((game.low, game.high), (crashed.low, crashed.high));
+        assert!(!game.can_count());
+
+        let secret = game.secret_number.reveal().to_string();
+        guess(&mut game, &secret);
+        assert!(game.won);
+        assert_eq!(game.progress.stats, achievements::Stats::default());
+        assert!(game.toasts.is_empty());
+        assert!(game.leaderboards.board(Metric::Attempts).is_empty());
+        assert!(game.leaderboards.board(Metric::Time).is_empty());
+    }
+}
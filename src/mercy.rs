@@ -0,0 +1,121 @@
+// Free hints the game volunteers after a run of wrong guesses, for younger players
+
+use crate::format::{format_number, Locale};
+use crate::secret::Secret;
+
+// Wrong guesses between one automatic hint and the next
+pub const GUESSES_PER_HINT: u32 = 5;
+
+// An automatic hint, in the order they are volunteered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MercyHint {
+    Parity,     // Whether the secret is even or odd
+    NearestTen, // The multiple of ten the secret rounds to
+    WithinFive, // A number the secret is at most five away from
+}
+
+impl MercyHint {
+    // Every automatic hint, least revealing first
+    pub const ALL: [MercyHint; 3] = [MercyHint::Parity, MercyHint::NearestTen, MercyHint::WithinFive];
+
+    // The name written to the game log
+    pub fn key(self) -> &'static str {
+        match self {
+            MercyHint::Parity => "parity",
+            MercyHint::NearestTen => "nearest_ten",
+            MercyHint::WithinFive => "within_five",
+        }
+    }
+
+    // What the hint tells the player
+    //
+    // Like bought hints, these decode the secret mid-game, each only as far as it tells.
+    pub fn text(self, secret: &Secret, locale: Locale) -> String {
+        let value = secret.reveal();
+        let n = |value: u32| format_number(locale, value);
+        match self {
+            MercyHint::Parity => {
+                let parity = if value.is_multiple_of(2) { "even" } else { "odd" };
+                format!("Here's a clue: the number is {}", parity)
+            }
+            MercyHint::NearestTen => format!("Here's a clue: the number rounds to {}", n(nearest(value, 10))),
+            // Rounding to the nearest five keeps the secret itself from being given away
+            MercyHint::WithinFive => format!("Here's a clue: it's within 5 of {}", n(nearest(value, 5))),
+        }
+    }
+}
+
+// The automatic hint due after `wrong_guesses`, given that `given` have already been volunteered
+//
+// A hint falls due every `GUESSES_PER_HINT` wrong guesses, and none once all have been given.
+// Only valid guesses that were wrong and not repeats should be counted.
+pub fn due(wrong_guesses: u32, given: usize) -> Option<MercyHint> {
+    let hint = MercyHint::ALL.get(given).copied()?;
+    let needed = GUESSES_PER_HINT.saturating_mul(given as u32 + 1);
+    (wrong_guesses >= needed).then_some(hint)
+}
+
+// `value` rounded to the nearest multiple of `step`, halves rounding up
+pub fn nearest(value: u32, step: u32) -> u32 {
+    value.saturating_add(step / 2) / step * step
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_hint_falls_due_every_five_wrong_guesses() {
+        let schedule = |given| (0..=20).filter(|&wrong| due(wrong, given).is_some()).min();
+        assert_eq!(schedule(0), Some(5));
+        assert_eq!(schedule(1), Some(10));
+        assert_eq!(schedule(2), Some(15));
+
+        assert_eq!(due(4, 0), None);
+        assert_eq!(due(5, 0), Some(MercyHint::Parity));
+        assert_eq!(due(9, 1), None);
+        assert_eq!(due(10, 1), Some(MercyHint::NearestTen));
+        assert_eq!(due(15, 2), Some(MercyHint::WithinFive));
+    }
+
+    #[test]
+    fn hints_missed_along_the_way_come_one_at_a_time() {
+        // Switched on late, after 12 wrong guesses, the first hint is still parity
+        assert_eq!(due(12, 0), Some(MercyHint::Parity));
+        assert_eq!(due(12, 1), Some(MercyHint::NearestTen));
+        assert_eq!(due(12, 2), None);
+    }
+
+    #[test]
+    fn no_hints_once_every_tier_is_given() {
+        assert_eq!(due(15, 3), None);
+        assert_eq!(due(u32::MAX, 3), None);
+        assert_eq!(due(u32::MAX, usize::MAX), None);
+    }
+
+    #[test]
+    fn rounding_goes_to_the_nearest_step_with_halves_up() {
+        assert_eq!(nearest(94, 10), 90);
+        assert_eq!(nearest(95, 10), 100);
+        assert_eq!(nearest(3, 10), 0);
+        assert_eq!(nearest(92, 5), 90);
+        assert_eq!(nearest(93, 5), 95);
+        assert_eq!(nearest(u32::MAX, 10), 4_294_967_290);
+    }
+
+    #[test]
+    fn each_hint_says_what_it_tells() {
+        let texts: Vec<String> = MercyHint::ALL.iter().map(|hint| hint.text(&Secret::new(1234), Locale::English)).collect();
+        assert_eq!(
+            texts,
+            [
+                "Here's a clue: the number is even",
+                "Here's a clue: the number rounds to 1,230",
+                "Here's a clue: it's within 5 of 1,235",
+            ]
+        );
+        for secret in 1..=100 {
+            assert!(nearest(secret, 5).abs_diff(secret) <= 5);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+// Versions of the files the game keeps, and upgrading old ones as they're read
+//
+// Every `key = value` file carries a `schema_version`. Reading one runs the steps from its
+// version up to the current one in order, so each step only knows about the version
+// before it. A file from a newer version is never overwritten or deleted: the game runs
+// on defaults instead, says so, and leaves the file for the version that wrote it.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::Mutex;
+
+use crate::storage;
+
+// The key every file's version is written under
+pub const VERSION_KEY: &str = "schema_version";
+
+// The values read from a file, as `storage::parse_key_values` returns them
+pub type Values = BTreeMap<String, String>;
+
+// One upgrade, from the version at its position in `Schema::steps` plus one to the next
+pub type Step = fn(&mut Values);
+
+// How one of the game's files has changed over time
+pub struct Schema {
+    pub file: &'static str,               // The file's name inside the data directory
+    pub name: &'static str,               // What the file holds, as the player would put it
+    pub legacy_key: Option<&'static str>, // Where the version was kept before `VERSION_KEY`, if anywhere
+    pub steps: &'static [Step],           // Upgrades from version 1 onwards, oldest first
+}
+
+impl Schema {
+    // The version written now, one past the last upgrade
+    pub const fn current(&self) -> u32 {
+        self.steps.len() as u32 + 1
+    }
+}
+
+// A file that couldn't be understood, so it's left alone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NewerVersion {
+    pub name: &'static str,  // What the file holds
+    pub found: Option<u32>,  // The version it claims, or `None` if that isn't even a number
+    pub supported: u32,      // The newest version this build reads
+}
+
+impl NewerVersion {
+    // The explanation shown to the player
+    pub fn message(&self) -> String {
+        let version = self
+            .found
+            .map_or_else(String::new, |found| format!(" (format {}, this version reads up to {})", found, self.supported));
+        format!(
+            "The {} file was created by a newer version of Guessing Game{} - it's left untouched, and defaults are used until you update",
+            self.name, version
+        )
+    }
+}
+
+// Files found to be from a newer version this run, which every save and delete skips
+static NEWER: Mutex<Vec<(&'static str, NewerVersion)>> = Mutex::new(Vec::new());
+
+// Each test thread keeps its own list, as it keeps its own scratch directory
+thread_local! {
+    static TEST_NEWER: RefCell<Vec<(&'static str, NewerVersion)>> = const { RefCell::new(Vec::new()) };
+}
+
+// Work with the files found to be from a newer version
+fn with_newer<T>(f: impl FnOnce(&mut Vec<(&'static str, NewerVersion)>) -> T) -> T {
+    if cfg!(test) {
+        return TEST_NEWER.with_borrow_mut(f);
+    }
+    f(&mut NEWER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+}
+
+// The version `values` were written in; files from before versions were kept are version 1
+fn version_of(schema: &Schema, values: &Values) -> Option<u32> {
+    let written = values.get(VERSION_KEY).or_else(|| schema.legacy_key.and_then(|key| values.get(key)));
+    match written {
+        Some(version) => version.parse().ok().filter(|&version| version >= 1),
+        None => Some(1),
+    }
+}
+
+// Upgrade `values` to the current version in place, returning the version they were in
+pub fn upgrade(schema: &Schema, values: &mut Values) -> Result<u32, NewerVersion> {
+    let found = version_of(schema, values);
+    let version = found
+        .filter(|&version| version <= schema.current())
+        .ok_or(NewerVersion { name: schema.name, found, supported: schema.current() })?;
+
+    for step in &schema.steps[version as usize - 1..] {
+        step(values);
+    }
+    if let Some(key) = schema.legacy_key {
+        values.remove(key);
+    }
+    values.insert(VERSION_KEY.to_string(), schema.current().to_string());
+    Ok(version)
+}
+
+// Read one of the game's files and upgrade it, returning `None` when it doesn't exist yet
+// or is from a newer version, which is then protected for the rest of the run
+pub fn load(schema: &Schema) -> io::Result<Option<Values>> {
+    let Some(contents) = storage::load(schema.file)? else {
+        return Ok(None);
+    };
+
+    let mut values = storage::parse_key_values(&contents);
+    match upgrade(schema, &mut values) {
+        Ok(version) => {
+            if version < schema.current() {
+                tracing::info!(file = schema.file, from = version, to = schema.current(), "Upgraded a saved file");
+            }
+            Ok(Some(values))
+        }
+        Err(newer) => {
+            tracing::warn!(file = schema.file, found = ?newer.found, "Leaving a file from a newer version untouched");
+            with_newer(|files| files.push((schema.file, newer)));
+            Ok(None)
+        }
+    }
+}
+
+// Whether the file was found to be from a newer version this run
+fn is_protected(schema: &Schema) -> bool {
+    with_newer(|files| files.iter().any(|&(file, _)| file == schema.file))
+}
+
+// Write one of the game's files, stamped with the current version, unless it's protected
+pub fn save(schema: &Schema, contents: &str) -> io::Result<()> {
+    if is_protected(schema) {
+        tracing::debug!(file = schema.file, "Skipped saving over a file from a newer version");
+        return Ok(());
+    }
+    storage::save(schema.file, &format!("{} = {}\n{}", VERSION_KEY, schema.current(), contents))
+}
+
+// Delete one of the game's files, unless it's protected
+pub fn remove(schema: &Schema) -> io::Result<()> {
+    if is_protected(schema) {
+        return Ok(());
+    }
+    storage::remove(schema.file)
+}
+
+// Every file found to be from a newer version so far
+pub fn newer_files() -> Vec<NewerVersion> {
+    with_newer(|files| files.iter().map(|&(_, newer)| newer).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Version 2 renamed `colour` to `color`
+    fn rename_colour(values: &mut Values) {
+        if let Some(colour) = values.remove("colour") {
+            values.insert("color".to_string(), colour);
+        }
+    }
+
+    // Version 3 added `size`, and needs version 2's `color` to be there already
+    fn add_size(values: &mut Values) {
+        let size = if values.contains_key("color") { "medium" } else { "unknown" };
+        values.insert("size".to_string(), size.to_string());
+    }
+
+    const WIDGETS: Schema = Schema { file: "widgets.txt", name: "widget", legacy_key: Some("v"), steps: &[rename_colour, add_size] };
+
+    // `contents` upgraded under `WIDGETS`, with the version it was in
+    fn upgraded(contents: &str) -> Result<(u32, Values), NewerVersion> {
+        let mut values = storage::parse_key_values(contents);
+        let version = upgrade(&WIDGETS, &mut values)?;
+        Ok((version, values))
+    }
+
+    // The values written by the current version
+    fn current(pairs: &[(&str, &str)]) -> Values {
+        pairs.iter().chain([&(VERSION_KEY, "3")]).map(|&(key, value)| (key.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn a_file_without_a_version_runs_every_step_in_order() {
+        assert_eq!(upgraded("colour = red\n"), Ok((1, current(&[("color", "red"), ("size", "medium")]))));
+    }
+
+    #[test]
+    fn a_file_only_runs_the_steps_after_its_version() {
+        let (version, values) = upgraded("v = 2\ncolour = kept\ncolor = red\n").unwrap();
+        assert_eq!(version, 2);
+        assert_eq!(values, current(&[("colour", "kept"), ("color", "red"), ("size", "medium")]));
+    }
+
+    #[test]
+    fn the_current_version_wins_over_the_legacy_key() {
+        let (version, values) = upgraded("schema_version = 3\nv = 1\ncolor = red\nsize = large\n").unwrap();
+        assert_eq!(version, 3);
+        assert_eq!(values, current(&[("color", "red"), ("size", "large")]), "no step ran, and the legacy key is gone");
+    }
+
+    #[test]
+    fn a_newer_file_is_refused_with_the_versions() {
+        let newer = upgraded("schema_version = 4\n").unwrap_err();
+        assert_eq!(newer, NewerVersion { name: "widget", found: Some(4), supported: 3 });
+        assert_eq!(
+            newer.message(),
+            "The widget file was created by a newer version of Guessing Game (format 4, this version reads up to 3) - \
+             it's left untouched, and defaults are used until you update"
+        );
+    }
+
+    #[test]
+    fn a_file_with_an_unreadable_version_is_treated_as_newer() {
+        for version in ["0", "-1", "three", ""] {
+            let newer = upgraded(&format!("schema_version = {}\n", version)).unwrap_err();
+            assert_eq!(newer.found, None, "{:?}", version);
+            assert_eq!(
+                newer.message(),
+                "The widget file was created by a newer version of Guessing Game - it's left untouched, and defaults are used until you update"
+            );
+        }
+    }
+
+    #[test]
+    fn saving_stamps_the_current_version() {
+        save(&WIDGETS, "color = red\n").unwrap();
+        assert_eq!(storage::load(WIDGETS.file).unwrap().as_deref(), Some("schema_version = 3\ncolor = red\n"));
+        assert_eq!(load(&WIDGETS).unwrap(), Some(current(&[("color", "red")])));
+    }
+
+    #[test]
+    fn a_newer_file_is_never_saved_over_or_deleted() {
+        storage::save(WIDGETS.file, "schema_version = 7\ncolor = ultraviolet\n").unwrap();
+        assert_eq!(load(&WIDGETS).unwrap(), None);
+        assert_eq!(newer_files(), [NewerVersion { name: "widget", found: Some(7), supported: 3 }]);
+
+        save(&WIDGETS, "color = red\n").unwrap();
+        remove(&WIDGETS).unwrap();
+        assert_eq!(storage::load(WIDGETS.file).unwrap().as_deref(), Some("schema_version = 7\ncolor = ultraviolet\n"));
+    }
+
+    #[test]
+    fn a_missing_file_loads_as_nothing_saved_yet() {
+        remove(&WIDGETS).unwrap();
+        assert_eq!(load(&WIDGETS).unwrap(), None);
+        assert!(newer_files().is_empty());
+    }
+}
@@ -0,0 +1,73 @@
+// Hidden operations for the mystery mode, applied to the guess and the secret before comparing
+
+use std::fmt;
+
+use crate::random::NumberSource;
+
+// An operation hidden from the player, so "too big" and "too small" describe its results
+//
+// Every operation must be strictly monotonic: always increasing or always decreasing over
+// the whole range. Then comparing the results still orders the guess against the secret
+// consistently, so feedback never contradicts itself, and different numbers never give the
+// same result. Wins are still decided on the raw numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MysteryOperation {
+    Square,        // x², increasing
+    Cube,          // x³, increasing
+    Negate,        // -x, decreasing, so the feedback points the other way
+    NegatedSquare, // -x², decreasing
+}
+
+impl MysteryOperation {
+    // Every operation a round can be given
+    pub const ALL: [MysteryOperation; 4] = [
+        MysteryOperation::Square,
+        MysteryOperation::Cube,
+        MysteryOperation::Negate,
+        MysteryOperation::NegatedSquare,
+    ];
+
+    // Pick an operation for a new round
+    pub fn random(numbers: &mut dyn NumberSource) -> MysteryOperation {
+        let last = MysteryOperation::ALL.len() as u32 - 1;
+        MysteryOperation::ALL[numbers.secret(0, last) as usize]
+    }
+
+    // Apply the operation, widened so even the cube of the largest u32 fits
+    pub fn apply(self, value: u32) -> i128 {
+        let value = i128::from(value);
+        match self {
+            MysteryOperation::Square => value * value,
+            MysteryOperation::Cube => value * value * value,
+            MysteryOperation::Negate => -value,
+            MysteryOperation::NegatedSquare => -(value * value),
+        }
+    }
+
+    // The value written to the recovery file
+    pub fn key(self) -> &'static str {
+        match self {
+            MysteryOperation::Square => "square",
+            MysteryOperation::Cube => "cube",
+            MysteryOperation::Negate => "negate",
+            MysteryOperation::NegatedSquare => "negated_square",
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<MysteryOperation> {
+        MysteryOperation::ALL.into_iter().find(|operation| operation.key() == key)
+    }
+}
+
+impl fmt::Display for MysteryOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            MysteryOperation::Square => "squaring",
+            MysteryOperation::Cube => "cubing",
+            MysteryOperation::Negate => "negating",
+            MysteryOperation::NegatedSquare => "squaring, then negating",
+        };
+        write!(f, "{}", label)
+    }
+}
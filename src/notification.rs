@@ -0,0 +1,48 @@
+// Desktop notifications for events the player may miss while the window is in the background
+
+use std::future::Future;
+use std::thread;
+
+use iced::futures::channel::oneshot;
+
+// What clicking the notification's body reports, on desktops that report it
+#[cfg(all(unix, not(target_os = "macos")))]
+const CLICK_ACTION: &str = "default";
+
+// Show a desktop notification announcing a win, resolving to whether the player clicked it
+//
+// The notification is sent from a background thread so a slow notification daemon never
+// stalls the UI, and failures are only logged since notifications are a nice-to-have. The
+// thread then waits for the notification to be clicked or dismissed; where the platform
+// can't say which, it resolves to `false` as soon as the notification is shown.
+pub fn notify_win(body: String) -> impl Future<Output = bool> {
+    let (answer, answered) = oneshot::channel();
+    thread::spawn(move || {
+        let clicked = show(&body).unwrap_or_else(|error| {
+            tracing::warn!(%error, "Could not show the win notification");
+            false
+        });
+        let _ = answer.send(clicked); // The game may have closed in the meantime
+    });
+    async move { answered.await.unwrap_or(false) }
+}
+
+// Show the notification and wait for the player to click or dismiss it
+#[cfg(all(unix, not(target_os = "macos")))]
+fn show(body: &str) -> notify_rust::error::Result<bool> {
+    let handle = notify_rust::Notification::new()
+        .summary("Guessing Game")
+        .body(body)
+        .action(CLICK_ACTION, "Show the game")
+        .show()?;
+
+    let mut clicked = false;
+    handle.wait_for_action(|action| clicked = action == CLICK_ACTION);
+    Ok(clicked)
+}
+
+// Show the notification; clicks aren't reported back here, so it never counts as clicked
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn show(body: &str) -> notify_rust::error::Result<bool> {
+    notify_rust::Notification::new().summary("Guessing Game").body(body).show().map(|_| false)
+}
@@ -0,0 +1,87 @@
+// The number line under the guess controls, where clicking a number puts it in the guess input
+//
+// With the guess assist on, numbers the feedback has already ruled out are greyed and don't
+// respond to clicks. The line only ever knows the bounds the player could work out for
+// themselves, never the secret, so it guides without giving anything away.
+
+use iced::mouse;
+use iced::widget::canvas::event::{self, Event};
+use iced::widget::canvas::{self, Cursor, Frame, Geometry, Path};
+use iced::{Color, Point, Rectangle, Size, Theme};
+
+// The line's height on screen, in logical pixels
+pub const HEIGHT: f32 = 24.0;
+
+// The widget, one cell per number from `min` to `max`
+pub struct NumberLine<Message> {
+    pub min: u32,               // The first number on the line
+    pub max: u32,               // The last number on the line
+    pub allowed: (u32, u32),    // The numbers that can still be picked, the whole line with the assist off
+    pub active: Color,          // The color of a number that's still possible
+    pub ruled_out: Color,       // The color of a number the feedback has ruled out
+    pub on_pick: fn(u32) -> Message, // The message a click on a possible number sends
+}
+
+impl<Message> NumberLine<Message> {
+    // The number under `x` pixels along a line `width` wide
+    fn number_at(&self, x: f32, width: f32) -> u32 {
+        let cells = self.max - self.min + 1;
+        let cell = (x / width.max(1.0) * cells as f32).floor().max(0.0) as u32;
+        self.min + cell.min(cells - 1)
+    }
+
+    // Whether `number` can still be picked
+    fn is_allowed(&self, number: u32) -> bool {
+        (self.allowed.0..=self.allowed.1).contains(&number)
+    }
+
+    // The possible number under the cursor, if it's over one
+    fn hovered(&self, bounds: Rectangle, cursor: Cursor) -> Option<u32> {
+        let position = cursor.position_in(&bounds)?;
+        Some(self.number_at(position.x, bounds.width)).filter(|&number| self.is_allowed(number))
+    }
+}
+
+impl<Message> canvas::Program<Message> for NumberLine<Message> {
+    type State = ();
+
+    fn update(&self, _state: &mut (), event: Event, bounds: Rectangle, cursor: Cursor) -> (event::Status, Option<Message>) {
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => match self.hovered(bounds, cursor) {
+                Some(number) => (event::Status::Captured, Some((self.on_pick)(number))),
+                None => (event::Status::Ignored, None), // Ruled-out numbers are disabled, not just grey
+            },
+            _ => (event::Status::Ignored, None),
+        }
+    }
+
+    fn draw(&self, _state: &(), _theme: &Theme, bounds: Rectangle, cursor: Cursor) -> Vec<Geometry> {
+        let mut frame = Frame::new(bounds.size());
+        let (width, height) = (frame.width(), frame.height());
+        let cells = self.max - self.min + 1;
+        let cell = width / cells as f32;
+        let gap = if cell >= 3.0 { 1.0 } else { 0.0 }; // On a narrow window the cells simply run together
+
+        // The number under the cursor stands taller, so it's clear which one a click picks
+        let hovered = self.hovered(bounds, cursor);
+        for number in self.min..=self.max {
+            let x = (number - self.min) as f32 * cell;
+            let (color, inset) = match number {
+                _ if hovered == Some(number) => (self.active, 0.0),
+                _ if self.is_allowed(number) => (self.active, height / 4.0),
+                _ => (self.ruled_out, height / 4.0),
+            };
+            let cell = Path::rectangle(Point::new(x, inset), Size::new((cell - gap).max(0.5), height - 2.0 * inset));
+            frame.fill(&cell, color);
+        }
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(&self, _state: &(), bounds: Rectangle, cursor: Cursor) -> mouse::Interaction {
+        match self.hovered(bounds, cursor) {
+            Some(_) => mouse::Interaction::Pointer,
+            None => mouse::Interaction::Idle,
+        }
+    }
+}
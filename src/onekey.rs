@@ -0,0 +1,228 @@
+// One-key play: Space alone steps the guess up and submits it, for playing with one hand
+//
+// With the mode on, Space works like this:
+//   tap         step the guess up by one, or onto the middle of the range from an empty field
+//   double-tap  guess; the second tap comes moments after the first, which is taken back
+//   hold        guess as it is, once the key has been down for the hold time
+//
+// A tap counts when the key comes back up, so a hold never steps the guess first. The
+// keyboard repeats a held key, and those presses only check whether the hold time is up.
+// How long a hold and the gap in a double-tap are is a setting, for hands that move slower.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+// How quickly the taps and holds have to come
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Timing {
+    Quick,
+    #[default]
+    Normal,
+    Relaxed,
+}
+
+impl Timing {
+    // Every choice, in the order shown in the picker
+    pub const ALL: [Timing; 3] = [Timing::Quick, Timing::Normal, Timing::Relaxed];
+
+    // How long Space has to be held down to guess
+    pub fn hold(self) -> Duration {
+        match self {
+            Timing::Quick => Duration::from_millis(400),
+            Timing::Normal => Duration::from_millis(600),
+            Timing::Relaxed => Duration::from_millis(900),
+        }
+    }
+
+    // The longest from one tap coming up to the next that still makes them a double-tap
+    pub fn double_tap(self) -> Duration {
+        match self {
+            Timing::Quick => Duration::from_millis(250),
+            Timing::Normal => Duration::from_millis(350),
+            Timing::Relaxed => Duration::from_millis(500),
+        }
+    }
+
+    // The value written to the preferences file
+    pub fn key(self) -> &'static str {
+        match self {
+            Timing::Quick => "quick",
+            Timing::Normal => "normal",
+            Timing::Relaxed => "relaxed",
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<Timing> {
+        Timing::ALL.into_iter().find(|timing| timing.key() == key)
+    }
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Timing::Quick => "Quick",
+            Timing::Normal => "Normal",
+            Timing::Relaxed => "Relaxed",
+        };
+        write!(f, "{} (hold {}s, double-tap {}s)", name, self.hold().as_secs_f32(), self.double_tap().as_secs_f32())
+    }
+}
+
+// What a press or release of the key asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Wait,                     // Nothing yet: the key went down, or is being held
+    Step,                     // A tap
+    Guess { undo_tap: bool }, // A hold, or a double-tap whose first tap has to be taken back
+}
+
+// Where the key is in a tap, double-tap or hold
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OneKey {
+    down_since: Option<Instant>, // When the key went down, while it's held
+    held: bool,                  // Whether this press has already guessed by being held
+    last_tap: Option<Instant>,   // When the last tap came back up, while a second could still follow it
+}
+
+impl OneKey {
+    // The key went down at `now`, or the keyboard repeated it while held
+    pub fn press(&mut self, now: Instant, timing: Timing) -> Action {
+        let Some(since) = self.down_since else {
+            self.down_since = Some(now);
+            return Action::Wait;
+        };
+        if !self.held && now.saturating_duration_since(since) >= timing.hold() {
+            self.held = true;
+            self.last_tap = None; // A tap before the hold can't start a double-tap with the next one
+            return Action::Guess { undo_tap: false };
+        }
+        Action::Wait
+    }
+
+    // The key came back up at `now`
+    pub fn release(&mut self, now: Instant, timing: Timing) -> Action {
+        let since = self.down_since.take();
+        if std::mem::take(&mut self.held) {
+            return Action::Wait; // The hold has guessed already
+        }
+        // Released too late to be a tap but before a repeat noticed, as with repeats switched off
+        if since.is_some_and(|since| now.saturating_duration_since(since) >= timing.hold()) {
+            self.last_tap = None;
+            return Action::Guess { undo_tap: false };
+        }
+        match self.last_tap.take() {
+            Some(tap) if now.saturating_duration_since(tap) <= timing.double_tap() => Action::Guess { undo_tap: true },
+            _ => {
+                self.last_tap = Some(now);
+                Action::Step
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A quick tap: down at `at`, up 50ms later
+    fn tap(key: &mut OneKey, at: Instant, timing: Timing) -> Action {
+        assert_eq!(key.press(at, timing), Action::Wait);
+        key.release(at + Duration::from_millis(50), timing)
+    }
+
+    #[test]
+    fn taps_apart_each_step() {
+        for timing in Timing::ALL {
+            let start = Instant::now();
+            let mut key = OneKey::default();
+            assert_eq!(tap(&mut key, start, timing), Action::Step, "{}", timing);
+            // The second tap comes up just too long after the first
+            let next = start + timing.double_tap() + Duration::from_millis(1);
+            assert_eq!(tap(&mut key, next, timing), Action::Step, "{}", timing);
+        }
+    }
+
+    #[test]
+    fn a_double_tap_guesses_and_takes_back_its_first_tap() {
+        for timing in Timing::ALL {
+            let start = Instant::now();
+            let mut key = OneKey::default();
+            assert_eq!(tap(&mut key, start, timing), Action::Step, "{}", timing);
+            // The second tap comes up right at the end of the gap allowed
+            let second = start + timing.double_tap();
+            assert_eq!(tap(&mut key, second, timing), Action::Guess { undo_tap: true }, "{}", timing);
+            // A third tap straight after starts over rather than making another double-tap
+            assert_eq!(tap(&mut key, second + Duration::from_millis(100), timing), Action::Step, "{}", timing);
+        }
+    }
+
+    #[test]
+    fn holding_guesses_once_and_the_release_does_nothing() {
+        for timing in Timing::ALL {
+            let start = Instant::now();
+            let mut key = OneKey::default();
+            assert_eq!(key.press(start, timing), Action::Wait);
+            assert_eq!(key.press(start + timing.hold() - Duration::from_millis(1), timing), Action::Wait, "{}", timing);
+            assert_eq!(key.press(start + timing.hold(), timing), Action::Guess { undo_tap: false }, "{}", timing);
+            assert_eq!(key.press(start + timing.hold() * 2, timing), Action::Wait, "repeats after the guess");
+            assert_eq!(key.release(start + timing.hold() * 2, timing), Action::Wait);
+        }
+    }
+
+    #[test]
+    fn a_hold_with_no_repeats_guesses_on_release() {
+        for timing in Timing::ALL {
+            let start = Instant::now();
+            let mut key = OneKey::default();
+            assert_eq!(key.press(start, timing), Action::Wait);
+            assert_eq!(key.release(start + timing.hold(), timing), Action::Guess { undo_tap: false }, "{}", timing);
+        }
+    }
+
+    #[test]
+    fn a_hold_just_after_a_tap_leaves_the_tap_alone() {
+        for timing in Timing::ALL {
+            let start = Instant::now();
+            let mut key = OneKey::default();
+            assert_eq!(tap(&mut key, start, timing), Action::Step);
+
+            let down = start + Duration::from_millis(100);
+            assert_eq!(key.press(down, timing), Action::Wait);
+            assert_eq!(key.press(down + timing.hold(), timing), Action::Guess { undo_tap: false }, "{}", timing);
+            assert_eq!(key.release(down + timing.hold(), timing), Action::Wait);
+
+            // The tap before the hold can't pair with the next one
+            assert_eq!(tap(&mut key, down + timing.hold(), timing), Action::Step, "{}", timing);
+        }
+    }
+
+    #[test]
+    fn slower_timings_allow_longer_gaps_and_holds() {
+        let start = Instant::now();
+        let gap = Duration::from_millis(400);
+        let hold = Duration::from_millis(500);
+        let cases = [
+            (Timing::Quick, Action::Step, Action::Guess { undo_tap: false }),
+            (Timing::Normal, Action::Step, Action::Step),
+            (Timing::Relaxed, Action::Guess { undo_tap: true }, Action::Step),
+        ];
+        for (timing, after_gap, after_hold) in cases {
+            let mut key = OneKey::default();
+            assert_eq!(tap(&mut key, start, timing), Action::Step);
+            assert_eq!(tap(&mut key, start + gap, timing), after_gap, "a {:?} gap under {}", gap, timing);
+
+            let mut key = OneKey::default();
+            assert_eq!(key.press(start, timing), Action::Wait);
+            assert_eq!(key.release(start + hold, timing), after_hold, "a {:?} press under {}", hold, timing);
+        }
+    }
+
+    #[test]
+    fn timings_round_trip_through_their_keys() {
+        for timing in Timing::ALL {
+            assert_eq!(Timing::from_key(timing.key()), Some(timing));
+        }
+        assert_eq!(Timing::from_key("fast"), None);
+    }
+}
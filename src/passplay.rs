@@ -0,0 +1,54 @@
+// Setting up a pass-and-play game, where one player types the secret for another
+
+use std::hint;
+use std::mem;
+
+use crate::format::{parse_number, Locale};
+use crate::secret::Secret;
+
+// The steps before the guesser takes over
+#[derive(Debug)]
+pub enum PassSetup {
+    Entering { input: String, invalid: bool }, // The setter types the secret into a hidden field
+    Handoff(Secret),                           // The secret is set; waiting for the guesser to press Ready
+}
+
+impl PassSetup {
+    // Start with an empty secret field
+    pub fn new() -> PassSetup {
+        PassSetup::Entering { input: String::new(), invalid: false }
+    }
+
+    // Check the typed secret, moving on to the hand-off if it lies within `min..=max`
+    //
+    // The typed text is wiped either way, so an invalid entry has to be typed again rather
+    // than sitting in the field for the guesser to reveal.
+    pub fn submit(&mut self, locale: Locale, min: u32, max: u32) {
+        let PassSetup::Entering { input, invalid } = self else {
+            return;
+        };
+
+        let value = parse_number(locale, input).ok().filter(|value| (min..=max).contains(value));
+        wipe(input);
+        match value {
+            Some(value) => *self = PassSetup::Handoff(Secret::new(value)),
+            None => *invalid = true,
+        }
+    }
+}
+
+// Clear typed text, overwriting its bytes rather than just forgetting them
+pub fn wipe(text: &mut String) {
+    let mut bytes = mem::take(text).into_bytes();
+    bytes.fill(0);
+    hint::black_box(&bytes); // Keep the overwrite from being optimised away
+}
+
+// Wipe whatever is still in the field when the setup is dropped, such as on cancelling
+impl Drop for PassSetup {
+    fn drop(&mut self) {
+        if let PassSetup::Entering { input, .. } = self {
+            wipe(input);
+        }
+    }
+}
@@ -0,0 +1,385 @@
+// Replay files: a finished round's range, secret and guesses, shared and played back step by step
+//
+// Unlike session recordings, which replay every message to reproduce a whole run of the
+// game, a replay file only holds what's needed to watch one round again. It's a small
+// `key = value` text file like a scorecard, so it survives being sent around, and reading
+// one checks everything, since a file cut short in transit must not crash the viewer.
+//
+// The same round can also be written out as CSV, a row per guess, for a spreadsheet.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::feedback::Feedback;
+use crate::storage;
+
+// The version of the format written; files from older versions are still read
+//
+// Guess times and timed-out attempts were added later as optional fields, so they didn't need
+// a new version.
+pub const FORMAT_VERSION: u32 = 1;
+
+// How long each guess stays up while a replay plays by itself
+pub const STEP_INTERVAL: Duration = Duration::from_millis(1200);
+
+// A round as written to the file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameReplay {
+    pub min: u32,          // Smallest possible secret
+    pub max: u32,          // Largest possible secret
+    pub secret: u32,       // The secret being guessed
+    pub guesses: Vec<u32>, // Every guess, in order
+    pub times: Vec<Option<Duration>>, // How long each guess took, where known
+    pub timeouts: Vec<usize>, // Attempts that ran out of time, as how many guesses came before each
+    pub won: bool,         // Whether the last guess won, which a close-enough win can't show by itself
+}
+
+// Why a replay file couldn't be played
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayFileError {
+    Unreadable,        // The file couldn't be opened or isn't text
+    Malformed,         // A field is missing, isn't a number, or is outside the range
+    Truncated,         // Fewer guesses were found than the file says it holds
+    NewerVersion(u32), // Written by a newer version of the game, in the format given
+}
+
+impl fmt::Display for ReplayFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayFileError::Unreadable => write!(f, "That file couldn't be opened"),
+            ReplayFileError::Malformed => write!(f, "That file isn't a replay, or parts of it are damaged"),
+            ReplayFileError::Truncated => write!(f, "That replay was cut short - ask for it to be sent again"),
+            ReplayFileError::NewerVersion(version) => {
+                write!(f, "That replay is in format {}, from a newer version of the game - update to watch it", version)
+            }
+        }
+    }
+}
+
+// The columns of a history exported as CSV, in order
+pub const CSV_HEADER: &str = "attempt,guess,feedback,low,high,seconds";
+
+// What an attempt that ran out of time shows in place of feedback
+pub const TOO_SLOW: &str = "Too slow!";
+
+// One guess as the replay shows it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub guess: u32,         // The guess made
+    pub feedback: Feedback, // What the game said about it
+    pub low: u32,           // Smallest number still possible after it
+    pub high: u32,          // Largest number still possible after it
+    pub thought: Option<Duration>, // How long the player took over it, if the file says
+}
+
+impl GameReplay {
+    // Render the replay as the file's contents
+    pub fn to_text(&self) -> String {
+        let header = "# Guessing Game replay - open it from the Replays panel to watch the game\n";
+        let guesses: Vec<String> = self.guesses.iter().map(u32::to_string).collect();
+        let timeouts: Vec<String> = self.timeouts.iter().map(usize::to_string).collect();
+        let values = storage::write_key_values([
+            ("format", FORMAT_VERSION.to_string()),
+            ("min", self.min.to_string()),
+            ("max", self.max.to_string()),
+            ("secret", self.secret.to_string()),
+            ("won", self.won.to_string()),
+            ("count", self.guesses.len().to_string()),
+            ("guesses", guesses.join(",")),
+            ("times", storage::write_times(&self.times)),
+            ("timeouts", timeouts.join(",")),
+        ]);
+        format!("{}{}", header, values)
+    }
+
+    // Read a file written by `to_text`, checking every number is one the round could have had
+    pub fn from_text(contents: &str) -> Result<GameReplay, ReplayFileError> {
+        let values = storage::parse_key_values(contents);
+        let number = |key: &str| values.get(key).and_then(|value| value.parse().ok()).ok_or(ReplayFileError::Malformed);
+
+        // Check the version first, since a newer format may have moved any other field
+        let version: u32 = number("format")?;
+        if version > FORMAT_VERSION {
+            return Err(ReplayFileError::NewerVersion(version));
+        }
+
+        let (min, max, secret): (u32, u32, u32) = (number("min")?, number("max")?, number("secret")?);
+        if min > max || !(min..=max).contains(&secret) {
+            return Err(ReplayFileError::Malformed);
+        }
+
+        // Guesses come last, so a file cut off part-way loses some of them, or all of them
+        let count = number("count")? as usize;
+        let list = values.get("guesses").map_or("", String::as_str);
+        let guesses = list
+            .split(',')
+            .filter(|guess| !guess.trim().is_empty())
+            .map(|guess| guess.trim().parse().ok().filter(|guess| (min..=max).contains(guess)))
+            .collect::<Option<Vec<u32>>>()
+            .ok_or(ReplayFileError::Malformed)?;
+        match guesses.len().cmp(&count) {
+            Ordering::Less => return Err(ReplayFileError::Truncated),
+            Ordering::Greater => return Err(ReplayFileError::Malformed),
+            Ordering::Equal => {}
+        }
+
+        let won = values.get("won").and_then(|value| value.parse().ok()).ok_or(ReplayFileError::Malformed)?;
+        let times = storage::parse_times(values.get("times").map(String::as_str), guesses.len());
+
+        // Missing from older files, which had no timeouts to keep; they must be in order, and
+        // none can come after the last guess
+        let timeouts = values
+            .get("timeouts")
+            .map_or("", String::as_str)
+            .split(',')
+            .filter(|place| !place.trim().is_empty())
+            .map(|place| place.trim().parse().ok())
+            .collect::<Option<Vec<usize>>>()
+            .ok_or(ReplayFileError::Malformed)?;
+        if timeouts.windows(2).any(|pair| pair[0] > pair[1]) || timeouts.last().is_some_and(|&place| place > guesses.len()) {
+            return Err(ReplayFileError::Malformed);
+        }
+        Ok(GameReplay { min, max, secret, guesses, times, timeouts, won })
+    }
+
+    // Render the round as CSV: each guess with what it earned, the range left after it and how
+    // long it took, in seconds to the tenth, or blank where it wasn't timed
+    //
+    // An attempt that ran out of time gets a row of its own where it happened, with no guess and
+    // the range as it was.
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{}\n", CSV_HEADER);
+        let mut attempt = 0;
+        let (mut low, mut high) = (self.min, self.max);
+        let mut timeouts = self.timeouts.iter().peekable();
+        let mut too_slow = |csv: &mut String, attempt: &mut u32, guesses: usize, low: u32, high: u32| {
+            while timeouts.next_if(|&&place| place <= guesses).is_some() {
+                *attempt += 1;
+                csv.push_str(&format!("{},,{},{},{},\n", attempt, TOO_SLOW, low, high));
+            }
+        };
+
+        for (i, step) in self.steps().into_iter().enumerate() {
+            too_slow(&mut csv, &mut attempt, i, low, high);
+            let feedback = match step.feedback {
+                Feedback::TooSmall(_) => "Too small!",
+                Feedback::TooBig(_) => "Too big!",
+                _ => "Correct!",
+            };
+            let seconds = step.thought.map_or(String::new(), |thought| {
+                let tenths = (thought.as_millis() + 50) / 100; // Rounded to the nearest tenth
+                format!("{}.{}", tenths / 10, tenths % 10)
+            });
+            attempt += 1;
+            csv.push_str(&format!("{},{},{},{},{},{}\n", attempt, step.guess, feedback, step.low, step.high, seconds));
+            (low, high) = (step.low, step.high);
+        }
+        too_slow(&mut csv, &mut attempt, self.guesses.len(), low, high);
+        csv
+    }
+
+    // Read a replay from `path`
+    pub fn load(path: &Path) -> Result<GameReplay, ReplayFileError> {
+        let contents = fs::read_to_string(path).map_err(|error| {
+            tracing::warn!(path = %path.display(), %error, "Could not read the replay");
+            ReplayFileError::Unreadable
+        })?;
+        GameReplay::from_text(&contents)
+    }
+
+    // The guesses worked through in order, narrowing the range as the game did
+    pub fn steps(&self) -> Vec<Step> {
+        let (mut low, mut high) = (self.min, self.max);
+        let last = self.guesses.len().saturating_sub(1);
+        self.guesses
+            .iter()
+            .enumerate()
+            .map(|(i, &guess)| {
+                let feedback = match guess.cmp(&self.secret) {
+                    _ if self.won && i == last => {
+                        (low, high) = (self.secret, self.secret);
+                        Feedback::Win { secret: self.secret }
+                    }
+                    Ordering::Less => {
+                        low = low.max(guess + 1);
+                        Feedback::TooSmall(guess)
+                    }
+                    Ordering::Greater => {
+                        high = high.min(guess - 1);
+                        Feedback::TooBig(guess)
+                    }
+                    Ordering::Equal => Feedback::Win { secret: self.secret },
+                };
+                Step { guess, feedback, low, high, thought: self.times.get(i).copied().flatten() }
+            })
+            .collect()
+    }
+}
+
+// A replay being watched, and how far through it is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Playback {
+    pub replay: GameReplay,
+    pub steps: Vec<Step>, // Worked out once, when the replay is opened
+    pub shown: usize,     // Guesses shown so far, from none up to all of them
+    pub playing: bool,    // Whether the replay is stepping forward by itself
+}
+
+impl Playback {
+    // Start watching `replay` from before its first guess
+    pub fn new(replay: GameReplay) -> Playback {
+        Playback { steps: replay.steps(), replay, shown: 0, playing: false }
+    }
+
+    // The latest guess shown, if any has been yet
+    pub fn current(&self) -> Option<&Step> {
+        self.shown.checked_sub(1).map(|index| &self.steps[index])
+    }
+
+    // Whether every guess has been shown
+    pub fn is_finished(&self) -> bool {
+        self.shown >= self.steps.len()
+    }
+
+    // Show one more guess, or one fewer, stopping at either end; the replay stops playing at the last guess
+    pub fn step(&mut self, forward: bool) {
+        self.shown = if forward { (self.shown + 1).min(self.steps.len()) } else { self.shown.saturating_sub(1) };
+        if self.is_finished() {
+            self.playing = false;
+        }
+    }
+}
+
+// Write the replay to the player's home folder, returning the path
+pub fn save(replay: GameReplay) -> Option<PathBuf> {
+    write_home("replay", &replay.to_text())
+}
+
+// Write the round's history as CSV to the player's home folder, returning the path
+pub fn save_csv(replay: GameReplay) -> Option<PathBuf> {
+    write_home("csv", &replay.to_csv())
+}
+
+// Write `contents` to a new file in the player's home folder with the given extension
+fn write_home(extension: &str, contents: &str) -> Option<PathBuf> {
+    let home = storage::home_dir().unwrap_or_else(|| PathBuf::from(".")); // Next to wherever the game was started from
+    let stamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+    let path = home.join(format!("guessing-game-{}.{}", stamp, extension)); // Named by time, since the secret would give it away
+
+    match fs::write(&path, contents) {
+        Ok(()) => Some(path),
+        Err(error) => {
+            tracing::warn!(path = %path.display(), %error, "Could not save the {} file", extension);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round() -> GameReplay {
+        GameReplay {
+            min: 1,
+            max: 100,
+            secret: 37,
+            guesses: vec![50, 25, 37],
+            times: vec![Some(Duration::from_millis(8_240)), None, Some(Duration::from_millis(950))],
+            timeouts: Vec::new(),
+            won: true,
+        }
+    }
+
+    #[test]
+    fn csv_has_a_row_per_guess_with_its_range_and_time() {
+        let csv = round().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            [CSV_HEADER, "1,50,Too big!,1,49,8.2", "2,25,Too small!,26,49,", "3,37,Correct!,37,37,1.0"]
+        );
+    }
+
+    #[test]
+    fn csv_shows_timed_out_attempts_where_they_happened() {
+        let replay = GameReplay { timeouts: vec![0, 2, 2], ..round() };
+        let lines: Vec<String> = replay.to_csv().lines().map(str::to_string).collect();
+        assert_eq!(
+            lines,
+            [
+                CSV_HEADER,
+                "1,,Too slow!,1,100,",
+                "2,50,Too big!,1,49,8.2",
+                "3,25,Too small!,26,49,",
+                "4,,Too slow!,26,49,",
+                "5,,Too slow!,26,49,",
+                "6,37,Correct!,37,37,1.0"
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_ends_with_timeouts_after_the_last_guess() {
+        let replay = GameReplay { guesses: vec![50, 25], won: false, timeouts: vec![2], ..round() };
+        assert!(replay.to_csv().ends_with("2,25,Too small!,26,49,\n3,,Too slow!,26,49,\n"));
+    }
+
+    #[test]
+    fn timeouts_round_trip_and_are_optional() {
+        let replay = GameReplay { timeouts: vec![1, 3], ..round() };
+        assert_eq!(GameReplay::from_text(&replay.to_text()), Ok(replay));
+
+        let older: String = round().to_text().lines().filter(|line| !line.starts_with("timeouts")).map(|line| format!("{}\n", line)).collect();
+        assert_eq!(GameReplay::from_text(&older), Ok(round()));
+    }
+
+    #[test]
+    fn timeouts_out_of_order_or_past_the_end_are_malformed() {
+        for timeouts in [vec![2, 1], vec![4]] {
+            let text = GameReplay { timeouts, ..round() }.to_text();
+            assert_eq!(GameReplay::from_text(&text), Err(ReplayFileError::Malformed));
+        }
+    }
+
+    #[test]
+    fn csv_of_a_round_without_guesses_is_just_the_header() {
+        let replay = GameReplay { guesses: Vec::new(), times: Vec::new(), won: false, ..round() };
+        assert_eq!(replay.to_csv(), format!("{}\n", CSV_HEADER));
+    }
+
+    #[test]
+    fn files_round_trip() {
+        assert_eq!(GameReplay::from_text(&round().to_text()), Ok(round()));
+    }
+
+    #[test]
+    fn a_file_cut_short_is_truncated() {
+        let text = round().to_text().replace("guesses = 50,25,37", "guesses = 50,25");
+        assert_eq!(GameReplay::from_text(&text), Err(ReplayFileError::Truncated));
+    }
+
+    #[test]
+    fn a_file_from_a_newer_version_is_refused() {
+        let text = round().to_text().replace("format = 1", "format = 9");
+        assert_eq!(GameReplay::from_text(&text), Err(ReplayFileError::NewerVersion(9)));
+    }
+
+    #[test]
+    fn a_guess_outside_the_range_is_malformed() {
+        let text = round().to_text().replace("guesses = 50,25,37", "guesses = 50,250,37");
+        assert_eq!(GameReplay::from_text(&text), Err(ReplayFileError::Malformed));
+    }
+
+    #[test]
+    fn a_file_written_before_guesses_were_timed_loads_untimed() {
+        let text: String = round().to_text().lines().filter(|line| !line.starts_with("times")).map(|line| format!("{}\n", line)).collect();
+        assert!(!text.contains("times"));
+        let replay = GameReplay::from_text(&text).expect("times are optional");
+        assert_eq!(replay.times, [None, None, None]);
+        assert_eq!(replay.guesses, round().guesses);
+    }
+}
@@ -0,0 +1,76 @@
+// Drills over part of the range, started straight from a range typed as "90-110"
+//
+// A drill draws its secret from inside the typed bounds and starts the game with them already
+// narrowed, so the guessing goes straight to the stretch being practised. Starting that much
+// closer makes a drill easier than a real game, so like a retry it never counts towards stats.
+
+use std::fmt;
+
+// Why a typed range can't be drilled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeError {
+    Malformed,                         // Not two whole numbers joined by a dash
+    OutOfRange { min: u32, max: u32 }, // Reaches past the game's own range
+    SingleNumber,                      // Both ends are the same, leaving nothing to guess
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::Malformed => write!(f, "Type a range as two whole numbers joined by a dash, like 40-60"),
+            RangeError::OutOfRange { min, max } => write!(f, "Practice ranges have to fit within {}-{}", min, max),
+            RangeError::SingleNumber => write!(f, "A practice range needs at least two numbers in it"),
+        }
+    }
+}
+
+// Read a range such as "90-110" or "60 – 40" as its bounds, smaller first, if it fits in `min..=max`
+pub fn parse(text: &str, min: u32, max: u32) -> Result<(u32, u32), RangeError> {
+    let (first, second) = text.split_once(['-', '–']).ok_or(RangeError::Malformed)?;
+    let bound = |part: &str| part.trim().parse::<u32>().map_err(|_| RangeError::Malformed);
+    let (first, second) = (bound(first)?, bound(second)?);
+    let (low, high) = (first.min(second), first.max(second));
+
+    if low < min || high > max {
+        Err(RangeError::OutOfRange { min, max })
+    } else if low == high {
+        Err(RangeError::SingleNumber)
+    } else {
+        Ok((low, high))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_are_read_either_way_round() {
+        let cases = [
+            ("90-110", Ok((90, 110))),
+            ("110-90", Ok((90, 110))),
+            (" 40 – 60 ", Ok((40, 60))),
+            ("1-1000", Ok((1, 1_000))),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse(input, 1, 1_000), expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn every_error_has_a_range_that_causes_it() {
+        let cases = [
+            ("90_110", RangeError::Malformed),
+            ("90", RangeError::Malformed),
+            ("", RangeError::Malformed),
+            ("ninety-110", RangeError::Malformed),
+            ("-5-10", RangeError::Malformed),
+            ("0-50", RangeError::OutOfRange { min: 1, max: 100 }),
+            ("90-110", RangeError::OutOfRange { min: 1, max: 100 }),
+            ("42-42", RangeError::SingleNumber),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse(input, 1, 100), Err(expected), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,521 @@
+// User preferences that persist between runs
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use crate::color::{Rgb, DEFAULT_ACCENT};
+use crate::fonts::FontChoice;
+use crate::format::LocaleChoice;
+use crate::migration::{self, Schema};
+use crate::onekey::Timing;
+use crate::rules::MinChange;
+use crate::solver::BotStrategy;
+use crate::speech::SpeechRate;
+use crate::swap::SurpriseSwap;
+use crate::storage;
+
+// File the preferences are stored in, inside the data directory
+const FILE_NAME: &str = "preferences.txt";
+
+// How the file has changed between versions
+const SCHEMA: Schema = Schema { file: FILE_NAME, name: "preferences", legacy_key: None, steps: &[] };
+
+// Which theme the user asked for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeChoice {
+    #[default]
+    System, // Follow the operating system's light/dark preference
+    SystemAccent, // Follow it too, and build the palette around the operating system's accent color
+    Light,  // Always light
+    Dark,   // Always dark
+}
+
+impl ThemeChoice {
+    // Every choice, in the order shown in the theme picker
+    pub const ALL: [ThemeChoice; 4] = [ThemeChoice::System, ThemeChoice::SystemAccent, ThemeChoice::Light, ThemeChoice::Dark];
+
+    // Whether the operating system's settings decide what's shown, so they need watching
+    pub fn follows_system(self) -> bool {
+        matches!(self, ThemeChoice::System | ThemeChoice::SystemAccent)
+    }
+
+    // The value written to the preferences file
+    pub fn key(self) -> &'static str {
+        match self {
+            ThemeChoice::System => "system",
+            ThemeChoice::SystemAccent => "system_accent",
+            ThemeChoice::Light => "light",
+            ThemeChoice::Dark => "dark",
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<ThemeChoice> {
+        ThemeChoice::ALL.into_iter().find(|choice| choice.key() == key)
+    }
+}
+
+impl fmt::Display for ThemeChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            ThemeChoice::System => "System",
+            ThemeChoice::SystemAccent => "System accent",
+            ThemeChoice::Light => "Light",
+            ThemeChoice::Dark => "Dark",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+// Whether feedback may be animated or is always shown instantly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MotionChoice {
+    #[default]
+    System,   // Follow the operating system's reduce-motion setting
+    Animated, // Always animate
+    Instant,  // Never animate, showing every change at once
+}
+
+impl MotionChoice {
+    // Every choice, in the order shown in the motion picker
+    pub const ALL: [MotionChoice; 3] = [MotionChoice::System, MotionChoice::Animated, MotionChoice::Instant];
+
+    // Whether to skip animations, given the operating system's setting if it could be read
+    pub fn reduce_motion(self, system: Option<bool>) -> bool {
+        match self {
+            MotionChoice::System => system.unwrap_or(false), // Animate when the system doesn't say
+            MotionChoice::Animated => false,
+            MotionChoice::Instant => true,
+        }
+    }
+
+    // The value written to the preferences file
+    pub fn key(self) -> &'static str {
+        match self {
+            MotionChoice::System => "system",
+            MotionChoice::Animated => "animated",
+            MotionChoice::Instant => "instant",
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<MotionChoice> {
+        MotionChoice::ALL.into_iter().find(|choice| choice.key() == key)
+    }
+}
+
+impl fmt::Display for MotionChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            MotionChoice::System => "System",
+            MotionChoice::Animated => "Animated",
+            MotionChoice::Instant => "Instant",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+// How long without any interaction before the game timer pauses itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdleTimeout {
+    Off,
+    Seconds30,
+    #[default]
+    Minute1,
+    Minutes2,
+    Minutes5,
+}
+
+impl IdleTimeout {
+    // Every choice, in the order shown in the idle timeout picker
+    pub const ALL: [IdleTimeout; 5] = [
+        IdleTimeout::Off,
+        IdleTimeout::Seconds30,
+        IdleTimeout::Minute1,
+        IdleTimeout::Minutes2,
+        IdleTimeout::Minutes5,
+    ];
+
+    // The inactivity period that triggers a pause, or `None` when idle pausing is off
+    pub fn duration(self) -> Option<Duration> {
+        let seconds = match self {
+            IdleTimeout::Off => return None,
+            IdleTimeout::Seconds30 => 30,
+            IdleTimeout::Minute1 => 60,
+            IdleTimeout::Minutes2 => 120,
+            IdleTimeout::Minutes5 => 300,
+        };
+        Some(Duration::from_secs(seconds))
+    }
+
+    // The value written to the preferences file
+    pub fn key(self) -> &'static str {
+        match self {
+            IdleTimeout::Off => "off",
+            IdleTimeout::Seconds30 => "30s",
+            IdleTimeout::Minute1 => "1m",
+            IdleTimeout::Minutes2 => "2m",
+            IdleTimeout::Minutes5 => "5m",
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<IdleTimeout> {
+        IdleTimeout::ALL.into_iter().find(|timeout| timeout.key() == key)
+    }
+}
+
+// What the game does on launch with a game left unfinished last time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Startup {
+    #[default]
+    Fresh,  // Start a new game, offering the unfinished one back
+    Resume, // Pick the unfinished game straight back up; with none, start a new game anyway
+}
+
+impl Startup {
+    // Every choice, in the order shown in the startup picker
+    pub const ALL: [Startup; 2] = [Startup::Fresh, Startup::Resume];
+
+    // The value written to the preferences file
+    pub fn key(self) -> &'static str {
+        match self {
+            Startup::Fresh => "fresh",
+            Startup::Resume => "resume",
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<Startup> {
+        Startup::ALL.into_iter().find(|startup| startup.key() == key)
+    }
+}
+
+impl fmt::Display for Startup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Startup::Fresh => "Start a fresh game",
+            Startup::Resume => "Resume my last game",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+impl fmt::Display for IdleTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            IdleTimeout::Off => "Never",
+            IdleTimeout::Seconds30 => "After 30 seconds",
+            IdleTimeout::Minute1 => "After 1 minute",
+            IdleTimeout::Minutes2 => "After 2 minutes",
+            IdleTimeout::Minutes5 => "After 5 minutes",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+// Everything the user can configure
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preferences {
+    pub theme: ThemeChoice,     // Light, dark, or follow the system
+    pub show_candidates: bool,  // Display how many values remain possible
+    pub accent: Rgb,            // Color of the primary button, focus rings and win highlight
+    pub notify_on_win: bool,    // Send a desktop notification for wins while unfocused
+    pub spectator_feed: bool,   // Keep a text file of the feedback for streaming software to show
+    pub font: FontChoice,       // Font used for all UI text
+    pub monospace_digits: bool, // Draw the input and numeric readouts in a monospaced font
+    pub number_format: LocaleChoice, // The thousands separators numbers are shown and typed with
+    pub colorblind: bool,       // Use colorblind-friendly color scales
+    pub always_on_top: bool,    // Keep the window above all others
+    pub compact: bool,          // Shrink the window to a minimal input strip
+    pub left_handed: bool,      // Mirror the horizontal order of the controls
+    pub one_key: bool,          // Step and guess with Space alone, for one-handed play
+    pub one_key_timing: Timing, // How long a hold and a double-tap's gap are in one-key play
+    pub idle_timeout: IdleTimeout, // Inactivity before the game timer pauses itself
+    pub startup: Startup,       // Whether to start fresh on launch or pick up the game left unfinished
+    pub motion: MotionChoice,   // Whether feedback is animated or shown instantly
+    pub speak_feedback: bool,   // Read the feedback aloud after every guess
+    pub speech_rate: SpeechRate, // How fast the feedback is read, where the platform allows changing it
+    pub speech_voice: String,   // The ID of the voice it's read in, or empty for the system's own
+    pub bot_strategy: BotStrategy, // How well the bot plays in the computer-guesses mode
+    pub mystery_mode: bool,     // Compare guesses through a hidden operation each round
+    pub guess_budget: bool,     // Limit guesses across the session, refilling over time and on wins
+    pub mercy_hints: bool,      // Volunteer a free hint after every few wrong guesses
+    pub assist_hints: bool,     // Make bought hints more generous while struggling and stricter while on form
+    pub guess_assist: bool,     // Grey out numbers on the number line that the feedback has ruled out
+    pub close_enough: bool,     // Let guesses a little off the secret win
+    pub min_change: MinChange,  // How far each guess must move from the one before
+    pub surprise_swap: SurpriseSwap, // How many guesses into a round the secret changes, if it does
+    pub timed_guesses: bool,    // Give each guess a few seconds, wasting the attempt when they run out
+    pub ghost_race: bool,       // Race the pace of the fastest win on the time-attack board
+    pub show_bases: bool,       // Show the typed guess in binary and hexadecimal
+    pub proximity_tones: bool,  // Play a tone after wrong guesses, higher the closer they were
+    pub analytics: bool,        // Record play statistics locally
+    pub last_seen_version: Option<String>, // Newest release whose "what's new" panel was dismissed
+}
+
+impl Default for Preferences {
+    fn default() -> Preferences {
+        Preferences {
+            theme: ThemeChoice::default(),
+            show_candidates: false, // Off by default since it makes the game much easier
+            accent: DEFAULT_ACCENT,
+            notify_on_win: false, // Opt-in, since not every desktop shows notifications nicely
+            spectator_feed: false,
+            font: FontChoice::default(),
+            monospace_digits: false,
+            number_format: LocaleChoice::default(),
+            colorblind: false,
+            always_on_top: false,
+            compact: false,
+            left_handed: false,
+            one_key: false,
+            one_key_timing: Timing::default(),
+            idle_timeout: IdleTimeout::default(),
+            startup: Startup::default(),
+            motion: MotionChoice::default(),
+            speak_feedback: false,
+            speech_rate: SpeechRate::default(),
+            speech_voice: String::new(),
+            bot_strategy: BotStrategy::default(),
+            mystery_mode: false,
+            guess_budget: false, // A challenge for those who want one, not the default game
+            mercy_hints: false,
+            assist_hints: true, // On unless a competitive player opts out, since it only helps those who need it
+            guess_assist: true, // It only shows what the feedback already said
+            close_enough: false, // Exact guesses only, as the game always played
+            min_change: MinChange::default(),
+            surprise_swap: SurpriseSwap::default(), // A twist for those who ask for it
+            timed_guesses: false,
+            ghost_race: true, // Nothing shows until a win sets a ghost, and then it's worth seeing
+            show_bases: false,
+            proximity_tones: false, // Off by default, since the pitch gives away how close a guess was
+            analytics: false, // Strictly opt-in
+            last_seen_version: None,
+        }
+    }
+}
+
+impl Preferences {
+    // Load the saved preferences, falling back to defaults for anything missing or unreadable
+    pub fn load() -> Preferences {
+        match migration::load(&SCHEMA) {
+            Ok(Some(values)) => Preferences::from_values(&values),
+            Ok(None) => Preferences::default(), // First run, nothing saved yet
+            Err(error) => {
+                tracing::warn!(%error, "Could not read preferences, using defaults");
+                Preferences::default()
+            }
+        }
+    }
+
+    // Read preferences written by `to_text`, using defaults for anything missing or malformed
+    pub fn from_text(contents: &str) -> Preferences {
+        Preferences::from_values(&storage::parse_key_values(contents))
+    }
+
+    // Read preferences from a file's values, using defaults for anything missing or malformed
+    fn from_values(values: &BTreeMap<String, String>) -> Preferences {
+        let defaults = Preferences::default();
+
+        Preferences {
+            theme: setting(values, "theme", ThemeChoice::from_key, defaults.theme),
+            show_candidates: flag(values, "show_candidates", defaults.show_candidates),
+            accent: setting(values, "accent", Rgb::from_hex, defaults.accent),
+            notify_on_win: flag(values, "notify_on_win", defaults.notify_on_win),
+            spectator_feed: flag(values, "spectator_feed", defaults.spectator_feed),
+            font: setting(values, "font", FontChoice::from_key, defaults.font),
+            monospace_digits: flag(values, "monospace_digits", defaults.monospace_digits),
+            number_format: setting(values, "number_format", LocaleChoice::from_key, defaults.number_format),
+            colorblind: flag(values, "colorblind", defaults.colorblind),
+            always_on_top: flag(values, "always_on_top", defaults.always_on_top),
+            compact: flag(values, "compact", defaults.compact),
+            left_handed: flag(values, "left_handed", defaults.left_handed),
+            one_key: flag(values, "one_key", defaults.one_key),
+            one_key_timing: setting(values, "one_key_timing", Timing::from_key, defaults.one_key_timing),
+            idle_timeout: setting(values, "idle_timeout", IdleTimeout::from_key, defaults.idle_timeout),
+            startup: setting(values, "startup", Startup::from_key, defaults.startup),
+            motion: setting(values, "motion", MotionChoice::from_key, defaults.motion),
+            speak_feedback: flag(values, "speak_feedback", defaults.speak_feedback),
+            speech_rate: setting(values, "speech_rate", SpeechRate::from_key, defaults.speech_rate),
+            speech_voice: values.get("speech_voice").cloned().unwrap_or(defaults.speech_voice),
+            bot_strategy: setting(values, "bot_strategy", BotStrategy::from_key, defaults.bot_strategy),
+            mystery_mode: flag(values, "mystery_mode", defaults.mystery_mode),
+            guess_budget: flag(values, "guess_budget", defaults.guess_budget),
+            mercy_hints: flag(values, "mercy_hints", defaults.mercy_hints),
+            assist_hints: flag(values, "assist_hints", defaults.assist_hints),
+            guess_assist: flag(values, "guess_assist", defaults.guess_assist),
+            close_enough: flag(values, "close_enough", defaults.close_enough),
+            min_change: setting(values, "min_change", MinChange::from_key, defaults.min_change),
+            surprise_swap: setting(values, "surprise_swap", SurpriseSwap::from_key, defaults.surprise_swap),
+            timed_guesses: flag(values, "timed_guesses", defaults.timed_guesses),
+            ghost_race: flag(values, "ghost_race", defaults.ghost_race),
+            show_bases: flag(values, "show_bases", defaults.show_bases),
+            proximity_tones: flag(values, "proximity_tones", defaults.proximity_tones),
+            analytics: flag(values, "analytics", defaults.analytics),
+            // Only a version number can be compared with the releases; drop anything else
+            last_seen_version: setting(values, "last_seen_version", version_number, None),
+        }
+    }
+
+    // Save the preferences, logging rather than failing if the file can't be written
+    pub fn save(&self) {
+        if let Err(error) = migration::save(&SCHEMA, &self.to_text()) {
+            tracing::warn!(%error, "Could not save preferences");
+        }
+    }
+
+    // Render the preferences as `key = value` lines
+    pub fn to_text(&self) -> String {
+        storage::write_key_values([
+            ("theme", self.theme.key().to_string()),
+            ("show_candidates", self.show_candidates.to_string()),
+            ("accent", self.accent.to_hex()),
+            ("notify_on_win", self.notify_on_win.to_string()),
+            ("spectator_feed", self.spectator_feed.to_string()),
+            ("font", self.font.key().to_string()),
+            ("monospace_digits", self.monospace_digits.to_string()),
+            ("number_format", self.number_format.key().to_string()),
+            ("colorblind", self.colorblind.to_string()),
+            ("always_on_top", self.always_on_top.to_string()),
+            ("compact", self.compact.to_string()),
+            ("left_handed", self.left_handed.to_string()),
+            ("one_key", self.one_key.to_string()),
+            ("one_key_timing", self.one_key_timing.key().to_string()),
+            ("idle_timeout", self.idle_timeout.key().to_string()),
+            ("startup", self.startup.key().to_string()),
+            ("motion", self.motion.key().to_string()),
+            ("speak_feedback", self.speak_feedback.to_string()),
+            ("speech_rate", self.speech_rate.key().to_string()),
+            ("speech_voice", self.speech_voice.clone()),
+            ("bot_strategy", self.bot_strategy.key().to_string()),
+            ("mystery_mode", self.mystery_mode.to_string()),
+            ("guess_budget", self.guess_budget.to_string()),
+            ("mercy_hints", self.mercy_hints.to_string()),
+            ("assist_hints", self.assist_hints.to_string()),
+            ("guess_assist", self.guess_assist.to_string()),
+            ("close_enough", self.close_enough.to_string()),
+            ("min_change", self.min_change.key().to_string()),
+            ("surprise_swap", self.surprise_swap.key().to_string()),
+            ("timed_guesses", self.timed_guesses.to_string()),
+            ("ghost_race", self.ghost_race.to_string()),
+            ("show_bases", self.show_bases.to_string()),
+            ("proximity_tones", self.proximity_tones.to_string()),
+            ("analytics", self.analytics.to_string()),
+            ("last_seen_version", self.last_seen_version.clone().unwrap_or_default()),
+        ])
+    }
+}
+
+// Read a setting with `parse`, keeping the default when it's missing or can't be used
+//
+// Every setting read from disk goes through here, so a hand-edited or corrupted value
+// can only ever fall back to its default, and each one that does is logged.
+fn setting<T>(values: &BTreeMap<String, String>, key: &str, parse: impl Fn(&str) -> Option<T>, default: T) -> T {
+    let Some(value) = values.get(key) else {
+        return default; // Not saved yet, such as a setting newer than the file
+    };
+
+    parse(value).unwrap_or_else(|| {
+        tracing::warn!(key, value = %value, "Ignoring an invalid value in the preferences, using the default");
+        default
+    })
+}
+
+// Accept a dotted version such as "0.1.0", the only kind `changelog` can compare, or an
+// empty value for none
+fn version_number(value: &str) -> Option<Option<String>> {
+    if value.is_empty() {
+        return Some(None);
+    }
+
+    let valid = value.split('.').all(|part| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit()));
+    valid.then(|| Some(value.to_string()))
+}
+
+// Read a true/false setting, keeping the default when it's missing or malformed
+fn flag(values: &BTreeMap<String, String>, key: &str, default: bool) -> bool {
+    setting(values, key, |value| value.parse().ok(), default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::Locale;
+
+    #[test]
+    fn preferences_round_trip_through_the_file() {
+        let preferences = Preferences {
+            theme: ThemeChoice::Light,
+            accent: Rgb::new(0x12, 0xab, 0xef),
+            number_format: LocaleChoice::Fixed(Locale::French),
+            one_key: true,
+            one_key_timing: Timing::Relaxed,
+            idle_timeout: IdleTimeout::Minutes5,
+            speech_voice: "en-GB = Daniel".to_string(),
+            min_change: MinChange::By10,
+            ghost_race: false,
+            last_seen_version: Some("1.12.0".to_string()),
+            ..Preferences::default()
+        };
+        assert_eq!(Preferences::from_text(&preferences.to_text()), preferences);
+    }
+
+    #[test]
+    fn an_empty_file_gives_the_defaults() {
+        assert_eq!(Preferences::from_text(""), Preferences::default());
+        assert_eq!(Preferences::from_text("# nothing but a comment\n\n"), Preferences::default());
+    }
+
+    #[test]
+    fn unknown_choices_fall_back_to_their_defaults() {
+        let preferences = Preferences::from_text(
+            "theme = sepia\nfont = comic\nnumber_format = nl\none_key_timing = 9\nidle_timeout = -1\n\
+             startup = sometimes\nmotion = MAX\nspeech_rate = 1e9\nbot_strategy = psychic\n\
+             min_change = 3\nsurprise_swap = always\n",
+        );
+        assert_eq!(preferences, Preferences::default());
+    }
+
+    #[test]
+    fn malformed_flags_fall_back_to_their_defaults() {
+        let preferences = Preferences::from_text("assist_hints = no\nanalytics = 1\nghost_race = TRUE\ncompact =\n");
+        assert_eq!(preferences, Preferences::default());
+    }
+
+    #[test]
+    fn a_bad_accent_keeps_the_default() {
+        for accent in ["red", "#12345", "#gggggg", "#ffffffff", "🎨🎨", ""] {
+            let preferences = Preferences::from_text(&format!("accent = {}\n", accent));
+            assert_eq!(preferences.accent, DEFAULT_ACCENT, "{:?}", accent);
+        }
+        assert_eq!(Preferences::from_text("accent = #fab\n").accent, Rgb::new(0xff, 0xaa, 0xbb));
+    }
+
+    #[test]
+    fn only_a_dotted_version_number_is_kept() {
+        let version = |value: &str| Preferences::from_text(&format!("last_seen_version = {}\n", value)).last_seen_version;
+        assert_eq!(version("0.1.0"), Some("0.1.0".to_string()));
+        assert_eq!(version("2"), Some("2".to_string()));
+        assert_eq!(version(""), None);
+        assert_eq!(version("1..0"), None);
+        assert_eq!(version("1.0-beta"), None);
+        assert_eq!(version("v1.0"), None);
+        assert_eq!(version("-1.0"), None);
+    }
+
+    #[test]
+    fn one_bad_value_leaves_the_rest_alone() {
+        let preferences = Preferences::from_text("theme = sepia\nshow_candidates = true\nmin_change = 25\n");
+        assert_eq!(preferences.theme, ThemeChoice::default());
+        assert!(preferences.show_candidates);
+        assert_eq!(preferences.min_change, MinChange::By25);
+    }
+
+    #[test]
+    fn settings_from_a_newer_version_are_ignored() {
+        let preferences = Preferences::from_text("volume = NaN\nmax_attempts = 0\nshow_bases = true\n");
+        assert_eq!(preferences, Preferences { show_bases: true, ..Preferences::default() });
+    }
+}
@@ -0,0 +1,291 @@
+// Named profiles of gameplay settings, each mode switching to its own when a game of it starts
+//
+// A profile only covers the settings that change how a game plays, such as the helpers a
+// practice game wants and a ranked one doesn't; the look of the game stays the player's
+// whatever the mode. Until a mode is given a profile, starting it leaves the settings alone.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::analytics;
+use crate::migration::{self, Schema};
+use crate::preferences::Preferences;
+use crate::storage;
+
+// File the profiles are stored in, inside the data directory
+const FILE_NAME: &str = "profiles.txt";
+
+// How the file has changed between versions
+const SCHEMA: Schema = Schema { file: FILE_NAME, name: "settings profiles", legacy_key: None, steps: &[] };
+
+// A mode that can have a profile, in the order the selector lists them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Mode {
+    #[default]
+    Classic,
+    Mystery,
+    Tournament,
+    Endless,
+    Golf,
+}
+
+impl Mode {
+    pub const ALL: [Mode; 5] = [Mode::Classic, Mode::Mystery, Mode::Tournament, Mode::Endless, Mode::Golf];
+
+    // The mode's key, the same one the game log and the statistics use
+    pub fn key(self) -> &'static str {
+        match self {
+            Mode::Classic => "classic",
+            Mode::Mystery => "mystery",
+            Mode::Tournament => "tournament",
+            Mode::Endless => "endless",
+            Mode::Golf => "golf",
+        }
+    }
+
+    // The mode with `key`; retries have none, keeping the settings of the game they retry
+    pub fn from_key(key: &str) -> Option<Mode> {
+        Mode::ALL.into_iter().find(|mode| mode.key() == key)
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", analytics::label(self.key()))
+    }
+}
+
+// The settings a profile bundles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    pub show_candidates: bool,
+    pub close_enough: bool,
+    pub timed_guesses: bool,
+    pub mercy_hints: bool,
+    pub assist_hints: bool,
+    pub guess_assist: bool,
+}
+
+impl Settings {
+    // The settings as `preferences` has them now
+    pub fn of(preferences: &Preferences) -> Settings {
+        Settings {
+            show_candidates: preferences.show_candidates,
+            close_enough: preferences.close_enough,
+            timed_guesses: preferences.timed_guesses,
+            mercy_hints: preferences.mercy_hints,
+            assist_hints: preferences.assist_hints,
+            guess_assist: preferences.guess_assist,
+        }
+    }
+
+    // Switch `preferences` to these settings, leaving the rest of them as they were
+    pub fn apply(self, preferences: &mut Preferences) {
+        preferences.show_candidates = self.show_candidates;
+        preferences.close_enough = self.close_enough;
+        preferences.timed_guesses = self.timed_guesses;
+        preferences.mercy_hints = self.mercy_hints;
+        preferences.assist_hints = self.assist_hints;
+        preferences.guess_assist = self.guess_assist;
+    }
+
+    // Each setting with its name in the file
+    fn pairs(self) -> [(&'static str, bool); 6] {
+        [
+            ("show_candidates", self.show_candidates),
+            ("close_enough", self.close_enough),
+            ("timed_guesses", self.timed_guesses),
+            ("mercy_hints", self.mercy_hints),
+            ("assist_hints", self.assist_hints),
+            ("guess_assist", self.guess_assist),
+        ]
+    }
+}
+
+// A named profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub settings: Settings,
+}
+
+// What a mode is set to: one of the profiles by name, or `None` to leave the settings alone
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Choice(pub Option<String>);
+
+impl fmt::Display for Choice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "Keep my settings"),
+        }
+    }
+}
+
+// The profiles that come with the game, for a file that doesn't exist yet
+fn defaults() -> Vec<Profile> {
+    let standard = Settings::of(&Preferences::default());
+    let practice = Settings {
+        show_candidates: true,
+        close_enough: true,
+        timed_guesses: false,
+        mercy_hints: true,
+        assist_hints: true,
+        guess_assist: true,
+    };
+    let ranked = Settings {
+        show_candidates: false,
+        close_enough: false,
+        timed_guesses: false,
+        mercy_hints: false,
+        assist_hints: false,
+        guess_assist: false,
+    };
+    [("Practice", practice), ("Standard", standard), ("Ranked", ranked)]
+        .into_iter()
+        .map(|(name, settings)| Profile { name: name.to_string(), settings })
+        .collect()
+}
+
+// Whether `name` can be written to the file and read back
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.trim() == name && !name.contains([',', '=', '.', '\n'])
+}
+
+// Every profile, and which one each mode switches to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profiles {
+    pub profiles: Vec<Profile>,
+    assigned: BTreeMap<Mode, String>, // Modes left out keep the player's settings
+}
+
+impl Default for Profiles {
+    fn default() -> Profiles {
+        Profiles { profiles: defaults(), assigned: BTreeMap::new() }
+    }
+}
+
+impl Profiles {
+    // Load the profiles, falling back on the ones that come with the game if there are none or they can't be read
+    pub fn load() -> Profiles {
+        let values = match migration::load(&SCHEMA) {
+            Ok(Some(values)) => values,
+            Ok(None) => return Profiles::default(), // No profiles saved yet
+            Err(error) => {
+                tracing::warn!(%error, "Could not read the settings profiles");
+                return Profiles::default();
+            }
+        };
+
+        // A setting missing from a profile takes the standard value, so a hand-edited file still loads
+        let standard = Settings::of(&Preferences::default());
+        let names = values.get("profiles").map_or("", String::as_str);
+        let profiles: Vec<Profile> = names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| is_valid_name(name))
+            .map(|name| {
+                let flag = |key: &str, default: bool| {
+                    values.get(&format!("profile.{}.{}", name, key)).and_then(|value| value.parse().ok()).unwrap_or(default)
+                };
+                let settings = Settings {
+                    show_candidates: flag("show_candidates", standard.show_candidates),
+                    close_enough: flag("close_enough", standard.close_enough),
+                    timed_guesses: flag("timed_guesses", standard.timed_guesses),
+                    mercy_hints: flag("mercy_hints", standard.mercy_hints),
+                    assist_hints: flag("assist_hints", standard.assist_hints),
+                    guess_assist: flag("guess_assist", standard.guess_assist),
+                };
+                Profile { name: name.to_string(), settings }
+            })
+            .collect();
+        if profiles.is_empty() {
+            return Profiles::default(); // Nothing usable in the file, so start again from the defaults
+        }
+
+        // A mode set to a profile that's gone keeps the player's settings instead
+        let assigned = Mode::ALL
+            .into_iter()
+            .filter_map(|mode| {
+                let name = values.get(&format!("mode.{}", mode.key()))?;
+                profiles.iter().any(|profile| &profile.name == name).then(|| (mode, name.clone()))
+            })
+            .collect();
+        Profiles { profiles, assigned }
+    }
+
+    // Save the profiles, logging rather than failing if the file can't be written
+    fn save(&self) {
+        let names: Vec<&str> = self.profiles.iter().map(|profile| profile.name.as_str()).collect();
+        let mut pairs = vec![("profiles".to_string(), names.join(","))];
+        for profile in &self.profiles {
+            for (key, value) in profile.settings.pairs() {
+                pairs.push((format!("profile.{}.{}", profile.name, key), value.to_string()));
+            }
+        }
+        for (mode, name) in &self.assigned {
+            pairs.push((format!("mode.{}", mode.key()), name.clone()));
+        }
+
+        let contents = storage::write_key_values(pairs.iter().map(|(key, value)| (key.as_str(), value.clone())));
+        if let Err(error) = migration::save(&SCHEMA, &contents) {
+            tracing::warn!(%error, "Could not save the settings profiles");
+        }
+    }
+
+    // What `mode` is set to
+    pub fn choice(&self, mode: Mode) -> Choice {
+        Choice(self.assigned.get(&mode).cloned())
+    }
+
+    // Everything a mode can be set to, keeping the player's settings first
+    pub fn choices(&self) -> Vec<Choice> {
+        let named = self.profiles.iter().map(|profile| Choice(Some(profile.name.clone())));
+        std::iter::once(Choice(None)).chain(named).collect()
+    }
+
+    // The settings `mode` switches to, if it has a profile
+    pub fn settings_for(&self, mode: Mode) -> Option<Settings> {
+        let name = self.assigned.get(&mode)?;
+        self.profiles.iter().find(|profile| &profile.name == name).map(|profile| profile.settings)
+    }
+
+    // Set `mode` to `choice`, and save
+    pub fn assign(&mut self, mode: Mode, choice: Choice) {
+        match choice.0 {
+            Some(name) if self.profiles.iter().any(|profile| profile.name == name) => {
+                self.assigned.insert(mode, name);
+            }
+            Some(_) => return, // Not one of the profiles, so there's nothing to switch to
+            None => {
+                self.assigned.remove(&mode);
+            }
+        }
+        self.save();
+    }
+
+    // Replace the settings kept in the profile `name` with `settings`, and save
+    pub fn update(&mut self, name: &str, settings: Settings) {
+        if let Some(profile) = self.profiles.iter_mut().find(|profile| profile.name == name) {
+            profile.settings = settings;
+            self.save();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_ranked_profile_gives_no_free_help() {
+        let ranked = defaults().into_iter().find(|profile| profile.name == "Ranked").unwrap().settings;
+        assert!(!ranked.mercy_hints);
+        assert!(!ranked.assist_hints);
+
+        let mut preferences = Preferences { mercy_hints: true, ..Preferences::default() };
+        ranked.apply(&mut preferences);
+        assert!(!preferences.mercy_hints);
+        assert_eq!(Settings::of(&preferences), ranked);
+    }
+}
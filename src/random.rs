@@ -0,0 +1,108 @@
+// Where secret numbers come from, behind a trait so tests can choose them
+
+use rand::Rng;
+
+// A source of secret numbers
+pub trait NumberSource {
+    // Produce a secret in the inclusive range `min..=max`
+    fn secret(&mut self, min: u32, max: u32) -> u32;
+}
+
+// The real source, drawing from the thread-local random number generator
+#[derive(Debug, Default)]
+pub struct ThreadRngSource;
+
+impl NumberSource for ThreadRngSource {
+    fn secret(&mut self, min: u32, max: u32) -> u32 {
+        rand::thread_rng().gen_range(min..=max)
+    }
+}
+
+// A seeded source that produces the same numbers on every machine and version
+//
+// This backs challenge codes, so its output must never change: doing so would break
+// every code already shared. It uses SplitMix64 rather than `rand`, whose generators
+// may change between releases.
+#[derive(Debug, Clone)]
+pub struct SeededSource {
+    state: u64, // Advanced by a fixed step for every number drawn
+}
+
+impl SeededSource {
+    pub fn new(seed: u64) -> SeededSource {
+        SeededSource { state: seed }
+    }
+
+    // The next 64 random bits
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl NumberSource for SeededSource {
+    fn secret(&mut self, min: u32, max: u32) -> u32 {
+        let size = u64::from(max - min) + 1; // Widen so the full u32 range doesn't overflow
+        min + (self.next() % size) as u32
+    }
+}
+
+// Derive a challenge's secret in `min..=max` from its seed
+pub fn seeded_secret(seed: u32, min: u32, max: u32) -> u32 {
+    SeededSource::new(u64::from(seed)).secret(min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A source that hands out the numbers it was given, in order, whatever the range
+    struct Fixed(Vec<u32>);
+
+    impl NumberSource for Fixed {
+        fn secret(&mut self, _min: u32, _max: u32) -> u32 {
+            self.0.remove(0)
+        }
+    }
+
+    #[test]
+    fn any_source_can_stand_behind_the_trait() {
+        let mut source: Box<dyn NumberSource> = Box::new(Fixed(vec![37, 12]));
+        assert_eq!(source.secret(1, 100), 37);
+        assert_eq!(source.secret(1, 100), 12);
+    }
+
+    #[test]
+    fn a_seeded_source_repeats_itself() {
+        let draw = |seed| {
+            let mut source = SeededSource::new(seed);
+            (0..20).map(|_| source.secret(1, 100)).collect::<Vec<u32>>()
+        };
+        assert_eq!(draw(42), draw(42));
+        assert_ne!(draw(42), draw(43));
+    }
+
+    #[test]
+    fn challenge_secrets_never_change() {
+        // Shared codes depend on these; a change here breaks every one of them
+        let cases = [(0, 36, 536), (7, 88, 488), (42, 14, 414), (1_000, 77, 777), (90_210, 37, 37), (u32::MAX, 81, 681)];
+        for (seed, classic, thousand) in cases {
+            assert_eq!(seeded_secret(seed, 1, 100), classic, "seed {}", seed);
+            assert_eq!(seeded_secret(seed, 1, 1_000), thousand, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn seeded_secrets_stay_in_range() {
+        let mut source = SeededSource::new(7);
+        for (min, max) in [(1, 100), (5, 5), (0, 1), (1_000, 1_999), (0, u32::MAX), (u32::MAX - 1, u32::MAX)] {
+            for _ in 0..200 {
+                let secret = source.secret(min, max);
+                assert!((min..=max).contains(&secret), "{} outside {}-{}", secret, min, max);
+            }
+        }
+    }
+}
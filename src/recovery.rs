@@ -0,0 +1,475 @@
+// Snapshots of the game in progress, so a crash or a killed process doesn't lose it
+
+use std::io;
+use std::time::Duration;
+
+use crate::daily::Date;
+use crate::migration::{self, Schema, Values};
+use crate::mystery::MysteryOperation;
+use crate::practice;
+use crate::secret::Secret;
+use crate::storage;
+
+// File the snapshot is stored in, inside the data directory
+//
+// The file is deleted on a clean exit, so finding it at startup means the last run
+// ended unexpectedly, unless the player chose to save the game for later.
+const FILE_NAME: &str = "recovery.txt";
+
+// How the snapshot file has changed; add a step whenever the fields change
+//
+// Snapshots kept their version under `version` before every file had one.
+const SCHEMA: Schema = Schema {
+    file: FILE_NAME,
+    name: "saved game",
+    legacy_key: Some("version"),
+    steps: &[add_operation, add_seed, add_saved, add_timeouts, add_times, add_warmup, add_swapped, place_timeouts, add_modes],
+};
+
+// Version 2 added the mystery operation, which older games never had
+fn add_operation(values: &mut Values) {
+    values.insert("operation".to_string(), "none".to_string());
+}
+
+// Version 3 added the seed; an older game's is lost, which only changes the challenge code
+// it offers, since the secret itself is stored apart from it
+fn add_seed(values: &mut Values) {
+    values.insert("seed".to_string(), "0".to_string());
+}
+
+// Version 4 told games saved on quitting from those left by a crash, the only kind before it
+fn add_saved(values: &mut Values) {
+    values.insert("saved".to_string(), "false".to_string());
+}
+
+// Version 5 counted attempts that ran out of time, which no older game had
+fn add_timeouts(values: &mut Values) {
+    values.insert("timeouts".to_string(), "0".to_string());
+}
+
+// Version 6 timed each guess; an older game's guesses are left untimed rather than guessed at
+fn add_times(values: &mut Values) {
+    let count = values.get("history").map_or(0, |list| list.split(',').filter(|guess| !guess.is_empty()).count());
+    values.insert("times".to_string(), vec![""; count].join(","));
+}
+
+// Version 7 kept whether the round's warm-up guess was taken; older games never had one to take
+fn add_warmup(values: &mut Values) {
+    values.insert("warmup_used".to_string(), "false".to_string());
+}
+
+// Version 8 kept whether the surprise swap had happened, which older games never had
+fn add_swapped(values: &mut Values) {
+    values.insert("swapped".to_string(), "false".to_string());
+}
+
+// Version 9 kept where each timed-out attempt fell among the guesses rather than just how many
+// there were; an older game's are put after its last guess, where the history shows them
+fn place_timeouts(values: &mut Values) {
+    let count = values.get("timeouts").and_then(|count| count.parse().ok()).unwrap_or(0);
+    let guesses = values.get("history").map_or(0, |list| list.split(',').filter(|guess| !guess.is_empty()).count());
+    values.insert("timeouts".to_string(), vec![guesses.to_string(); count].join(","));
+}
+
+// Version 10 kept the mode the game was played in, so a drill or a practice game doesn't come back
+// as one that counts; older games all come back as plain ones, as they did before
+fn add_modes(values: &mut Values) {
+    for (key, value) in [("practice", "none"), ("daily", "none"), ("pass_and_play", "false"), ("retry", "false"), ("voided", "false")] {
+        values.insert(key.to_string(), value.to_string());
+    }
+}
+
+// Everything needed to put an unfinished game back the way it was
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub secret: Secret,     // The masked secret, saved without decoding it
+    pub seed: u32,          // Seed the secret was derived from, for challenge codes
+    pub low: u32,           // Smallest value still consistent with the feedback
+    pub high: u32,          // Largest value still consistent with the feedback
+    pub history: Vec<u32>,  // Every valid guess so far, in order
+    pub times: Vec<Option<Duration>>, // How long each guess in the history took, where known
+    pub timeouts: Vec<usize>, // Attempts wasted by running out of time, as how many guesses came before each
+    pub warmup_used: bool,  // Whether the free warm-up guess was taken, which isn't in the history either
+    pub swapped: bool,      // Whether the secret has already had its surprise swap
+    pub elapsed: Duration,  // Time on the game clock
+    pub operation: Option<MysteryOperation>, // The round's hidden operation, in the mystery mode
+    pub saved: bool,        // Whether the player kept it on purpose when quitting, rather than it being left by a crash
+    pub practice: Option<(u32, u32)>, // The drill's range, when it was a drill
+    pub daily: Option<Date>, // The day whose challenge it was, when it was a daily challenge
+    pub pass_and_play: bool, // Whether another player set the secret
+    pub retry: bool,        // Whether it was another try at a secret already given up on
+    pub voided: bool,       // Whether the debug overlay showed its secret, so winning it can't count
+}
+
+impl Snapshot {
+    // Render the snapshot as `key = value` lines
+    fn to_text(&self) -> String {
+        let (masked, mask) = self.secret.parts();
+        let history: Vec<String> = self.history.iter().map(u32::to_string).collect();
+        let timeouts: Vec<String> = self.timeouts.iter().map(usize::to_string).collect();
+
+        storage::write_key_values([
+            ("masked_secret", masked.to_string()),
+            ("mask", mask.to_string()),
+            ("seed", self.seed.to_string()),
+            ("low", self.low.to_string()),
+            ("high", self.high.to_string()),
+            ("history", history.join(",")),
+            ("times", storage::write_times(&self.times)),
+            ("timeouts", timeouts.join(",")),
+            ("warmup_used", self.warmup_used.to_string()),
+            ("swapped", self.swapped.to_string()),
+            ("elapsed_ms", self.elapsed.as_millis().to_string()),
+            ("operation", self.operation.map_or("none", MysteryOperation::key).to_string()),
+            ("saved", self.saved.to_string()),
+            ("practice", self.practice.map_or("none".to_string(), |(low, high)| format!("{}-{}", low, high))),
+            ("daily", self.daily.map_or("none".to_string(), Date::key)),
+            ("pass_and_play", self.pass_and_play.to_string()),
+            ("retry", self.retry.to_string()),
+            ("voided", self.voided.to_string()),
+        ])
+    }
+
+    // Read a snapshot from its file's values, already upgraded, rejecting inconsistent games
+    fn from_values(values: &Values) -> Option<Snapshot> {
+        let number = |key: &str| values.get(key)?.parse::<u64>().ok();
+        let small = |key: &str| u32::try_from(number(key)?).ok();
+
+        let history = match values.get("history")?.as_str() {
+            "" => Vec::new(),
+            list => list.split(',').map(|guess| guess.parse().ok()).collect::<Option<Vec<u32>>>()?,
+        };
+        // Where the timeouts fell, in order and never after the last guess
+        let timeouts = match values.get("timeouts")?.as_str() {
+            "" => Vec::new(),
+            list => list.split(',').map(|place| place.parse().ok()).collect::<Option<Vec<usize>>>()?,
+        };
+        if timeouts.windows(2).any(|pair| pair[0] > pair[1]) || timeouts.last().is_some_and(|&place| place > history.len()) {
+            return None;
+        }
+        let operation = match values.get("operation")?.as_str() {
+            "none" => None,
+            key => Some(MysteryOperation::from_key(key)?),
+        };
+        let practice = match values.get("practice")?.as_str() {
+            "none" => None,
+            range => Some(practice::parse(range, 0, u32::MAX).ok()?),
+        };
+        let daily = match values.get("daily")?.as_str() {
+            "none" => None,
+            key => Some(Date::from_key(key)?),
+        };
+        let snapshot = Snapshot {
+            secret: Secret::from_parts(small("masked_secret")?, small("mask")?),
+            seed: small("seed")?,
+            low: small("low")?,
+            high: small("high")?,
+            times: storage::parse_times(values.get("times").map(String::as_str), history.len()),
+            history,
+            timeouts,
+            warmup_used: values.get("warmup_used")?.parse().ok()?,
+            swapped: values.get("swapped")?.parse().ok()?,
+            elapsed: Duration::from_millis(number("elapsed_ms")?),
+            operation,
+            saved: values.get("saved")?.parse().ok()?,
+            practice,
+            daily,
+            pass_and_play: values.get("pass_and_play")?.parse().ok()?,
+            retry: values.get("retry")?.parse().ok()?,
+            voided: values.get("voided")?.parse().ok()?,
+        };
+
+        // The secret has to lie within the bounds, or the file was tampered with or damaged
+        let secret = snapshot.secret;
+        (secret.compare(snapshot.low).is_le() && secret.compare(snapshot.high).is_ge()).then_some(snapshot)
+    }
+}
+
+// Save a snapshot, logging rather than failing if the file can't be written
+pub fn save(snapshot: &Snapshot) {
+    if let Err(error) = migration::save(&SCHEMA, &snapshot.to_text()) {
+        tracing::warn!(%error, "Could not save the recovery snapshot");
+    }
+}
+
+// The snapshot left behind by a run that didn't exit cleanly or saved its game, if there is a usable one
+//
+// A damaged snapshot is deleted, so it can't fail again on every startup; an old one is upgraded.
+pub fn load() -> Option<Snapshot> {
+    // No file means the last run exited cleanly with nothing saved; one from a newer version
+    // is kept for it, and never deleted here
+    let values = match migration::load(&SCHEMA) {
+        Ok(values) => values?,
+        // Not even text, so it would fail the same way every time
+        Err(error) if error.kind() == io::ErrorKind::InvalidData => {
+            tracing::warn!(%error, "Discarding an unreadable recovery snapshot");
+            discard();
+            return None;
+        }
+        Err(error) => {
+            tracing::warn!(%error, "Could not read the recovery snapshot");
+            return None;
+        }
+    };
+
+    let snapshot = Snapshot::from_values(&values);
+    if snapshot.is_none() {
+        tracing::warn!("Discarding an unreadable recovery snapshot");
+        discard();
+    }
+    snapshot
+}
+
+// Delete the snapshot, on a clean exit or once there's nothing left worth recovering
+pub fn discard() {
+    if let Err(error) = migration::remove(&SCHEMA) {
+        tracing::warn!(%error, "Could not delete the recovery snapshot");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    // A game part-way through, as saved now
+    fn sample() -> Snapshot {
+        Snapshot {
+            secret: Secret::from_parts(37 ^ 0xA5A5, 0xA5A5),
+            seed: 42,
+            low: 26,
+            high: 49,
+            history: vec![50, 25],
+            times: vec![Some(Duration::from_millis(8_240)), None],
+            timeouts: vec![1],
+            warmup_used: false,
+            swapped: false,
+            elapsed: Duration::from_millis(12_500),
+            operation: None,
+            saved: true,
+            practice: None,
+            daily: None,
+            pass_and_play: false,
+            retry: false,
+            voided: false,
+        }
+    }
+
+    // The snapshot read from a file that an older version wrote, upgraded to the current one
+    fn upgraded(fixture: &str) -> Option<Snapshot> {
+        let mut values = storage::parse_key_values(fixture);
+        migration::upgrade(&SCHEMA, &mut values).ok()?;
+        assert_eq!(values[migration::VERSION_KEY], SCHEMA.current().to_string());
+        assert!(!values.contains_key("version"), "the legacy key is dropped");
+        Snapshot::from_values(&values)
+    }
+
+    // The game every fixture below holds, with whatever its version didn't keep left at the default
+    fn upgraded_sample() -> Snapshot {
+        Snapshot {
+            seed: 0,
+            times: vec![None, None],
+            timeouts: Vec::new(),
+            saved: false,
+            ..sample()
+        }
+    }
+
+    // Version 1 didn't write its version at all
+    const V1: &str = "masked_secret = 42368\nmask = 42405\nlow = 26\nhigh = 49\nhistory = 50,25\nelapsed_ms = 12500\n";
+
+    #[test]
+    fn version_1_gains_every_later_field() {
+        assert_eq!(upgraded(V1), Some(upgraded_sample()));
+    }
+
+    #[test]
+    fn version_2_keeps_its_operation() {
+        let fixture = format!("version = 2\n{}operation = cube\n", V1);
+        assert_eq!(upgraded(&fixture), Some(Snapshot { operation: Some(MysteryOperation::Cube), ..upgraded_sample() }));
+    }
+
+    #[test]
+    fn version_3_keeps_its_seed() {
+        let fixture = format!("version = 3\n{}operation = none\nseed = 42\n", V1);
+        assert_eq!(upgraded(&fixture), Some(Snapshot { seed: 42, ..upgraded_sample() }));
+    }
+
+    #[test]
+    fn version_4_keeps_whether_it_was_saved_on_purpose() {
+        let fixture = format!("version = 4\n{}operation = none\nseed = 42\nsaved = true\n", V1);
+        assert_eq!(upgraded(&fixture), Some(Snapshot { seed: 42, saved: true, ..upgraded_sample() }));
+    }
+
+    #[test]
+    fn version_5_puts_its_timeouts_after_the_last_guess() {
+        let fixture = format!("version = 5\n{}operation = none\nseed = 42\nsaved = true\ntimeouts = 2\n", V1);
+        assert_eq!(upgraded(&fixture), Some(Snapshot { seed: 42, saved: true, timeouts: vec![2, 2], ..upgraded_sample() }));
+    }
+
+    #[test]
+    fn version_6_keeps_its_times() {
+        let fixture = format!("version = 6\n{}operation = none\nseed = 42\nsaved = true\ntimeouts = 1\ntimes = 8240,\n", V1);
+        let expected = Snapshot { seed: 42, saved: true, timeouts: vec![2], times: sample().times, ..upgraded_sample() };
+        assert_eq!(upgraded(&fixture), Some(expected));
+    }
+
+    #[test]
+    fn version_7_keeps_its_warm_up() {
+        let fixture = format!(
+            "version = 7\n{}operation = none\nseed = 42\nsaved = true\ntimeouts = 0\ntimes = 8240,\nwarmup_used = true\n",
+            V1
+        );
+        let expected = Snapshot { seed: 42, saved: true, times: sample().times, warmup_used: true, ..upgraded_sample() };
+        assert_eq!(upgraded(&fixture), Some(expected));
+    }
+
+    #[test]
+    fn version_8_keeps_its_swap() {
+        let fixture = format!(
+            "schema_version = 8\n{}operation = none\nseed = 42\nsaved = true\ntimeouts = 1\ntimes = 8240,\nwarmup_used = false\nswapped = true\n",
+            V1
+        );
+        let expected = Snapshot { timeouts: vec![2], swapped: true, ..sample() };
+        assert_eq!(upgraded(&fixture), Some(expected));
+    }
+
+    #[test]
+    fn version_9_comes_back_as_a_plain_game() {
+        let fixture = format!(
+            "schema_version = 9\n{}operation = none\nseed = 42\nsaved = true\ntimeouts = \ntimes = 8240,\nwarmup_used = false\nswapped = false\n",
+            V1
+        );
+        assert_eq!(upgraded(&fixture), Some(Snapshot { timeouts: Vec::new(), ..sample() }));
+    }
+
+    #[test]
+    fn a_snapshot_keeps_the_mode_it_was_played_in() {
+        let drill = Snapshot { practice: Some((20, 60)), retry: true, voided: true, ..sample() };
+        save(&drill);
+        assert_eq!(load(), Some(drill));
+        let daily = Snapshot { daily: Some(Date { year: 2026, month: 10, day: 14 }), pass_and_play: true, ..sample() };
+        save(&daily);
+        assert_eq!(load(), Some(daily));
+    }
+
+    #[test]
+    fn a_snapshot_in_the_current_version_is_read_as_it_is() {
+        let fixture = format!("schema_version = {}\n{}", SCHEMA.current(), sample().to_text());
+        assert_eq!(upgraded(&fixture), Some(sample()));
+    }
+
+    #[test]
+    fn placing_timeouts_with_no_guesses_puts_them_first() {
+        let mut values = storage::parse_key_values("history = \ntimeouts = 3\n");
+        place_timeouts(&mut values);
+        assert_eq!(values["timeouts"], "0,0,0");
+        let mut values = storage::parse_key_values("history = 5,7\ntimeouts = 0\n");
+        place_timeouts(&mut values);
+        assert_eq!(values["timeouts"], "");
+    }
+
+    #[test]
+    fn an_old_snapshot_file_is_upgraded_as_it_loads() {
+        leave(format!("version = 5\n{}operation = none\nseed = 42\nsaved = true\ntimeouts = 2\n", V1).as_bytes());
+        assert_eq!(load(), Some(Snapshot { seed: 42, saved: true, timeouts: vec![2, 2], ..upgraded_sample() }));
+    }
+
+    // Leave `contents` in the snapshot file, as a crash or a stray edit could
+    fn leave(contents: &[u8]) {
+        let dir = storage::data_dir().unwrap();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(FILE_NAME), contents).unwrap();
+    }
+
+    // Whether the snapshot file is there
+    fn exists() -> bool {
+        storage::data_dir().unwrap().join(FILE_NAME).exists()
+    }
+
+    // After a damaged snapshot: it's gone, and saving and loading work as normal again
+    fn assert_discarded() {
+        assert!(!exists(), "a damaged snapshot is deleted so it can't fail every startup");
+        assert_eq!(load(), None);
+        save(&sample());
+        assert_eq!(load(), Some(sample()));
+    }
+
+    #[test]
+    fn a_snapshot_loads_back_as_it_was_saved() {
+        save(&sample());
+        assert_eq!(load(), Some(sample()));
+        discard();
+        assert_eq!(load(), None);
+    }
+
+    #[test]
+    fn a_truncated_snapshot_is_discarded() {
+        let text = format!("schema_version = {}\n{}", SCHEMA.current(), sample().to_text());
+        leave(&text.as_bytes()[..text.len() / 2]);
+        assert_eq!(load(), None);
+        assert_discarded();
+    }
+
+    #[test]
+    fn a_snapshot_cut_off_mid_value_is_discarded() {
+        let text = format!("schema_version = {}\n{}", SCHEMA.current(), sample().to_text());
+        let cut = text.trim_end().strip_suffix("false").unwrap().to_string() + "fal";
+        leave(cut.as_bytes());
+        assert_eq!(load(), None);
+        assert_discarded();
+    }
+
+    #[test]
+    fn a_garbage_snapshot_is_discarded() {
+        leave(b"{\"not\": \"a snapshot\"}\n= = =\nhistory = 5,,7\n");
+        assert_eq!(load(), None);
+        assert_discarded();
+    }
+
+    #[test]
+    fn a_snapshot_that_isnt_text_is_discarded() {
+        leave(&[0xff, 0xfe, 0x00, 0x9f, 0x92, 0x96]);
+        assert_eq!(load(), None);
+        assert_discarded();
+    }
+
+    #[test]
+    fn a_snapshot_with_its_secret_outside_the_bounds_is_discarded() {
+        save(&Snapshot { low: 40, ..sample() });
+        assert_eq!(load(), None);
+        assert_discarded();
+    }
+
+    #[test]
+    fn a_snapshot_with_misplaced_timeouts_is_discarded() {
+        save(&Snapshot { timeouts: vec![2, 1], ..sample() });
+        assert_eq!(load(), None);
+        assert_discarded();
+        save(&Snapshot { timeouts: vec![3], ..sample() });
+        assert_eq!(load(), None);
+    }
+
+    #[test]
+    fn a_snapshot_from_a_newer_version_is_kept_for_it() {
+        let contents = format!("schema_version = {}\n{}", SCHEMA.current() + 1, sample().to_text());
+        leave(contents.as_bytes());
+        assert_eq!(load(), None);
+
+        // Nothing this version does touches it, for the rest of the run
+        save(&sample());
+        discard();
+        assert_eq!(fs::read_to_string(storage::data_dir().unwrap().join(FILE_NAME)).unwrap(), contents);
+        assert_eq!(migration::newer_files().len(), 1);
+    }
+
+    #[test]
+    fn a_snapshot_with_an_unreadable_version_is_kept_too() {
+        for version in ["0", "nine", "-1"] {
+            leave(format!("schema_version = {}\n{}", version, sample().to_text()).as_bytes());
+            assert_eq!(load(), None, "{}", version);
+            assert!(exists());
+        }
+    }
+}
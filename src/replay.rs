@@ -0,0 +1,666 @@
+// Recording sessions message by message, and reading them back for a replay
+//
+// A recording starts with the seed, locale and preferences the session began with,
+// followed by one line per message: milliseconds since launch, the message's name and
+// its argument. A clean exit adds a digest of the final game state, which a replay of
+// the same messages has to reproduce exactly.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::appearance::SystemTheme;
+use crate::closing::QuitChoice;
+use crate::color::Rgb;
+use crate::daily::Date;
+use crate::focus::SettingKey;
+use crate::leaderboard::Metric;
+use crate::onekey;
+use crate::fonts::FontChoice;
+use crate::format::{Locale, LocaleChoice};
+use crate::preferences::{IdleTimeout, MotionChoice, Preferences, Startup, ThemeChoice};
+use crate::profiles::{self, Choice};
+use crate::rules::MinChange;
+use crate::solver::{Answer, BotStrategy};
+use crate::speech::{SpeechRate, VoiceChoice};
+use crate::swap::SurpriseSwap;
+use crate::trend;
+use crate::Message;
+
+// First line of every recording, so other files are rejected up front
+const HEADER: &str = "GuessingGame recording 1";
+
+// Writes each message of the running session to a recording file
+pub struct Recorder {
+    file: File,       // The recording being written
+    started: Instant, // Launch time, which message times are measured from
+}
+
+impl Recorder {
+    // Start a recording of a session beginning with the given seed, locale and preferences
+    pub fn create(path: &Path, seed: u32, locale: Locale, preferences: &Preferences) -> io::Result<Recorder> {
+        let mut file = File::create(path)?;
+        writeln!(file, "{}", HEADER)?;
+        writeln!(file, "seed {}", seed)?;
+        writeln!(file, "locale {}", locale.tag())?;
+        writeln!(file, "preferences {}", escape(&preferences.to_text()))?;
+
+        Ok(Recorder { file, started: Instant::now() })
+    }
+
+    // Append a message, logging rather than failing if the file can't be written
+    pub fn record(&mut self, message: &Message) {
+        let elapsed = self.started.elapsed().as_millis();
+        let line = match argument(message, self.started) {
+            Some(argument) => format!("{} {} {}", elapsed, name(message), argument),
+            None => format!("{} {}", elapsed, name(message)),
+        };
+
+        if let Err(error) = writeln!(self.file, "{}", line) {
+            tracing::warn!(%error, "Could not write to the session recording");
+        }
+    }
+
+    // Finish the recording with the digest of the final state
+    pub fn finish(&mut self, digest: u64) {
+        if let Err(error) = writeln!(self.file, "digest {:016x}", digest) {
+            tracing::warn!(%error, "Could not finish the session recording");
+        }
+    }
+}
+
+// A recording read back from disk
+pub struct Recording {
+    pub seed: u32,                           // Seed every random number was drawn from
+    pub locale: Locale,                      // Number conventions the session used
+    pub preferences: Preferences,            // Preferences at the start of the session
+    pub messages: Vec<(Duration, Message)>, // Every message, with when it arrived after launch
+    pub digest: Option<u64>,                 // Digest of the final state, if the session closed cleanly
+}
+
+impl Recording {
+    // Read a recording written by `Recorder`, with message times counted from `start`
+    pub fn load(path: &Path, start: Instant) -> Result<Recording, String> {
+        let contents = fs::read_to_string(path).map_err(|error| format!("could not read {}: {}", path.display(), error))?;
+        Recording::parse(&contents, start)
+    }
+
+    // Read the contents of a recording, with message times counted from `start`
+    pub fn parse(contents: &str, start: Instant) -> Result<Recording, String> {
+        let mut lines = contents.lines().enumerate();
+
+        if lines.next().map(|(_, line)| line) != Some(HEADER) {
+            return Err("not a session recording".to_string());
+        }
+
+        let mut header = |key: &str| match lines.next() {
+            Some((_, line)) => line.strip_prefix(key).and_then(|rest| rest.strip_prefix(' ')).map(str::to_string),
+            None => None,
+        }
+        .ok_or_else(|| format!("missing {} line", key));
+        let seed = header("seed")?.parse().map_err(|_| "malformed seed".to_string())?;
+        let locale = Locale::from_tag(&header("locale")?);
+        let preferences = Preferences::from_text(&unescape(&header("preferences")?));
+
+        let mut messages = Vec::new();
+        let mut digest = None;
+        for (index, line) in lines {
+            if let Some(hex) = line.strip_prefix("digest ") {
+                digest = Some(u64::from_str_radix(hex, 16).map_err(|_| "malformed digest".to_string())?);
+                continue;
+            }
+
+            let message = decode(line, start).ok_or_else(|| format!("unreadable message on line {}", index + 1))?;
+            messages.push(message);
+        }
+
+        Ok(Recording { seed, locale, preferences, messages, digest })
+    }
+}
+
+// Hash a description of the game state; FNV-1a, so digests match across builds and platforms
+pub fn digest(state: &str) -> u64 {
+    state.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3))
+}
+
+// The name a message is recorded under, without its argument
+//
+// The match is exhaustive on purpose, so a new message can't be added without deciding
+// how it is recorded.
+pub fn name(message: &Message) -> &'static str {
+    match message {
+        Message::GuessInputChanged(_) => "GuessInputChanged",
+        Message::GuessStepped(_) => "GuessStepped",
+        Message::NumberPicked(_) => "NumberPicked",
+        Message::GuessButtonPressed => "GuessButtonPressed",
+        Message::HintRequested => "HintRequested",
+        Message::PeekRequested => "PeekRequested",
+        Message::NewGame => "NewGame",
+        Message::ReviewToggled => "ReviewToggled",
+        Message::GradeExplained => "GradeExplained",
+        Message::ReviewScrolled(_) => "ReviewScrolled",
+        Message::ShowCandidatesToggled(_) => "ShowCandidatesToggled",
+        Message::ShowBasesToggled(_) => "ShowBasesToggled",
+        Message::ThemeSelected(_) => "ThemeSelected",
+        Message::SystemThemePoll => "SystemThemePoll",
+        Message::SystemThemeDetected(_) => "SystemThemeDetected",
+        Message::SystemAccentDetected(_) => "SystemAccentDetected",
+        Message::SettingsToggled => "SettingsToggled",
+        Message::AchievementsToggled => "AchievementsToggled",
+        Message::ProfileModeSelected(_) => "ProfileModeSelected",
+        Message::ProfileChosen(_) => "ProfileChosen",
+        Message::ProfileSaved => "ProfileSaved",
+        Message::TrendWindowSelected(_) => "TrendWindowSelected",
+        Message::LeaderboardToggled => "LeaderboardToggled",
+        Message::LeaderboardMetricSelected(_) => "LeaderboardMetricSelected",
+        Message::DailyChallengeStarted(_) => "DailyChallengeStarted",
+        Message::CalendarToggled => "CalendarToggled",
+        Message::CalendarMonthMoved(_) => "CalendarMonthMoved",
+        Message::AccentSelected(_) => "AccentSelected",
+        Message::AccentHexChanged(_) => "AccentHexChanged",
+        Message::AccentHexSubmitted => "AccentHexSubmitted",
+        Message::NotifyOnWinToggled(_) => "NotifyOnWinToggled",
+        Message::SpeakFeedbackToggled(_) => "SpeakFeedbackToggled",
+        Message::SpeechRateSelected(_) => "SpeechRateSelected",
+        Message::SpeechVoiceSelected(_) => "SpeechVoiceSelected",
+        Message::FeedbackSpoken(_) => "FeedbackSpoken",
+        Message::WinNotificationAnswered(_) => "WinNotificationAnswered",
+        Message::MysteryModeToggled(_) => "MysteryModeToggled",
+        Message::TimedGuessesToggled(_) => "TimedGuessesToggled",
+        Message::GhostRaceToggled(_) => "GhostRaceToggled",
+        Message::GuessBudgetToggled(_) => "GuessBudgetToggled",
+        Message::MercyHintsToggled(_) => "MercyHintsToggled",
+        Message::AssistHintsToggled(_) => "AssistHintsToggled",
+        Message::GuessAssistToggled(_) => "GuessAssistToggled",
+        Message::CloseEnoughToggled(_) => "CloseEnoughToggled",
+        Message::ProximityTonesToggled(_) => "ProximityTonesToggled",
+        Message::ChallengeCodeCopied => "ChallengeCodeCopied",
+        Message::ChallengeInputChanged(_) => "ChallengeInputChanged",
+        Message::ChallengeSubmitted => "ChallengeSubmitted",
+        Message::PracticeInputChanged(_) => "PracticeInputChanged",
+        Message::PracticeSubmitted => "PracticeSubmitted",
+        Message::AnalyticsToggled(_) => "AnalyticsToggled",
+        Message::AnalyticsDeleted => "AnalyticsDeleted",
+        Message::RecordsResetRequested => "RecordsResetRequested",
+        Message::RecordsResetConfirmed(_) => "RecordsResetConfirmed",
+        Message::InsightsToggled => "InsightsToggled",
+        Message::WindowFocusChanged(_) => "WindowFocusChanged",
+        Message::FontSelected(_) => "FontSelected",
+        Message::NumberFormatSelected(_) => "NumberFormatSelected",
+        Message::MonospaceDigitsToggled(_) => "MonospaceDigitsToggled",
+        Message::ColorblindToggled(_) => "ColorblindToggled",
+        Message::AlwaysOnTopToggled(_) => "AlwaysOnTopToggled",
+        Message::AlwaysOnTopShortcut => "AlwaysOnTopShortcut",
+        Message::CompactToggled(_) => "CompactToggled",
+        Message::CompactShortcut => "CompactShortcut",
+        Message::LeftHandedToggled(_) => "LeftHandedToggled",
+        Message::OneKeyToggled(_) => "OneKeyToggled",
+        Message::OneKeyTimingSelected(_) => "OneKeyTimingSelected",
+        Message::OneKeyPressed(_) => "OneKeyPressed",
+        Message::OneKeyReleased(_) => "OneKeyReleased",
+        Message::IdleTimeoutSelected(_) => "IdleTimeoutSelected",
+        Message::StartupSelected(_) => "StartupSelected",
+        Message::MinChangeSelected(_) => "MinChangeSelected",
+        Message::SurpriseSwapSelected(_) => "SurpriseSwapSelected",
+        Message::MotionSelected(_) => "MotionSelected",
+        Message::PassAndPlayOpened => "PassAndPlayOpened",
+        Message::PassSecretChanged(_) => "PassSecretChanged",
+        Message::PassSecretSubmitted => "PassSecretSubmitted",
+        Message::PassReady => "PassReady",
+        Message::PassCancelled => "PassCancelled",
+        Message::GaveUp => "GaveUp",
+        Message::WarmUpGuessed => "WarmUpGuessed",
+        Message::RetryRequested => "RetryRequested",
+        Message::ResultCardRequested => "ResultCardRequested",
+        Message::ResultCardSaved(_) => "ResultCardSaved",
+        Message::ScorecardPathChanged(_) => "ScorecardPathChanged",
+        Message::ScorecardImported => "ScorecardImported",
+        Message::ScorecardExportRequested => "ScorecardExportRequested",
+        Message::ScorecardExported(_) => "ScorecardExported",
+        Message::ReplayExportRequested => "ReplayExportRequested",
+        Message::ReplayExported(_) => "ReplayExported",
+        Message::HistoryExportRequested => "HistoryExportRequested",
+        Message::HistoryExported(_) => "HistoryExported",
+        Message::ReplaysToggled => "ReplaysToggled",
+        Message::ReplayPathChanged(_) => "ReplayPathChanged",
+        Message::ReplayOpened => "ReplayOpened",
+        Message::PlaybackStepped(_) => "PlaybackStepped",
+        Message::PlaybackToggled => "PlaybackToggled",
+        Message::PlaybackAdvanced => "PlaybackAdvanced",
+        Message::PlaybackClosed => "PlaybackClosed",
+        Message::GalleryOpened => "GalleryOpened",
+        Message::GalleryAdvanced => "GalleryAdvanced",
+        Message::GalleryClosed => "GalleryClosed",
+        Message::BotModeToggled(_) => "BotModeToggled",
+        Message::BotAnswered(_) => "BotAnswered",
+        Message::BotStrategySelected(_) => "BotStrategySelected",
+        Message::NewBotGame => "NewBotGame",
+        Message::ConstraintsChanged(_) => "ConstraintsChanged",
+        Message::ConstraintsSubmitted => "ConstraintsSubmitted",
+        Message::SameAgain => "SameAgain",
+        Message::TournamentStarted(_) => "TournamentStarted",
+        Message::TournamentAborted => "TournamentAborted",
+        Message::TournamentExitConfirmed(_) => "TournamentExitConfirmed",
+        Message::EndlessStarted => "EndlessStarted",
+        Message::CourseStarted => "CourseStarted",
+        Message::CourseAborted => "CourseAborted",
+        Message::EndlessStopped => "EndlessStopped",
+        Message::WhatsNewOpened => "WhatsNewOpened",
+        Message::WhatsNewDismissed => "WhatsNewDismissed",
+        Message::RecoveryAutosave => "RecoveryAutosave",
+        Message::SpectatorRefresh => "SpectatorRefresh",
+        Message::SpectatorFeedToggled(_) => "SpectatorFeedToggled",
+        Message::RecoveryRestored => "RecoveryRestored",
+        Message::RecoveryDeclined => "RecoveryDeclined",
+        Message::CloseConfirmed(_) => "CloseConfirmed",
+        Message::CloseRequested => "CloseRequested",
+        Message::FocusMoved(_) => "FocusMoved",
+        Message::SettingKey(_) => "SettingKey",
+        Message::DebugOverlayToggled => "DebugOverlayToggled",
+        Message::Tick(_) => "Tick",
+    }
+}
+
+// A message's argument as written to a recording, after its name
+fn argument(message: &Message, started: Instant) -> Option<String> {
+    let flag = |value: &bool| Some(value.to_string());
+    match message {
+        Message::GuessInputChanged(text) => Some(escape(text)),
+        Message::GuessStepped(by) => Some(by.to_string()),
+        Message::NumberPicked(number) => Some(number.to_string()),
+        Message::GuessButtonPressed => None,
+        Message::HintRequested => None,
+        Message::PeekRequested => None,
+        Message::NewGame => None,
+        Message::ReviewToggled => None,
+        Message::GradeExplained => None,
+        Message::ReviewScrolled(offset) => Some(offset.to_string()),
+        Message::ShowCandidatesToggled(show) => flag(show),
+        Message::ShowBasesToggled(show) => flag(show),
+        Message::ThemeSelected(theme) => Some(theme.key().to_string()),
+        Message::SystemThemePoll => None,
+        Message::SystemThemeDetected(theme) => {
+            let theme = match theme {
+                Some(SystemTheme::Light) => "light",
+                Some(SystemTheme::Dark) => "dark",
+                None => "unknown",
+            };
+            Some(theme.to_string())
+        }
+        Message::SystemAccentDetected(accent) => accent.map(Rgb::to_hex),
+        Message::SettingsToggled => None,
+        Message::AchievementsToggled => None,
+        Message::ProfileModeSelected(mode) => Some(mode.key().to_string()),
+        Message::ProfileChosen(choice) => Some(choice.0.as_deref().map_or(String::new(), escape)),
+        Message::ProfileSaved => None,
+        Message::TrendWindowSelected(window) => Some(window.key().to_string()),
+        Message::LeaderboardToggled => None,
+        Message::LeaderboardMetricSelected(metric) => Some(metric.key().to_string()),
+        Message::DailyChallengeStarted(date) => Some(date.key()),
+        Message::CalendarToggled => None,
+        Message::CalendarMonthMoved(forward) => flag(forward),
+        Message::AccentSelected(accent) => Some(accent.to_hex()),
+        Message::AccentHexChanged(text) => Some(escape(text)),
+        Message::AccentHexSubmitted => None,
+        Message::NotifyOnWinToggled(notify) => flag(notify),
+        Message::SpeakFeedbackToggled(speak) => flag(speak),
+        Message::SpeechRateSelected(rate) => Some(rate.key().to_string()),
+        Message::SpeechVoiceSelected(voice) => Some(escape(&voice.id)),
+        Message::FeedbackSpoken(result) => result.as_ref().err().map(|reason| escape(reason)),
+        Message::WinNotificationAnswered(clicked) => flag(clicked),
+        Message::MysteryModeToggled(mystery) => flag(mystery),
+        Message::TimedGuessesToggled(timed) => flag(timed),
+        Message::GhostRaceToggled(race) => flag(race),
+        Message::GuessBudgetToggled(budget) => flag(budget),
+        Message::MercyHintsToggled(mercy) => flag(mercy),
+        Message::AssistHintsToggled(assist) => flag(assist),
+        Message::GuessAssistToggled(assist) => flag(assist),
+        Message::CloseEnoughToggled(close) => flag(close),
+        Message::ProximityTonesToggled(tones) => flag(tones),
+        Message::ChallengeCodeCopied => None,
+        Message::ChallengeInputChanged(text) => Some(escape(text)),
+        Message::ChallengeSubmitted => None,
+        Message::PracticeInputChanged(text) => Some(escape(text)),
+        Message::PracticeSubmitted => None,
+        Message::AnalyticsToggled(enabled) => flag(enabled),
+        Message::AnalyticsDeleted => None,
+        Message::RecordsResetRequested => None,
+        Message::RecordsResetConfirmed(reset) => flag(reset),
+        Message::InsightsToggled => None,
+        Message::WindowFocusChanged(focused) => flag(focused),
+        Message::FontSelected(font) => Some(font.key().to_string()),
+        Message::NumberFormatSelected(choice) => Some(choice.key().to_string()),
+        Message::MonospaceDigitsToggled(monospace) => flag(monospace),
+        Message::ColorblindToggled(colorblind) => flag(colorblind),
+        Message::AlwaysOnTopToggled(on_top) => flag(on_top),
+        Message::AlwaysOnTopShortcut => None,
+        Message::CompactToggled(compact) => flag(compact),
+        Message::CompactShortcut => None,
+        Message::LeftHandedToggled(left_handed) => flag(left_handed),
+        Message::OneKeyToggled(one_key) => flag(one_key),
+        Message::OneKeyTimingSelected(timing) => Some(timing.key().to_string()),
+        Message::OneKeyPressed(at) => Some(at.saturating_duration_since(started).as_millis().to_string()),
+        Message::OneKeyReleased(at) => Some(at.saturating_duration_since(started).as_millis().to_string()),
+        Message::IdleTimeoutSelected(timeout) => Some(timeout.key().to_string()),
+        Message::StartupSelected(startup) => Some(startup.key().to_string()),
+        Message::MinChangeSelected(change) => Some(change.key().to_string()),
+        Message::SurpriseSwapSelected(swap) => Some(swap.key().to_string()),
+        Message::MotionSelected(motion) => Some(motion.key().to_string()),
+        Message::PassAndPlayOpened => None,
+        // Never write the secret a player typed; replays of pass-and-play games can't reproduce it
+        Message::PassSecretChanged(_) => None,
+        Message::PassSecretSubmitted => None,
+        Message::PassReady => None,
+        Message::PassCancelled => None,
+        Message::GaveUp => None,
+        Message::WarmUpGuessed => None,
+        Message::RetryRequested => None,
+        Message::ResultCardRequested => None,
+        Message::ResultCardSaved(path) => path.as_ref().map(|path| escape(&path.display().to_string())),
+        Message::ScorecardPathChanged(text) => Some(escape(text)),
+        Message::ScorecardImported => None,
+        Message::ScorecardExportRequested => None,
+        Message::ScorecardExported(path) => path.as_ref().map(|path| escape(&path.display().to_string())),
+        Message::ReplayExportRequested => None,
+        Message::ReplayExported(path) => path.as_ref().map(|path| escape(&path.display().to_string())),
+        Message::HistoryExportRequested => None,
+        Message::HistoryExported(path) => path.as_ref().map(|path| escape(&path.display().to_string())),
+        Message::ReplaysToggled => None,
+        Message::ReplayPathChanged(text) => Some(escape(text)),
+        Message::ReplayOpened => None,
+        Message::PlaybackStepped(forward) => Some(forward.to_string()),
+        Message::PlaybackToggled => None,
+        Message::PlaybackAdvanced => None,
+        Message::PlaybackClosed => None,
+        Message::GalleryOpened => None,
+        Message::GalleryAdvanced => None,
+        Message::GalleryClosed => None,
+        Message::BotModeToggled(active) => flag(active),
+        Message::BotAnswered(answer) => {
+            let answer = match answer {
+                Answer::TooSmall => "too_small",
+                Answer::TooBig => "too_big",
+                Answer::Correct => "correct",
+            };
+            Some(answer.to_string())
+        }
+        Message::BotStrategySelected(strategy) => Some(strategy.key().to_string()),
+        Message::NewBotGame => None,
+        Message::ConstraintsChanged(text) => Some(escape(text)),
+        Message::ConstraintsSubmitted => None,
+        Message::SameAgain => None,
+        Message::TournamentStarted(length) => Some(length.to_string()),
+        Message::TournamentAborted => None,
+        Message::TournamentExitConfirmed(leave) => flag(leave),
+        Message::EndlessStarted => None,
+        Message::CourseStarted => None,
+        Message::CourseAborted => None,
+        Message::EndlessStopped => None,
+        Message::WhatsNewOpened => None,
+        Message::WhatsNewDismissed => None,
+        Message::RecoveryAutosave => None,
+        Message::SpectatorRefresh => None,
+        Message::SpectatorFeedToggled(enabled) => flag(enabled),
+        Message::RecoveryRestored => None,
+        Message::RecoveryDeclined => None,
+        Message::CloseConfirmed(choice) => Some(choice.key().to_string()),
+        Message::CloseRequested => None,
+        Message::FocusMoved(forward) => flag(forward),
+        Message::SettingKey(key) => Some(key.key().to_string()),
+        Message::DebugOverlayToggled => None,
+        Message::Tick(now) => Some(now.saturating_duration_since(started).as_millis().to_string()),
+    }
+}
+
+// Read a message line written by `Recorder::record`, with times counted from `start`
+fn decode(line: &str, start: Instant) -> Option<(Duration, Message)> {
+    let (elapsed, rest) = line.split_once(' ')?;
+    let elapsed = Duration::from_millis(elapsed.parse().ok()?);
+    // Only split once more: free text after the name is kept exactly, spaces and all
+    let (name, argument) = rest.split_once(' ').unwrap_or((rest, ""));
+    let flag = || argument.parse::<bool>().ok();
+
+    let message = match name {
+        "GuessInputChanged" => Message::GuessInputChanged(unescape(argument)),
+        "GuessStepped" => Message::GuessStepped(argument.parse().ok()?),
+        "NumberPicked" => Message::NumberPicked(argument.parse().ok()?),
+        "GuessButtonPressed" => Message::GuessButtonPressed,
+        "HintRequested" => Message::HintRequested,
+        "PeekRequested" => Message::PeekRequested,
+        "NewGame" => Message::NewGame,
+        "ReviewToggled" => Message::ReviewToggled,
+        "GradeExplained" => Message::GradeExplained,
+        "ReviewScrolled" => Message::ReviewScrolled(argument.parse().ok()?),
+        "ShowCandidatesToggled" => Message::ShowCandidatesToggled(flag()?),
+        "ShowBasesToggled" => Message::ShowBasesToggled(flag()?),
+        "ThemeSelected" => Message::ThemeSelected(ThemeChoice::from_key(argument)?),
+        "SystemThemePoll" => Message::SystemThemePoll,
+        "SystemThemeDetected" => Message::SystemThemeDetected(match argument {
+            "light" => Some(SystemTheme::Light),
+            "dark" => Some(SystemTheme::Dark),
+            _ => None,
+        }),
+        "SystemAccentDetected" => Message::SystemAccentDetected(Rgb::from_hex(argument)),
+        "SettingsToggled" => Message::SettingsToggled,
+        "AchievementsToggled" => Message::AchievementsToggled,
+        "ProfileModeSelected" => Message::ProfileModeSelected(profiles::Mode::from_key(argument)?),
+        "ProfileChosen" => Message::ProfileChosen(Choice((!argument.is_empty()).then(|| unescape(argument)))),
+        "ProfileSaved" => Message::ProfileSaved,
+        "TrendWindowSelected" => Message::TrendWindowSelected(trend::Window::from_key(argument)?),
+        "LeaderboardToggled" => Message::LeaderboardToggled,
+        "LeaderboardMetricSelected" => Message::LeaderboardMetricSelected(Metric::from_key(argument)?),
+        "DailyChallengeStarted" => Message::DailyChallengeStarted(Date::from_key(argument)?),
+        "CalendarToggled" => Message::CalendarToggled,
+        "CalendarMonthMoved" => Message::CalendarMonthMoved(flag()?),
+        "AccentSelected" => Message::AccentSelected(Rgb::from_hex(argument)?),
+        "AccentHexChanged" => Message::AccentHexChanged(unescape(argument)),
+        "AccentHexSubmitted" => Message::AccentHexSubmitted,
+        "NotifyOnWinToggled" => Message::NotifyOnWinToggled(flag()?),
+        "SpeakFeedbackToggled" => Message::SpeakFeedbackToggled(flag()?),
+        "SpeechRateSelected" => Message::SpeechRateSelected(SpeechRate::from_key(argument)?),
+        // Only the ID decides anything, so the name shown in the picker isn't recorded
+        "SpeechVoiceSelected" => Message::SpeechVoiceSelected(VoiceChoice { id: unescape(argument), name: String::new() }),
+        "WinNotificationAnswered" => Message::WinNotificationAnswered(flag()?),
+        "FeedbackSpoken" => Message::FeedbackSpoken(if argument.is_empty() { Ok(()) } else { Err(unescape(argument)) }),
+        "MysteryModeToggled" => Message::MysteryModeToggled(flag()?),
+        "TimedGuessesToggled" => Message::TimedGuessesToggled(flag()?),
+        "GhostRaceToggled" => Message::GhostRaceToggled(flag()?),
+        "GuessBudgetToggled" => Message::GuessBudgetToggled(flag()?),
+        "MercyHintsToggled" => Message::MercyHintsToggled(flag()?),
+        "AssistHintsToggled" => Message::AssistHintsToggled(flag()?),
+        "GuessAssistToggled" => Message::GuessAssistToggled(flag()?),
+        "CloseEnoughToggled" => Message::CloseEnoughToggled(flag()?),
+        "ProximityTonesToggled" => Message::ProximityTonesToggled(flag()?),
+        "ChallengeCodeCopied" => Message::ChallengeCodeCopied,
+        "ChallengeInputChanged" => Message::ChallengeInputChanged(unescape(argument)),
+        "ChallengeSubmitted" => Message::ChallengeSubmitted,
+        "PracticeInputChanged" => Message::PracticeInputChanged(unescape(argument)),
+        "PracticeSubmitted" => Message::PracticeSubmitted,
+        "AnalyticsToggled" => Message::AnalyticsToggled(flag()?),
+        "AnalyticsDeleted" => Message::AnalyticsDeleted,
+        "RecordsResetRequested" => Message::RecordsResetRequested,
+        "RecordsResetConfirmed" => Message::RecordsResetConfirmed(flag()?),
+        "InsightsToggled" => Message::InsightsToggled,
+        "WindowFocusChanged" => Message::WindowFocusChanged(flag()?),
+        "FontSelected" => Message::FontSelected(FontChoice::from_key(argument)?),
+        "NumberFormatSelected" => Message::NumberFormatSelected(LocaleChoice::from_key(argument)?),
+        "MonospaceDigitsToggled" => Message::MonospaceDigitsToggled(flag()?),
+        "ColorblindToggled" => Message::ColorblindToggled(flag()?),
+        "AlwaysOnTopToggled" => Message::AlwaysOnTopToggled(flag()?),
+        "AlwaysOnTopShortcut" => Message::AlwaysOnTopShortcut,
+        "CompactToggled" => Message::CompactToggled(flag()?),
+        "CompactShortcut" => Message::CompactShortcut,
+        "LeftHandedToggled" => Message::LeftHandedToggled(flag()?),
+        "OneKeyToggled" => Message::OneKeyToggled(flag()?),
+        "OneKeyTimingSelected" => Message::OneKeyTimingSelected(onekey::Timing::from_key(argument)?),
+        "OneKeyPressed" => Message::OneKeyPressed(start + Duration::from_millis(argument.parse().ok()?)),
+        "OneKeyReleased" => Message::OneKeyReleased(start + Duration::from_millis(argument.parse().ok()?)),
+        "IdleTimeoutSelected" => Message::IdleTimeoutSelected(IdleTimeout::from_key(argument)?),
+        "StartupSelected" => Message::StartupSelected(Startup::from_key(argument)?),
+        "MinChangeSelected" => Message::MinChangeSelected(MinChange::from_key(argument)?),
+        "SurpriseSwapSelected" => Message::SurpriseSwapSelected(SurpriseSwap::from_key(argument)?),
+        "MotionSelected" => Message::MotionSelected(MotionChoice::from_key(argument)?),
+        "PassAndPlayOpened" => Message::PassAndPlayOpened,
+        "PassSecretChanged" => Message::PassSecretChanged(String::new()),
+        "PassSecretSubmitted" => Message::PassSecretSubmitted,
+        "PassReady" => Message::PassReady,
+        "PassCancelled" => Message::PassCancelled,
+        "GaveUp" => Message::GaveUp,
+        "WarmUpGuessed" => Message::WarmUpGuessed,
+        "RetryRequested" => Message::RetryRequested,
+        "BotModeToggled" => Message::BotModeToggled(flag()?),
+        "BotAnswered" => Message::BotAnswered(match argument {
+            "too_small" => Answer::TooSmall,
+            "too_big" => Answer::TooBig,
+            "correct" => Answer::Correct,
+            _ => return None,
+        }),
+        "BotStrategySelected" => Message::BotStrategySelected(BotStrategy::from_key(argument)?),
+        "NewBotGame" => Message::NewBotGame,
+        "ConstraintsChanged" => Message::ConstraintsChanged(unescape(argument)),
+        "ConstraintsSubmitted" => Message::ConstraintsSubmitted,
+        "SameAgain" => Message::SameAgain,
+        "TournamentStarted" => Message::TournamentStarted(argument.parse().ok()?),
+        "TournamentAborted" => Message::TournamentAborted,
+        "TournamentExitConfirmed" => Message::TournamentExitConfirmed(flag()?),
+        "EndlessStarted" => Message::EndlessStarted,
+        "CourseStarted" => Message::CourseStarted,
+        "CourseAborted" => Message::CourseAborted,
+        "EndlessStopped" => Message::EndlessStopped,
+        "WhatsNewOpened" => Message::WhatsNewOpened,
+        "WhatsNewDismissed" => Message::WhatsNewDismissed,
+        "RecoveryAutosave" => Message::RecoveryAutosave,
+        "SpectatorRefresh" => Message::SpectatorRefresh,
+        "SpectatorFeedToggled" => Message::SpectatorFeedToggled(flag()?),
+        "RecoveryRestored" => Message::RecoveryRestored,
+        "RecoveryDeclined" => Message::RecoveryDeclined,
+        "CloseConfirmed" => Message::CloseConfirmed(QuitChoice::from_key(argument)?),
+        "CloseRequested" => Message::CloseRequested,
+        "FocusMoved" => Message::FocusMoved(flag()?),
+        "SettingKey" => Message::SettingKey(SettingKey::from_key(argument)?),
+        "DebugOverlayToggled" => Message::DebugOverlayToggled,
+        "ResultCardRequested" => Message::ResultCardRequested,
+        "ResultCardSaved" => Message::ResultCardSaved((!argument.is_empty()).then(|| PathBuf::from(unescape(argument)))),
+        "ScorecardPathChanged" => Message::ScorecardPathChanged(unescape(argument)),
+        "ScorecardImported" => Message::ScorecardImported,
+        "ScorecardExportRequested" => Message::ScorecardExportRequested,
+        "ScorecardExported" => Message::ScorecardExported((!argument.is_empty()).then(|| PathBuf::from(unescape(argument)))),
+        "ReplayExportRequested" => Message::ReplayExportRequested,
+        "ReplayExported" => Message::ReplayExported((!argument.is_empty()).then(|| PathBuf::from(unescape(argument)))),
+        "HistoryExportRequested" => Message::HistoryExportRequested,
+        "HistoryExported" => Message::HistoryExported((!argument.is_empty()).then(|| PathBuf::from(unescape(argument)))),
+        "ReplaysToggled" => Message::ReplaysToggled,
+        "ReplayPathChanged" => Message::ReplayPathChanged(unescape(argument)),
+        "ReplayOpened" => Message::ReplayOpened,
+        "PlaybackStepped" => Message::PlaybackStepped(argument.parse().ok()?),
+        "PlaybackToggled" => Message::PlaybackToggled,
+        "PlaybackAdvanced" => Message::PlaybackAdvanced,
+        "PlaybackClosed" => Message::PlaybackClosed,
+        "GalleryOpened" => Message::GalleryOpened,
+        "GalleryAdvanced" => Message::GalleryAdvanced,
+        "GalleryClosed" => Message::GalleryClosed,
+        "Tick" => Message::Tick(start + Duration::from_millis(argument.parse().ok()?)),
+        _ => return None,
+    };
+
+    Some((elapsed, message))
+}
+
+// Keep free text on one line, reversibly
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\n', "\\n").replace('\r', "\\r")
+}
+
+// Undo `escape`
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Record `message` the way `Recorder::record` does and read it back
+    fn round_trip(message: &Message, start: Instant) -> Message {
+        let line = match argument(message, start) {
+            Some(argument) => format!("250 {} {}", name(message), argument),
+            None => format!("250 {}", name(message)),
+        };
+        let (elapsed, decoded) = decode(&line, start).expect("recorded line should decode");
+        assert_eq!(elapsed, Duration::from_millis(250));
+        decoded
+    }
+
+    #[test]
+    fn names_match_what_is_decoded() {
+        let start = Instant::now();
+        let messages = [
+            Message::GuessButtonPressed,
+            Message::GuessInputChanged("4 2".to_string()),
+            Message::GuessStepped(-10),
+            Message::ThemeSelected(ThemeChoice::Dark),
+            Message::TournamentExitConfirmed(true),
+            Message::BotAnswered(Answer::TooBig),
+            Message::Tick(start + Duration::from_millis(1_500)),
+        ];
+        for message in &messages {
+            assert_eq!(name(&round_trip(message, start)), name(message));
+        }
+    }
+
+    #[test]
+    fn free_text_is_kept_verbatim() {
+        let start = Instant::now();
+        let text = "  50 + 13\\n\nsecond line\r ";
+        match round_trip(&Message::GuessInputChanged(text.to_string()), start) {
+            Message::GuessInputChanged(decoded) => assert_eq!(decoded, text),
+            other => panic!("decoded as {:?}", other),
+        }
+    }
+
+    #[test]
+    fn times_are_kept_to_the_millisecond() {
+        let start = Instant::now();
+        match round_trip(&Message::Tick(start + Duration::from_millis(1_234)), start) {
+            Message::Tick(at) => assert_eq!(at - start, Duration::from_millis(1_234)),
+            other => panic!("decoded as {:?}", other),
+        }
+    }
+
+    #[test]
+    fn typed_pass_secrets_are_never_written() {
+        assert_eq!(argument(&Message::PassSecretChanged("42".to_string()), Instant::now()), None);
+    }
+
+    #[test]
+    fn unknown_names_are_rejected() {
+        assert!(decode("10 NoSuchMessage", Instant::now()).is_none());
+        assert!(decode("soon GuessButtonPressed", Instant::now()).is_none());
+    }
+
+    #[test]
+    fn every_trend_window_is_replayed() {
+        let start = Instant::now();
+        for window in trend::Window::ALL {
+            match round_trip(&Message::TrendWindowSelected(window), start) {
+                Message::TrendWindowSelected(decoded) => assert_eq!(decoded, window),
+                other => panic!("decoded as {:?}", other),
+            }
+        }
+    }
+}
@@ -0,0 +1,152 @@
+// Rules deciding whether a guess wins, and which way to point the player when it doesn't
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::mystery::MysteryOperation;
+
+// How far off a guess may be and still win in the close-enough mode
+pub const CLOSE_ENOUGH: u32 = 2;
+
+// How far each guess must move from the one before it, for a more strategic game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MinChange {
+    #[default]
+    Off, // Any guess may follow any other
+    By2,
+    By5,
+    By10,
+    By25,
+}
+
+impl MinChange {
+    // Every choice, in the order shown in the picker
+    pub const ALL: [MinChange; 5] = [MinChange::Off, MinChange::By2, MinChange::By5, MinChange::By10, MinChange::By25];
+
+    // The smallest change allowed between guesses, or `None` when the rule is off
+    pub fn delta(self) -> Option<u32> {
+        match self {
+            MinChange::Off => None,
+            MinChange::By2 => Some(2),
+            MinChange::By5 => Some(5),
+            MinChange::By10 => Some(10),
+            MinChange::By25 => Some(25),
+        }
+    }
+
+    // Whether the rule can be played over `min..=max`
+    //
+    // From the middle of the range the furthest guess is only half the range away, so a
+    // larger change could leave the player no guess inside the range to make next.
+    pub fn fits(self, min: u32, max: u32) -> bool {
+        self.delta().is_none_or(|delta| delta <= (max - min) / 2)
+    }
+
+    // The choices that can be played over `min..=max`
+    pub fn choices(min: u32, max: u32) -> Vec<MinChange> {
+        MinChange::ALL.into_iter().filter(|choice| choice.fits(min, max)).collect()
+    }
+
+    // The value written to the preferences file
+    pub fn key(self) -> &'static str {
+        match self {
+            MinChange::Off => "off",
+            MinChange::By2 => "2",
+            MinChange::By5 => "5",
+            MinChange::By10 => "10",
+            MinChange::By25 => "25",
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<MinChange> {
+        MinChange::ALL.into_iter().find(|choice| choice.key() == key)
+    }
+}
+
+impl fmt::Display for MinChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.delta() {
+            Some(delta) => write!(f, "At least {}", delta),
+            None => write!(f, "Any amount"),
+        }
+    }
+}
+
+// What a guess earned under a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    TooSmall, // The player is told to guess bigger
+    TooBig,   // The player is told to guess smaller
+    Win,
+}
+
+impl Outcome {
+    // The outcome of comparing an exact guess, where `ordering` is the guess against the secret
+    pub fn from_ordering(ordering: Ordering) -> Outcome {
+        match ordering {
+            Ordering::Less => Outcome::TooSmall,
+            Ordering::Greater => Outcome::TooBig,
+            Ordering::Equal => Outcome::Win,
+        }
+    }
+}
+
+// A way of judging guesses against the secret
+//
+// Implementations must give the same direction for every losing guess on the same side of
+// the secret, so the feedback never contradicts itself, and must always let the secret
+// itself win. The range is still narrowed on the raw numbers, whatever a rule says.
+pub trait WinRule {
+    fn evaluate(&self, guess: u32, secret: u32) -> Outcome;
+}
+
+// Only the secret itself wins
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Exact;
+
+impl WinRule for Exact {
+    fn evaluate(&self, guess: u32, secret: u32) -> Outcome {
+        Outcome::from_ordering(guess.cmp(&secret))
+    }
+}
+
+// Only the secret wins, with the feedback describing guesses after a hidden operation
+#[derive(Debug, Clone, Copy)]
+pub struct Transformed(pub MysteryOperation);
+
+impl WinRule for Transformed {
+    fn evaluate(&self, guess: u32, secret: u32) -> Outcome {
+        Outcome::from_ordering(self.0.apply(guess).cmp(&self.0.apply(secret)))
+    }
+}
+
+// Any guess within `within` of the secret wins; others are judged by `inner`
+pub struct Tolerance {
+    pub within: u32,
+    pub inner: Box<dyn WinRule>, // Points the player towards the secret after a miss
+}
+
+impl WinRule for Tolerance {
+    fn evaluate(&self, guess: u32, secret: u32) -> Outcome {
+        if guess.abs_diff(secret) <= self.within {
+            Outcome::Win
+        } else {
+            self.inner.evaluate(guess, secret)
+        }
+    }
+}
+
+// The rule for a game with the given mystery operation, and whether close guesses win
+pub fn for_mode(operation: Option<MysteryOperation>, close_enough: bool) -> Box<dyn WinRule> {
+    let rule: Box<dyn WinRule> = match operation {
+        Some(operation) => Box::new(Transformed(operation)),
+        None => Box::new(Exact),
+    };
+
+    if close_enough {
+        Box::new(Tolerance { within: CLOSE_ENOUGH, inner: rule })
+    } else {
+        rule
+    }
+}
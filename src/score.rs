@@ -0,0 +1,77 @@
+// Points for a won game, and what hints cost out of them
+
+// Points a game starts with
+pub const BASE: u32 = 1000;
+
+// Points taken off for every guess, including the winning one
+pub const GUESS_COST: u32 = 40;
+
+// Points each hint costs, in the order hints are given
+pub const HINT_COSTS: [u32; 5] = [30, 50, 80, 100, 200];
+
+// Points a peek at the secret costs, steep since it all but gives the game away
+pub const PEEK_COST: u32 = 500;
+
+// The score for a game won in `guesses` guesses with `spent` points spent on hints and peeks
+//
+// The score never drops below zero, however many guesses or hints it took.
+pub fn score(guesses: u32, spent: u32) -> u32 {
+    BASE.saturating_sub(guesses.saturating_mul(GUESS_COST)).saturating_sub(spent)
+}
+
+// The most a game can still score: what it would be if the next guess wins
+pub fn best_remaining(attempts: u32, spent: u32) -> u32 {
+    score(attempts.saturating_add(1), spent)
+}
+
+// Whether something costing `cost` can still be paid for out of the best remaining score
+pub fn can_afford(attempts: u32, spent: u32, cost: u32) -> bool {
+    best_remaining(attempts, spent) >= cost
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_guess_and_point_spent_comes_off_the_base() {
+        assert_eq!(score(0, 0), BASE);
+        assert_eq!(score(1, 0), 960);
+        assert_eq!(score(3, 100), 780);
+        assert_eq!(score(7, HINT_COSTS[0] + HINT_COSTS[1]), 1000 - 280 - 80);
+    }
+
+    #[test]
+    fn the_score_stops_at_zero() {
+        assert_eq!(score(25, 0), 0);
+        assert_eq!(score(26, 0), 0);
+        assert_eq!(score(24, 40), 0);
+        assert_eq!(score(24, 41), 0);
+        assert_eq!(score(1, PEEK_COST + HINT_COSTS.iter().sum::<u32>()), 0);
+        assert_eq!(score(u32::MAX, u32::MAX), 0);
+    }
+
+    #[test]
+    fn the_best_remaining_score_assumes_the_next_guess_wins() {
+        assert_eq!(best_remaining(0, 0), 960);
+        assert_eq!(best_remaining(2, 30), 850);
+        assert_eq!(best_remaining(24, 0), 0);
+        assert_eq!(best_remaining(u32::MAX, 0), 0);
+    }
+
+    #[test]
+    fn a_cost_is_affordable_up_to_exactly_what_remains() {
+        // After 11 guesses and a peek, the best left is 1000 - 12 × 40 - 500 = 20
+        assert!(can_afford(11, PEEK_COST, 20));
+        assert!(!can_afford(11, PEEK_COST, 21));
+        assert!(!can_afford(11, PEEK_COST, HINT_COSTS[0]));
+        assert!(can_afford(24, 0, 0), "something free is always affordable");
+        assert!(!can_afford(24, 0, 1));
+    }
+
+    #[test]
+    fn hints_get_dearer_and_every_one_is_cheaper_than_a_peek() {
+        assert!(HINT_COSTS.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(HINT_COSTS.iter().all(|&cost| cost < PEEK_COST));
+    }
+}
@@ -0,0 +1,127 @@
+// Scorecards: a finished game's challenge and result, exported for a friend to play and beat
+//
+// A scorecard is a small `key = value` text file, so it survives email and chat apps and a
+// curious player can read it. The challenge travels as its usual code, checksum and all.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::challenge::{Challenge, ChallengeError};
+use crate::storage;
+
+// The version of the format written; files from older versions are still read
+pub const FORMAT_VERSION: u32 = 1;
+
+// A finished game, as written to the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scorecard {
+    pub challenge: Challenge, // The game that was played
+    pub guesses: u32,         // Guesses taken to win
+    pub score: u32,           // Points left at the end
+    pub seconds: u64,         // Time taken, excluding pauses
+}
+
+// Why a scorecard file couldn't be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScorecardError {
+    Unreadable,                // The file couldn't be opened or isn't text
+    Malformed,                 // A field is missing or isn't a number
+    NewerVersion(u32),         // Written by a newer version of the game, in the format given
+    Challenge(ChallengeError), // The challenge code inside is broken, or for another range
+}
+
+impl fmt::Display for ScorecardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScorecardError::Unreadable => write!(f, "That file couldn't be opened"),
+            ScorecardError::Malformed => write!(f, "That file isn't a scorecard, or parts of it are missing"),
+            ScorecardError::NewerVersion(version) => {
+                write!(f, "That scorecard is in format {}, from a newer version of the game - update to play it", version)
+            }
+            ScorecardError::Challenge(error) => write!(f, "That scorecard's challenge can't be played: {}", error),
+        }
+    }
+}
+
+// How a game compared with an opponent's scorecard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Won,
+    Lost,
+    Tied,
+}
+
+impl Scorecard {
+    // Render the scorecard as the file's contents
+    pub fn to_text(self) -> String {
+        let header = "# Guessing Game scorecard - load it from the challenge panel to play the same game\n";
+        let values = storage::write_key_values([
+            ("format", FORMAT_VERSION.to_string()),
+            ("challenge", self.challenge.encode()),
+            ("guesses", self.guesses.to_string()),
+            ("score", self.score.to_string()),
+            ("seconds", self.seconds.to_string()),
+        ]);
+        format!("{}{}", header, values)
+    }
+
+    // Read a file written by `to_text`, checking its challenge is one this version plays
+    pub fn from_text(contents: &str, min: u32, max: u32) -> Result<Scorecard, ScorecardError> {
+        let values = storage::parse_key_values(contents);
+        let number = |key: &str| values.get(key).and_then(|value| value.parse().ok()).ok_or(ScorecardError::Malformed);
+
+        // Check the version first, since a newer format may have moved any other field
+        let version: u32 = number("format")?;
+        if version > FORMAT_VERSION {
+            return Err(ScorecardError::NewerVersion(version));
+        }
+
+        let code = values.get("challenge").ok_or(ScorecardError::Malformed)?;
+        let challenge = Challenge::decode(code).map_err(ScorecardError::Challenge)?;
+        if (challenge.min, challenge.max) != (min, max) {
+            return Err(ScorecardError::Challenge(ChallengeError::UnsupportedRange));
+        }
+
+        let seconds = values.get("seconds").and_then(|value| value.parse().ok()).ok_or(ScorecardError::Malformed)?;
+        Ok(Scorecard { challenge, guesses: number("guesses")?, score: number("score")?, seconds })
+    }
+
+    // Read a scorecard from `path`
+    pub fn load(path: &Path, min: u32, max: u32) -> Result<Scorecard, ScorecardError> {
+        let contents = fs::read_to_string(path).map_err(|error| {
+            tracing::warn!(path = %path.display(), %error, "Could not read the scorecard");
+            ScorecardError::Unreadable
+        })?;
+        Scorecard::from_text(&contents, min, max)
+    }
+
+    // How this result compares with `opponent`'s: the higher score wins, then fewer guesses, then the faster time
+    pub fn compare(&self, opponent: &Scorecard) -> Verdict {
+        let ordering = self
+            .score
+            .cmp(&opponent.score)
+            .then(opponent.guesses.cmp(&self.guesses))
+            .then(opponent.seconds.cmp(&self.seconds));
+        match ordering {
+            Ordering::Greater => Verdict::Won,
+            Ordering::Less => Verdict::Lost,
+            Ordering::Equal => Verdict::Tied,
+        }
+    }
+}
+
+// Write the scorecard to the player's home folder, returning the path
+pub fn save(scorecard: Scorecard) -> Option<PathBuf> {
+    let home = storage::home_dir().unwrap_or_else(|| PathBuf::from(".")); // Next to wherever the game was started from
+    let path = home.join(format!("guessing-game-{}.scorecard", scorecard.challenge.encode()));
+
+    match fs::write(&path, scorecard.to_text()) {
+        Ok(()) => Some(path),
+        Err(error) => {
+            tracing::warn!(path = %path.display(), %error, "Could not save the scorecard");
+            None
+        }
+    }
+}
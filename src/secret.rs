@@ -0,0 +1,129 @@
+// The secret number, kept masked so it never sits in memory as a plain value
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::rules::{Outcome, WinRule};
+
+// A secret stored XORed with a random mask chosen fresh for every game
+//
+// This won't stop a determined cheater, but it keeps the number from turning up in a
+// memory scanner searching for a value between 1 and 100, or in a file saved mid-game.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Secret {
+    masked: u32, // The secret XORed with `mask`
+    mask: u32,   // Random bits, drawn when the secret was created
+}
+
+impl Secret {
+    // Hide a freshly drawn secret behind a new random mask
+    pub fn new(value: u32) -> Secret {
+        let mask = rand::random::<u32>();
+        Secret { masked: value ^ mask, mask }
+    }
+
+    // Rebuild a secret from the parts written by `parts`, keeping its mask
+    pub fn from_parts(masked: u32, mask: u32) -> Secret {
+        Secret { masked, mask }
+    }
+
+    // The masked value and the mask, for saving the secret without decoding it
+    pub fn parts(&self) -> (u32, u32) {
+        (self.masked, self.mask)
+    }
+
+    // Compare a guess with the secret; this and `judge` are the only places it is decoded during play
+    pub fn compare(&self, guess: u32) -> Ordering {
+        guess.cmp(&(self.masked ^ self.mask))
+    }
+
+    // Judge a guess under a win rule, which is handed the decoded secret
+    pub fn judge(&self, guess: u32, rule: &dyn WinRule) -> Outcome {
+        rule.evaluate(guess, self.masked ^ self.mask)
+    }
+
+    // How far a guess is from the secret, without handing back the secret itself
+    pub fn distance(&self, guess: u32) -> u32 {
+        guess.abs_diff(self.masked ^ self.mask)
+    }
+
+    // The plain secret, for use only once the game is over and it's no longer a secret
+    pub fn reveal(&self) -> u32 {
+        self.masked ^ self.mask
+    }
+}
+
+// Never print the decoded value, so logging a game can't give the answer away
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(hidden)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{Exact, Tolerance};
+
+    // Masks with no bits, every bit, and a mix, so the mask can't happen to line up with the answer
+    const MASKS: [u32; 4] = [0, u32::MAX, 0xA5A5_A5A5, 0x0000_0040];
+
+    // The secret `value` behind `mask`
+    fn masked(value: u32, mask: u32) -> Secret {
+        Secret::from_parts(value ^ mask, mask)
+    }
+
+    #[test]
+    fn a_masked_secret_compares_like_the_plain_number() {
+        for mask in MASKS {
+            for value in [0, 1, 37, 100, u32::MAX] {
+                let secret = masked(value, mask);
+                assert_eq!(secret.reveal(), value);
+                for guess in [0, 1, 36, 37, 38, 100, u32::MAX] {
+                    assert_eq!(secret.compare(guess), guess.cmp(&value));
+                    assert_eq!(secret.distance(guess), guess.abs_diff(value));
+                    assert_eq!(secret.judge(guess, &Exact), Exact.evaluate(guess, value));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn a_masked_secret_is_handed_to_the_rule_decoded() {
+        let rule = Tolerance { within: 2, inner: Box::new(Exact) };
+        for mask in MASKS {
+            let secret = masked(50, mask);
+            assert_eq!(secret.judge(48, &rule), Outcome::Win);
+            assert_eq!(secret.judge(47, &rule), Outcome::TooSmall);
+            assert_eq!(secret.judge(53, &rule), Outcome::TooBig);
+        }
+    }
+
+    #[test]
+    fn a_new_secret_has_the_value_it_was_given() {
+        assert_eq!(Secret::new(42).reveal(), 42);
+    }
+
+    #[test]
+    fn debug_never_prints_the_value() {
+        for mask in MASKS {
+            let secret = masked(1234, mask);
+            let (hidden, mask) = secret.parts();
+            let printed = format!("{:?} {:#?}", secret, Some(secret));
+            assert!(!printed.contains("1234"), "{}", printed);
+            assert!(!printed.contains(&hidden.to_string()), "{}", printed);
+            assert!(!printed.contains(&mask.to_string()), "{}", printed);
+        }
+    }
+
+    #[test]
+    fn parts_rebuild_the_same_secret() {
+        for value in [0, 50, u32::MAX] {
+            let secret = Secret::new(value);
+            let (hidden, mask) = secret.parts();
+            let rebuilt = Secret::from_parts(hidden, mask);
+            assert_eq!(rebuilt, secret);
+            assert_eq!(rebuilt.reveal(), value);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+// Estimating how the player is getting on, so assist mode can tune the hints to match
+
+// Wins remembered for the estimate; older ones make way
+pub const RECENT_GAMES: usize = 5;
+
+// Wins needed before the estimate moves off steady, so one fluke doesn't change the hints
+pub const MIN_GAMES: usize = 3;
+
+// How the player's recent wins compare with the optimal guess count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Skill {
+    Struggling, // Averaging half as many guesses again as the optimal count, or more
+    Steady,     // Somewhere in between, or too few wins to tell
+    Strong,     // Averaging the optimal count or better
+}
+
+impl Skill {
+    // Estimate from the guesses `recent` wins took, each against `optimal` for its range
+    pub fn estimate(recent: &[u32], optimal: u32) -> Skill {
+        if recent.len() < MIN_GAMES || optimal == 0 {
+            return Skill::Steady;
+        }
+
+        // Compare totals in whole numbers, doubled so "half as many again" doesn't need fractions
+        let total = recent.iter().map(|&attempts| u64::from(attempts)).sum::<u64>();
+        let optimal = u64::from(optimal) * recent.len() as u64;
+        if total * 2 >= optimal * 3 {
+            Skill::Struggling
+        } else if total <= optimal {
+            Skill::Strong
+        } else {
+            Skill::Steady
+        }
+    }
+
+    // How generous bought hints should be for a player at this skill
+    pub fn hint_strength(self) -> Strength {
+        match self {
+            Skill::Struggling => Strength::Generous,
+            Skill::Steady => Strength::Normal,
+            Skill::Strong => Strength::Strict,
+        }
+    }
+}
+
+// How much the range-narrowing hints give away
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Strength {
+    Generous, // Tighter ranges, for a player who's struggling
+    #[default]
+    Normal,   // The hints as they've always been
+    Strict,   // Looser ranges, for a player on form
+}
+
+impl Strength {
+    // The pieces the half hint splits the remaining range into, naming the secret's
+    pub fn parts(self) -> u32 {
+        match self {
+            Strength::Generous => 4,
+            Strength::Normal | Strength::Strict => 2, // Anything less than halves tells nothing
+        }
+    }
+
+    // How long a run of numbers the decade hint names
+    pub fn span(self) -> u32 {
+        match self {
+            Strength::Generous => 5,
+            Strength::Normal => 10,
+            Strength::Strict => 25,
+        }
+    }
+
+    // What assist mode is doing, shown with the hints while it changes anything
+    pub fn note(self) -> Option<&'static str> {
+        match self {
+            Strength::Generous => Some("Assist: hints are more generous while you find your feet"),
+            Strength::Normal => None,
+            Strength::Strict => Some("Assist: hints are stricter while you're on form"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn too_few_wins_are_steady_whatever_they_were() {
+        assert_eq!(Skill::estimate(&[], 7), Skill::Steady);
+        assert_eq!(Skill::estimate(&[1, 1], 7), Skill::Steady);
+        assert_eq!(Skill::estimate(&[30, 30], 7), Skill::Steady);
+        assert_eq!(Skill::estimate(&[1, 1, 1], 0), Skill::Steady, "a range with no optimal count tells nothing");
+    }
+
+    #[test]
+    fn the_estimate_moves_at_exactly_its_thresholds() {
+        // Three wins at an optimal 7 make 21; half as many again is 31.5
+        assert_eq!(Skill::estimate(&[7, 7, 7], 7), Skill::Strong);
+        assert_eq!(Skill::estimate(&[7, 7, 8], 7), Skill::Steady);
+        assert_eq!(Skill::estimate(&[10, 10, 11], 7), Skill::Steady);
+        assert_eq!(Skill::estimate(&[10, 11, 11], 7), Skill::Struggling);
+        assert_eq!(Skill::estimate(&[1, 1, 40], 7), Skill::Struggling, "it's the average that counts");
+    }
+
+    #[test]
+    fn huge_counts_do_not_overflow() {
+        assert_eq!(Skill::estimate(&[u32::MAX; RECENT_GAMES], u32::MAX), Skill::Strong);
+        assert_eq!(Skill::estimate(&[u32::MAX; RECENT_GAMES], 1), Skill::Struggling);
+    }
+
+    #[test]
+    fn struggling_players_get_generous_hints_and_strong_ones_strict() {
+        assert_eq!(Skill::Struggling.hint_strength(), Strength::Generous);
+        assert_eq!(Skill::Steady.hint_strength(), Strength::Normal);
+        assert_eq!(Skill::Strong.hint_strength(), Strength::Strict);
+    }
+
+    #[test]
+    fn generous_hints_name_less_and_strict_ones_more() {
+        assert!(Strength::Generous.parts() > Strength::Normal.parts());
+        assert_eq!(Strength::Strict.parts(), Strength::Normal.parts());
+        assert!(Strength::Generous.span() < Strength::Normal.span());
+        assert!(Strength::Strict.span() > Strength::Normal.span());
+        assert_eq!(Strength::Normal.note(), None, "nothing to say when nothing changes");
+        assert!(Strength::Generous.note().is_some() && Strength::Strict.note().is_some());
+    }
+}
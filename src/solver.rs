@@ -0,0 +1,315 @@
+// The computer player, which guesses a number the player is thinking of
+
+use std::fmt;
+
+use crate::analysis;
+use crate::random::{NumberSource, SeededSource};
+
+// A way of choosing the computer's next guess
+//
+// Implementations must always return a value within `low..=high`, the range still
+// consistent with every answer so far. `history` holds the earlier guesses, oldest first.
+pub trait GuesserStrategy {
+    fn next_guess(&mut self, low: u32, high: u32, history: &[u32]) -> u32;
+}
+
+// Always guess the midpoint, halving the range every time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bisection;
+
+impl GuesserStrategy for Bisection {
+    fn next_guess(&mut self, low: u32, high: u32, _history: &[u32]) -> u32 {
+        analysis::optimal_guess(low, high)
+    }
+}
+
+// Guess any value that is still possible
+#[derive(Debug, Clone)]
+pub struct RandomInBounds {
+    numbers: SeededSource, // Seeded so benchmark runs can be repeated
+}
+
+impl RandomInBounds {
+    pub fn new(seed: u64) -> RandomInBounds {
+        RandomInBounds { numbers: SeededSource::new(seed) }
+    }
+}
+
+impl GuesserStrategy for RandomInBounds {
+    fn next_guess(&mut self, low: u32, high: u32, _history: &[u32]) -> u32 {
+        self.numbers.secret(low, high)
+    }
+}
+
+// Count up from the smallest possible value
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Linear;
+
+impl GuesserStrategy for Linear {
+    fn next_guess(&mut self, low: u32, _high: u32, _history: &[u32]) -> u32 {
+        low
+    }
+}
+
+// A registered strategy, as offered in the difficulty picker
+#[derive(Debug, Clone, Copy)]
+pub struct BotStrategy {
+    key: &'static str,         // The value written to the preferences file
+    label: &'static str,       // The difficulty name shown in the picker
+    description: &'static str, // A short description of how the bot is playing
+    build: fn(u64) -> Box<dyn GuesserStrategy>, // Create the strategy from a random seed
+}
+
+impl BotStrategy {
+    // Every strategy, from weakest to strongest; registering a new one only takes an entry here
+    pub const ALL: &'static [BotStrategy] = &[
+        BotStrategy {
+            key: "linear",
+            label: "Easy",
+            description: "counting up one at a time",
+            build: |_| Box::new(Linear),
+        },
+        BotStrategy {
+            key: "random",
+            label: "Medium",
+            description: "guessing at random within the range",
+            build: |seed| Box::new(RandomInBounds::new(seed)),
+        },
+        BotStrategy {
+            key: "binary",
+            label: "Hard",
+            description: "binary search, halving the range every guess",
+            build: |_| Box::new(Bisection),
+        },
+    ];
+
+    // A short description of how the bot is playing
+    pub fn description(self) -> &'static str {
+        self.description
+    }
+
+    // Create a fresh instance of the strategy
+    pub fn build(self, seed: u64) -> Box<dyn GuesserStrategy> {
+        (self.build)(seed)
+    }
+
+    // The value written to the preferences file
+    pub fn key(self) -> &'static str {
+        self.key
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<BotStrategy> {
+        BotStrategy::ALL.iter().copied().find(|strategy| strategy.key == key)
+    }
+}
+
+// Medium, so beginners can win without the bot being hopeless
+impl Default for BotStrategy {
+    fn default() -> BotStrategy {
+        BotStrategy::ALL[1]
+    }
+}
+
+// Strategies are told apart by their key, which is unique within the registry
+impl PartialEq for BotStrategy {
+    fn eq(&self, other: &BotStrategy) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for BotStrategy {}
+
+impl fmt::Display for BotStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+// The player's answer to one of the bot's guesses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    TooSmall,
+    TooBig,
+    Correct,
+}
+
+// A round of the computer-guesses mode
+pub struct BotGame {
+    pub strategy: BotStrategy, // Which strategy this round's guesses come from
+    guesser: Box<dyn GuesserStrategy>, // The strategy's state for this round
+    pub low: u32,              // Smallest value consistent with the player's answers
+    pub high: u32,             // Largest value consistent with the player's answers
+    pub guess: u32,            // The bot's current guess, awaiting an answer
+    pub history: Vec<u32>,     // Guesses made so far, including the current one
+    pub solved: bool,          // Whether the player confirmed a guess as correct
+}
+
+impl BotGame {
+    // Start a round over `min..=max` with the bot's first guess already made
+    pub fn new(strategy: BotStrategy, min: u32, max: u32, seed: u64) -> BotGame {
+        let mut guesser = strategy.build(seed);
+        let guess = guesser.next_guess(min, max, &[]);
+
+        BotGame {
+            strategy,
+            guesser,
+            low: min,
+            high: max,
+            guess,
+            history: vec![guess],
+            solved: false,
+        }
+    }
+
+    // Guesses made so far, including the current one
+    pub fn attempts(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    // Apply the player's answer and make the next guess
+    //
+    // Returns `false`, leaving the round untouched, if the answer contradicts earlier ones
+    // so that no value is left for the bot to guess.
+    pub fn answer(&mut self, answer: Answer) -> bool {
+        let narrowed = match answer {
+            Answer::Correct => {
+                self.solved = true;
+                return true;
+            }
+            Answer::TooSmall => self.guess.checked_add(1).map(|low| (low, self.high)),
+            Answer::TooBig => self.guess.checked_sub(1).map(|high| (self.low, high)),
+        };
+
+        // An empty range means the answers can't all be true
+        let (low, high) = match narrowed {
+            Some((low, high)) if low <= high => (low, high),
+            _ => return false,
+        };
+
+        self.low = low;
+        self.high = high;
+        self.guess = self.guesser.next_guess(low, high, &self.history);
+        self.history.push(self.guess);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Answer `bot` truthfully until it finds `secret`, checking every guess stays in bounds
+    fn solve(bot: &mut BotGame, secret: u32) -> u32 {
+        while bot.guess != secret {
+            assert!((bot.low..=bot.high).contains(&bot.guess), "{} outside {}..={}", bot.guess, bot.low, bot.high);
+            let answer = if bot.guess < secret { Answer::TooSmall } else { Answer::TooBig };
+            assert!(bot.answer(answer));
+        }
+        assert!(bot.answer(Answer::Correct));
+        assert!(bot.solved);
+        bot.attempts()
+    }
+
+    #[test]
+    fn every_strategy_finds_every_secret() {
+        for &strategy in BotStrategy::ALL {
+            for secret in 1..=100 {
+                let mut bot = BotGame::new(strategy, 1, 100, u64::from(secret));
+                let attempts = solve(&mut bot, secret);
+                assert!(attempts <= 100, "{} took {} guesses for {}", strategy, attempts, secret);
+            }
+        }
+    }
+
+    #[test]
+    fn bisection_never_needs_more_than_the_optimal_guesses() {
+        let strategy = BotStrategy::from_key("binary").unwrap();
+        for (min, max) in [(1, 100), (1, 1000), (5, 5), (0, 1)] {
+            let optimal = analysis::optimal_guesses(min, max);
+            for secret in min..=max {
+                let mut bot = BotGame::new(strategy, min, max, 0);
+                assert!(solve(&mut bot, secret) <= optimal, "{} in {}..={}", secret, min, max);
+            }
+        }
+    }
+
+    #[test]
+    fn linear_counts_up_from_the_bottom() {
+        let mut bot = BotGame::new(BotStrategy::from_key("linear").unwrap(), 1, 100, 0);
+        assert_eq!(solve(&mut bot, 37), 37);
+        assert_eq!(bot.history, (1..=37).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn random_guesses_repeat_for_the_same_seed() {
+        let play = |seed| {
+            let mut bot = BotGame::new(BotStrategy::from_key("random").unwrap(), 1, 1000, seed);
+            solve(&mut bot, 777);
+            bot.history
+        };
+        assert_eq!(play(3), play(3));
+
+        let mut guesser = RandomInBounds::new(9);
+        for _ in 0..1000 {
+            assert!((40..=60).contains(&guesser.next_guess(40, 60, &[])));
+        }
+        assert_eq!(guesser.next_guess(8, 8, &[]), 8);
+    }
+
+    #[test]
+    fn an_answer_narrows_the_range_past_the_guess() {
+        let mut bot = BotGame::new(BotStrategy::from_key("binary").unwrap(), 1, 100, 0);
+        assert_eq!(bot.guess, analysis::optimal_guess(1, 100));
+        let first = bot.guess;
+
+        assert!(bot.answer(Answer::TooSmall));
+        assert_eq!((bot.low, bot.high), (first + 1, 100));
+        let second = bot.guess;
+        assert!(bot.answer(Answer::TooBig));
+        assert_eq!((bot.low, bot.high), (first + 1, second - 1));
+        assert_eq!(bot.history, vec![first, second, bot.guess]);
+        assert_eq!(bot.attempts(), 3);
+    }
+
+    #[test]
+    fn contradictory_answers_leave_the_round_untouched() {
+        let mut bot = BotGame::new(BotStrategy::from_key("linear").unwrap(), 1, 3, 0);
+        assert!(bot.answer(Answer::TooSmall));
+        assert!(bot.answer(Answer::TooSmall));
+        assert_eq!((bot.guess, bot.low, bot.high), (3, 3, 3));
+
+        // Nothing is left above 3, or below it now that 1 and 2 are ruled out
+        for answer in [Answer::TooSmall, Answer::TooBig] {
+            assert!(!bot.answer(answer));
+            assert_eq!((bot.guess, bot.low, bot.high, bot.attempts()), (3, 3, 3, 3));
+            assert!(!bot.solved);
+        }
+    }
+
+    #[test]
+    fn answers_at_the_edges_of_u32_do_not_overflow() {
+        let mut bot = BotGame::new(BotStrategy::from_key("linear").unwrap(), 0, 0, 0);
+        assert!(!bot.answer(Answer::TooBig));
+
+        let mut bot = BotGame::new(BotStrategy::from_key("binary").unwrap(), u32::MAX, u32::MAX, 0);
+        assert!(!bot.answer(Answer::TooSmall));
+
+        let mut bot = BotGame::new(BotStrategy::from_key("binary").unwrap(), 0, u32::MAX, 0);
+        assert!(solve(&mut bot, u32::MAX) <= analysis::optimal_guesses(0, u32::MAX));
+    }
+
+    #[test]
+    fn keys_round_trip_and_are_unique() {
+        for &strategy in BotStrategy::ALL {
+            assert_eq!(BotStrategy::from_key(strategy.key()), Some(strategy));
+            assert!(!strategy.description().is_empty());
+        }
+        assert_eq!(BotStrategy::from_key("genius"), None);
+        assert_eq!(BotStrategy::from_key(""), None);
+
+        let labels: Vec<String> = BotStrategy::ALL.iter().map(ToString::to_string).collect();
+        assert_eq!(labels, ["Easy", "Medium", "Hard"]);
+        assert_eq!(BotStrategy::default().key(), "random");
+    }
+}
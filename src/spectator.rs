@@ -0,0 +1,40 @@
+// A spectator feed for streaming: the current feedback and guess count, kept in a text file
+//
+// iced 0.9 drives a single window, so there's no companion window to show viewers. Instead
+// the feed is a small file that streaming software, such as OBS with a text source reading
+// from a file, can put on screen. It's only written while the setting is on.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::storage;
+
+// File the feed is written to, inside the data directory
+const FILE_NAME: &str = "spectator.txt";
+
+// How often the feed is checked for changes while it's on
+pub const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+// Where the feed is written, for pointing the streaming software at it
+pub fn path() -> Option<PathBuf> {
+    storage::data_dir().map(|dir| dir.join(FILE_NAME))
+}
+
+// The feed's contents: the feedback line, then the guess count or whatever stands in for it
+pub fn render(feedback: &str, progress: &str) -> String {
+    format!("{}\n{}\n", feedback, progress)
+}
+
+// Replace the feed, logging rather than failing if it can't be written
+pub fn write(contents: &str) {
+    if let Err(error) = storage::save(FILE_NAME, contents) {
+        tracing::warn!(%error, "Could not write the spectator feed");
+    }
+}
+
+// Delete the feed once it's switched off, so a stale game doesn't stay on stream
+pub fn clear() {
+    if let Err(error) = storage::remove(FILE_NAME) {
+        tracing::warn!(%error, "Could not delete the spectator feed");
+    }
+}
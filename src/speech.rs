@@ -0,0 +1,137 @@
+// Reading the feedback aloud through the platform's speech engine, for players who can't see it
+//
+// The engine is only started once the setting is first wanted, since on some systems that
+// starts a speech service. Each announcement is spoken from an async command, so a slow
+// engine never holds up the window, and a newer announcement cuts off the one before it.
+
+use std::fmt;
+
+use tts::{Tts, Voice};
+
+// How fast the engine speaks, relative to its own normal rate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpeechRate {
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+}
+
+impl SpeechRate {
+    // Every choice, in the order shown in the rate picker
+    pub const ALL: [SpeechRate; 3] = [SpeechRate::Slow, SpeechRate::Normal, SpeechRate::Fast];
+
+    // The value written to the preferences file
+    pub fn key(self) -> &'static str {
+        match self {
+            SpeechRate::Slow => "slow",
+            SpeechRate::Normal => "normal",
+            SpeechRate::Fast => "fast",
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<SpeechRate> {
+        SpeechRate::ALL.into_iter().find(|rate| rate.key() == key)
+    }
+
+    // The engine rate for this choice: halfway from normal to the slowest or fastest it allows
+    fn engine_rate(self, tts: &Tts) -> f32 {
+        let normal = tts.normal_rate();
+        match self {
+            SpeechRate::Slow => normal - (normal - tts.min_rate()) / 2.0,
+            SpeechRate::Normal => normal,
+            SpeechRate::Fast => normal + (tts.max_rate() - normal) / 2.0,
+        }
+    }
+}
+
+impl fmt::Display for SpeechRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SpeechRate::Slow => "Slow",
+            SpeechRate::Normal => "Normal",
+            SpeechRate::Fast => "Fast",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+// A voice the engine offers, as the voice picker lists it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoiceChoice {
+    pub id: String, // What the preferences file keeps; empty for the engine's own default
+    pub name: String,
+}
+
+impl fmt::Display for VoiceChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+// The running engine, and what this platform lets the player change about it
+#[derive(Clone)]
+pub struct Engine {
+    tts: Tts,
+    pub can_set_rate: bool,       // Whether the rate picker is worth showing
+    pub voices: Vec<VoiceChoice>, // The voices to pick from, the default first; just that where voices can't be picked
+}
+
+impl Engine {
+    // Start the platform's engine, or say why there isn't one
+    pub fn start() -> Result<Engine, String> {
+        let tts = Tts::default().map_err(|error| {
+            tracing::warn!(%error, "Could not start text-to-speech");
+            error.to_string()
+        })?;
+
+        let features = tts.supported_features();
+        let mut voices = vec![VoiceChoice { id: String::new(), name: "System voice".to_string() }];
+        if features.voice {
+            match tts.voices() {
+                Ok(found) => voices.extend(found.iter().map(|voice| VoiceChoice { id: voice.id(), name: voice.name() })),
+                Err(error) => tracing::warn!(%error, "Could not list the text-to-speech voices"),
+            }
+        }
+        Ok(Engine { tts, can_set_rate: features.rate, voices })
+    }
+
+    // The choice for the voice with `id`, falling back on the default for one that's gone
+    pub fn voice(&self, id: &str) -> VoiceChoice {
+        self.voices.iter().find(|voice| voice.id == id).unwrap_or(&self.voices[0]).clone()
+    }
+}
+
+// The words to say for the feedback `text` as the screen shows it, without the emoji a voice would read out by name
+pub fn spoken(text: &str) -> String {
+    let kept: String = text
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace() || c.is_ascii_punctuation() || matches!(c, '–' | '—' | '−'))
+        .collect();
+    kept.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Say `text` at `rate` in the voice with `id`, interrupting anything still being said
+pub async fn say(engine: Engine, text: String, rate: SpeechRate, voice: String) -> Result<(), String> {
+    let Engine { mut tts, can_set_rate, .. } = engine;
+    if can_set_rate {
+        let rate = rate.engine_rate(&tts);
+        if let Err(error) = tts.set_rate(rate) {
+            tracing::warn!(%error, rate, "Could not set the speech rate");
+        }
+    }
+    if !voice.is_empty() {
+        let found = tts.voices().ok().and_then(|voices| voices.into_iter().find(|found: &Voice| found.id() == voice));
+        if let Some(found) = found {
+            if let Err(error) = tts.set_voice(&found) {
+                tracing::warn!(%error, voice, "Could not set the speech voice");
+            }
+        }
+    }
+
+    tts.speak(text, true).map(|_| ()).map_err(|error| {
+        tracing::warn!(%error, "Could not speak the feedback");
+        error.to_string()
+    })
+}
@@ -0,0 +1,81 @@
+// Stepping the typed guess up or down, from the +/− buttons and the mouse wheel
+
+use crate::format::{format_number, parse_number, Locale};
+
+// How far one step moves, and how far with Shift held
+pub const STEP: u32 = 1;
+pub const SHIFT_STEP: u32 = 10;
+
+// How far a step moves the guess, Shift making it a bigger one
+pub fn stride(shift: bool) -> u32 {
+    if shift {
+        SHIFT_STEP
+    } else {
+        STEP
+    }
+}
+
+// The guess after moving `input` by `by`, which is negative to step down, kept within `min..=max`
+//
+// A field that doesn't hold a number, including an empty one, lands on the middle of the
+// range instead, the best first guess there is.
+pub fn step(locale: Locale, input: &str, by: i32, min: u32, max: u32) -> String {
+    let value = match parse_number(locale, input) {
+        Ok(value) => (i64::from(value) + i64::from(by)).clamp(i64::from(min), i64::from(max)) as u32,
+        Err(_) => min + (max - min) / 2,
+    };
+    format_number(locale, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shift_makes_a_bigger_step() {
+        assert_eq!(stride(false), 1);
+        assert_eq!(stride(true), 10);
+    }
+
+    #[test]
+    fn a_number_steps_by_the_stride() {
+        assert_eq!(step(Locale::English, "50", 1, 1, 100), "51");
+        assert_eq!(step(Locale::English, "50", -1, 1, 100), "49");
+        assert_eq!(step(Locale::English, "50", 10, 1, 100), "60");
+        assert_eq!(step(Locale::English, " 50 ", -10, 1, 100), "40");
+        assert_eq!(step(Locale::English, "50", -30, 1, 100), "20", "several wheel notches at once");
+    }
+
+    #[test]
+    fn steps_stop_at_the_ends_of_the_range() {
+        assert_eq!(step(Locale::English, "100", 1, 1, 100), "100");
+        assert_eq!(step(Locale::English, "95", 10, 1, 100), "100");
+        assert_eq!(step(Locale::English, "1", -1, 1, 100), "1");
+        assert_eq!(step(Locale::English, "5", -10, 1, 100), "1");
+        assert_eq!(step(Locale::English, "0", -1, 0, u32::MAX), "0");
+        assert_eq!(step(Locale::English, "4294967295", 10, 0, u32::MAX), "4,294,967,295");
+    }
+
+    #[test]
+    fn a_number_outside_the_range_steps_back_into_it() {
+        assert_eq!(step(Locale::English, "500", -1, 1, 100), "100");
+        assert_eq!(step(Locale::English, "500", 1, 1, 100), "100");
+        assert_eq!(step(Locale::English, "3", 1, 40, 60), "40");
+    }
+
+    #[test]
+    fn a_field_without_a_number_starts_from_the_middle() {
+        for input in ["", "   ", "abc", "-5", "1,00", "99999999999"] {
+            assert_eq!(step(Locale::English, input, 1, 1, 100), "50", "{:?}", input);
+            assert_eq!(step(Locale::English, input, -10, 1, 100), "50", "{:?}", input);
+        }
+        assert_eq!(step(Locale::English, "", 1, 51, 100), "75");
+        assert_eq!(step(Locale::English, "", 1, 0, u32::MAX), "2,147,483,647");
+    }
+
+    #[test]
+    fn the_stepped_guess_is_written_for_the_locale() {
+        assert_eq!(step(Locale::German, "1.000", 1, 1, 2000), "1.001");
+        assert_eq!(step(Locale::English, "999", 1, 1, 2000), "1,000");
+    }
+}
@@ -0,0 +1,375 @@
+// Locating and writing the files the game keeps between runs
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+// Name of the folder created inside the platform's data directory
+const APP_DIR: &str = "GuessingGame";
+
+// File inside the data directory that the running game holds a lock on
+const LOCK_FILE: &str = "instance.lock";
+
+// Held for every write, so the game log's thread and the window's can't interleave on a file
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+// The open lock file, kept for as long as the game runs; the lock goes with the process
+static INSTANCE_LOCK: OnceLock<File> = OnceLock::new();
+
+// Set while another copy of the game has the data directory, so only that one writes to it
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+// Set while replaying a recorded session, so the replay can't touch the player's real files
+static DETACHED: AtomicBool = AtomicBool::new(false);
+
+// Tests run side by side in one process, so each test thread keeps its own switches, as it
+// keeps its own scratch directory
+thread_local! {
+    static TEST_READ_ONLY: Cell<bool> = const { Cell::new(false) };
+    static TEST_DETACHED: Cell<bool> = const { Cell::new(false) };
+}
+
+// Stop every later write, turning saves and deletions into silent no-ops
+pub fn set_read_only() {
+    if cfg!(test) {
+        TEST_READ_ONLY.set(true);
+    } else {
+        READ_ONLY.store(true, Ordering::Relaxed);
+    }
+}
+
+// Leave the data directory alone from now on: every file reads as not saved yet, and every
+// write is skipped as when read-only
+pub fn set_detached() {
+    if cfg!(test) {
+        TEST_DETACHED.set(true);
+    } else {
+        DETACHED.store(true, Ordering::Relaxed);
+    }
+    set_read_only();
+}
+
+// Whether the data directory is being left alone
+fn detached() -> bool {
+    if cfg!(test) {
+        return TEST_DETACHED.get();
+    }
+    DETACHED.load(Ordering::Relaxed)
+}
+
+// Whether writes are currently being skipped
+fn read_only() -> bool {
+    if cfg!(test) {
+        return TEST_READ_ONLY.get();
+    }
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
+// Take the write lock, carrying on past a thread that panicked while holding it
+fn writing() -> MutexGuard<'static, ()> {
+    WRITE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Who has the data directory, as found when the game starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirLock {
+    Acquired,      // This copy of the game has it to itself
+    HeldElsewhere, // Another copy of the game is already running on it
+    Unavailable,   // Locking isn't possible here, so writes go ahead unguarded
+}
+
+// Lock the data directory for this run, so a second copy of the game can tell it's not alone
+//
+// Two copies saving over each other would lose whichever wrote first, so a copy that finds
+// the lock held turns read-only, and plays on from the files as they are. The lock is
+// advisory and released by the system when the process ends, crash or not, so a stale lock
+// file never blocks a later launch.
+pub fn lock_data_dir() -> DirLock {
+    let Some(dir) = data_dir() else {
+        return DirLock::Unavailable;
+    };
+    let opened = fs::create_dir_all(&dir)
+        .and_then(|()| OpenOptions::new().create(true).truncate(false).write(true).open(dir.join(LOCK_FILE)));
+    let file = match opened {
+        Ok(file) => file,
+        Err(error) => {
+            tracing::warn!(%error, "Could not open the data directory's lock file");
+            return DirLock::Unavailable;
+        }
+    };
+
+    match file.try_lock() {
+        Ok(()) => {
+            let _ = INSTANCE_LOCK.set(file);
+            DirLock::Acquired
+        }
+        Err(TryLockError::WouldBlock) => {
+            set_read_only();
+            DirLock::HeldElsewhere
+        }
+        Err(TryLockError::Error(error)) => {
+            tracing::warn!(%error, "Could not lock the data directory");
+            DirLock::Unavailable
+        }
+    }
+}
+
+// The per-user directory the game stores its files in, if one can be determined
+pub fn data_dir() -> Option<PathBuf> {
+    if detached() {
+        return None;
+    }
+    // Tests get a scratch directory of their own, so they never see or change the player's
+    // files, one for each test thread so that tests running at once never see each other's
+    if cfg!(test) {
+        let test = thread::current().name().unwrap_or("unnamed").replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+        return Some(env::temp_dir().join(format!("{}-tests-{}", APP_DIR, process::id())).join(test));
+    }
+
+    let base = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+
+    base.map(|base| base.join(APP_DIR))
+}
+
+// The player's home folder, where files meant for them rather than the game are written
+pub fn home_dir() -> Option<PathBuf> {
+    env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" }).map(PathBuf::from)
+}
+
+// Read one of the game's files, returning `None` when it doesn't exist yet
+pub fn load(name: &str) -> io::Result<Option<String>> {
+    if detached() {
+        return Ok(None);
+    }
+    let path = data_dir().ok_or_else(no_data_dir)?.join(name);
+
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+// Write one of the game's files, replacing it atomically so a crash never leaves it half-written
+pub fn save(name: &str, contents: &str) -> io::Result<()> {
+    if read_only() {
+        tracing::trace!(file = name, "Skipped a save while read-only");
+        return Ok(());
+    }
+
+    let dir = data_dir().ok_or_else(no_data_dir)?;
+    let _writing = writing();
+    fs::create_dir_all(&dir)?;
+
+    // Named for this process, so no other copy of the game can be halfway through the same one
+    let temp = dir.join(format!("{}.{}.tmp", name, process::id()));
+    fs::write(&temp, contents)?;
+    fs::rename(temp, dir.join(name))?;
+    tracing::debug!(file = name, bytes = contents.len(), "Saved");
+    Ok(())
+}
+
+// Add to the end of one of the game's files, creating it if needed
+pub fn append(name: &str, contents: &str) -> io::Result<()> {
+    if read_only() {
+        return Ok(());
+    }
+
+    let dir = data_dir().ok_or_else(no_data_dir)?;
+    let _writing = writing();
+    fs::create_dir_all(&dir)?;
+    OpenOptions::new().create(true).append(true).open(dir.join(name))?.write_all(contents.as_bytes())?;
+    tracing::debug!(file = name, bytes = contents.len(), "Appended");
+    Ok(())
+}
+
+// The size in bytes of one of the game's files, or 0 when it doesn't exist yet
+pub fn size(name: &str) -> io::Result<u64> {
+    if detached() {
+        return Ok(0);
+    }
+    let path = data_dir().ok_or_else(no_data_dir)?.join(name);
+
+    match fs::metadata(path) {
+        Ok(metadata) => Ok(metadata.len()),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(0),
+        Err(error) => Err(error),
+    }
+}
+
+// Rename one of the game's files, replacing any file already called `to`
+pub fn rename(from: &str, to: &str) -> io::Result<()> {
+    if read_only() {
+        return Ok(());
+    }
+
+    let dir = data_dir().ok_or_else(no_data_dir)?;
+    let _writing = writing();
+    fs::rename(dir.join(from), dir.join(to))?;
+    tracing::debug!(from, to, "Renamed");
+    Ok(())
+}
+
+// Delete one of the game's files, treating one that's already gone as deleted
+pub fn remove(name: &str) -> io::Result<()> {
+    if read_only() {
+        return Ok(());
+    }
+
+    let path = data_dir().ok_or_else(no_data_dir)?.join(name);
+    let _writing = writing();
+
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => {
+            tracing::debug!(file = name, "Removed");
+            Ok(())
+        }
+    }
+}
+
+// Parse `key = value` lines, skipping blank lines and `#` comments
+pub fn parse_key_values(contents: &str) -> BTreeMap<String, String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+// Render `key = value` lines in the format `parse_key_values` reads back
+pub fn write_key_values<'a>(pairs: impl IntoIterator<Item = (&'a str, String)>) -> String {
+    pairs
+        .into_iter()
+        .map(|(key, value)| format!("{} = {}\n", key, value))
+        .collect()
+}
+
+// Render per-guess times as a list of milliseconds, leaving a gap for each one that isn't known
+pub fn write_times(times: &[Option<Duration>]) -> String {
+    let times: Vec<String> = times.iter().map(|time| time.map_or(String::new(), |time| time.as_millis().to_string())).collect();
+    times.join(",")
+}
+
+// Read a list written by `write_times` for `count` guesses; anything missing or unreadable is unknown
+pub fn parse_times(list: Option<&str>, count: usize) -> Vec<Option<Duration>> {
+    let mut times: Vec<Option<Duration>> = match list {
+        Some(list) if count > 0 => list.split(',').map(|ms| ms.trim().parse().ok().map(Duration::from_millis)).collect(),
+        _ => Vec::new(), // Written before times were kept, or with no guesses to time
+    };
+    times.resize(count, None);
+    times
+}
+
+// The error reported when no data directory can be found for this user
+pub fn no_data_dir() -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, "no data directory available")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_files_read_back() {
+        save("saved.txt", "one = 1\n").unwrap();
+        assert_eq!(load("saved.txt").unwrap().as_deref(), Some("one = 1\n"));
+        save("saved.txt", "two = 2\n").unwrap();
+        assert_eq!(load("saved.txt").unwrap().as_deref(), Some("two = 2\n"));
+        assert_eq!(size("saved.txt").unwrap(), 8);
+    }
+
+    #[test]
+    fn missing_files_read_as_not_saved_yet() {
+        remove("missing.txt").unwrap();
+        assert_eq!(load("missing.txt").unwrap(), None);
+        assert_eq!(size("missing.txt").unwrap(), 0);
+        remove("missing.txt").expect("a file that's already gone counts as deleted");
+    }
+
+    #[test]
+    fn saves_at_once_leave_one_whole_file() {
+        let test = thread::current().name().unwrap().to_string();
+        let contents = |n: usize| write_key_values([("writer", n.to_string()), ("padding", "x".repeat(64 * 1024))]);
+        let writers: Vec<_> = (0..16)
+            .map(|n| {
+                // Named for the test, so they share its scratch directory
+                thread::Builder::new().name(test.clone()).spawn(move || save("shared.txt", &contents(n)).unwrap()).unwrap()
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let saved = load("shared.txt").unwrap().expect("one of the saves won");
+        let values = parse_key_values(&saved);
+        let writer: usize = values["writer"].parse().unwrap();
+        assert_eq!(saved, contents(writer), "the file is exactly one writer's, never a mix");
+        let leftovers = fs::read_dir(data_dir().unwrap()).unwrap().filter(|entry| {
+            entry.as_ref().unwrap().file_name().to_string_lossy().ends_with(".tmp")
+        });
+        assert_eq!(leftovers.count(), 0);
+    }
+
+    #[test]
+    fn a_second_copy_finds_the_directory_held_and_turns_read_only() {
+        save("before.txt", "kept\n").unwrap();
+        assert_eq!(lock_data_dir(), DirLock::Acquired);
+        assert!(!read_only());
+
+        assert_eq!(lock_data_dir(), DirLock::HeldElsewhere);
+        assert!(read_only());
+        save("before.txt", "overwritten\n").unwrap();
+        remove("before.txt").unwrap();
+        append("before.txt", "more\n").unwrap();
+        assert_eq!(load("before.txt").unwrap().as_deref(), Some("kept\n"), "it still reads the files");
+    }
+
+    #[test]
+    fn a_detached_game_leaves_the_directory_alone() {
+        save("detached.txt", "kept\n").unwrap();
+        set_detached();
+        assert_eq!(data_dir(), None);
+        assert_eq!(load("detached.txt").unwrap(), None);
+        assert_eq!(size("detached.txt").unwrap(), 0);
+        save("detached.txt", "overwritten\n").unwrap();
+    }
+
+    #[test]
+    fn key_values_round_trip() {
+        let text = write_key_values([("name", "Ada Lovelace".to_string()), ("empty", String::new()), ("sum", "1 = 1".to_string())]);
+        let values = parse_key_values(&format!("# a comment\n\n{}not a pair\n", text));
+        assert_eq!(values.len(), 3);
+        assert_eq!(values["name"], "Ada Lovelace");
+        assert_eq!(values["empty"], "");
+        assert_eq!(values["sum"], "1 = 1");
+    }
+
+    #[test]
+    fn times_round_trip_with_gaps() {
+        let times = [Some(Duration::from_millis(8_240)), None, Some(Duration::ZERO)];
+        assert_eq!(write_times(&times), "8240,,0");
+        assert_eq!(parse_times(Some(&write_times(&times)), 3), times);
+        assert_eq!(parse_times(Some("8240,x"), 3), [Some(Duration::from_millis(8_240)), None, None]);
+        assert_eq!(parse_times(None, 2), [None, None]);
+        assert_eq!(parse_times(Some("1,2,3"), 0), []);
+    }
+}
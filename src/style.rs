@@ -0,0 +1,197 @@
+// Custom widget styles built from the user's accent color
+
+use iced::{
+    theme::{self, Theme},
+    widget::{button, container, text_input},
+    Background, Color, Vector,
+};
+
+use crate::color::Rgb;
+
+// Style for the primary action button, with hover/pressed shades derived from the accent
+pub struct AccentButton(pub Rgb);
+
+impl AccentButton {
+    // Appearance of the button filled with the given shade
+    fn filled(&self, fill: Rgb) -> button::Appearance {
+        button::Appearance {
+            shadow_offset: Vector::new(0.0, 0.0),
+            background: Some(Background::Color(fill.into())),
+            border_radius: 4.0,
+            border_width: 0.0,
+            border_color: Color::TRANSPARENT,
+            text_color: fill.readable_text().into(), // Keep the label legible on any accent
+        }
+    }
+}
+
+impl button::StyleSheet for AccentButton {
+    type Style = Theme;
+
+    fn active(&self, _style: &Self::Style) -> button::Appearance {
+        self.filled(self.0)
+    }
+
+    fn hovered(&self, _style: &Self::Style) -> button::Appearance {
+        self.filled(self.0.hovered())
+    }
+
+    fn pressed(&self, _style: &Self::Style) -> button::Appearance {
+        self.filled(self.0.pressed())
+    }
+}
+
+// Style for an accent swatch, outlined when it is the current accent
+pub struct Swatch {
+    pub color: Rgb,
+    pub selected: bool,
+}
+
+impl button::StyleSheet for Swatch {
+    type Style = Theme;
+
+    fn active(&self, style: &Self::Style) -> button::Appearance {
+        button::Appearance {
+            background: Some(Background::Color(self.color.into())),
+            border_radius: 12.0,
+            border_width: if self.selected { 3.0 } else { 0.0 },
+            border_color: style.palette().text, // Outline in the text color so it shows on any background
+            ..button::Appearance::default()
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style) -> button::Appearance {
+        button::Appearance {
+            background: Some(Background::Color(self.color.hovered().into())),
+            ..self.active(style)
+        }
+    }
+}
+
+// Style for a text input tinted to show how close the last guess was
+//
+// Only the border and a faint wash of the background change; everything else comes from
+// the theme, so the field still looks like the others.
+pub struct ProximityInput(pub Rgb);
+
+impl ProximityInput {
+    // Tint one of the theme's appearances
+    fn tinted(&self, appearance: text_input::Appearance, style: &Theme) -> text_input::Appearance {
+        let background = Rgb::from(style.palette().background).mix(self.0, 0.12);
+        text_input::Appearance {
+            background: Background::Color(background.into()),
+            border_width: 2.0,
+            border_color: self.0.into(),
+            ..appearance
+        }
+    }
+}
+
+impl text_input::StyleSheet for ProximityInput {
+    type Style = Theme;
+
+    fn active(&self, style: &Self::Style) -> text_input::Appearance {
+        self.tinted(style.active(&theme::TextInput::Default), style)
+    }
+
+    fn focused(&self, style: &Self::Style) -> text_input::Appearance {
+        self.tinted(style.focused(&theme::TextInput::Default), style)
+    }
+
+    fn hovered(&self, style: &Self::Style) -> text_input::Appearance {
+        self.tinted(style.hovered(&theme::TextInput::Default), style)
+    }
+
+    fn disabled(&self, style: &Self::Style) -> text_input::Appearance {
+        style.disabled(&theme::TextInput::Default)
+    }
+
+    fn placeholder_color(&self, style: &Self::Style) -> Color {
+        style.placeholder_color(&theme::TextInput::Default)
+    }
+
+    fn value_color(&self, style: &Self::Style) -> Color {
+        style.value_color(&theme::TextInput::Default)
+    }
+
+    fn disabled_color(&self, style: &Self::Style) -> Color {
+        style.disabled_color(&theme::TextInput::Default)
+    }
+
+    fn selection_color(&self, style: &Self::Style) -> Color {
+        style.selection_color(&theme::TextInput::Default)
+    }
+}
+
+// Style for the debug overlay: a dark, slightly see-through panel that stands out on either theme
+pub struct DebugPanel;
+
+impl container::StyleSheet for DebugPanel {
+    type Style = Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        container::Appearance {
+            text_color: Some(Color::WHITE),
+            background: Some(Background::Color(Color { a: 0.8, ..Color::BLACK })),
+            border_radius: 4.0,
+            border_width: 1.0,
+            border_color: Color::from_rgb(1.0, 0.3, 0.3), // Red, as a reminder it shows cheat info
+        }
+    }
+}
+
+// Style for a day in the daily challenge calendar: filled with the accent once solved, and
+// outlined when it's today
+pub struct DayCell {
+    pub accent: Rgb,
+    pub solved: bool,
+    pub today: bool,
+}
+
+impl container::StyleSheet for DayCell {
+    type Style = Theme;
+
+    fn appearance(&self, style: &Self::Style) -> container::Appearance {
+        container::Appearance {
+            text_color: self.solved.then(|| self.accent.readable_text().into()),
+            background: self.solved.then(|| Background::Color(self.accent.into())),
+            border_radius: 4.0,
+            border_width: if self.today { 2.0 } else { 0.0 },
+            border_color: style.palette().text,
+        }
+    }
+}
+
+// Style around a settings control, outlined in the accent while keyboard focus rests on it
+pub struct FocusRing {
+    pub accent: Rgb,
+    pub focused: bool,
+}
+
+impl container::StyleSheet for FocusRing {
+    type Style = Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        container::Appearance {
+            border_radius: 4.0,
+            border_width: if self.focused { 2.0 } else { 0.0 }, // Always wrapped, so focus never moves the layout
+            border_color: self.accent.into(),
+            ..container::Appearance::default()
+        }
+    }
+}
+
+// Wrap a stylesheet so it can be passed to `Container::style`
+pub fn custom_container(style: impl container::StyleSheet<Style = Theme> + 'static) -> theme::Container {
+    theme::Container::Custom(Box::new(style))
+}
+
+// Wrap a stylesheet so it can be passed to `TextInput::style`
+pub fn custom_text_input(style: impl text_input::StyleSheet<Style = Theme> + 'static) -> theme::TextInput {
+    theme::TextInput::Custom(Box::new(style))
+}
+
+// Wrap a stylesheet so it can be passed to `Button::style`
+pub fn custom_button(style: impl button::StyleSheet<Style = Theme> + 'static) -> theme::Button {
+    theme::Button::Custom(Box::new(style))
+}
@@ -0,0 +1,160 @@
+// The surprise swap: once a round, after a set number of guesses, the secret changes to another number
+//
+// The new secret has to be one the player could still be looking for, so that everything they
+// were told stays true: it's inside the bounds, every guess in the history judges exactly as it
+// did under the round's rule (which a close-enough or mystery round can make stricter), and
+// every clue a hint gave still holds. The thermometer measured distance to the old secret, which
+// no other number can keep true, so it starts over instead, and the swap is always announced.
+
+use std::fmt;
+
+use crate::hints::Hint;
+use crate::mercy::{self, MercyHint};
+use crate::random::{NumberSource, SeededSource};
+use crate::rules::WinRule;
+use crate::secret::Secret;
+
+// Mixed into the game's seed, so the swap's pick doesn't simply repeat the secret's draw
+const SEED_SALT: u64 = 0x5157_4150;
+
+// How many guesses into a round the swap comes, if at all
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SurpriseSwap {
+    #[default]
+    Off,
+    After3,
+    After5,
+    After8,
+}
+
+impl SurpriseSwap {
+    // Every choice, in the order shown in the picker
+    pub const ALL: [SurpriseSwap; 4] = [SurpriseSwap::Off, SurpriseSwap::After3, SurpriseSwap::After5, SurpriseSwap::After8];
+
+    // The guesses a round takes before the swap, or `None` when there's no swap
+    pub fn guesses(self) -> Option<usize> {
+        match self {
+            SurpriseSwap::Off => None,
+            SurpriseSwap::After3 => Some(3),
+            SurpriseSwap::After5 => Some(5),
+            SurpriseSwap::After8 => Some(8),
+        }
+    }
+
+    // The value written to the preferences file
+    pub fn key(self) -> &'static str {
+        match self {
+            SurpriseSwap::Off => "off",
+            SurpriseSwap::After3 => "3",
+            SurpriseSwap::After5 => "5",
+            SurpriseSwap::After8 => "8",
+        }
+    }
+
+    // Read a value written by `key`
+    pub fn from_key(key: &str) -> Option<SurpriseSwap> {
+        SurpriseSwap::ALL.into_iter().find(|choice| choice.key() == key)
+    }
+}
+
+impl fmt::Display for SurpriseSwap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.guesses() {
+            Some(guesses) => write!(f, "After {} guesses", guesses),
+            None => write!(f, "Never"),
+        }
+    }
+}
+
+// The clues the player has been given that the bounds don't already capture
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Clues {
+    pub parity: bool,      // Whether the secret is even or odd
+    pub last_digit: bool,  // The digit it ends in
+    pub nearest_ten: bool, // The multiple of ten it rounds to
+    pub within_five: bool, // The multiple of five it rounds to
+}
+
+impl Clues {
+    // The clues given by the first `bought` hints and the first `volunteered` automatic ones
+    //
+    // The digits, half and decade hints only ever narrow the bounds, so they need nothing more;
+    // the last digit narrows them too, but still has to hold for every number in between.
+    pub fn given(bought: usize, volunteered: usize) -> Clues {
+        let bought = &Hint::ALL[..bought.min(Hint::ALL.len())];
+        let volunteered = &MercyHint::ALL[..volunteered.min(MercyHint::ALL.len())];
+        Clues {
+            parity: bought.contains(&Hint::Parity) || volunteered.contains(&MercyHint::Parity),
+            last_digit: bought.contains(&Hint::LastDigit),
+            nearest_ten: volunteered.contains(&MercyHint::NearestTen),
+            within_five: volunteered.contains(&MercyHint::WithinFive),
+        }
+    }
+
+    // Whether `candidate` fits every clue that `secret` gave
+    fn hold(self, candidate: u32, secret: u32) -> bool {
+        (!self.parity || candidate % 2 == secret % 2)
+            && (!self.last_digit || candidate % 10 == secret % 10)
+            && (!self.nearest_ten || mercy::nearest(candidate, 10) == mercy::nearest(secret, 10))
+            && (!self.within_five || mercy::nearest(candidate, 5) == mercy::nearest(secret, 5))
+    }
+}
+
+// Every number the secret could change to without contradicting anything the player was told
+//
+// Like hints, this decodes the secret mid-game, and only to compare the candidates against it.
+pub fn candidates(secret: &Secret, low: u32, high: u32, history: &[u32], rule: &dyn WinRule, clues: Clues) -> Vec<u32> {
+    let value = secret.reveal();
+    let judged: Vec<_> = history.iter().map(|&guess| (guess, secret.judge(guess, rule))).collect();
+    (low..=high)
+        .filter(|&candidate| candidate != value && clues.hold(candidate, value))
+        .filter(|&candidate| judged.iter().all(|&(guess, outcome)| rule.evaluate(guess, candidate) == outcome))
+        .collect()
+}
+
+// The new secret, picked from `candidates` the same way every time for the same `seed`
+pub fn pick(candidates: &[u32], seed: u32) -> Option<u32> {
+    let last = u32::try_from(candidates.len().checked_sub(1)?).ok()?;
+    let mut source = SeededSource::new(u64::from(seed) ^ SEED_SALT);
+    candidates.get(source.secret(0, last) as usize).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Exact;
+
+    #[test]
+    fn clues_come_from_the_hints_bought_and_given() {
+        assert_eq!(Clues::given(0, 0), Clues::default());
+        assert_eq!(Clues::given(2, 0), Clues { parity: true, ..Clues::default() });
+        assert_eq!(Clues::given(3, 0), Clues { parity: true, last_digit: true, ..Clues::default() });
+        assert_eq!(Clues::given(99, 0), Clues::given(Hint::ALL.len(), 0));
+        assert_eq!(
+            Clues::given(0, MercyHint::ALL.len()),
+            Clues { parity: true, last_digit: false, nearest_ten: true, within_five: true }
+        );
+    }
+
+    #[test]
+    fn a_swap_after_the_last_digit_keeps_it() {
+        let secret = Secret::new(95);
+        let found = candidates(&secret, 15, 95, &[], &Exact, Clues::given(3, 0));
+        assert_eq!(found, [15, 25, 35, 45, 55, 65, 75, 85]);
+
+        let guessed = candidates(&secret, 15, 95, &[50, 80], &Exact, Clues::given(3, 0));
+        assert_eq!(guessed, [85], "the guesses still judge the same way");
+        assert!(candidates(&secret, 95, 95, &[], &Exact, Clues::given(3, 0)).is_empty());
+    }
+
+    #[test]
+    fn the_pick_is_one_of_the_candidates_and_the_same_for_a_seed() {
+        let candidates = [15, 25, 35, 45];
+        for seed in 0..50 {
+            let picked = pick(&candidates, seed).unwrap();
+            assert!(candidates.contains(&picked));
+            assert_eq!(pick(&candidates, seed), Some(picked));
+        }
+        assert_eq!(pick(&[], 7), None);
+    }
+}
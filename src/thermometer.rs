@@ -0,0 +1,73 @@
+// The thermometer beside the guess input, filling up the closer the latest guess was
+
+use iced::widget::canvas::{self, Cursor, Frame, Geometry, Path, Stroke};
+use iced::{Color, Point, Rectangle, Size, Theme};
+
+// The thermometer's size on screen, in logical pixels
+pub const WIDTH: f32 = 18.0;
+pub const HEIGHT: f32 = 56.0;
+
+// How full the thermometer is for a guess `distance` from the secret in a range `range` wide
+//
+// Empty when the guess is off by the whole range or more, full when it's the secret itself.
+pub fn fill_fraction(distance: u32, range: u32) -> f32 {
+    let range = range.max(1);
+    1.0 - distance.min(range) as f32 / range as f32
+}
+
+// The widget, drawn at `level` from 0.0 (empty) to 1.0 (full)
+pub struct Thermometer {
+    pub level: f32,     // How full to draw it
+    pub fill: Color,    // The color of the liquid, from the proximity scale
+    pub outline: Color, // The color of the glass, readable on the theme's background
+}
+
+impl<Message> canvas::Program<Message> for Thermometer {
+    type State = ();
+
+    fn draw(&self, _state: &(), _theme: &Theme, bounds: Rectangle, _cursor: Cursor) -> Vec<Geometry> {
+        let mut frame = Frame::new(bounds.size());
+        let (width, height) = (frame.width(), frame.height());
+
+        // A round bulb at the bottom, with a narrower tube rising out of it
+        let radius = width / 2.0 - 1.0;
+        let bulb = Point::new(width / 2.0, height - radius - 1.0);
+        let tube_width = width / 2.0;
+        let tube_left = (width - tube_width) / 2.0;
+        let tube_height = bulb.y - 1.0;
+
+        // The liquid rises from the bulb up the tube; an empty thermometer shows only glass
+        let level = self.level.clamp(0.0, 1.0);
+        if level > 0.0 {
+            let risen = tube_height * level;
+            frame.fill(&Path::circle(bulb, radius - 2.0), self.fill);
+            let top = Point::new(tube_left + 2.0, bulb.y - risen);
+            frame.fill_rectangle(top, Size::new(tube_width - 4.0, risen), self.fill);
+        }
+
+        let glass = Stroke::default().with_color(self.outline).with_width(1.5);
+        frame.stroke(&Path::circle(bulb, radius), glass.clone());
+        frame.stroke(&Path::rectangle(Point::new(tube_left, 1.0), Size::new(tube_width, tube_height)), glass);
+
+        vec![frame.into_geometry()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_fill_rises_as_the_guess_closes_in() {
+        assert_eq!(fill_fraction(0, 99), 1.0, "the secret itself fills it");
+        assert_eq!(fill_fraction(25, 100), 0.75);
+        assert_eq!(fill_fraction(99, 99), 0.0, "off by the whole range");
+        assert_eq!(fill_fraction(500, 99), 0.0, "further off than that is still empty");
+    }
+
+    #[test]
+    fn a_range_with_no_width_is_full_or_empty() {
+        assert_eq!(fill_fraction(0, 0), 1.0);
+        assert_eq!(fill_fraction(5, 0), 0.0);
+    }
+}
@@ -0,0 +1,63 @@
+// Pausable time tracking for games
+
+use std::time::{Duration, Instant};
+
+// How long each guess may take in the timed-guesses mode, counted on the game clock
+pub const ATTEMPT_LIMIT: Duration = Duration::from_secs(10);
+
+// Time left for the current guess, with the game clock at `elapsed` and the guess's time begun at `since`
+//
+// Both readings come from the game clock, so the guess's time stops with it whenever the
+// game pauses, and doesn't start until the clock does with the first keystroke.
+pub fn attempt_remaining(elapsed: Duration, since: Duration) -> Duration {
+    ATTEMPT_LIMIT.saturating_sub(elapsed.saturating_sub(since))
+}
+
+// A stopwatch that only counts time while running
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stopwatch {
+    running_since: Option<Instant>, // When the current running stretch began, if running
+    accumulated: Duration,          // Time counted in earlier, finished stretches
+}
+
+impl Stopwatch {
+    // A stopped stopwatch that has already counted `elapsed`, for picking up a saved game
+    pub fn from_elapsed(elapsed: Duration) -> Stopwatch {
+        Stopwatch { running_since: None, accumulated: elapsed }
+    }
+
+    // Whether the stopwatch is currently counting
+    pub fn is_running(&self) -> bool {
+        self.running_since.is_some()
+    }
+
+    // Whether any time has been counted, or is being counted, since the last reset
+    pub fn has_started(&self) -> bool {
+        self.is_running() || self.accumulated > Duration::ZERO
+    }
+
+    // Start or resume counting from `now`
+    pub fn start(&mut self, now: Instant) {
+        if self.running_since.is_none() {
+            self.running_since = Some(now);
+        }
+    }
+
+    // Stop counting as of `at`, which may lie in the past to discard time already elapsed
+    pub fn pause(&mut self, at: Instant) {
+        if let Some(since) = self.running_since.take() {
+            self.accumulated += at.saturating_duration_since(since);
+        }
+    }
+
+    // Total time counted as of `now`
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        let running = self.running_since.map_or(Duration::ZERO, |since| now.saturating_duration_since(since));
+        self.accumulated + running
+    }
+
+    // Stop and clear the stopwatch
+    pub fn reset(&mut self) {
+        *self = Stopwatch::default();
+    }
+}
@@ -0,0 +1,59 @@
+// Tournaments: a set number of classic rounds played back to back, their scores added up
+
+use std::time::Duration;
+
+// The tournament lengths offered, in rounds
+pub const LENGTHS: [u32; 3] = [3, 5, 7];
+
+// How long a won round's result stays up before the next round starts by itself
+pub const ROUND_BREAK: Duration = Duration::from_secs(3);
+
+// One finished round
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub attempts: u32,     // Guesses it took
+    pub score: u32,        // Points the round scored
+    pub elapsed: Duration, // Time on the game clock
+}
+
+// A tournament in progress, or just finished and showing its standings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tournament {
+    pub length: u32,        // Rounds to play
+    pub rounds: Vec<Round>, // Rounds finished so far, in order
+}
+
+impl Tournament {
+    // A tournament of `length` rounds, none played yet
+    pub fn new(length: u32) -> Tournament {
+        Tournament { length, rounds: Vec::new() }
+    }
+
+    // The number of the round being played, from 1, or of the last one once all are finished
+    pub fn current(&self) -> u32 {
+        (self.rounds.len() as u32 + 1).min(self.length)
+    }
+
+    // Add a finished round's result
+    pub fn record(&mut self, round: Round) {
+        if !self.is_finished() {
+            self.rounds.push(round);
+        }
+    }
+
+    // Whether every round has been played
+    pub fn is_finished(&self) -> bool {
+        self.rounds.len() as u32 >= self.length
+    }
+
+    // Points scored across the rounds finished so far
+    pub fn total(&self) -> u32 {
+        self.rounds.iter().map(|round| round.score).sum()
+    }
+
+    // The position of the highest-scoring round, the earlier one winning a tie
+    pub fn best(&self) -> Option<usize> {
+        let best = self.rounds.iter().map(|round| round.score).max()?;
+        self.rounds.iter().position(|round| round.score == best)
+    }
+}
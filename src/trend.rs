@@ -0,0 +1,270 @@
+// The trend chart on the stats panel: how many guesses wins have taken, over recent games or by day
+//
+// Wins over other ranges are scaled to the classic range by the optimal guess count, so a
+// 1–1000 win that took ten guesses, optimal there, sits with a seven-guess win over 1–100.
+
+use std::fmt;
+
+use iced::alignment;
+use iced::widget::canvas::{self, Cursor, Frame, Geometry, Path, Stroke, Text};
+use iced::{Color, Point, Rectangle, Theme};
+
+use crate::analysis;
+use crate::daily::Date;
+use crate::gamelog::Win;
+use crate::{MAX_NUMBER, MIN_NUMBER};
+
+// The chart's height on screen, in logical pixels
+pub const HEIGHT: f32 = 180.0;
+
+// Room left of and below the plot for the axis labels
+const LEFT_MARGIN: f32 = 32.0;
+const BOTTOM_MARGIN: f32 = 20.0;
+
+// Seconds in a day, for grouping wins by the UTC day they were won on
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+// How much of the history the chart covers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Window {
+    #[default]
+    Last20,  // The last 20 wins, one point each
+    Last100, // The last 100 wins, one point each
+    All,     // Every win, averaged by day
+}
+
+impl Window {
+    pub const ALL: [Window; 3] = [Window::Last20, Window::Last100, Window::All];
+
+    // The name used in recorded sessions
+    pub fn key(self) -> &'static str {
+        match self {
+            Window::Last20 => "last_20",
+            Window::Last100 => "last_100",
+            Window::All => "all",
+        }
+    }
+
+    // The window named `key`, if it is one
+    pub fn from_key(key: &str) -> Option<Window> {
+        Window::ALL.into_iter().find(|window| window.key() == key)
+    }
+}
+
+impl fmt::Display for Window {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Window::Last20 => write!(f, "Last 20 wins"),
+            Window::Last100 => write!(f, "Last 100 wins"),
+            Window::All => write!(f, "All time, by day"),
+        }
+    }
+}
+
+// A win's guesses as if it had been over the classic range
+pub fn normalized(win: &Win) -> f32 {
+    let reference = analysis::optimal_guesses(MIN_NUMBER, MAX_NUMBER).max(1);
+    let optimal = analysis::optimal_guesses(win.min, win.max).max(1);
+    win.attempts as f32 * reference as f32 / optimal as f32
+}
+
+// The points to plot, oldest first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trend {
+    pub points: Vec<(f32, f32)>, // Games or days from the first point, and the guesses there
+    pub first_day: Option<Date>, // The day the first point is on, when grouped by day
+    pub last_day: Option<Date>,  // The day the last point is on, when grouped by day
+}
+
+impl Trend {
+    // Work out the points for the wins in `wins`, oldest first, over `window`
+    pub fn new(wins: &[Win], window: Window) -> Trend {
+        let recent = |count: usize| {
+            let points = wins[wins.len().saturating_sub(count)..]
+                .iter()
+                .enumerate()
+                .map(|(i, win)| (i as f32, normalized(win)))
+                .collect();
+            Trend { points, first_day: None, last_day: None }
+        };
+        match window {
+            Window::Last20 => recent(20),
+            Window::Last100 => recent(100),
+            Window::All => Trend::by_day(wins),
+        }
+    }
+
+    // One point per day with wins, placed by the day so days without any show as gaps
+    fn by_day(wins: &[Win]) -> Trend {
+        let mut days: Vec<(u64, f32, u32)> = Vec::new(); // The day, its total and its count
+        for win in wins {
+            let day = win.time / SECONDS_PER_DAY;
+            match days.iter_mut().find(|(found, ..)| *found == day) {
+                Some((_, total, count)) => {
+                    *total += normalized(win);
+                    *count += 1;
+                }
+                None => days.push((day, normalized(win), 1)),
+            }
+        }
+        days.sort_by_key(|&(day, ..)| day); // The clock may have been changed between games
+
+        let (Some(&(first, ..)), Some(&(last, ..))) = (days.first(), days.last()) else {
+            return Trend::default();
+        };
+        Trend {
+            points: days.iter().map(|&(day, total, count)| ((day - first) as f32, total / count as f32)).collect(),
+            first_day: Some(Date::from_days(first as i64)),
+            last_day: Some(Date::from_days(last as i64)),
+        }
+    }
+
+    // Whether there's nothing to plot, so the panel shows a placeholder instead
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+}
+
+// The widget, plotting `trend` with the optimal guess count as a reference line
+pub struct TrendChart {
+    pub trend: Trend,
+    pub line: Color, // The color of the trend itself
+    pub axes: Color, // The color of the axes, labels and reference line
+}
+
+impl<Message> canvas::Program<Message> for TrendChart {
+    type State = ();
+
+    fn draw(&self, _state: &(), _theme: &Theme, bounds: Rectangle, _cursor: Cursor) -> Vec<Geometry> {
+        let mut frame = Frame::new(bounds.size());
+        let plot = Rectangle {
+            x: LEFT_MARGIN,
+            y: 4.0,
+            width: (frame.width() - LEFT_MARGIN - 8.0).max(1.0),
+            height: (frame.height() - BOTTOM_MARGIN - 4.0).max(1.0),
+        };
+
+        // Scale to a whole number of guesses, always above the optimal line so it's never off the top
+        let optimal = analysis::optimal_guesses(MIN_NUMBER, MAX_NUMBER) as f32;
+        let top = self.trend.points.iter().map(|&(_, y)| y).fold(optimal + 1.0, f32::max).ceil();
+        let span = self.trend.points.last().map_or(0.0, |&(x, _)| x).max(1.0);
+        let at = |x: f32, y: f32| Point::new(plot.x + plot.width * x / span, plot.y + plot.height * (1.0 - y / top));
+
+        let axes = Stroke::default().with_color(self.axes).with_width(1.0);
+        let origin = Point::new(plot.x, plot.y + plot.height);
+        frame.stroke(&Path::line(Point::new(plot.x, plot.y), origin), axes.clone());
+        frame.stroke(&Path::line(origin, Point::new(plot.x + plot.width, origin.y)), axes.clone());
+        frame.stroke(&Path::line(at(0.0, optimal), at(span, optimal)), Stroke { width: 0.5, ..axes.clone() });
+
+        // Label the scale at the top and the optimal line, and the ends of the time axis
+        let label = |content: String, position: Point, horizontal: alignment::Horizontal| Text {
+            content,
+            position,
+            color: self.axes,
+            size: 12.0,
+            horizontal_alignment: horizontal,
+            vertical_alignment: alignment::Vertical::Center,
+            ..Text::default()
+        };
+        for value in [top, optimal] {
+            let y = at(0.0, value).y;
+            frame.fill_text(label(format!("{}", value as u32), Point::new(plot.x - 4.0, y), alignment::Horizontal::Right));
+        }
+        let (start, end) = match (self.trend.first_day, self.trend.last_day) {
+            (Some(first), Some(last)) => (first.key(), last.key()),
+            _ => ("Oldest".to_string(), "Latest".to_string()),
+        };
+        let below = origin.y + BOTTOM_MARGIN / 2.0;
+        frame.fill_text(label(start, Point::new(plot.x, below), alignment::Horizontal::Left));
+        frame.fill_text(label(end, Point::new(plot.x + plot.width, below), alignment::Horizontal::Right));
+
+        // The trend as a line through a dot for each point; a lone point is just its dot
+        let points: Vec<Point> = self.trend.points.iter().map(|&(x, y)| at(x, y)).collect();
+        if points.len() > 1 {
+            let line = Path::new(|builder| {
+                builder.move_to(points[0]);
+                for &point in &points[1..] {
+                    builder.line_to(point);
+                }
+            });
+            frame.stroke(&line, Stroke::default().with_color(self.line).with_width(2.0));
+        }
+        for &point in &points {
+            frame.fill(&Path::circle(point, 2.5), self.line);
+        }
+        vec![frame.into_geometry()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = SECONDS_PER_DAY;
+
+    fn win(time: u64, min: u32, max: u32, attempts: u32) -> Win {
+        Win { time, min, max, attempts, took: 30 }
+    }
+
+    #[test]
+    fn wins_over_other_ranges_are_scaled_to_the_classic_one() {
+        assert_eq!(normalized(&win(0, 1, 100, 5)), 5.0);
+        assert_eq!(normalized(&win(0, 1, 1_000, 10)), 7.0, "optimal over 1-1000 is optimal over 1-100");
+        assert_eq!(normalized(&win(0, 1, 1_000, 20)), 14.0);
+        assert_eq!(normalized(&win(0, 1, 10, 4)), 7.0);
+        assert_eq!(normalized(&win(0, 7, 7, 1)), 7.0, "a range of one still has an optimal count of one");
+        assert!(normalized(&win(0, 0, u32::MAX, 32)).is_finite());
+    }
+
+    #[test]
+    fn the_recent_windows_keep_the_latest_wins_oldest_first() {
+        let wins: Vec<Win> = (0..150).map(|i| win(i * 60, 1, 100, 1 + (i % 7) as u32)).collect();
+
+        let last20 = Trend::new(&wins, Window::Last20);
+        assert_eq!(last20.points.len(), 20);
+        assert_eq!(last20.points[0], (0.0, normalized(&wins[130])));
+        assert_eq!(last20.points[19], (19.0, normalized(&wins[149])));
+        assert_eq!((last20.first_day, last20.last_day), (None, None), "games aren't labelled by day");
+
+        let last100 = Trend::new(&wins, Window::Last100);
+        assert_eq!(last100.points.len(), 100);
+        assert_eq!(last100.points[0].1, normalized(&wins[50]));
+
+        let few = Trend::new(&wins[..3], Window::Last100);
+        assert_eq!(few.points, [(0.0, 1.0), (1.0, 2.0), (2.0, 3.0)]);
+    }
+
+    #[test]
+    fn all_time_averages_each_day_and_leaves_gaps_for_days_without_wins() {
+        let start = 19_000 * DAY; // 2022-01-08
+        let wins = [
+            win(start + 10, 1, 100, 4),
+            win(start + DAY - 1, 1, 100, 8), // Still the first day
+            win(start + 3 * DAY, 1, 1_000, 20),
+            win(start + DAY + 5, 1, 100, 9), // Out of order, as when the clock was changed
+        ];
+        let trend = Trend::new(&wins, Window::All);
+        assert_eq!(trend.points, [(0.0, 6.0), (1.0, 9.0), (3.0, 14.0)]);
+        assert_eq!(trend.first_day.map(Date::key).as_deref(), Some("2022-01-08"));
+        assert_eq!(trend.last_day.map(Date::key).as_deref(), Some("2022-01-11"));
+    }
+
+    #[test]
+    fn no_wins_leave_nothing_to_plot_in_any_window() {
+        for window in Window::ALL {
+            let trend = Trend::new(&[], window);
+            assert!(trend.is_empty(), "{}", window);
+            assert_eq!(trend, Trend::default());
+        }
+        assert!(!Trend::new(&[win(0, 1, 100, 3)], Window::All).is_empty());
+    }
+
+    #[test]
+    fn keys_read_back_and_are_unique() {
+        for window in Window::ALL {
+            assert_eq!(Window::from_key(window.key()), Some(window));
+        }
+        assert_eq!(Window::from_key("last_50"), None);
+        assert_eq!(Window::default().to_string(), "Last 20 wins");
+    }
+}
@@ -0,0 +1,1082 @@
+// What the classic game screen shows, decided without building any widgets
+//
+// `view` only translates these values into widgets, so every presentation decision —
+// which controls are enabled, the exact wording, what is visible — lives here, in plain
+// data that can be inspected without a window.
+
+use std::time::Duration;
+
+use crate::analysis;
+use crate::budget;
+use crate::card::SaveState;
+use crate::color::{self, Rgb};
+use crate::hints::Hint;
+use crate::leaderboard::Metric;
+use crate::score;
+use crate::scorecard::{Scorecard, Verdict};
+use crate::golf::{self, Course};
+use crate::tournament::Tournament;
+use crate::feedback::Feedback;
+use crate::format::{
+    format_duration, format_number, format_number_within, format_seconds, format_thinking_time, parse_number, Locale,
+};
+use crate::ghost::Split;
+use crate::history;
+use crate::playback;
+use crate::{GuessingGame, MAX_NUMBER, MIN_NUMBER};
+
+// Widest a number may be in the compact strip before it's abbreviated, as in "4.2B"
+const COMPACT_NUMBER_CHARS: usize = 7;
+
+// How the feedback line should be emphasised
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,    // Instructions and hints, in the normal text color
+    Error,   // The input couldn't be used, in the theme's danger color
+    Success, // The game is won, in the accent color
+}
+
+// The classic game screen; not `Eq`, since the thermometer's level is a fraction
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameViewModel {
+    pub feedback: String,               // The feedback line, formatted for the locale
+    pub severity: Severity,             // How to emphasise the feedback line
+    pub announcement: Option<String>,   // An automatic hint just volunteered, shown under the feedback
+    pub worked_sum: Option<String>,     // The sum the latest guess was typed as, with what it came to
+    pub card_status: Option<String>,    // How saving the result card went, also shown under the feedback
+    pub scorecard_status: Option<String>, // How exporting the scorecard went, shown with it
+    pub replay_status: Option<String>,  // How exporting the replay file went, shown with it
+    pub history_status: Option<String>, // How exporting the history as CSV went, shown with it
+    pub opponent_note: Option<String>,  // The friend's result to beat, while playing their scorecard
+    pub ghost: Option<GhostPace>,       // The race against the fastest win, while this game could beat it
+    pub guess_enabled: bool,            // Whether the Guess button accepts presses
+    pub bases: Option<String>,          // The typed guess in binary and hex, when enabled and it's a number
+    pub range_tip: Option<String>,      // Why the typed number can't be the secret, only while it can't
+    pub input_tint: Option<Rgb>,        // How close the last guess was, as a tint for the input; neutral if `None`
+    pub thermometer: Option<f32>,       // How full to draw the proximity thermometer, or `None` to hide it
+    pub number_line: Option<(u32, u32)>, // The numbers the number line lets the player pick, until the game is over
+    pub game_over: bool,                // Whether the game was won or given up, so only a new one can start
+    pub give_up_enabled: bool,          // Whether to offer giving up, in pass-and-play games still going
+    pub warmup_enabled: bool,           // Whether to offer the round's free warm-up guess, until it's taken
+    pub retry_enabled: bool,            // Whether to offer another try at the secret, once it was given up on
+    pub retry_note: Option<&'static str>, // Why a retry or a drill doesn't count, while one is being played
+    pub setter_note: Option<&'static str>, // Who the secret came from, in pass-and-play games
+    pub last_played: Option<String>,    // The last game played, offered again before the first guess
+    pub last_played_notice: Option<String>, // Why that game couldn't be restored as it was
+    pub budget: Option<String>,         // Guesses left this session, while the budget is on
+    pub attempt_timer: Option<String>,  // Time left for this guess, in the timed-guesses mode once the clock runs
+    pub operation_note: Option<String>, // The mystery-mode explanation, while one is in play
+    pub hints: Vec<String>,             // Hints bought so far
+    pub hint_button: Option<String>,    // The label offering the next hint and its cost, while one is left
+    pub assist_note: Option<&'static str>, // How assist mode is adjusting the hints, while it adjusts them
+    pub hint_refused: Option<String>,   // Why the last hint wasn't given
+    pub peek_button: Option<PeekButton>, // The offer of this game's peek, until it's used
+    pub compact_readout: String,        // The one-line readout in the compact strip
+    pub summary: Option<SummaryViewModel>, // How the game went, once it is won
+    pub tournament: Option<TournamentViewModel>, // Where the tournament stands, while one is on
+    pub endless: Option<String>,        // The endless session's totals so far, while one is on
+    pub course: Option<CourseViewModel>, // Where the golf course stands, while one is on
+}
+
+// The status bar along the bottom of the window, gathering the game's secondary numbers in one line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusBar {
+    pub segments: Vec<String>, // Guesses, the clock, the best win, the range left and any candidates, in that order
+}
+
+impl StatusBar {
+    // Decide what the status bar shows for the current state; every value is kept short, so the
+    // line fits the window's width rather than wrapping
+    pub fn new(game: &GuessingGame) -> StatusBar {
+        let n = |value: u32| format_number(game.locale, value);
+        let mut segments = vec![format!("{} {}", n(game.attempts), if game.attempts == 1 { "guess" } else { "guesses" })];
+
+        if game.stopwatch.has_started() {
+            let clock = format!("Time {}", format_duration(game.stopwatch.elapsed(game.now)));
+            segments.push(if game.idle_paused { format!("{} (paused while you're away)", clock) } else { clock });
+        }
+        if let Some(best) = game.leaderboards.board(Metric::Attempts).first() {
+            segments.push(format!("Best {}", n(best.attempts)));
+        }
+        segments.push(format!("Range {}–{}", n(game.low), n(game.high)));
+        if game.preferences.show_candidates {
+            let count = game.candidates_left();
+            segments.push(format!("{} {} left", n(count), if count == 1 { "candidate" } else { "candidates" }));
+        }
+        StatusBar { segments }
+    }
+}
+
+// The golf course's progress, shown above the guess controls
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CourseViewModel {
+    pub heading: String,            // The hole being played and the total against par, or the final result
+    pub holes: Option<Vec<String>>, // The scorecard hole by hole, once the last one is played
+}
+
+// The tournament's progress, shown above the guess controls
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TournamentViewModel {
+    pub heading: String,                // The round being played and the points so far, or the final total
+    pub next_round: bool,               // Whether the next round is about to start by itself
+    pub standings: Option<Vec<String>>, // Every round's result, once the last one is won
+    pub best: Option<usize>,            // Which of the standings to pick out as the best round
+    pub length: u32,                    // Rounds played, for offering another tournament like it
+}
+
+// The race against the ghost of the fastest win
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostPace {
+    pub progress: String,              // How far along the ghost is, as in "Ghost: 3 of 6 guesses, wins at 14.2s"
+    pub split: Option<(String, bool)>, // How the latest guess compared with the ghost's, and whether it was ahead
+}
+
+// The button that spends the game's peek at the secret
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeekButton {
+    pub label: String, // The price, or why it can't be paid
+    pub enabled: bool, // Whether the best remaining score still covers the price
+}
+
+// The end-of-game summary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummaryViewModel {
+    pub result: String,                  // Guesses taken, the optimal count and the grade
+    pub score: String,                   // How the score was worked out, item by item
+    pub review_label: &'static str,      // The label of the button that shows or hides the review
+    pub play_again_label: &'static str,  // The label of the button that starts the next game or round
+    pub golf: String,                    // The guesses as strokes against par, as in "Par 7: birdie (−1)"
+    pub thinking: Option<String>,        // The quickest and slowest guesses, once two or more were timed
+    pub card_enabled: bool,              // Whether a result card can be saved, so not while one is saving
+    pub scorecard_enabled: Option<bool>, // Whether a scorecard can be exported now, if this game can have one
+    pub replay_enabled: bool,            // Whether a replay file can be exported now
+    pub history_enabled: bool,           // Whether the history can be exported as CSV now
+    pub comparison: Option<String>,      // How the game went against the friend's scorecard, if one was loaded
+    pub time_refused: Option<String>,    // Why the win didn't go on the time-attack board, if it didn't
+    pub review: Option<ReviewList>,      // The step-by-step review, when shown
+    pub explanation: Option<GradeExplanation>, // Why the game got its grade, when asked; shown instead of the rest
+}
+
+// The breakdown behind the strategy grade
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GradeExplanation {
+    pub lines: Vec<String>,      // The counts the grade was judged on, and what the next grade up needed
+    pub deviations: Vec<String>, // Where the guesses strayed from the midpoint, the first few of them
+}
+
+// The part of the step-by-step review on screen, and how many rows it stands in for either side
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewList {
+    pub header: String,      // How many guesses there were and over what range, without walking them
+    pub rows: Vec<ReviewRow>, // The rows in and near the viewport
+    pub above: usize,        // Rows scrolled past, left unbuilt
+    pub below: usize,        // Rows still to come, left unbuilt
+}
+
+// One guess in the step-by-step review
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewRow {
+    pub text: String,         // The guess, the range it was made in and the best guess there
+    pub suboptimal: bool,     // Whether the guess strayed far from the midpoint
+    pub time: Option<String>, // How long the guess took, as in "+8.2s", if it was timed
+}
+
+impl GameViewModel {
+    // Decide what the classic game screen shows for the current state
+    pub fn new(game: &GuessingGame) -> GameViewModel {
+        let n = |value: u32| format_number(game.locale, value);
+
+        // While the reveal plays, the feedback area shows its counter instead of the message,
+        // and a peek takes it over for as long as the peek lasts
+        let peeking = game.peek_until.is_some_and(|until| game.now < until);
+        let describe = |n: &dyn Fn(u32) -> String| match game.reveal.filter(|reveal| !reveal.is_finished(game.now)) {
+            _ if peeking => format!("Peek: {}", n(game.secret_number.reveal())),
+            Some(reveal) => {
+                let stars = "✦".repeat(reveal.flourish(game.now));
+                format!("{} {} {}", stars, n(reveal.value(game.now)), stars)
+            }
+            None => game.feedback.render_with(n),
+        };
+        let feedback = describe(&n);
+        let compact_feedback = describe(&|value| format_number_within(game.locale, value, COMPACT_NUMBER_CHARS));
+        let guesses = |count: u32| format!("{} {}", n(count), if count == 1 { "guess" } else { "guesses" });
+
+        let severity = match game.feedback {
+            Feedback::Win { .. } | Feedback::Solved { .. } => Severity::Success,
+            Feedback::InvalidNumber(_) | Feedback::BadSum(_) | Feedback::TooClose { .. } | Feedback::TooSlow => Severity::Error,
+            Feedback::Welcome { .. }
+            | Feedback::TooSmall(_)
+            | Feedback::TooBig(_)
+            | Feedback::GaveUp { .. }
+            | Feedback::WarmUp { .. }
+            | Feedback::Swapped { .. } => Severity::Info,
+        };
+
+        // Explain the mystery operation while it's in play, and name it once the game is won
+        let operation_note = game.operation.map(|operation| {
+            if game.won {
+                format!("The mystery operation was {}", operation)
+            } else {
+                "Mystery operation: hints compare a hidden operation applied to your guess and the secret".to_string()
+            }
+        });
+
+        // Keep the endless session's running totals in view, counting guesses on the secret in play
+        let endless = game.endless.map(|session| {
+            format!("Endless: {} solved, {}", n(session.solved), guesses(session.total_guesses(game.attempts)))
+        });
+
+        let game_over = game.won || game.gave_up;
+        let setter_note = game.pass_and_play.then_some(if game.gave_up {
+            "The setter stumped you this time"
+        } else if game.won {
+            "You found the number the other player set"
+        } else {
+            "The other player set this number"
+        });
+
+        // Offer the last game again only on a fresh screen, where it can't cut a game short
+        let fresh = game.history.is_empty() && !game.pass_and_play && game.daily.is_none();
+        let last_played = game.last_played.filter(|_| fresh).map(|last| format!("Last played: {}", last));
+        let last_played_notice = game.last_played_notice.filter(|_| fresh).map(|fallback| fallback.to_string());
+
+        // Count down to the next refill once the budget has run dry
+        let budget_on = game.preferences.guess_budget;
+        let exhausted = budget_on && game.budget.is_exhausted();
+        let budget = budget_on.then(|| match game.budget.next_refill_in(game.now) {
+            Some(wait) if exhausted => format!("Out of guesses - next one in {}", format_duration(wait)),
+            _ => format!("Guesses left: {} of {}", n(game.budget.remaining()), n(budget::CAPACITY)),
+        });
+
+        // Count the guess's time down in tenths, so the last seconds visibly run out
+        let attempt_timer = game.attempt_remaining().map(|left| {
+            let tenths = left.as_millis().div_ceil(100) as u32;
+            format!("{}{}{}s left", n(tenths / 10), game.locale.decimal_separator(), tenths % 10)
+        });
+
+        // Hidden whenever the input isn't a number, including while it's empty
+        let bases = parse_number(game.locale, &game.guess)
+            .ok()
+            .filter(|_| game.preferences.show_bases)
+            .map(|guess| format!("{} = {:#b} = {:#x}", n(guess), guess, guess));
+
+        // Explain the bounds next to the input while the typed number falls outside them: outside
+        // the game's range always, and outside what feedback has left only with the guess assist on
+        let range_tip = parse_number(game.locale, &game.guess).ok().filter(|_| !game_over).and_then(|guess| {
+            if !(MIN_NUMBER..=MAX_NUMBER).contains(&guess) {
+                Some(format!("Out of range - guesses go from {} to {}", n(MIN_NUMBER), n(MAX_NUMBER)))
+            } else if game.preferences.guess_assist && !(game.low..=game.high).contains(&guess) {
+                Some(format!("Already ruled out - the number is from {} to {}", n(game.low), n(game.high)))
+            } else {
+                None
+            }
+        });
+
+        // Price the next hint up front, so the player knows what pressing it costs
+        let next_hint = Hint::next(game.hints_bought).filter(|_| !game_over);
+        let hint_button = next_hint.map(|hint| format!("Hint (costs {} points)", n(hint.cost())));
+        let assist_note = next_hint.and_then(|_| game.hint_strength().note());
+        let hint_refused = next_hint.filter(|_| game.hint_refused).map(|hint| {
+            let best = score::best_remaining(game.attempts, game.points_spent);
+            format!("Not enough points left for that hint: it costs {}, and at most {} remain", n(hint.cost()), n(best))
+        });
+
+        let peek_button = (!game.peeked && !game_over).then(|| {
+            let enabled = score::can_afford(game.attempts, game.points_spent, score::PEEK_COST);
+            let label = if enabled {
+                format!("Peek at the secret (costs {} points, once a game)", n(score::PEEK_COST))
+            } else {
+                "Peek at the secret (not enough points left)".to_string()
+            };
+            PeekButton { label, enabled }
+        });
+
+        GameViewModel {
+            compact_readout: format!("{} ({})", compact_feedback, guesses(game.attempts)),
+            feedback,
+            severity,
+            worked_sum: game
+                .history
+                .len()
+                .checked_sub(1)
+                .and_then(|last| Some(format!("{} = {}", game.sums.get(&last)?, n(game.history[last])))),
+            announcement: game.mercy_announcement.clone(),
+            card_status: game.card_save.as_ref().map(|state| match state {
+                SaveState::Saving => "Saving the result card…".to_string(),
+                SaveState::Saved(path) => format!("Saved the result card to {}", path.display()),
+                SaveState::Failed => "Could not save the result card".to_string(),
+            }),
+            scorecard_status: game.scorecard_save.as_ref().map(|state| match state {
+                SaveState::Saving => "Exporting the scorecard…".to_string(),
+                SaveState::Saved(path) => format!("Exported the scorecard to {} - send it to a friend to play", path.display()),
+                SaveState::Failed => "Could not export the scorecard".to_string(),
+            }),
+            replay_status: game.replay_save.as_ref().map(|state| match state {
+                SaveState::Saving => "Exporting the replay…".to_string(),
+                SaveState::Saved(path) => format!("Exported the replay to {} - anyone can watch it from Replays", path.display()),
+                SaveState::Failed => "Could not export the replay".to_string(),
+            }),
+            history_status: game.history_save.as_ref().map(|state| match state {
+                SaveState::Saving => "Exporting the history…".to_string(),
+                SaveState::Saved(path) => format!("Exported the history to {}", path.display()),
+                SaveState::Failed => "Could not export the history".to_string(),
+            }),
+            opponent_note: game.opponent.filter(|_| !game.won).map(|opponent| {
+                format!("Your friend's score to beat: {} in {}", n(opponent.score), guesses(opponent.guesses))
+            }),
+            ghost: ghost_pace(game).filter(|_| !game.won),
+            guess_enabled: !game_over && !exhausted, // Disabled until a new game, or a refill
+            bases,
+            range_tip,
+            thermometer: Some(game.gauge.level(game.now)),
+            number_line: (!game_over).then_some(if game.preferences.guess_assist {
+                (game.low, game.high) // Everything the feedback has ruled out is greyed
+            } else {
+                (MIN_NUMBER, MAX_NUMBER)
+            }),
+            input_tint: game
+                .proximity
+                .filter(|_| !game_over) // Back to neutral once the game is over
+                .map(|proximity| color::proximity_tint(proximity, game.preferences.colorblind)),
+            game_over,
+            give_up_enabled: game.pass_and_play && !game_over,
+            warmup_enabled: !game.warmup_used && !game_over && !exhausted,
+            retry_enabled: game.gave_up && !game.won, // After a win the secret holds nothing left to try
+            retry_note: if game.retry {
+                Some("Retrying a number you've seen - this game won't count towards stats or streaks")
+            } else {
+                game.practice.map(|_| "Practising part of the range - this game won't count towards stats or streaks")
+            },
+            setter_note,
+            last_played,
+            last_played_notice,
+            budget,
+            attempt_timer,
+            operation_note,
+            hints: game.hints.clone(),
+            hint_button,
+            assist_note,
+            hint_refused,
+            peek_button,
+            summary: game.won.then(|| SummaryViewModel::new(game)),
+            tournament: game.tournament.as_ref().map(|tournament| tournament_view_model(game, tournament)),
+            endless,
+            course: game.course.as_ref().map(|course| course_view_model(game, course)),
+        }
+    }
+}
+
+impl SummaryViewModel {
+    // Summarise a won game; only called after a win, when the secret is safe to reveal
+    fn new(game: &GuessingGame) -> SummaryViewModel {
+        let n = |value: u32| format_number(game.locale, value);
+        let optimal = analysis::optimal_guesses(MIN_NUMBER, MAX_NUMBER);
+        let grade = analysis::grade(game.attempts, optimal);
+        let noun = if game.attempts == 1 { "guess" } else { "guesses" };
+
+        // Only the rows near where the review is scrolled to are formatted; the rest are just counted
+        let review = game.show_review.then(|| {
+            let total = game.reviews.len() + game.timeouts.len();
+            let shown = history::visible(total, game.review_offset);
+            let noun = if game.reviews.len() == 1 { "guess" } else { "guesses" };
+            let mut header = format!("{} {} over {}-{}", n(game.reviews.len() as u32), noun, n(MIN_NUMBER), n(MAX_NUMBER));
+            if !game.timeouts.is_empty() {
+                header.push_str(&format!(", {} too slow", n(game.timeouts.len() as u32)));
+            }
+            ReviewList {
+                header,
+                rows: shown
+                    .clone()
+                    .map(|row| match history::entry(row, &game.timeouts) {
+                        history::Entry::Guess(i) => {
+                            let sum = game.sums.get(&i).map(String::as_str);
+                            review_row(game.locale, i, &game.reviews[i], sum, game.guess_times.get(i).copied().flatten())
+                        }
+                        history::Entry::TimedOut => timed_out_row(),
+                    })
+                    .collect(),
+                above: shown.start,
+                below: total - shown.end,
+            }
+        });
+
+        SummaryViewModel {
+            result: format!(
+                "Solved in {} {} (optimal: {}) - Grade {}",
+                n(game.attempts),
+                noun,
+                n(optimal),
+                grade
+            ),
+            score: score_breakdown(game),
+            review_label: if game.show_review { "Hide review" } else { "Review my guesses" },
+            play_again_label: match &game.tournament {
+                Some(tournament) if !tournament.is_finished() => "Next round",
+                _ if game.course.as_ref().is_some_and(|course| !course.is_finished()) => "Next hole",
+                _ => "Play again",
+            },
+            golf: {
+                let par = golf::par(MIN_NUMBER, MAX_NUMBER);
+                format!("Par {}: {}", n(par), golf::describe(game.attempts, par))
+            },
+            thinking: thinking_summary(game),
+            card_enabled: game.card_save != Some(SaveState::Saving),
+            // A typed secret can't be recreated from a code, so pass-and-play games have no scorecard,
+            // and nor do drills, whose secret a code's range would draw differently
+            scorecard_enabled: (!game.pass_and_play && game.practice.is_none()).then_some(game.scorecard_save != Some(SaveState::Saving)),
+            replay_enabled: game.replay_save != Some(SaveState::Saving),
+            history_enabled: game.history_save != Some(SaveState::Saving),
+            comparison: game.opponent.map(|opponent| comparison(game, &opponent)),
+            time_refused: game.time_refused.map(|reason| reason.to_string()),
+            review,
+            explanation: game.grade_breakdown.as_ref().map(|breakdown| grade_explanation(game.locale, breakdown)),
+        }
+    }
+}
+
+// Deviations listed in the grade breakdown before the rest are only counted
+const DEVIATIONS_LISTED: usize = 8;
+
+// Put the grade's breakdown into words: the counts, the scale, and each guess that strayed
+fn grade_explanation(locale: Locale, breakdown: &analysis::GradeBreakdown) -> GradeExplanation {
+    let n = |value: u32| format_number(locale, value);
+    let guesses = |count: u32| format!("{} {}", n(count), if count == 1 { "guess" } else { "guesses" });
+    let (attempts, optimal) = (breakdown.attempts, breakdown.optimal);
+
+    let mut lines = vec![
+        format!("Grade {}: {} against an optimal {}", breakdown.grade, guesses(attempts), n(optimal)),
+        format!("Halving what's left every time finds any number in {} at most", guesses(optimal)),
+    ];
+    lines.push(match attempts.checked_sub(optimal).filter(|&over| over > 0) {
+        Some(over) => format!("You took {} more than that", guesses(over)),
+        None => "You did no worse than that, which is the best grade there is".to_string(),
+    });
+    let scale: Vec<String> = [analysis::Grade::A, analysis::Grade::B, analysis::Grade::C, analysis::Grade::D]
+        .into_iter()
+        .filter_map(|grade| Some(format!("{} up to {}", grade, n(grade.most_attempts(optimal)?))))
+        .collect();
+    lines.push(format!("The scale: {}, F beyond", scale.join(", ")));
+    if let Some((better, most)) = breakdown.next {
+        lines.push(format!("{} fewer would have made it {}", guesses(attempts.saturating_sub(most)), better));
+    }
+    let wasted = breakdown.deviations.len() as u32;
+    lines.push(match wasted {
+        0 => "Every guess stayed near the middle of what was left".to_string(),
+        _ => format!("{} strayed far from the middle of what was left:", guesses(wasted)),
+    });
+
+    let mut deviations: Vec<String> = breakdown
+        .deviations
+        .iter()
+        .take(DEVIATIONS_LISTED)
+        .map(|(i, review)| {
+            format!(
+                "Guess {}: {} with {}-{} left, where {} would have halved it",
+                n(*i as u32 + 1),
+                n(review.guess),
+                n(review.low),
+                n(review.high),
+                n(review.optimal)
+            )
+        })
+        .collect();
+    if breakdown.deviations.len() > DEVIATIONS_LISTED {
+        deviations.push(format!("...and {} more", n((breakdown.deviations.len() - DEVIATIONS_LISTED) as u32)));
+    }
+    GradeExplanation { lines, deviations }
+}
+
+// One row of the review: guess `index` (from 0), what it was typed as if a sum, and how long it took
+pub fn review_row(locale: Locale, index: usize, review: &analysis::StepReview, sum: Option<&str>, time: Option<Duration>) -> ReviewRow {
+    let n = |value: u32| format_number(locale, value);
+
+    // A guess typed as a sum shows the sum too, as in "2. 2^6 = 64"
+    let guess = match sum {
+        Some(sum) => format!("{} = {}", sum, n(review.guess)),
+        None => n(review.guess),
+    };
+    let mut text =
+        format!("{}. {} (range {}-{}, best {})", index + 1, guess, n(review.low), n(review.high), n(review.optimal));
+    if review.suboptimal {
+        text.push_str(" - far from optimal");
+    }
+    ReviewRow { text, suboptimal: review.suboptimal, time: time.map(|time| format_thinking_time(locale, time)) }
+}
+
+// The row standing in the review for an attempt that ran out of time
+fn timed_out_row() -> ReviewRow {
+    ReviewRow { text: playback::TOO_SLOW.to_string(), suboptimal: false, time: None }
+}
+
+// Where the race against the ghost stands, for a game that could take its place on the board
+fn ghost_pace(game: &GuessingGame) -> Option<GhostPace> {
+    let ghost = game.ghost.as_ref().filter(|_| game.preferences.ghost_race && game.can_count())?;
+    let n = |value: usize| format_number(game.locale, value as u64);
+    let clock = game.stopwatch.elapsed(game.now);
+    let finish = format_seconds(game.locale, ghost.finish());
+
+    let progress = match ghost.guesses_by(clock) {
+        0 if !game.stopwatch.has_started() => format!("Ghost to beat: {} guesses in {}", n(ghost.guesses()), finish),
+        made if made < ghost.guesses() => format!("Ghost: {} of {} guesses, wins at {}", n(made), n(ghost.guesses()), finish),
+        _ => format!("The ghost won at {} - beat it next time", finish),
+    };
+
+    // The clock at the latest guess is the sum of the guesses' times, when all of them were timed
+    let made = game.history.len();
+    let at: Option<Duration> = game.guess_times.iter().copied().sum();
+    let split = at.and_then(|at| ghost.split(made, at)).map(|split| match split {
+        Split::Ahead(by) => (format!("Guess {}: {} ahead of the ghost", n(made), format_seconds(game.locale, by)), true),
+        Split::Behind(by) => (format!("Guess {}: {} behind the ghost", n(made), format_seconds(game.locale, by)), false),
+        Split::Past => (format!("Guess {}: the ghost had won by now", n(made)), false),
+    });
+    Some(GhostPace { progress, split })
+}
+
+// Name the guesses the player was quickest and slowest over, leaving out any that weren't timed
+fn thinking_summary(game: &GuessingGame) -> Option<String> {
+    let timed: Vec<(usize, Duration)> =
+        game.guess_times.iter().enumerate().filter_map(|(i, time)| Some((i + 1, (*time)?))).collect();
+    if timed.len() < 2 {
+        return None; // With one guess, quickest and slowest are the same thing
+    }
+
+    // The earliest guess wins a tie either way
+    let quickest = timed.iter().min_by_key(|&&(i, time)| (time, i))?;
+    let slowest = timed.iter().max_by_key(|&&(i, time)| (time, std::cmp::Reverse(i)))?;
+    let n = |value: usize| format_number(game.locale, value as u64);
+    Some(format!(
+        "Quickest guess: {} (guess {}) - slowest: {} (guess {})",
+        format_thinking_time(game.locale, quickest.1),
+        n(quickest.0),
+        format_thinking_time(game.locale, slowest.1),
+        n(slowest.0)
+    ))
+}
+
+// Describe the tournament's progress, listing every round once it's over
+fn tournament_view_model(game: &GuessingGame, tournament: &Tournament) -> TournamentViewModel {
+    let n = |value: u32| format_number(game.locale, value);
+    let finished = tournament.is_finished();
+
+    let heading = if finished {
+        format!("Tournament over: {} points from {} rounds", n(tournament.total()), n(tournament.length))
+    } else {
+        format!(
+            "Tournament round {} of {} - {} points so far",
+            n(tournament.current()),
+            n(tournament.length),
+            n(tournament.total())
+        )
+    };
+    let standings = finished.then(|| {
+        tournament
+            .rounds
+            .iter()
+            .enumerate()
+            .map(|(i, round)| {
+                let noun = if round.attempts == 1 { "guess" } else { "guesses" };
+                format!(
+                    "Round {}: {} points, {} {} in {}",
+                    n(i as u32 + 1),
+                    n(round.score),
+                    n(round.attempts),
+                    noun,
+                    format_duration(round.elapsed)
+                )
+            })
+            .collect()
+    });
+
+    TournamentViewModel {
+        heading,
+        next_round: game.next_round_at.is_some(),
+        standings,
+        best: tournament.best().filter(|_| finished),
+        length: tournament.length,
+    }
+}
+
+// Describe the course's progress, listing every hole once it's over
+fn course_view_model(game: &GuessingGame, course: &Course) -> CourseViewModel {
+    let n = |value: u32| format_number(game.locale, value);
+    let to_par = golf::relative(course.to_par());
+
+    let heading = if course.is_finished() {
+        format!("Course finished: {} strokes, {} against par {}", n(course.total()), to_par, n(course.par * golf::HOLES))
+    } else {
+        format!("Hole {} of {}, par {} - {} so far", n(course.current()), n(golf::HOLES), n(course.par), to_par)
+    };
+    let holes = course.is_finished().then(|| {
+        course
+            .strokes
+            .iter()
+            .enumerate()
+            .map(|(i, &strokes)| {
+                let noun = if strokes == 1 { "stroke" } else { "strokes" };
+                format!("Hole {}: {} {}, {}", n(i as u32 + 1), n(strokes), noun, golf::describe(strokes, course.par))
+            })
+            .collect()
+    });
+
+    CourseViewModel { heading, holes }
+}
+
+// Both results side by side, with who came out ahead
+fn comparison(game: &GuessingGame, opponent: &Scorecard) -> String {
+    let n = |value: u32| format_number(game.locale, value);
+    let mine = game.scorecard();
+    let result = |scorecard: &Scorecard| {
+        let noun = if scorecard.guesses == 1 { "guess" } else { "guesses" };
+        let time = format_duration(Duration::from_secs(scorecard.seconds));
+        format!("{} points in {} {} ({})", n(scorecard.score), n(scorecard.guesses), noun, time)
+    };
+
+    let verdict = match mine.compare(opponent) {
+        Verdict::Won => "You beat your friend",
+        Verdict::Lost => "Your friend wins this one",
+        Verdict::Tied => "A dead heat",
+    };
+    format!("{}: you scored {}, they scored {}", verdict, result(&mine), result(opponent))
+}
+
+// Itemise the score, as in "Base 1000 − 3 guesses × 40 − hint 100 = 780"
+fn score_breakdown(game: &GuessingGame) -> String {
+    let n = |value: u32| format_number(game.locale, value);
+    let noun = if game.attempts == 1 { "guess" } else { "guesses" };
+
+    let mut breakdown = format!(
+        "Base {} − {} {} × {}",
+        n(score::BASE),
+        n(game.attempts),
+        noun,
+        n(score::GUESS_COST)
+    );
+    for hint in Hint::ALL.iter().take(game.hints_bought) {
+        breakdown.push_str(&format!(" − hint {}", n(hint.cost())));
+    }
+    if game.peeked {
+        breakdown.push_str(&format!(" − peek {}", n(score::PEEK_COST)));
+    }
+    breakdown.push_str(&format!(" = {}", n(score::score(game.attempts, game.points_spent))));
+    breakdown
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::Application;
+
+    use super::*;
+    use crate::format::ParseError;
+    use crate::preferences::Preferences;
+    use crate::random::SeededSource;
+    use crate::skill::Strength;
+    use crate::{Message, Saved};
+
+    // A game drawing from seed 42, whose secret is 95, with `preferences`
+    fn game_with(preferences: Preferences) -> GuessingGame {
+        GuessingGame::with_setup(Box::new(SeededSource::new(42)), preferences, Locale::English, Saved::default())
+    }
+
+    // Type `text` and press Guess
+    fn guess(game: &mut GuessingGame, text: &str) {
+        let _ = game.update(Message::GuessInputChanged(text.to_string()));
+        let _ = game.update(Message::GuessButtonPressed);
+    }
+
+    // Type `text` without pressing Guess
+    fn type_in(game: &mut GuessingGame, text: &str) {
+        let _ = game.update(Message::GuessInputChanged(text.to_string()));
+    }
+
+    #[test]
+    fn a_fresh_game_welcomes_the_player_and_offers_everything() {
+        let game = game_with(Preferences::default());
+        let model = GameViewModel::new(&game);
+        assert_eq!(model.feedback, "Welcome to the Guessing Game! Pick a number between 1 and 100.");
+        assert_eq!(model.severity, Severity::Info);
+        assert!(model.guess_enabled);
+        assert!(!model.game_over);
+        assert!(model.warmup_enabled);
+        assert_eq!(model.number_line, Some((MIN_NUMBER, MAX_NUMBER)));
+        assert!(model.hint_button.is_some());
+        assert!(model.peek_button.is_some_and(|peek| peek.enabled));
+        assert_eq!(model.budget, None, "the budget is off by default");
+        assert_eq!(model.attempt_timer, None);
+        assert_eq!(model.summary, None);
+        assert_eq!(model.compact_readout, format!("{} (0 guesses)", model.feedback));
+    }
+
+    #[test]
+    fn an_unreadable_guess_is_an_error_that_costs_nothing() {
+        let mut game = game_with(Preferences::default());
+        guess(&mut game, "5x");
+        let model = GameViewModel::new(&game);
+        assert_eq!(game.feedback, Feedback::InvalidNumber(ParseError::InvalidCharacter('x')));
+        assert_eq!(model.feedback, "\"x\" isn't a digit - please enter a whole number.");
+        assert_eq!(model.severity, Severity::Error);
+        assert!(model.guess_enabled, "the player can fix it and guess again");
+        assert_eq!(model.range_tip, None, "only numbers get a range tip");
+        assert_eq!(game.attempts, 0);
+    }
+
+    #[test]
+    fn a_sum_that_cant_be_worked_out_is_an_error_too() {
+        let mut game = game_with(Preferences::default());
+        guess(&mut game, "7/2");
+        let model = GameViewModel::new(&game);
+        assert_eq!(model.severity, Severity::Error);
+        assert_eq!(model.feedback, "That sum doesn't come out to a whole number - division has to be exact.");
+        assert_eq!(model.worked_sum, None);
+    }
+
+    #[test]
+    fn a_guess_typed_as_a_sum_shows_what_it_came_to() {
+        let mut game = game_with(Preferences::default());
+        guess(&mut game, "100/2");
+        let model = GameViewModel::new(&game);
+        assert_eq!(model.feedback, "50 is too small!");
+        assert_eq!(model.worked_sum.as_deref(), Some("100/2 = 50"));
+    }
+
+    #[test]
+    fn numbers_that_cant_be_the_secret_are_explained_next_to_the_input() {
+        let mut game = game_with(Preferences::default());
+        type_in(&mut game, "500");
+        assert_eq!(GameViewModel::new(&game).range_tip.as_deref(), Some("Out of range - guesses go from 1 to 100"));
+
+        guess(&mut game, "50");
+        type_in(&mut game, "20");
+        let model = GameViewModel::new(&game);
+        assert_eq!(model.range_tip.as_deref(), Some("Already ruled out - the number is from 51 to 100"));
+        assert_eq!(model.number_line, Some((51, 100)), "the guess assist greys out what's ruled out");
+
+        type_in(&mut game, "75");
+        assert_eq!(GameViewModel::new(&game).range_tip, None);
+    }
+
+    #[test]
+    fn without_the_guess_assist_ruled_out_numbers_arent_pointed_out() {
+        let mut game = game_with(Preferences { guess_assist: false, ..Preferences::default() });
+        guess(&mut game, "50");
+        type_in(&mut game, "20");
+        let model = GameViewModel::new(&game);
+        assert_eq!(model.range_tip, None);
+        assert_eq!(model.number_line, Some((MIN_NUMBER, MAX_NUMBER)));
+    }
+
+    #[test]
+    fn the_input_is_tinted_by_how_close_the_last_guess_was() {
+        let mut game = game_with(Preferences::default());
+        assert_eq!(GameViewModel::new(&game).input_tint, None, "neutral before the first guess");
+        guess(&mut game, "50");
+        let proximity = 1.0 - 45.0 / 99.0;
+        assert_eq!(GameViewModel::new(&game).input_tint, Some(color::proximity_tint(proximity, false)));
+        guess(&mut game, "94");
+        let closer = GameViewModel::new(&game).input_tint;
+        assert_eq!(closer, Some(color::proximity_tint(1.0 - 1.0 / 99.0, false)));
+
+        game.preferences.colorblind = true;
+        assert_eq!(GameViewModel::new(&game).input_tint, Some(color::proximity_tint(1.0 - 1.0 / 99.0, true)));
+    }
+
+    #[test]
+    fn a_won_game_locks_every_control_but_a_new_game() {
+        let mut game = game_with(Preferences::default());
+        guess(&mut game, "50");
+        guess(&mut game, "95");
+        type_in(&mut game, "500");
+        let model = GameViewModel::new(&game);
+
+        assert_eq!(model.severity, Severity::Success);
+        assert!(model.game_over);
+        assert!(!model.guess_enabled);
+        assert!(!model.warmup_enabled);
+        assert!(!model.give_up_enabled);
+        assert!(!model.retry_enabled, "after a win there's nothing left to try");
+        assert_eq!(model.number_line, None);
+        assert_eq!(model.range_tip, None);
+        assert_eq!(model.hint_button, None);
+        assert_eq!(model.peek_button, None);
+        assert_eq!(model.input_tint, None);
+
+        let summary = model.summary.expect("a won game is summarised");
+        let optimal = analysis::optimal_guesses(MIN_NUMBER, MAX_NUMBER);
+        assert_eq!(
+            summary.result,
+            format!("Solved in 2 guesses (optimal: {}) - Grade {}", optimal, analysis::grade(2, optimal))
+        );
+        assert_eq!(summary.play_again_label, "Play again");
+        assert_eq!(summary.review, None, "the review waits to be asked for");
+
+        // Pressing Guess anyway changes nothing
+        guess(&mut game, "95");
+        assert_eq!(game.attempts, 2);
+        assert_eq!(game.history, [50, 95]);
+    }
+
+    #[test]
+    fn a_won_game_can_be_reviewed_guess_by_guess() {
+        let mut game = game_with(Preferences::default());
+        guess(&mut game, "50");
+        guess(&mut game, "95");
+        let _ = game.update(Message::ReviewToggled);
+
+        let summary = GameViewModel::new(&game).summary.unwrap();
+        assert_eq!(summary.review_label, "Hide review");
+        let review = summary.review.expect("the review was asked for");
+        assert_eq!(review.header, "2 guesses over 1-100");
+        assert_eq!(review.rows.len(), 2);
+        assert_eq!((review.above, review.below), (0, 0));
+        assert!(review.rows[0].text.starts_with("1. 50 "));
+        assert!(review.rows[1].text.starts_with("2. 95 "));
+    }
+
+    #[test]
+    fn running_out_of_budget_blocks_guessing_until_the_refill() {
+        let mut game = game_with(Preferences { guess_budget: true, ..Preferences::default() });
+        assert_eq!(GameViewModel::new(&game).budget.as_deref(), Some("Guesses left: 25 of 25"));
+
+        for n in 1..=budget::CAPACITY {
+            guess(&mut game, &n.to_string());
+        }
+        let model = GameViewModel::new(&game);
+        let budget = model.budget.expect("the budget is on");
+        // The clock read for the view can lag the first spend by a moment
+        assert!(matches!(budget.as_str(), "Out of guesses - next one in 1:00" | "Out of guesses - next one in 0:59"), "{}", budget);
+        assert!(!model.guess_enabled);
+        assert!(!model.warmup_enabled);
+        assert!(!model.game_over, "the game itself goes on once a guess comes back");
+
+        guess(&mut game, "95");
+        assert_eq!(game.attempts, budget::CAPACITY);
+        assert!(!game.won);
+    }
+
+    #[test]
+    fn the_status_bar_counts_guesses_and_the_range_left() {
+        let mut game = game_with(Preferences { show_candidates: true, ..Preferences::default() });
+        assert_eq!(StatusBar::new(&game).segments, ["0 guesses", "Range 1–100", "100 candidates left"]);
+        guess(&mut game, "90");
+        let segments = StatusBar::new(&game).segments;
+        assert_eq!(segments[0], "1 guess");
+        assert_eq!(segments[segments.len() - 2..], ["Range 91–100", "10 candidates left"]);
+    }
+
+    #[test]
+    fn hints_are_priced_up_front_and_itemised_in_the_score() {
+        let mut game = game_with(Preferences::default());
+        assert_eq!(GameViewModel::new(&game).hint_button.as_deref(), Some("Hint (costs 30 points)"));
+        let _ = game.update(Message::HintRequested);
+        assert_eq!(GameViewModel::new(&game).hint_button.as_deref(), Some("Hint (costs 50 points)"));
+        let _ = game.update(Message::HintRequested);
+        assert_eq!(game.points_spent, 80);
+
+        guess(&mut game, "51");
+        guess(&mut game, "95");
+        let summary = GameViewModel::new(&game).summary.expect("the game is won");
+        assert_eq!(summary.score, "Base 1,000 − 2 guesses × 40 − hint 30 − hint 50 = 840");
+        assert_eq!(GameViewModel::new(&game).hint_button, None, "no hints once the game is over");
+    }
+
+    #[test]
+    fn a_hint_the_score_cannot_cover_is_refused_with_a_reason() {
+        let mut game = game_with(Preferences::default());
+        let _ = game.update(Message::PeekRequested);
+        for n in 20..=30 {
+            guess(&mut game, &n.to_string());
+        }
+        let _ = game.update(Message::HintRequested);
+        assert!(game.hints.is_empty());
+        assert_eq!(game.points_spent, score::PEEK_COST);
+        let model = GameViewModel::new(&game);
+        assert_eq!(
+            model.hint_refused.as_deref(),
+            Some("Not enough points left for that hint: it costs 30, and at most 20 remain")
+        );
+        assert_eq!(model.peek_button, None, "the peek is used up");
+
+        guess(&mut game, "95");
+        let summary = GameViewModel::new(&game).summary.expect("the game is won");
+        assert_eq!(summary.score, "Base 1,000 − 12 guesses × 40 − peek 500 = 20");
+    }
+
+    #[test]
+    fn the_score_floors_at_zero_in_the_breakdown() {
+        let mut game = game_with(Preferences::default());
+        for n in 1..=30 {
+            guess(&mut game, &n.to_string());
+        }
+        assert!(GameViewModel::new(&game).peek_button.is_some_and(|peek| !peek.enabled));
+        guess(&mut game, "95");
+        let summary = GameViewModel::new(&game).summary.expect("the game is won");
+        assert_eq!(summary.score, "Base 1,000 − 31 guesses × 40 = 0");
+    }
+
+    #[test]
+    fn mercy_hints_count_only_new_wrong_guesses_and_stop_after_the_last() {
+        let mut game = game_with(Preferences { mercy_hints: true, ..Preferences::default() });
+        for text in ["1", "2", "3", "4", "abc", "4", ""] {
+            guess(&mut game, text);
+        }
+        assert!(game.hints.is_empty(), "the invalid, repeated and empty guesses aside, only four are wrong");
+        assert_eq!(game.wrong_guesses, 4);
+
+        guess(&mut game, "5");
+        assert_eq!(GameViewModel::new(&game).announcement.as_deref(), Some("Here's a clue: the number is odd"));
+        assert_eq!(game.hints, ["Here's a clue: the number is odd (automatic)"]);
+        assert_eq!(game.points_spent, 0, "automatic hints are free");
+
+        guess(&mut game, "6");
+        assert_eq!(GameViewModel::new(&game).announcement, None, "the announcement is for one guess");
+        for n in 7..=30 {
+            guess(&mut game, &n.to_string());
+        }
+        assert_eq!(
+            game.hints,
+            [
+                "Here's a clue: the number is odd (automatic)",
+                "Here's a clue: the number rounds to 100 (automatic)",
+                "Here's a clue: it's within 5 of 95 (automatic)",
+            ]
+        );
+    }
+
+    #[test]
+    fn mercy_hints_stay_off_unless_asked_for() {
+        let mut game = game_with(Preferences::default());
+        for n in 1..=20 {
+            guess(&mut game, &n.to_string());
+        }
+        assert!(game.hints.is_empty());
+    }
+
+    #[test]
+    fn the_compact_readout_abbreviates_numbers_too_wide_for_it() {
+        let mut game = game_with(Preferences::default());
+        guess(&mut game, "4294967295");
+        let model = GameViewModel::new(&game);
+        assert_eq!(model.feedback, "4,294,967,295 is too big!");
+        assert_eq!(model.compact_readout, "4.2B is too big! (1 guess)");
+    }
+
+    #[test]
+    fn the_digit_hint_narrows_the_range_the_game_shows() {
+        let mut game = game_with(Preferences { guess_assist: true, ..Preferences::default() });
+        let _ = game.update(Message::HintRequested);
+        assert_eq!(game.hints, ["The number has 2 digits"]);
+        assert_eq!((game.low, game.high), (10, 99));
+        assert_eq!(GameViewModel::new(&game).number_line, Some((10, 99)));
+
+        type_in(&mut game, "100");
+        assert_eq!(
+            GameViewModel::new(&game).range_tip.as_deref(),
+            Some("Already ruled out - the number is from 10 to 99")
+        );
+    }
+
+    #[test]
+    fn a_golf_course_shows_its_progress_and_then_the_scorecard() {
+        let mut game = game_with(Preferences::default());
+        let _ = game.update(Message::CourseStarted);
+        let heading = |game: &GuessingGame| GameViewModel::new(game).course.expect("a course is on").heading;
+        assert_eq!(heading(&game), "Hole 1 of 9, par 7 - E so far");
+
+        for hole in 0..golf::HOLES {
+            if hole == 0 {
+                let wrong = if game.secret_number.reveal() == 50 { "51" } else { "50" };
+                guess(&mut game, wrong); // A two-stroke first hole, the rest holes in one
+            }
+            let secret = game.secret_number.reveal().to_string();
+            guess(&mut game, &secret);
+            if hole == 0 {
+                assert_eq!(GameViewModel::new(&game).summary.unwrap().golf, "Par 7: condor (−5)");
+            }
+            if hole + 1 < golf::HOLES {
+                let _ = game.update(Message::NewGame);
+            }
+        }
+
+        let course = GameViewModel::new(&game).course.expect("the scorecard is up with the last hole's result");
+        assert_eq!(course.heading, "Course finished: 10 strokes, −53 against par 63");
+        let holes = course.holes.expect("every hole is listed once the course is over");
+        assert_eq!(holes.len(), 9);
+        assert_eq!(holes[0], "Hole 1: 2 strokes, condor (−5)");
+        assert_eq!(holes[8], "Hole 9: 1 stroke, hole in one (−6)");
+
+        let _ = game.update(Message::NewGame);
+        assert_eq!(GameViewModel::new(&game).course, None, "playing again leaves the course");
+    }
+
+    #[test]
+    fn assist_mode_tunes_the_hints_to_recent_wins() {
+        let mut game = game_with(Preferences { assist_hints: true, ..Preferences::default() });
+        assert_eq!(GameViewModel::new(&game).assist_note, None, "too few wins to tell");
+
+        game.recent_wins = vec![12, 12, 12];
+        assert_eq!(game.hint_strength(), Strength::Generous);
+        assert_eq!(
+            GameViewModel::new(&game).assist_note,
+            Some("Assist: hints are more generous while you find your feet")
+        );
+
+        game.recent_wins = vec![5, 6, 7];
+        assert_eq!(game.hint_strength(), Strength::Strict);
+
+        game.preferences.assist_hints = false;
+        assert_eq!(game.hint_strength(), Strength::Normal, "competitive players can opt out");
+        assert_eq!(GameViewModel::new(&game).assist_note, None);
+    }
+
+    #[test]
+    fn the_summary_names_the_quickest_and_slowest_guesses() {
+        let mut game = game_with(Preferences::default());
+        for text in ["10", "20", "30", "95"] {
+            guess(&mut game, text);
+        }
+        game.guess_times = [3_000, 800, 12_400, 800].map(|ms| Some(Duration::from_millis(ms))).to_vec();
+        let summary = GameViewModel::new(&game).summary.expect("the game is won");
+        assert_eq!(
+            summary.thinking.as_deref(),
+            Some("Quickest guess: +0.8s (guess 2) - slowest: +12s (guess 3)"),
+            "the earlier of two equal times is named"
+        );
+
+        game.guess_times = vec![None, Some(Duration::from_secs(2)), None, Some(Duration::from_secs(5))];
+        let summary = GameViewModel::new(&game).summary.unwrap();
+        assert_eq!(summary.thinking.as_deref(), Some("Quickest guess: +2.0s (guess 2) - slowest: +5.0s (guess 4)"));
+
+        game.guess_times = vec![None, None, None, Some(Duration::from_secs(2))];
+        assert_eq!(GameViewModel::new(&game).summary.unwrap().thinking, None, "one timed guess has nothing to compare");
+    }
+
+    #[test]
+    fn a_review_of_ten_thousand_guesses_formats_only_the_rows_in_view() {
+        let mut game = game_with(Preferences::default());
+        guess(&mut game, "95");
+        let guesses: Vec<u32> = (0..10_000).map(|i| 1 + i % 94).chain([95]).collect();
+        game.reviews = analysis::review_guesses(MIN_NUMBER, MAX_NUMBER, 95, &guesses);
+        game.timeouts = vec![0, 5_000];
+        game.show_review = true;
+        let total = guesses.len() + 2;
+
+        for offset in [0.0, 0.37, 1.0] {
+            game.review_offset = offset;
+            let review = GameViewModel::new(&game).summary.unwrap().review.expect("the review is open");
+            assert!(review.rows.len() < 40, "{} rows formatted", review.rows.len());
+            assert_eq!(review.above + review.rows.len() + review.below, total);
+        }
+        assert!(GameViewModel::new(&game).summary.unwrap().review.unwrap().header.starts_with("10,001 guesses over 1-100, 2 too slow"));
+    }
+
+    #[test]
+    fn the_last_digit_is_the_third_hint() {
+        let mut game = game_with(Preferences { guess_assist: true, ..Preferences::default() });
+        for _ in 0..3 {
+            let _ = game.update(Message::HintRequested);
+        }
+        assert_eq!(game.hints, ["The number has 2 digits", "The number is odd", "The number ends in 5"]);
+        assert_eq!((game.low, game.high), (15, 95));
+        assert_eq!(GameViewModel::new(&game).number_line, Some((15, 95)));
+        assert_eq!(game.hints_bought, 3);
+    }
+}
@@ -0,0 +1,154 @@
+// A wrapper that turns the mouse wheel into a message while the cursor is over its content
+//
+// iced 0.9 has no hover events, so the wheel can't be tied to one widget from `update`.
+// This wrapper lays out, draws and handles events exactly as its content does, and only
+// claims a wheel movement when the cursor is over it. Everywhere else the wheel carries on to
+// whatever it scrolled before, such as the review's scrollable.
+
+use iced_native::event::{self, Event};
+use iced_native::layout::{self, Layout};
+use iced_native::renderer;
+use iced_native::widget::tree::{self, Tree};
+use iced_native::widget::{Operation, Widget};
+use iced_native::{keyboard, mouse, overlay};
+use iced_native::{Clipboard, Element, Length, Point, Rectangle, Shell};
+
+// Notches the wheel turned, up being positive, and whether Shift was held at the time
+pub type OnWheel<'a, Message> = Box<dyn Fn(i32, bool) -> Message + 'a>;
+
+// The content, and the message a wheel notch over it sends
+pub struct WheelArea<'a, Message, Renderer> {
+    content: Element<'a, Message, Renderer>,
+    on_wheel: OnWheel<'a, Message>,
+}
+
+// The modifiers last seen, since a wheel event doesn't carry them
+#[derive(Debug, Clone, Copy, Default)]
+struct State {
+    modifiers: keyboard::Modifiers,
+}
+
+impl<'a, Message, Renderer> WheelArea<'a, Message, Renderer> {
+    // Wrap `content`, sending `on_wheel` for each wheel movement over it
+    pub fn new(content: impl Into<Element<'a, Message, Renderer>>, on_wheel: impl Fn(i32, bool) -> Message + 'a) -> Self {
+        WheelArea { content: content.into(), on_wheel: Box::new(on_wheel) }
+    }
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for WheelArea<'a, Message, Renderer>
+where
+    Renderer: iced_native::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    // Pass focus operations through, so the wrapped text input can still be focused by its ID
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, renderer: &Renderer, operation: &mut dyn Operation<Message>) {
+        self.content.as_widget().operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        if let Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) = event {
+            tree.state.downcast_mut::<State>().modifiers = modifiers;
+        }
+
+        // Only a vertical movement over the content counts; a notch is a notch however far it scrolled
+        if let Event::Mouse(mouse::Event::WheelScrolled { delta }) = event {
+            let y = match delta {
+                mouse::ScrollDelta::Lines { y, .. } | mouse::ScrollDelta::Pixels { y, .. } => y,
+            };
+            if y != 0.0 && layout.bounds().contains(cursor_position) {
+                let shift = tree.state.downcast_ref::<State>().modifiers.shift();
+                shell.publish((self.on_wheel)(if y > 0.0 { 1 } else { -1 }, shift));
+                return event::Status::Captured;
+            }
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Renderer::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(&tree.children[0], renderer, theme, style, layout, cursor_position, viewport);
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(&tree.children[0], layout, cursor_position, viewport, renderer)
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content.as_widget_mut().overlay(&mut tree.children[0], layout, renderer)
+    }
+}
+
+impl<'a, Message, Renderer> From<WheelArea<'a, Message, Renderer>> for Element<'a, Message, Renderer>
+where
+    Message: 'a,
+    Renderer: iced_native::Renderer + 'a,
+{
+    fn from(area: WheelArea<'a, Message, Renderer>) -> Self {
+        Element::new(area)
+    }
+}